@@ -1,5 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, SamplingMode, Throughput};
-use lzfse_rust::{self, LzfseDecoder, LzfseEncoder, LzfseRingDecoder, LzfseRingEncoder};
+use lzfse_rust::{
+    self, LzfseDecoder, LzfseEncoder, LzfseRingDecoder, LzfseRingEncoder, MatchStrategy,
+};
 use std::time::Duration;
 
 const SAMPLE_SIZE: usize = 20;
@@ -87,6 +89,7 @@ fn all(c: &mut Criterion) {
     snappy(c, lzfse_ref_decode);
     snappy(c, rust_encode);
     snappy(c, rust_decode);
+    snappy(c, rust_decode_optimized);
     snappy(c, rust_ring_encode);
     snappy(c, rust_ring_decode);
 
@@ -112,6 +115,13 @@ fn all(c: &mut Criterion) {
     synth_repl(c, lzfse_ref_decode);
     synth_repl(c, rust_decode);
     synth_repl(c, rust_ring_decode);
+
+    tiny(c, rust_encode_tiny);
+}
+
+/// Tiny data: stress per call overhead.
+fn tiny(c: &mut Criterion, mut engine: impl FnMut(&mut Criterion, &str, &[u8])) {
+    engine(c, "tiny_08", b"01234567");
 }
 
 /// Synthetic data
@@ -200,6 +210,16 @@ fn rust_encode(c: &mut Criterion, tag: &str, enc: &[u8]) {
     })
 }
 
+fn rust_encode_tiny(c: &mut Criterion, tag: &str, dec: &[u8]) {
+    let mut encoder = LzfseEncoder::default();
+    let mut enc = Vec::with_capacity(64);
+    let bench_name = format!("encode/{}", tag);
+    execute(c, "rust", &bench_name, dec, &mut enc, dec.len(), |src, dst| {
+        dst.clear();
+        encoder.encode_bytes(src, dst).expect("encode error");
+    })
+}
+
 fn rust_decode(c: &mut Criterion, tag: &str, enc: &[u8]) {
     let mut decoder = LzfseDecoder::default();
     decode(c, "rust", tag, enc, |src, dst| {
@@ -208,6 +228,20 @@ fn rust_decode(c: &mut Criterion, tag: &str, enc: &[u8]) {
     })
 }
 
+// Decode data encoded using MatchStrategy::DecodeOptimized, compare against `rust` decode.
+fn rust_decode_optimized(c: &mut Criterion, tag: &str, enc: &[u8]) {
+    let dec = decode_bytes(enc);
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_match_strategy(MatchStrategy::DecodeOptimized);
+    let mut enc = Vec::default();
+    encoder.encode_bytes(&dec, &mut enc).expect("encode error");
+    let mut decoder = LzfseDecoder::default();
+    decode(c, "rust_decode_optimized", tag, &enc, |src, dst| {
+        dst.clear();
+        decoder.decode_bytes(src, dst).expect("decode error");
+    })
+}
+
 fn rust_ring_encode(c: &mut Criterion, tag: &str, enc: &[u8]) {
     let mut encoder = LzfseRingEncoder::default();
     encode(c, "rust_ring", tag, enc, |mut src, dst| {
@@ -226,15 +260,17 @@ fn rust_ring_decode(c: &mut Criterion, tag: &str, enc: &[u8]) {
 
 #[cfg(feature = "lzfse_ref")]
 fn lzfse_ref_encode(c: &mut Criterion, tag: &str, enc: &[u8]) {
+    let mut scratch = Vec::default();
     encode(c, "lzfse_ref", tag, enc, |src, dst| {
-        assert_ne!(lzfse_sys::encode(src, dst.as_mut_slice()), 0);
+        assert_ne!(lzfse_sys::encode_with_scratch(src, dst.as_mut_slice(), &mut scratch), 0);
     })
 }
 
 #[cfg(feature = "lzfse_ref")]
 fn lzfse_ref_decode(c: &mut Criterion, tag: &str, enc: &[u8]) {
+    let mut scratch = Vec::default();
     decode(c, "lzfse_ref", tag, enc, |src, dst| {
-        assert_ne!(lzfse_sys::decode(src, dst.as_mut_slice()), 0);
+        assert_ne!(lzfse_sys::decode_with_scratch(src, dst.as_mut_slice(), &mut scratch), 0);
     })
 }
 
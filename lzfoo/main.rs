@@ -1,23 +1,49 @@
-use clap::{crate_version, App, AppSettings, Arg, ArgMatches, SubCommand};
-use lzfse_rust::{LzfseRingDecoder, LzfseRingEncoder};
+use clap::{crate_version, App, AppSettings, Arg, ArgMatches, ErrorKind, SubCommand, Values};
+use lzfse_rust::{
+    BlockStrategy, LzfseDecoder, LzfseEncoder, LzfseRingDecoder, LzfseRingEncoder, Metadata,
+    NormalizationStrategy,
+};
 
 use core::panic;
 use std::fmt;
-use std::fs::File;
+use std::fs::{self, File, OpenOptions};
 use std::io;
 use std::io::prelude::*;
+use std::path::Path;
 use std::process;
-use std::time::Instant;
+use std::time::{Duration, Instant, UNIX_EPOCH};
 
 const STDIN: &str = "stdin";
 const STDOUT: &str = "stdout";
 
+const SUFFIX: &str = ".lzfse";
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum Mode {
     Encode,
     Decode,
 }
 
+// Multiple file options.
+#[derive(Copy, Clone)]
+struct FileOptions {
+    keep: bool,
+    stdout: bool,
+    force: bool,
+    verbose: bool,
+}
+
+impl FileOptions {
+    fn new(m: &ArgMatches) -> Self {
+        Self {
+            keep: m.occurrences_of("keep") != 0,
+            stdout: m.occurrences_of("stdout") != 0,
+            force: m.occurrences_of("force") != 0,
+            verbose: m.occurrences_of("v") != 0,
+        }
+    }
+}
+
 impl fmt::Display for Mode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -29,7 +55,8 @@ impl fmt::Display for Mode {
 
 fn main() {
     process::exit(match execute() {
-        Ok(()) => 0,
+        Ok(true) => 0,
+        Ok(false) => 1,
         Err(lzfse_rust::Error::Io(err)) if err.kind() == io::ErrorKind::BrokenPipe => 0,
         Err(lzfse_rust::Error::Io(err)) => {
             eprint!("Error: IO: {err}");
@@ -46,34 +73,179 @@ fn main() {
     });
 }
 
-fn execute() -> lzfse_rust::Result<()> {
+// Returns `false` if any file in a multiple file invocation failed.
+fn execute() -> lzfse_rust::Result<bool> {
     let matches = arg_matches();
     match matches.subcommand() {
         ("-encode", Some(m)) => {
             let input = m.value_of("input");
             let output = m.value_of("output");
             let verbose = m.occurrences_of("v") != 0;
+            if let Some(files) = m.values_of("files") {
+                let store = m.occurrences_of("m") != 0;
+                let options = FileOptions::new(m);
+                return Ok(batch(files, verbose, |path| encode_file(path, store, options)));
+            }
+            let metadata = match input {
+                Some(r) if m.occurrences_of("m") != 0 => Some(file_metadata(r)?),
+                None if m.occurrences_of("m") != 0 => clap::Error::with_description(
+                    "-m requires a FILE input, either -i <FILE> or FILE...",
+                    ErrorKind::MissingRequiredArgument,
+                )
+                .exit(),
+                _ => None,
+            };
+            let metadata = metadata.as_ref();
             match (input, output) {
-                (None, None) => encode(io::stdin(), io::stdout(), STDIN, STDOUT, verbose),
-                (Some(r), None) => encode(File::open(r)?, io::stdout(), r, STDOUT, verbose),
-                (None, Some(w)) => encode(io::stdin(), File::create(w)?, STDIN, w, verbose),
-                (Some(r), Some(w)) => encode(File::open(r)?, File::create(w)?, r, w, verbose),
+                (None, None) => encode(io::stdin(), io::stdout(), STDIN, STDOUT, metadata, verbose),
+                (Some(r), None) => {
+                    encode(File::open(r)?, io::stdout(), r, STDOUT, metadata, verbose)
+                }
+                (None, Some(w)) => {
+                    encode(io::stdin(), File::create(w)?, STDIN, w, metadata, verbose)
+                }
+                (Some(r), Some(w)) => {
+                    encode(File::open(r)?, File::create(w)?, r, w, metadata, verbose)
+                }
             }?;
         }
         ("-decode", Some(m)) => {
             let input = m.value_of("input");
             let output = m.value_of("output");
             let verbose = m.occurrences_of("v") != 0;
+            let restore = m.occurrences_of("m") != 0;
+            if let Some(files) = m.values_of("files") {
+                let options = FileOptions::new(m);
+                return Ok(batch(files, verbose, |path| decode_file(path, restore, options)));
+            }
+            let force = m.occurrences_of("force") != 0;
             match (input, output) {
+                (None, None) if restore => {
+                    decode_restore(io::stdin(), STDIN, force, verbose).map(|(_, u)| u)
+                }
+                (Some(r), None) if restore => {
+                    decode_restore(File::open(r)?, r, force, verbose).map(|(_, u)| u)
+                }
                 (None, None) => decode(io::stdin(), io::stdout(), STDIN, STDOUT, verbose),
                 (Some(r), None) => decode(File::open(r)?, io::stdout(), r, STDOUT, verbose),
                 (None, Some(w)) => decode(io::stdin(), File::create(w)?, STDIN, w, verbose),
                 (Some(r), Some(w)) => decode(File::open(r)?, File::create(w)?, r, w, verbose),
             }?;
         }
+        ("-compare", Some(m)) => match m.value_of("input") {
+            None => compare(io::stdin(), STDIN),
+            Some(r) => compare(File::open(r)?, r),
+        }?,
         _ => panic!(),
     };
 
+    Ok(true)
+}
+
+// Process each file, continuing after per file errors, returning `false` if any failed.
+fn batch<F>(files: Values, verbose: bool, mut f: F) -> bool
+where
+    F: FnMut(&str) -> lzfse_rust::Result<()>,
+{
+    let mut n_files = 0;
+    let mut n_failed = 0;
+    for path in files {
+        n_files += 1;
+        if let Err(err) = f(path) {
+            eprintln!("Error: {path}: {err}");
+            n_failed += 1;
+        }
+    }
+    if n_failed != 0 || verbose {
+        eprintln!();
+        eprintln!("Files: {n_files}, failed: {n_failed}");
+    }
+    n_failed == 0
+}
+
+fn encode_file(path: &str, store: bool, options: FileOptions) -> lzfse_rust::Result<()> {
+    let metadata = if store { Some(file_metadata(path)?) } else { None };
+    let metadata = metadata.as_ref();
+    let src = File::open(path)?;
+    let verbose = options.verbose;
+    if options.stdout {
+        encode(src, io::stdout(), path, STDOUT, metadata, verbose)?;
+        return Ok(());
+    }
+    let output = format!("{path}{SUFFIX}");
+    with_output(path, &output, options, |dst| {
+        encode(src, dst, path, &output, metadata, verbose).map_err(Into::into)
+    })
+}
+
+fn decode_file(path: &str, restore: bool, options: FileOptions) -> lzfse_rust::Result<()> {
+    if restore {
+        let (dst, n_raw_bytes) =
+            decode_restore(File::open(path)?, path, options.force, options.verbose)?;
+        if !options.keep {
+            verify_output(&dst, n_raw_bytes)?;
+            fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    if options.stdout {
+        decode(File::open(path)?, io::stdout(), path, STDOUT, options.verbose)?;
+        return Ok(());
+    }
+    let output = match path.strip_suffix(SUFFIX) {
+        Some(output) if !output.is_empty() && !output.ends_with('/') => output,
+        _ => {
+            let err = format!("unknown suffix, expected '{SUFFIX}'");
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, err).into());
+        }
+    };
+    let src = File::open(path)?;
+    with_output(path, output, options, |dst| decode(src, dst, path, output, options.verbose))
+}
+
+// Create `output` and pass it to `f`, which returns the number of bytes written. On failure
+// `output` is removed. On success `input` is removed, unless kept, but only once `output` is
+// verified.
+fn with_output<F>(input: &str, output: &str, options: FileOptions, f: F) -> lzfse_rust::Result<()>
+where
+    F: FnOnce(&mut File) -> lzfse_rust::Result<u64>,
+{
+    let mut dst = create_output(output, options.force)
+        .map_err(|err| io::Error::new(err.kind(), format!("{output}: {err}")))?;
+    let result = f(&mut dst).and_then(|n_bytes| {
+        if !options.keep {
+            verify_output(&dst, n_bytes)?;
+        }
+        Ok(())
+    });
+    if let Err(err) = result {
+        let _ = fs::remove_file(output);
+        return Err(err);
+    }
+    if !options.keep {
+        fs::remove_file(input)?;
+    }
+    Ok(())
+}
+
+// Existing files are only overwritten when forced.
+fn create_output<P: AsRef<Path>>(output: P, force: bool) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if force {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+    options.open(output)
+}
+
+// Flush `dst` to storage and verify its length, prior to removing the input.
+fn verify_output(dst: &File, n_bytes: u64) -> io::Result<()> {
+    dst.sync_all()?;
+    if dst.metadata()?.len() != n_bytes {
+        return Err(io::Error::other("short output"));
+    }
     Ok(())
 }
 
@@ -83,31 +255,140 @@ fn encode<R: Read, W: Write>(
     mut dst: W,
     input: &str,
     output: &str,
+    metadata: Option<&Metadata>,
     verbose: bool,
-) -> io::Result<()> {
+) -> io::Result<u64> {
     let instant = if verbose { Some(Instant::now()) } else { None };
+    let n_metadata_bytes = match metadata {
+        Some(metadata) => metadata.write_to(&mut dst)?,
+        None => 0,
+    };
     let (n_raw_bytes, n_payload_bytes) = LzfseRingEncoder::default().encode(&mut src, &mut dst)?;
+    let n_payload_bytes = n_payload_bytes + n_metadata_bytes;
     if let Some(start) = instant {
         stats(start, n_raw_bytes, n_payload_bytes, input, output, Mode::Encode)
     }
-    Ok(())
+    Ok(n_payload_bytes)
 }
 
 fn decode<R: Read, W: Write>(
-    mut src: R,
+    src: R,
     mut dst: W,
     input: &str,
     output: &str,
     verbose: bool,
-) -> lzfse_rust::Result<()> {
+) -> lzfse_rust::Result<u64> {
+    let instant = if verbose { Some(Instant::now()) } else { None };
+    let (_, mut src) = read_metadata(src)?;
+    let (n_payload_bytes, n_raw_bytes) = LzfseRingDecoder::default().decode(&mut src, &mut dst)?;
+    if let Some(start) = instant {
+        stats(start, n_payload_bytes, n_raw_bytes, input, output, Mode::Decode)
+    }
+    Ok(n_raw_bytes)
+}
+
+// Returns the restored file and the number of bytes written into it.
+fn decode_restore<R: Read>(
+    src: R,
+    input: &str,
+    force: bool,
+    verbose: bool,
+) -> lzfse_rust::Result<(File, u64)> {
     let instant = if verbose { Some(Instant::now()) } else { None };
-    let (n_raw_bytes, n_payload_bytes) = LzfseRingDecoder::default().decode(&mut src, &mut dst)?;
+    let (metadata, mut src) = read_metadata(src)?;
+    let metadata = metadata.ok_or(lzfse_rust::Error::BadMetadata)?;
+    // Strip any directory components, we only restore into the current directory.
+    let output = Path::new(&metadata.name).file_name().ok_or(lzfse_rust::Error::BadMetadata)?;
+    let mut dst = create_output(output, force)?;
+    let (n_payload_bytes, n_raw_bytes) = LzfseRingDecoder::default().decode(&mut src, &mut dst)?;
+    dst.set_modified(UNIX_EPOCH + Duration::from_secs(metadata.mtime))?;
     if let Some(start) = instant {
-        stats(start, n_raw_bytes, n_payload_bytes, input, output, Mode::Decode)
+        let output = output.to_string_lossy();
+        stats(start, n_payload_bytes, n_raw_bytes, input, &output, Mode::Decode)
+    }
+    Ok((dst, n_raw_bytes))
+}
+
+// Source positioned at the LZFSE stream, following any metadata frame.
+type MetadataReader<R> = io::Chain<io::Cursor<Vec<u8>>, R>;
+
+// Read the optional metadata frame, returning the source positioned at the LZFSE stream.
+fn read_metadata<R: Read>(mut src: R) -> lzfse_rust::Result<(Option<Metadata>, MetadataReader<R>)> {
+    let mut magic = Vec::default();
+    (&mut src).take(Metadata::MAGIC.len() as u64).read_to_end(&mut magic)?;
+    let is_metadata = Metadata::is_metadata(&magic);
+    let mut src = io::Cursor::new(magic).chain(src);
+    let metadata = if is_metadata { Some(Metadata::read_from(&mut src)?) } else { None };
+    Ok((metadata, src))
+}
+
+fn file_metadata(path: &str) -> io::Result<Metadata> {
+    let name = match Path::new(path).file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "bad file name")),
+    };
+    let mtime = fs::metadata(path)?
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    Ok(Metadata { name, mtime })
+}
+
+fn compare<R: Read>(mut src: R, input: &str) -> lzfse_rust::Result<()> {
+    // Buffer once, stdin cannot be rewound.
+    let mut dec = Vec::default();
+    src.read_to_end(&mut dec)?;
+    let n_raw_bytes = dec.len() as u64;
+    let mut enc = Vec::default();
+    let mut out = Vec::default();
+    println!("Input: {input}");
+    println!("Input size: {n_raw_bytes} B");
+    println!("CPU features: {}", lzfse_rust::cpu_features());
+    println!();
+    println!(
+        "{:<10} {:<14} {:>12} {:>8} {:>14} {:>14}",
+        "Block", "Normalization", "Size (B)", "Ratio", "Encode (MB/s)", "Decode (MB/s)"
+    );
+    for &(block_label, block) in &[
+        ("auto", BlockStrategy::Auto),
+        ("never-raw", BlockStrategy::NeverRaw),
+        ("compact", BlockStrategy::Compact),
+    ] {
+        for &(norm_label, norm) in
+            &[("fast", NormalizationStrategy::Fast), ("accurate", NormalizationStrategy::Accurate)]
+        {
+            let mut encoder = LzfseEncoder::default();
+            encoder.set_block_strategy(block);
+            encoder.set_normalization_strategy(norm);
+            enc.clear();
+            let start = Instant::now();
+            let n_payload_bytes = encoder.encode_bytes(&dec, &mut enc)?;
+            let encode_secs = (Instant::now() - start).as_secs_f64();
+            out.clear();
+            let start = Instant::now();
+            LzfseDecoder::default().decode_bytes(&enc, &mut out)?;
+            let decode_secs = (Instant::now() - start).as_secs_f64();
+            assert!(out == dec, "internal error: round trip mismatch");
+            println!(
+                "{:<10} {:<14} {:>12} {:>8.3} {:>14.2} {:>14.2}",
+                block_label,
+                norm_label,
+                n_payload_bytes,
+                n_raw_bytes as f64 / n_payload_bytes as f64,
+                mb_per_sec(n_raw_bytes, encode_secs),
+                mb_per_sec(n_raw_bytes, decode_secs)
+            );
+        }
     }
     Ok(())
 }
 
+#[inline(always)]
+fn mb_per_sec(n_raw_bytes: u64, secs: f64) -> f64 {
+    n_raw_bytes as f64 / secs / 1024.0 / 1024.0
+}
+
 #[cold]
 fn stats(
     start: Instant,
@@ -124,7 +405,7 @@ fn stats(
         Mode::Decode => (n_output_bytes, n_input_bytes),
     };
     let ns_per_byte = 1.0e9 * secs / n_raw_bytes as f64;
-    let mb_per_sec = n_raw_bytes as f64 / secs / 1024.0 / 1024.0;
+    let mb_per_sec = mb_per_sec(n_raw_bytes, secs);
     if output == STDOUT {
         eprintln!();
     }
@@ -135,6 +416,20 @@ fn stats(
     eprintln!("Output size: {n_output_bytes} B");
     eprintln!("Compression ratio: {:.3}", n_raw_bytes as f64 / n_payload_bytes as f64);
     eprintln!("Speed: {ns_per_byte:.2} ns/B, {mb_per_sec:.2} MB/s");
+    eprintln!("CPU features: {}", lzfse_rust::cpu_features());
+}
+
+// Multiple file flags.
+fn file_args() -> [Arg<'static, 'static>; 3] {
+    [
+        Arg::with_name("keep").short("k").long("keep").help("Keeps (does not delete) FILE inputs"),
+        Arg::with_name("stdout")
+            .short("c")
+            .long("stdout")
+            .help("Writes FILE outputs to standard output, keeping FILE inputs")
+            .conflicts_with("output"),
+        Arg::with_name("force").short("f").long("force").help("Overwrites existing outputs"),
+    ]
 }
 
 fn arg_matches() -> ArgMatches<'static> {
@@ -143,12 +438,41 @@ fn arg_matches() -> ArgMatches<'static> {
         .author("Vin Singh <github.com/shampoofactory>")
         .about("LZFSE compressor/ decompressor")
         .after_help("See 'lzfoo help <command>' for more information on a specific command.")
+        .subcommand(
+            SubCommand::with_name("-compare")
+                .alias("compare")
+                .about("Compare encoder settings")
+                .after_help(
+                    "Encodes and decodes the input with each encoder setting and prints the \
+                     compression ratio and speed. If no input specified reads from standard \
+                     input.",
+                )
+                .arg(
+                    Arg::with_name("input")
+                        .short("i")
+                        .help("input")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("-decode")
                 .alias("decode")
                 .about("Decode (decompress)")
                 .after_help(
-                    "If no input/ output specified reads/ writes from standard input/ output.",
+                    "If no input/ output specified reads/ writes from standard input/ output. \
+                     Metadata frames are skipped unless restoring with -m, in which case the \
+                     output is written to the stored file name in the current directory. \
+                     Multiple FILE inputs are each decoded to their name less the '.lzfse' \
+                     suffix, continuing past any failures. Inputs are removed once their \
+                     output is written and verified unless kept with -k.",
+                )
+                .arg(
+                    Arg::with_name("files")
+                        .help("inputs, each decoded to FILE less the '.lzfse' suffix")
+                        .multiple(true)
+                        .value_name("FILE")
+                        .conflicts_with_all(&["input", "output"]),
                 )
                 .arg(
                     Arg::with_name("input")
@@ -164,6 +488,13 @@ fn arg_matches() -> ArgMatches<'static> {
                         .takes_value(true)
                         .value_name("FILE"),
                 )
+                .arg(
+                    Arg::with_name("m")
+                        .short("m")
+                        .help("Restores the stored file name and modification time")
+                        .conflicts_with_all(&["output", "stdout"]),
+                )
+                .args(&file_args())
                 .arg(Arg::with_name("v").short("v").help("Sets the level of verbosity")),
         )
         .subcommand(
@@ -171,7 +502,18 @@ fn arg_matches() -> ArgMatches<'static> {
                 .alias("encode")
                 .about("Encode (compress)")
                 .after_help(
-                    "If no input/ output specified reads/ writes from standard input/ output",
+                    "If no input/ output specified reads/ writes from standard input/ output. \
+                     Multiple FILE inputs are each encoded to FILE.lzfse, continuing past any \
+                     failures. Inputs are removed once their output is written and verified \
+                     unless kept with -k. The -m metadata frame is specific to lzfoo and is not readable \
+                     by other LZFSE implementations.",
+                )
+                .arg(
+                    Arg::with_name("files")
+                        .help("inputs, each encoded to FILE.lzfse")
+                        .multiple(true)
+                        .value_name("FILE")
+                        .conflicts_with_all(&["input", "output"]),
                 )
                 .arg(
                     Arg::with_name("input")
@@ -187,6 +529,12 @@ fn arg_matches() -> ArgMatches<'static> {
                         .takes_value(true)
                         .value_name("FILE"),
                 )
+                .arg(
+                    Arg::with_name("m")
+                        .short("m")
+                        .help("Stores the input file name and modification time (non standard)"),
+                )
+                .args(&file_args())
                 .arg(Arg::with_name("v").short("v").help("Sets the level of verbosity")),
         )
         .setting(AppSettings::SubcommandRequiredElseHelp)
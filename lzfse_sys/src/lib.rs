@@ -26,7 +26,116 @@ pub fn decode(src: &[u8], dst: &mut [u8]) -> usize {
     }
 }
 
+/// As [encode], but with a reusable `scratch` buffer in place of the reference library's
+/// internal per call allocation. `scratch` is resized as required.
+pub fn encode_with_scratch(src: &[u8], dst: &mut [u8], scratch: &mut Vec<u8>) -> usize {
+    let scratch_ptr = scratch_ptr(scratch, encode_scratch_size());
+    unsafe {
+        lzfse_encode_buffer(
+            dst.as_mut_ptr() as *mut _,
+            dst.len() as size_t,
+            src.as_ptr() as *const _,
+            src.len() as size_t,
+            scratch_ptr,
+        )
+    }
+}
+
+/// As [decode], but with a reusable `scratch` buffer in place of the reference library's
+/// internal per call allocation. `scratch` is resized as required.
+pub fn decode_with_scratch(src: &[u8], dst: &mut [u8], scratch: &mut Vec<u8>) -> usize {
+    let scratch_ptr = scratch_ptr(scratch, decode_scratch_size());
+    unsafe {
+        lzfse_decode_buffer(
+            dst.as_mut_ptr() as *mut _,
+            dst.len() as size_t,
+            src.as_ptr() as *const _,
+            src.len() as size_t,
+            scratch_ptr,
+        )
+    }
+}
+
+/// The reference encoder scratch size in bytes.
+pub fn encode_scratch_size() -> usize {
+    unsafe { lzfse_encode_scratch_size() }
+}
+
+/// The reference decoder scratch size in bytes.
+pub fn decode_scratch_size() -> usize {
+    unsafe { lzfse_decode_scratch_size() }
+}
+
+/// Encode `src` returning the encoded bytes, or `None` on failure.
+///
+/// The destination is sized and grown as required, up to the reference encoder's raw block
+/// bound of `src.len() + 12` bytes which is always sufficient.
+pub fn encode_vec(src: &[u8]) -> Option<Vec<u8>> {
+    let mut scratch = Vec::default();
+    let max = src.len() + RAW_OVERHEAD;
+    let mut dst = vec![0; (src.len() / 2 + 0x0100).min(max)];
+    loop {
+        let n = encode_with_scratch(src, &mut dst, &mut scratch);
+        if n != 0 {
+            dst.truncate(n);
+            return Some(dst);
+        }
+        if dst.len() == max {
+            return None;
+        }
+        dst.resize((dst.len() * 2).min(max), 0);
+    }
+}
+
+/// Decode `src` returning the decoded bytes, or `None` if `src` is corrupt.
+///
+/// The destination is initially sized to `hint` bytes and grown as required. The reference
+/// decoder returns the destination length when the destination is too small and zero on
+/// failure. As such streams that decode to zero bytes are indistinguishable from corrupt
+/// streams and are also reported as `None`.
+pub fn decode_vec(src: &[u8], hint: usize) -> Option<Vec<u8>> {
+    let mut scratch = Vec::default();
+    // Plus one, a full destination is otherwise indistinguishable from an exact fit.
+    let mut dst = vec![0; hint.max(0x0100) + 1];
+    loop {
+        let n = decode_with_scratch(src, &mut dst, &mut scratch);
+        if n == 0 {
+            return None;
+        }
+        if n < dst.len() {
+            dst.truncate(n);
+            return Some(dst);
+        }
+        dst.resize(dst.len() * 2, 0);
+    }
+}
+
+// Reference encoder raw block overhead: block header and end of stream marker.
+const RAW_OVERHEAD: usize = 12;
+
+// Reference scratch buffers hold C state structs, we align them generously.
+const SCRATCH_ALIGN: usize = 16;
+
+fn scratch_ptr(scratch: &mut Vec<u8>, len: usize) -> *mut c_void {
+    if scratch.len() < len + SCRATCH_ALIGN {
+        scratch.resize(len + SCRATCH_ALIGN, 0);
+    }
+    let offset = scratch.as_ptr().align_offset(SCRATCH_ALIGN);
+    assert!(offset < SCRATCH_ALIGN);
+    unsafe { scratch.as_mut_ptr().add(offset) as *mut c_void }
+}
+
 extern "C" {
+    // *  @return
+    // *  The number of bytes required to use as scratch space for
+    // *  lzfse_encode_buffer.
+    pub fn lzfse_encode_scratch_size() -> size_t;
+
+    // *  @return
+    // *  The number of bytes required to use as scratch space for
+    // *  lzfse_decode_buffer.
+    pub fn lzfse_decode_scratch_size() -> size_t;
+
     // *  @return
     // *  The number of bytes written to the destination buffer if the input is
     // *  successfully compressed. If the input cannot be compressed to fit into
@@ -70,4 +179,40 @@ mod tests {
         let n = decode(&enc[..n], dec.as_mut());
         assert_eq!(DATA, &dec[..n]);
     }
+
+    #[test]
+    fn encode_decode_vec() {
+        for n in [0, 1, DATA.len()] {
+            let src = DATA.repeat(0x0100 * n);
+            let enc = encode_vec(&src).unwrap();
+            for hint in [0, src.len()] {
+                match decode_vec(&enc, hint) {
+                    Some(dec) => assert_eq!(src, dec),
+                    None => assert!(src.is_empty()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn encode_decode_scratch() {
+        let mut scratch = Vec::default();
+        for _ in 0..4 {
+            let mut enc = [0u8; 256];
+            let n = encode_with_scratch(DATA, enc.as_mut(), &mut scratch);
+            assert!(scratch.len() >= encode_scratch_size());
+            let mut dec = [0u8; 256];
+            let n = decode_with_scratch(&enc[..n], dec.as_mut(), &mut scratch);
+            assert!(scratch.len() >= decode_scratch_size());
+            assert_eq!(DATA, &dec[..n]);
+        }
+    }
+
+    #[test]
+    fn decode_vec_corrupt() {
+        let mut enc = encode_vec(DATA).unwrap();
+        enc.truncate(enc.len() - 1);
+        assert_eq!(decode_vec(&enc, 0), None);
+        assert_eq!(decode_vec(b"corrupt", 0), None);
+    }
 }
@@ -0,0 +1,292 @@
+use crate::decode::LzfseRingDecoder;
+use crate::encode::LzfseRingEncoder;
+use crate::error::Error;
+
+use std::convert::TryInto;
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+// Footer layout: index offset (u64 LE), member count (u32 LE), magic bytes.
+const FOOTER_LEN: u64 = 16;
+
+const MAGIC: [u8; 4] = *b"lzfa";
+
+/// Archive member index entry.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ArchiveMember {
+    /// Member name.
+    pub name: String,
+    /// Offset of the member LZFSE stream from the start of the archive.
+    pub offset: u64,
+    /// Decoded member size.
+    pub n_raw_bytes: u64,
+    /// Encoded member size.
+    pub n_payload_bytes: u64,
+}
+
+impl ArchiveMember {
+    fn write_to<W: Write>(&self, dst: &mut W) -> io::Result<()> {
+        let len: u16 = self
+            .name
+            .len()
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "name overflow"))?;
+        dst.write_all(&len.to_le_bytes())?;
+        dst.write_all(self.name.as_bytes())?;
+        dst.write_all(&self.offset.to_le_bytes())?;
+        dst.write_all(&self.n_raw_bytes.to_le_bytes())?;
+        dst.write_all(&self.n_payload_bytes.to_le_bytes())?;
+        Ok(())
+    }
+
+    fn read_from(src: &mut &[u8]) -> crate::Result<Self> {
+        let len = u16::from_le_bytes(take(src, 2)?.try_into().unwrap()) as usize;
+        let name = String::from_utf8(take(src, len)?.to_vec())
+            .map_err(|err| Error::Utf8(err.utf8_error()))?;
+        let offset = u64::from_le_bytes(take(src, 8)?.try_into().unwrap());
+        let n_raw_bytes = u64::from_le_bytes(take(src, 8)?.try_into().unwrap());
+        let n_payload_bytes = u64::from_le_bytes(take(src, 8)?.try_into().unwrap());
+        Ok(Self { name, offset, n_raw_bytes, n_payload_bytes })
+    }
+}
+
+fn take<'a>(src: &mut &'a [u8], n: usize) -> crate::Result<&'a [u8]> {
+    if src.len() < n {
+        return Err(Error::BadArchive);
+    }
+    let (head, tail) = src.split_at(n);
+    *src = tail;
+    Ok(head)
+}
+
+/// Archive writer.
+///
+/// A lightweight container of multiple named members, each an independent LZFSE stream, followed
+/// by an index and footer enabling random access, see [ArchiveReader]. The container is specific
+/// to this crate and is NOT part of the LZFSE format. Layout: member streams, index entries in
+/// member order, footer. Index entry: name length (u16 LE), UTF-8 name, stream offset (u64 LE),
+/// decoded size (u64 LE), encoded size (u64 LE). Footer: index offset (u64 LE), member count
+/// (u32 LE), magic bytes `lzfa`.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{ArchiveReader, ArchiveWriter};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = ArchiveWriter::new(Vec::default());
+///     writer.add("a.txt", &mut b"test a".as_ref())?;
+///     writer.add("b.txt", &mut b"test b".as_ref())?;
+///     let archive = writer.finalize()?;
+///     let mut reader = ArchiveReader::new(Cursor::new(archive))?;
+///     assert_eq!(reader.members().len(), 2);
+///     let mut dec = Vec::default();
+///     reader.extract("b.txt", &mut dec)?;
+///     assert_eq!(dec, b"test b");
+///     Ok(())
+/// }
+/// ```
+pub struct ArchiveWriter<W: Write> {
+    encoder: LzfseRingEncoder,
+    inner: W,
+    members: Vec<ArchiveMember>,
+    offset: u64,
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    /// Create a new instance writing the archive into `inner`.
+    pub fn new(inner: W) -> Self {
+        Self { encoder: LzfseRingEncoder::default(), inner, members: Vec::default(), offset: 0 }
+    }
+
+    /// Encode `reader` as member `name`, returning the member index entry.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::InvalidInput](std::io::ErrorKind) if `name` exceeds `u16::MAX` bytes or
+    ///   duplicates an existing member name.
+    /// * [Error](std::io::Error) in case of `reader` or `inner` IO errors, after which the archive
+    ///   is incomplete.
+    pub fn add<R: Read>(&mut self, name: &str, reader: &mut R) -> io::Result<&ArchiveMember> {
+        if name.len() > u16::MAX as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "name overflow"));
+        }
+        if self.members.iter().any(|member| member.name == name) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "duplicate name"));
+        }
+        let (n_raw_bytes, n_payload_bytes) = self.encoder.encode(reader, &mut self.inner)?;
+        self.members.push(ArchiveMember {
+            name: name.to_owned(),
+            offset: self.offset,
+            n_raw_bytes,
+            n_payload_bytes,
+        });
+        self.offset += n_payload_bytes;
+        Ok(self.members.last().unwrap())
+    }
+
+    /// Members added so far.
+    #[inline(always)]
+    pub fn members(&self) -> &[ArchiveMember] {
+        &self.members
+    }
+
+    /// Write the index and footer, returning the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::InvalidInput](std::io::ErrorKind) if the member count exceeds `u32::MAX`.
+    /// * [Error](std::io::Error) in case of `inner` IO errors.
+    pub fn finalize(mut self) -> io::Result<W> {
+        let n_members: u32 = self
+            .members
+            .len()
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "member overflow"))?;
+        for member in self.members.iter() {
+            member.write_to(&mut self.inner)?;
+        }
+        self.inner.write_all(&self.offset.to_le_bytes())?;
+        self.inner.write_all(&n_members.to_le_bytes())?;
+        self.inner.write_all(&MAGIC)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> fmt::Debug for ArchiveWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchiveWriter").field("members", &self.members).finish()
+    }
+}
+
+/// Archive reader.
+///
+/// Reads the index of an archive written by [ArchiveWriter] on construction. Members are then
+/// extracted by name, seeking directly to the member stream without reading other members.
+pub struct ArchiveReader<R: Read + Seek> {
+    decoder: LzfseRingDecoder,
+    inner: R,
+    members: Vec<ArchiveMember>,
+}
+
+impl<R: Read + Seek> ArchiveReader<R> {
+    /// Create a new instance reading the archive index from `inner`.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::BadArchive](crate::Error::BadArchive) if the footer or index is missing or
+    ///   malformed.
+    /// * [Error::Utf8](crate::Error::Utf8) if a member name is not valid UTF-8.
+    /// * [Error](crate::Error) in case of `inner` IO errors.
+    pub fn new(mut inner: R) -> crate::Result<Self> {
+        let end = inner.seek(SeekFrom::End(0))?;
+        let footer_pos = end.checked_sub(FOOTER_LEN).ok_or(Error::BadArchive)?;
+        let mut footer = [0u8; FOOTER_LEN as usize];
+        inner.seek(SeekFrom::Start(footer_pos))?;
+        inner.read_exact(&mut footer)?;
+        if footer[12..] != MAGIC {
+            return Err(Error::BadArchive);
+        }
+        let index_pos = u64::from_le_bytes(footer[..8].try_into().unwrap());
+        let n_members = u32::from_le_bytes(footer[8..12].try_into().unwrap());
+        let index_len = footer_pos.checked_sub(index_pos).ok_or(Error::BadArchive)?;
+        let index_len: usize = index_len.try_into().map_err(|_| Error::BadArchive)?;
+        let mut index = vec![0u8; index_len];
+        inner.seek(SeekFrom::Start(index_pos))?;
+        inner.read_exact(&mut index)?;
+        let mut src = index.as_slice();
+        let mut members = Vec::default();
+        for _ in 0..n_members {
+            let member = ArchiveMember::read_from(&mut src)?;
+            match member.offset.checked_add(member.n_payload_bytes) {
+                Some(u) if u <= index_pos => {}
+                _ => return Err(Error::BadArchive),
+            }
+            members.push(member);
+        }
+        if !src.is_empty() {
+            return Err(Error::BadArchive);
+        }
+        Ok(Self { decoder: LzfseRingDecoder::default(), inner, members })
+    }
+
+    /// Archive members in order.
+    #[inline(always)]
+    pub fn members(&self) -> &[ArchiveMember] {
+        &self.members
+    }
+
+    /// Returns the index entry of member `name`, if present.
+    pub fn member(&self, name: &str) -> Option<&ArchiveMember> {
+        self.members.iter().find(|member| member.name == name)
+    }
+
+    /// Extract member `name` into `writer` returning the number of bytes written into `writer`.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::MemberNotFound](crate::Error::MemberNotFound) if `name` is not present.
+    /// * [Error::OutputSizeMismatch](crate::Error::OutputSizeMismatch) if the decoded size does
+    ///   not match the index entry.
+    /// * [Error](crate::Error) detailing the nature of any other errors.
+    pub fn extract<W: Write>(&mut self, name: &str, writer: &mut W) -> crate::Result<u64> {
+        let member = self.member(name).ok_or(Error::MemberNotFound)?;
+        let (offset, n_raw_bytes, n_payload_bytes) =
+            (member.offset, member.n_raw_bytes, member.n_payload_bytes);
+        self.inner.seek(SeekFrom::Start(offset))?;
+        let mut reader = (&mut self.inner).take(n_payload_bytes);
+        let (_, n) = self.decoder.decode(&mut reader, writer)?;
+        if n != n_raw_bytes {
+            return Err(Error::OutputSizeMismatch);
+        }
+        Ok(n)
+    }
+
+    /// Unwraps this `ArchiveReader<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read + Seek> fmt::Debug for ArchiveReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchiveReader").field("members", &self.members).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn member_round_trip() -> crate::Result<()> {
+        for name in &["", "a.txt", "ταχίστη αλώπηξ"] {
+            let member = ArchiveMember {
+                name: (*name).to_owned(),
+                offset: 1,
+                n_raw_bytes: 2,
+                n_payload_bytes: u64::MAX,
+            };
+            let mut dst = Vec::default();
+            member.write_to(&mut dst)?;
+            let mut src = dst.as_slice();
+            assert_eq!(ArchiveMember::read_from(&mut src)?, member);
+            assert!(src.is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn member_truncated() -> crate::Result<()> {
+        let member = ArchiveMember { name: "a.txt".to_owned(), ..ArchiveMember::default() };
+        let mut dst = Vec::default();
+        member.write_to(&mut dst)?;
+        for n in 0..dst.len() {
+            let mut src = &dst[..n];
+            assert!(matches!(ArchiveMember::read_from(&mut src), Err(Error::BadArchive)));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use super::magic_bytes::MagicBytes;
+
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::mem;
+
+/// LZFSE block kind, as identified by the block's leading magic bytes.
+///
+/// A stream is a sequence of blocks terminated by an end of stream block. See [peek_frame_kind].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FrameKind {
+    /// Uncompressed block, magic bytes `bvx-`.
+    Raw,
+    /// LZVN block, magic bytes `bvxn`.
+    Vxn,
+    /// LZFSE V1 block with uncompressed tables, magic bytes `bvx1`.
+    Vx1,
+    /// LZFSE V2 block with compressed tables, magic bytes `bvx2`.
+    Vx2,
+    /// End of stream block, magic bytes `bvx$`.
+    Eos,
+}
+
+impl FrameKind {
+    /// Returns the kind's magic bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::FrameKind;
+    ///
+    /// assert_eq!(&FrameKind::Vx2.magic_bytes(), b"bvx2");
+    /// ```
+    pub fn magic_bytes(self) -> [u8; 4] {
+        u32::from(MagicBytes::from(self)).to_le_bytes()
+    }
+}
+
+impl From<MagicBytes> for FrameKind {
+    #[inline(always)]
+    fn from(v: MagicBytes) -> Self {
+        match v {
+            MagicBytes::Eos => Self::Eos,
+            MagicBytes::Raw => Self::Raw,
+            MagicBytes::Vx1 => Self::Vx1,
+            MagicBytes::Vx2 => Self::Vx2,
+            MagicBytes::Vxn => Self::Vxn,
+        }
+    }
+}
+
+impl From<FrameKind> for MagicBytes {
+    #[inline(always)]
+    fn from(v: FrameKind) -> Self {
+        match v {
+            FrameKind::Eos => Self::Eos,
+            FrameKind::Raw => Self::Raw,
+            FrameKind::Vx1 => Self::Vx1,
+            FrameKind::Vx2 => Self::Vx2,
+            FrameKind::Vxn => Self::Vxn,
+        }
+    }
+}
+
+impl fmt::Display for FrameKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let magic_bytes = self.magic_bytes();
+        // Magic bytes are ASCII.
+        f.write_str(std::str::from_utf8(&magic_bytes).unwrap())
+    }
+}
+
+/// Identify the kind of the LZFSE block at the start of `src` from its leading magic bytes,
+/// without decoding.
+///
+/// Only the first four bytes are inspected, the block itself may still contain errors. Applied to
+/// a stream, this identifies the first block, which for streams produced by this crate is
+/// [FrameKind::Raw] for tiny inputs, [FrameKind::Vxn] for small inputs and [FrameKind::Vx2]
+/// otherwise.
+///
+/// # Errors
+///
+/// * [Error::PayloadUnderflow](crate::Error::PayloadUnderflow) if `src` is shorter than four
+///   bytes.
+/// * [Error::BadBlock](crate::Error::BadBlock) if `src` does not begin with LZFSE magic bytes.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::FrameKind;
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     lzfse_rust::encode_bytes(b"test", &mut enc)?;
+///     assert_eq!(lzfse_rust::peek_frame_kind(&enc)?, FrameKind::Raw);
+///     assert_eq!(lzfse_rust::peek_frame_kind(&enc[12..])?, FrameKind::Eos);
+///     Ok(())
+/// }
+/// ```
+pub fn peek_frame_kind(src: &[u8]) -> crate::Result<FrameKind> {
+    let bytes = src.get(..mem::size_of::<u32>()).ok_or(crate::Error::PayloadUnderflow)?;
+    let u = u32::from_le_bytes(bytes.try_into().unwrap());
+    MagicBytes::try_from(u).map(FrameKind::from)
+}
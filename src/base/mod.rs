@@ -1,3 +1,7 @@
+mod frame_kind;
 mod magic_bytes;
+mod trailer_kind;
 
+pub use frame_kind::{peek_frame_kind, FrameKind};
 pub use magic_bytes::MagicBytes;
+pub use trailer_kind::TrailerKind;
@@ -0,0 +1,61 @@
+use std::convert::TryFrom;
+
+const TM_OPAQUE: u32 = 0x7478_7662;
+const TM_CRC32: u32 = 0x6378_7662;
+const TM_DIGEST: u32 = 0x6478_7662;
+
+/// Stream trailer kind, as identified by the trailer's leading magic bytes.
+///
+/// Trailers optionally follow the end of stream block and are skipped by this crate's decoders.
+/// Trailer layout: magic bytes (u32 LE), payload length (u32 LE), payload.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TrailerKind {
+    /// Opaque payload, magic bytes `bvxt`. Output padding, see
+    /// [set_pad_output_to](crate::LzfseEncoder::set_pad_output_to), is an opaque zero filled
+    /// trailer.
+    Opaque,
+    /// CRC32 checksum and decoded length, magic bytes `bvxc`, see [checksum](crate::checksum).
+    Crc32,
+    /// Decoded data digest, magic bytes `bvxd`, see `encode_with_digest`.
+    Digest,
+}
+
+impl TrailerKind {
+    /// Returns the kind's magic bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::TrailerKind;
+    ///
+    /// assert_eq!(&TrailerKind::Crc32.magic_bytes(), b"bvxc");
+    /// ```
+    pub fn magic_bytes(self) -> [u8; 4] {
+        u32::from(self).to_le_bytes()
+    }
+}
+
+impl TryFrom<u32> for TrailerKind {
+    type Error = crate::Error;
+
+    #[inline(always)]
+    fn try_from(u: u32) -> Result<TrailerKind, Self::Error> {
+        match u {
+            TM_OPAQUE => Ok(TrailerKind::Opaque),
+            TM_CRC32 => Ok(TrailerKind::Crc32),
+            TM_DIGEST => Ok(TrailerKind::Digest),
+            _ => Err(crate::Error::BadTrailer),
+        }
+    }
+}
+
+impl From<TrailerKind> for u32 {
+    #[inline(always)]
+    fn from(v: TrailerKind) -> Self {
+        match v {
+            TrailerKind::Opaque => TM_OPAQUE,
+            TrailerKind::Crc32 => TM_CRC32,
+            TrailerKind::Digest => TM_DIGEST,
+        }
+    }
+}
@@ -47,9 +47,9 @@ impl BitDst for Vec<u8> {
         debug_assert!(n_bytes <= mem::size_of::<usize>());
         let index = self.len();
         assert!(mem::size_of::<usize>() <= self.capacity() - self.len());
-        let src = bytes.to_le_bytes().as_ptr();
+        let src = bytes.to_le_bytes();
         let dst = self.as_mut_ptr().add(index);
-        ptr::copy_nonoverlapping(src, dst, mem::size_of::<usize>());
+        ptr::copy_nonoverlapping(src.as_ptr(), dst, mem::size_of::<usize>());
         self.set_len(index + n_bytes);
     }
 
@@ -0,0 +1,140 @@
+/*!
+CRC32 checksummed LZFSE streams.
+
+LZFSE has no integrity check of its own, corrupted data may decode without error. This module
+encodes a standard LZFSE stream followed by a trailer holding the CRC32 (IEEE) checksum and the
+length of the decoded data, both of which are verified on decoding.
+
+The LZFSE stream itself is unaltered. The trailer follows the end of stream block, it is ignored
+by this crate's decoders and by other LZFSE implementations that stop at the end of stream block.
+See [strip_frame](crate::strip_frame) to obtain the bare stream.
+
+# Examples
+
+```
+use std::io;
+
+fn main() -> io::Result<()> {
+    let mut enc = Vec::default();
+    lzfse_rust::checksum::encode_bytes(b"test", &mut enc)?;
+    let mut dec = Vec::default();
+    lzfse_rust::checksum::decode_bytes(&enc, &mut dec)?;
+    assert_eq!(dec, b"test");
+    // Plain decoding ignores the trailer.
+    dec.clear();
+    lzfse_rust::decode_bytes(&enc, &mut dec)?;
+    assert_eq!(dec, b"test");
+    Ok(())
+}
+```
+*/
+
+use crate::base::TrailerKind;
+use crate::decode::LzfseDecoder;
+use crate::encode::LzfseEncoder;
+use crate::error::Error;
+use crate::trailer;
+
+use std::convert::TryInto;
+use std::io;
+use std::mem;
+
+const U32: usize = mem::size_of::<u32>();
+
+// Trailer payload: CRC32 u32, decoded length u64.
+const PAYLOAD_LEN: usize = U32 + mem::size_of::<u64>();
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+/// Encode `src` into `dst` followed by a checksum trailer, returning the number of bytes written
+/// into `dst`.
+///
+/// # Errors
+///
+/// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` or `dst` buffer overflow.
+pub fn encode_bytes(src: &[u8], dst: &mut Vec<u8>) -> io::Result<u64> {
+    let mark = dst.len();
+    LzfseEncoder::default().encode_bytes(src, dst)?;
+    let crc = crc32(src).to_le_bytes();
+    let n = (src.len() as u64).to_le_bytes();
+    trailer::write(dst, TrailerKind::Crc32, vec![&crc[..], &n[..]])?;
+    Ok((dst.len() - mark) as u64)
+}
+
+/// Decode `src` into `dst`, as encoded by [encode_bytes], verifying the decoded output against
+/// the trailer checksum and length. Returns the number of bytes written into `dst`.
+///
+/// # Errors
+///
+/// * [Error::BadTrailer](crate::Error::BadTrailer) if the trailers are malformed or none is a
+///   [TrailerKind::Crc32](crate::TrailerKind::Crc32) checksum trailer.
+/// * [Error::ChecksumMismatch](crate::Error::ChecksumMismatch) if the decoded output checksum or
+///   length does not match the trailer, in which case `dst` contains the decoded output.
+/// * [Error](crate::Error) detailing the nature of any other decoding errors.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::Error;
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     lzfse_rust::checksum::encode_bytes(b"test", &mut enc)?;
+///     // Corrupt the raw block payload, which LZFSE alone cannot detect.
+///     enc[8] ^= 1;
+///     let mut dec = Vec::default();
+///     match lzfse_rust::checksum::decode_bytes(&enc, &mut dec) {
+///         Err(Error::ChecksumMismatch) => {}
+///         _ => panic!(),
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn decode_bytes(src: &[u8], dst: &mut Vec<u8>) -> crate::Result<u64> {
+    let trailer = trailer::find(src, TrailerKind::Crc32).map_err(|_| Error::BadTrailer)?;
+    if trailer.len() != PAYLOAD_LEN {
+        return Err(Error::BadTrailer);
+    }
+    let crc = u32::from_le_bytes(trailer[..U32].try_into().unwrap());
+    let n = u64::from_le_bytes(trailer[U32..].try_into().unwrap());
+    let mark = dst.len();
+    let n_raw_bytes = LzfseDecoder::default().decode_bytes(src, dst)?;
+    if n_raw_bytes != n || crc32(&dst[mark..]) != crc {
+        return Err(Error::ChecksumMismatch);
+    }
+    Ok(n_raw_bytes)
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &u| CRC32_TABLE[((crc ^ u as u32) & 0xFF) as usize] ^ (crc >> 8))
+}
+
+// CRC32 (IEEE), reflected polynomial.
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_check() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414F_A339);
+    }
+}
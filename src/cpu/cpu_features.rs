@@ -0,0 +1,80 @@
+use std::fmt;
+
+/// CPU acceleration features.
+///
+/// Describes which acceleration paths are compiled into this build and whether the host CPU
+/// supports them. Obtain via [cpu_features].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CpuFeatures {
+    bmi2: bool,
+    bmi2_detected: bool,
+}
+
+impl CpuFeatures {
+    /// Returns `true` if BMI2 optimized bit masks are compiled in.
+    ///
+    /// BMI2 is enabled at compile time, e.g. `RUSTFLAGS="-C target-cpu=native"`.
+    #[inline(always)]
+    pub fn bmi2(&self) -> bool {
+        self.bmi2
+    }
+
+    /// Returns `true` if the host CPU supports BMI2 instructions, regardless of whether they are
+    /// compiled in.
+    #[inline(always)]
+    pub fn bmi2_detected(&self) -> bool {
+        self.bmi2_detected
+    }
+}
+
+impl fmt::Display for CpuFeatures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.bmi2, self.bmi2_detected) {
+            (true, _) => f.write_str("bmi2: enabled"),
+            (false, true) => f.write_str("bmi2: disabled (supported)"),
+            (false, false) => f.write_str("bmi2: disabled"),
+        }
+    }
+}
+
+/// Query the CPU acceleration features in use.
+///
+/// Compiled in features reflect the same `cfg` flags used internally, detected features are
+/// queried at runtime.
+///
+/// # Examples
+///
+/// ```
+/// let features = lzfse_rust::cpu_features();
+/// if features.bmi2() {
+///     assert!(features.bmi2_detected());
+/// }
+/// ```
+pub fn cpu_features() -> CpuFeatures {
+    CpuFeatures { bmi2: cfg!(target_feature = "bmi2"), bmi2_detected: bmi2_detected() }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn bmi2_detected() -> bool {
+    is_x86_feature_detected!("bmi2")
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn bmi2_detected() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consistent() {
+        let features = cpu_features();
+        assert_eq!(features, cpu_features());
+        assert_eq!(features.bmi2(), cfg!(target_feature = "bmi2"));
+        if features.bmi2() {
+            assert!(features.bmi2_detected());
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod cpu_features;
+
+pub use cpu_features::{cpu_features, CpuFeatures};
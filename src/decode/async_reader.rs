@@ -0,0 +1,218 @@
+use crate::base::MagicBytes;
+use crate::error::Error;
+use crate::fse::Fse;
+use crate::lmd::DMax;
+use crate::ops::PeekData;
+use crate::trailer;
+
+use super::constants::{HEADER_LIMIT, READ_LEN};
+use super::decoder::LzfseDecoder;
+use super::probe::probe_block;
+
+use tokio::io::{AsyncRead, ReadBuf};
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+// Retained decoded history, sufficient for any match distance.
+const HISTORY_LEN: usize = Fse::MAX_MATCH_DISTANCE as usize + 1;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum State {
+    Block,
+    Eos,
+    Err,
+}
+
+/// LZFSE asynchronous decoding reader.
+///
+/// Exposes a LZFSE decoder via the tokio [AsyncRead] interface, decoding from an inner
+/// [AsyncRead]. Encoded input is staged in an internal buffer until a complete block is
+/// available, the block is then decoded synchronously, never blocking. Memory use is bounded by
+/// the largest block in the stream, see
+/// [LzfseDecoder::set_max_block_payload](crate::LzfseDecoder::set_max_block_payload), plus the
+/// retained match history.
+///
+/// As with the synchronous decoders the inner reader is read to its end, bytes that follow the
+/// end of stream block are validated as a trailer.
+///
+/// Instances are created using
+/// [LzfseDecoder::async_reader](crate::LzfseDecoder::async_reader) or
+/// [LzfseRingDecoder::async_reader](crate::LzfseRingDecoder::async_reader).
+///
+/// # Errors
+///
+/// * [Error](crate::Error) detailing the nature of any errors, converted into an
+///   [io::Error](std::io::Error).
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::LzfseDecoder;
+/// use tokio::io::AsyncReadExt;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// // "test" string encoded.
+/// let enc = vec![
+///     0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76, 0x78,
+///     0x24,
+/// ];
+/// let mut decoder = LzfseDecoder::default();
+/// let mut reader = decoder.async_reader(enc.as_slice());
+/// let mut dec = Vec::default();
+/// reader.read_to_end(&mut dec).await?;
+/// assert_eq!(dec, b"test");
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncLzfseReader<'a, I> {
+    decoder: &'a mut LzfseDecoder,
+    inner: I,
+    src: Vec<u8>,
+    src_pos: usize,
+    dst: Vec<u8>,
+    dst_pos: usize,
+    is_eof: bool,
+    state: State,
+}
+
+impl<'a, I> AsyncLzfseReader<'a, I> {
+    #[inline(always)]
+    pub(super) fn new(decoder: &'a mut LzfseDecoder, inner: I) -> Self {
+        Self {
+            decoder,
+            inner,
+            src: Vec::default(),
+            src_pos: 0,
+            dst: Vec::default(),
+            dst_pos: 0,
+            is_eof: false,
+            state: State::Block,
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    pub fn get_ref(&self) -> &I {
+        &self.inner
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    // Decode the next block if it is complete, returning `false` if more input is required.
+    fn decode_block(&mut self) -> crate::Result<bool> {
+        let src = &self.src[self.src_pos..];
+        if src.len() < 4 {
+            return if self.is_eof { Err(Error::PayloadUnderflow) } else { Ok(false) };
+        }
+        let magic_bytes = MagicBytes::try_from(src.peek_u32())?;
+        if magic_bytes == MagicBytes::Eos {
+            if !self.is_eof {
+                return Ok(false);
+            }
+            trailer::skip_eos(&mut &src[..])?;
+            self.src_pos = self.src.len();
+            self.state = State::Eos;
+            return Ok(true);
+        }
+        let n_payload_bytes = match probe_block(src, magic_bytes) {
+            Ok((n_payload_bytes, _)) => n_payload_bytes as usize,
+            Err(_) if src.len() < HEADER_LIMIT && !self.is_eof => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        if src.len() < n_payload_bytes {
+            return if self.is_eof { Err(Error::PayloadUnderflow) } else { Ok(false) };
+        }
+        let mut block = &src[..n_payload_bytes];
+        self.decoder.block_vec(&mut self.dst, &mut block)?;
+        if !block.is_empty() {
+            return Err(Error::PayloadOverflow);
+        }
+        self.src_pos += n_payload_bytes;
+        Ok(true)
+    }
+
+    // Discard delivered output, retaining sufficient history for subsequent blocks.
+    fn trim(&mut self) {
+        debug_assert_eq!(self.dst_pos, self.dst.len());
+        if self.dst.len() >= HISTORY_LEN * 2 {
+            let n = self.dst.len() - HISTORY_LEN;
+            self.dst.drain(..n);
+            self.dst_pos -= n;
+        }
+    }
+}
+
+impl<'a, I: AsyncRead + Unpin> AsyncLzfseReader<'a, I> {
+    // Read more encoded input from the inner reader.
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.src_pos != 0 {
+            self.src.drain(..self.src_pos);
+            self.src_pos = 0;
+        }
+        let len = self.src.len();
+        self.src.resize(len + READ_LEN, 0);
+        let mut buf = ReadBuf::new(&mut self.src[len..]);
+        let poll = Pin::new(&mut self.inner).poll_read(cx, &mut buf);
+        let n = buf.filled().len();
+        self.src.truncate(len + n);
+        if let Poll::Ready(Ok(())) = poll {
+            self.is_eof = n == 0;
+        }
+        poll
+    }
+}
+
+impl<'a, I: AsyncRead + Unpin> AsyncRead for AsyncLzfseReader<'a, I> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.dst_pos < this.dst.len() {
+                let n = (this.dst.len() - this.dst_pos).min(buf.remaining());
+                buf.put_slice(&this.dst[this.dst_pos..this.dst_pos + n]);
+                this.dst_pos += n;
+                return Poll::Ready(Ok(()));
+            }
+            match this.state {
+                State::Block => {}
+                State::Eos => return Poll::Ready(Ok(())),
+                State::Err => return Poll::Ready(Err(Error::BadReaderState.into())),
+            }
+            if buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+            this.trim();
+            match this.decode_block() {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(err) => {
+                    this.state = State::Err;
+                    return Poll::Ready(Err(err.into()));
+                }
+            }
+            match this.poll_fill(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(err)) => {
+                    this.state = State::Err;
+                    return Poll::Ready(Err(err));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<'a, I> fmt::Debug for AsyncLzfseReader<'a, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncLzfseReader").finish()
+    }
+}
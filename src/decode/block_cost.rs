@@ -0,0 +1,39 @@
+/// LZFSE FSE block bit cost breakdown.
+///
+/// Reports how the encoded bits of a single FSE compressed block are spent. Produced by
+/// [LzfseDecoder::explain](crate::LzfseDecoder::explain).
+///
+/// The header, literal, literal length, match length, match distance and padding bits together
+/// account for the block's payload in full.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BlockCost {
+    /// Block index within the stream, counting all block types.
+    pub index: u32,
+    /// Decoded block size in bytes.
+    pub n_raw_bytes: u32,
+    /// Encoded block size in bytes, including the header.
+    pub n_payload_bytes: u32,
+    /// Header bits, including the FSE weights and initial states.
+    pub header_bits: u64,
+    /// Literal bits.
+    pub literal_bits: u64,
+    /// Literal length bits.
+    pub literal_len_bits: u64,
+    /// Match length bits.
+    pub match_len_bits: u64,
+    /// Match distance bits.
+    pub match_distance_bits: u64,
+    /// Padding bits, including the 8 byte bitstream pad.
+    pub pad_bits: u64,
+}
+
+impl BlockCost {
+    /// Total symbol and header bits, excluding padding.
+    pub fn n_bits(&self) -> u64 {
+        self.header_bits
+            + self.literal_bits
+            + self.literal_len_bits
+            + self.match_len_bits
+            + self.match_distance_bits
+    }
+}
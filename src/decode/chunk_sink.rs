@@ -0,0 +1,69 @@
+use super::output_sink::OutputSink;
+
+use std::io;
+
+/// Fixed size chunk output sink. Decoded bytes are assembled into `chunk_size` chunks, passed to
+/// `f` as they complete.
+pub struct ChunkSink<F> {
+    buf: Vec<u8>,
+    chunk_size: usize,
+    f: F,
+}
+
+impl<F: FnMut(&[u8])> ChunkSink<F> {
+    #[inline(always)]
+    pub fn new(chunk_size: usize, f: F) -> Self {
+        assert_ne!(chunk_size, 0);
+        Self { buf: Vec::with_capacity(chunk_size), chunk_size, f }
+    }
+
+    /// Pass any incomplete trailing chunk to `f`.
+    pub fn flush(&mut self) {
+        if !self.buf.is_empty() {
+            (self.f)(&self.buf);
+            self.buf.clear();
+        }
+    }
+}
+
+impl<F: FnMut(&[u8])> OutputSink for ChunkSink<F> {
+    fn write_decoded(&mut self, mut bytes: &[u8]) -> io::Result<()> {
+        if !self.buf.is_empty() {
+            let n = (self.chunk_size - self.buf.len()).min(bytes.len());
+            self.buf.extend_from_slice(&bytes[..n]);
+            bytes = &bytes[n..];
+            if self.buf.len() != self.chunk_size {
+                return Ok(());
+            }
+            (self.f)(&self.buf);
+            self.buf.clear();
+        }
+        // Whole chunks are passed directly.
+        let mut chunks = bytes.chunks_exact(self.chunk_size);
+        for chunk in &mut chunks {
+            (self.f)(chunk);
+        }
+        self.buf.extend_from_slice(chunks.remainder());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assemble() -> io::Result<()> {
+        let mut chunks = Vec::default();
+        let mut sink = ChunkSink::new(3, |chunk: &[u8]| chunks.push(chunk.to_vec()));
+        sink.write_decoded(b"a")?;
+        sink.write_decoded(b"bcdefgh")?;
+        sink.write_decoded(b"")?;
+        sink.write_decoded(b"ij")?;
+        sink.write_decoded(b"k")?;
+        sink.flush();
+        sink.flush();
+        assert_eq!(chunks, vec![b"abc".to_vec(), b"def".to_vec(), b"ghi".to_vec(), b"jk".to_vec()]);
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+use super::output_sink::OutputSink;
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Fixed size circular file [OutputSink].
+///
+/// Intended for rolling logs where only the most recent decoded bytes are of interest. Decoded
+/// bytes are written into `inner` with wraparound at `capacity` bytes, so arbitrarily large streams
+/// decode into bounded storage. The file grows until `capacity` bytes are written, after which the
+/// oldest bytes are overwritten. The physical offset of the oldest retained byte, the logical head,
+/// is tracked, see [head](Self::head), and [copy_to](Self::copy_to) copies the retained bytes in
+/// stream order.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{CircularFileSink, LzfseRingDecoder};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     lzfse_rust::encode_bytes(b"0123456789", &mut enc)?;
+///     let mut sink = CircularFileSink::new(Cursor::new(Vec::default()), 4)?;
+///     LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut sink)?;
+///     assert_eq!(sink.head(), 2);
+///     assert_eq!(sink.get_ref().get_ref(), b"8967");
+///     let mut dec = Vec::default();
+///     sink.copy_to(&mut dec)?;
+///     assert_eq!(dec, b"6789");
+///     Ok(())
+/// }
+/// ```
+pub struct CircularFileSink<F = File> {
+    inner: F,
+    capacity: u64,
+    pos: u64,
+    n_bytes: u64,
+}
+
+impl CircularFileSink<File> {
+    /// Create, or truncate, the file at `path` and return a new instance writing into it with
+    /// wraparound at `capacity` bytes.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::InvalidInput](std::io::ErrorKind) if `capacity` is zero.
+    /// * [Error](std::io::Error) in case of file IO errors.
+    pub fn create<P: AsRef<Path>>(path: P, capacity: u64) -> io::Result<Self> {
+        let file =
+            OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path)?;
+        Self::new(file, capacity)
+    }
+}
+
+impl<F: Write + Seek> CircularFileSink<F> {
+    /// Create a new instance writing into `inner`, from offset zero, with wraparound at
+    /// `capacity` bytes.
+    ///
+    /// `inner` is expected to be empty, existing bytes beyond `capacity` are left untouched.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::InvalidInput](std::io::ErrorKind) if `capacity` is zero.
+    /// * [Error](std::io::Error) in case of `inner` IO errors.
+    pub fn new(mut inner: F, capacity: u64) -> io::Result<Self> {
+        if capacity == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "zero capacity"));
+        }
+        inner.seek(SeekFrom::Start(0))?;
+        Ok(Self { inner, capacity, pos: 0, n_bytes: 0 })
+    }
+
+    /// Wraparound capacity in bytes.
+    #[inline(always)]
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Total number of bytes written, including overwritten bytes.
+    #[inline(always)]
+    pub fn n_bytes(&self) -> u64 {
+        self.n_bytes
+    }
+
+    /// Number of retained bytes, the lesser of [n_bytes](Self::n_bytes) and
+    /// [capacity](Self::capacity).
+    #[inline(always)]
+    pub fn len(&self) -> u64 {
+        self.n_bytes.min(self.capacity)
+    }
+
+    /// Returns `true` if no bytes have been written.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.n_bytes == 0
+    }
+
+    /// Physical offset of the oldest retained byte, the logical head.
+    #[inline(always)]
+    pub fn head(&self) -> u64 {
+        if self.n_bytes < self.capacity {
+            0
+        } else {
+            self.pos
+        }
+    }
+
+    /// Flush `inner`.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) in case of `inner` IO errors.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Copy the retained bytes in stream order into `writer`, returning the number of bytes
+    /// copied.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) in case of `inner` or `writer` IO errors.
+    pub fn copy_to<W: Write>(&mut self, writer: &mut W) -> io::Result<u64>
+    where
+        F: Read,
+    {
+        let head = self.head();
+        let len = self.len();
+        self.inner.flush()?;
+        self.inner.seek(SeekFrom::Start(head))?;
+        let mut n = io::copy(&mut (&mut self.inner).take(len - head), writer)?;
+        self.inner.seek(SeekFrom::Start(0))?;
+        n += io::copy(&mut (&mut self.inner).take(head), writer)?;
+        self.inner.seek(SeekFrom::Start(self.pos))?;
+        if n != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(n)
+    }
+
+    /// Gets a reference to the underlying writer.
+    #[inline(always)]
+    pub fn get_ref(&self) -> &F {
+        &self.inner
+    }
+
+    /// Unwraps this `CircularFileSink<F>`, returning the underlying writer.
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+}
+
+impl<F: Write + Seek> OutputSink for CircularFileSink<F> {
+    fn write_decoded(&mut self, mut bytes: &[u8]) -> io::Result<()> {
+        if bytes.len() as u64 > self.capacity {
+            // Bytes that would be overwritten within this call are skipped.
+            let n_skip = bytes.len() as u64 - self.capacity;
+            self.n_bytes += n_skip;
+            self.pos = (self.pos + n_skip) % self.capacity;
+            bytes = &bytes[n_skip as usize..];
+            self.inner.seek(SeekFrom::Start(self.pos))?;
+        }
+        while !bytes.is_empty() {
+            let n = ((self.capacity - self.pos).min(bytes.len() as u64)) as usize;
+            self.inner.write_all(&bytes[..n])?;
+            self.n_bytes += n as u64;
+            self.pos += n as u64;
+            bytes = &bytes[n..];
+            if self.pos == self.capacity {
+                self.pos = 0;
+                self.inner.seek(SeekFrom::Start(0))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<F> fmt::Debug for CircularFileSink<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircularFileSink")
+            .field("capacity", &self.capacity)
+            .field("pos", &self.pos)
+            .field("n_bytes", &self.n_bytes)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    fn sink(capacity: u64) -> CircularFileSink<Cursor<Vec<u8>>> {
+        CircularFileSink::new(Cursor::new(Vec::default()), capacity).unwrap()
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let err = CircularFileSink::new(Cursor::new(Vec::default()), 0).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn wrap() -> io::Result<()> {
+        let mut sink = sink(4);
+        sink.write_decoded(b"abc")?;
+        assert_eq!(sink.head(), 0);
+        assert_eq!(sink.get_ref().get_ref(), b"abc");
+        sink.write_decoded(b"def")?;
+        assert_eq!(sink.head(), 2);
+        assert_eq!(sink.get_ref().get_ref(), b"efcd");
+        sink.write_decoded(b"ghijklmnopq")?;
+        assert_eq!(sink.n_bytes(), 17);
+        assert_eq!(sink.len(), 4);
+        assert_eq!(sink.head(), 1);
+        assert_eq!(sink.get_ref().get_ref(), b"qnop");
+        let mut dst = Vec::default();
+        assert_eq!(sink.copy_to(&mut dst)?, 4);
+        assert_eq!(dst, b"nopq");
+        // Position restored.
+        sink.write_decoded(b"r")?;
+        assert_eq!(sink.get_ref().get_ref(), b"qrop");
+        Ok(())
+    }
+}
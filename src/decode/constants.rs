@@ -1,5 +1,11 @@
 use crate::ring::{RingBlock, RingSize, RingType};
 
+// Sufficient to probe any block header.
+pub const HEADER_LIMIT: usize = 0x40;
+
+// Staged block decoding inner read size.
+pub const READ_LEN: usize = 0x4000;
+
 #[derive(Copy, Clone, Debug)]
 pub struct Input;
 
@@ -29,3 +35,66 @@ unsafe impl RingType for Output {
 unsafe impl RingBlock for Output {
     const RING_BLK_SIZE: u32 = 0x0001_0000;
 }
+
+// Reduced output rings, see `RingProfile`. `RING_LIMIT` is fixed by the FSE `MAX_M_VALUE` and
+// `RING_BLK_SIZE` is scaled to `RING_SIZE / 8` in line with `Output`.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Output32K;
+
+unsafe impl RingSize for Output32K {
+    const RING_SIZE: u32 = 0x0000_8000;
+}
+
+unsafe impl RingType for Output32K {
+    const RING_LIMIT: u32 = 0x0940;
+}
+
+unsafe impl RingBlock for Output32K {
+    const RING_BLK_SIZE: u32 = 0x1000;
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Output64K;
+
+unsafe impl RingSize for Output64K {
+    const RING_SIZE: u32 = 0x0001_0000;
+}
+
+unsafe impl RingType for Output64K {
+    const RING_LIMIT: u32 = 0x0940;
+}
+
+unsafe impl RingBlock for Output64K {
+    const RING_BLK_SIZE: u32 = 0x2000;
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Output128K;
+
+unsafe impl RingSize for Output128K {
+    const RING_SIZE: u32 = 0x0002_0000;
+}
+
+unsafe impl RingType for Output128K {
+    const RING_LIMIT: u32 = 0x0940;
+}
+
+unsafe impl RingBlock for Output128K {
+    const RING_BLK_SIZE: u32 = 0x4000;
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Output256K;
+
+unsafe impl RingSize for Output256K {
+    const RING_SIZE: u32 = 0x0004_0000;
+}
+
+unsafe impl RingType for Output256K {
+    const RING_LIMIT: u32 = 0x0940;
+}
+
+unsafe impl RingBlock for Output256K {
+    const RING_BLK_SIZE: u32 = 0x8000;
+}
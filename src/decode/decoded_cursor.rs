@@ -0,0 +1,126 @@
+use super::decoder::LzfseDecoder;
+
+use std::fmt;
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+
+/// LZFSE decoded output cursor.
+///
+/// Exposes [Read] + [Seek] over the decoded output of a byte slice, for consumers that expect a
+/// seekable reader. The source is decoded in full into an internal buffer on first access,
+/// seeking relative to the start or current position does not trigger decoding. As such,
+/// intended for small streams.
+///
+/// Decoding errors are reported by the first access and by every access thereafter.
+///
+/// Instances are created using [LzfseDecoder::cursor].
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::LzfseDecoder;
+/// use std::io::{self, Read, Seek, SeekFrom};
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     lzfse_rust::encode_bytes(b"semper fidelis", &mut enc)?;
+///     let mut decoder = LzfseDecoder::default();
+///     let mut cursor = decoder.cursor(&enc);
+///     cursor.seek(SeekFrom::End(-7))?;
+///     let mut dec = String::default();
+///     cursor.read_to_string(&mut dec)?;
+///     assert_eq!(dec, "fidelis");
+///     Ok(())
+/// }
+/// ```
+pub struct DecodedCursor<'a> {
+    decoder: &'a mut LzfseDecoder,
+    src: &'a [u8],
+    dec: Option<Vec<u8>>,
+    pos: u64,
+}
+
+impl<'a> DecodedCursor<'a> {
+    #[inline(always)]
+    pub(super) fn new(decoder: &'a mut LzfseDecoder, src: &'a [u8]) -> Self {
+        Self { decoder, src, dec: None, pos: 0 }
+    }
+
+    /// Returns the decoded output, decoding the source if not already decoded.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any decoding errors.
+    pub fn decoded(&mut self) -> crate::Result<&[u8]> {
+        if self.dec.is_none() {
+            let mut dec = Vec::default();
+            self.decoder.decode_bytes(self.src, &mut dec)?;
+            self.dec = Some(dec);
+        }
+        Ok(self.dec.as_deref().unwrap_or_default())
+    }
+
+    /// Unwraps this `DecodedCursor`, returning the decoded output.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any decoding errors.
+    pub fn into_decoded(mut self) -> crate::Result<Vec<u8>> {
+        self.decoded()?;
+        Ok(self.dec.take().unwrap_or_default())
+    }
+}
+
+impl<'a> BufRead for DecodedCursor<'a> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let pos = self.pos;
+        let dec = self.decoded()?;
+        let index = pos.min(dec.len() as u64) as usize;
+        Ok(&dec[index..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt as u64;
+    }
+}
+
+impl<'a> Read for DecodedCursor<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.fill_buf()?.read(buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<'a> Seek for DecodedCursor<'a> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => {
+                self.pos = n;
+                return Ok(n);
+            }
+            SeekFrom::End(n) => (self.decoded()?.len() as u64, n),
+            SeekFrom::Current(n) => (self.pos, n),
+        };
+        let pos = if offset >= 0 {
+            base.checked_add(offset as u64)
+        } else {
+            base.checked_sub(offset.unsigned_abs())
+        };
+        match pos {
+            Some(n) => {
+                self.pos = n;
+                Ok(n)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for DecodedCursor<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodedCursor").field("pos", &self.pos).finish()
+    }
+}
@@ -1,13 +1,29 @@
 use crate::base::MagicBytes;
 use crate::error::Error;
-use crate::fse::FseCore;
+use crate::fse::{Fse, FseCore, PadMode};
+use crate::kit;
+use crate::lmd::DMax;
 use crate::lz::LzWriter;
+use crate::ops::PeekData;
 use crate::raw::RawBlock;
+use crate::trailer;
 use crate::types::ByteReader;
-use crate::vn::VnCore;
+use crate::vn::{VnCore, VN_HEADER_SIZE};
 
-use std::convert::TryInto;
+#[cfg(feature = "tokio")]
+use super::async_reader::AsyncLzfseReader;
+use super::block_cost::BlockCost;
+use super::constants::{HEADER_LIMIT, READ_LEN};
+use super::decoded_cursor::DecodedCursor;
+use super::leniency::{DecodeLeniency, SentinelWriter};
+use super::probe::{probe, probe_block, probe_last};
+use super::report::{DecodeReport, ReportWriter};
+use super::slice_writer::SliceWriter;
+use super::trace::{DecodeEvent, TraceWriter};
+
+use std::convert::{TryFrom, TryInto};
 use std::fmt;
+use std::io::{self, Read};
 
 /// LZFSE decoder.
 ///
@@ -16,6 +32,9 @@ use std::fmt;
 #[derive(Default)]
 pub struct LzfseDecoder {
     pub(super) fse_core: FseCore,
+    max_block_payload: Option<u32>,
+    max_output: Option<u64>,
+    leniency: DecodeLeniency,
     n_payload_bytes: u64,
     dst_mark: u64,
 }
@@ -26,11 +45,22 @@ pub struct LzfseDecoder {
 // https://stackoverflow.com/questions/35592750/how-does-for-syntax-differ-from-a-regular-lifetime-bound/35595491#35595491
 
 impl LzfseDecoder {
+    pub(crate) const HEAP_SIZE: usize = FseCore::HEAP_SIZE;
+
     /// Decode `src` into `dst` returning the number of bytes written into `dst`.
     ///
+    /// Each block header's raw byte count is validated against the block's actual decoded
+    /// output, streams that misstate their decoded size are rejected. Invalid matches are handled
+    /// as per the current [DecodeLeniency].
+    ///
+    /// The `src` must hold exactly one frame, any bytes following the end of stream block, other
+    /// than an optional trailer, are rejected.
+    ///
     /// # Errors
     ///
     /// * [Error](crate::Error) detailing the nature of any errors.
+    /// * [Error::PayloadOverflow](crate::Error::PayloadOverflow) if bytes follow the frame, see
+    ///   [decode_bytes_concatenated](Self::decode_bytes_concatenated) for multiple frame streams.
     ///
     /// # Aborts
     ///
@@ -61,43 +91,1001 @@ impl LzfseDecoder {
     pub fn decode_bytes(&mut self, mut src: &[u8], dst: &mut Vec<u8>) -> crate::Result<u64> {
         let src_len = src.len();
         let dst_len = dst.len();
-        self.execute(dst, &mut src).map(|u| {
+        let result = match self.leniency {
+            DecodeLeniency::Strict => self.execute(dst, &mut src),
+            DecodeLeniency::FillSentinel(u) => {
+                self.execute(&mut SentinelWriter::new(dst, u), &mut src)
+            }
+        };
+        result.map(|u| {
             debug_assert_eq!(u.0, src_len as u64);
             debug_assert_eq!(dst_len as u64 + u.1, dst.len() as u64);
             u.1
         })
     }
 
+    /// Decode `reader` into `dst` returning the number of bytes written into `dst`.
+    ///
+    /// Encoded input is read incrementally, there is no need to read the entire stream into
+    /// memory first, nor to wrap `reader` in a [BufReader](std::io::BufReader). Input is staged
+    /// in an internal buffer until a complete block is available, the block is then decoded
+    /// directly into `dst` as per [decode_bytes](Self::decode_bytes).
+    ///
+    /// Input memory use is bounded by the largest block in the stream, see
+    /// [set_max_block_payload](Self::set_max_block_payload), whereas the entire decoded output is
+    /// held in `dst`. In contrast [LzfseRingDecoder](crate::LzfseRingDecoder) bounds both input
+    /// and output memory use with fixed size ring buffers, at the cost of copying output through
+    /// its output ring.
+    ///
+    /// As with [decode_bytes](Self::decode_bytes), `reader` must hold exactly one frame and is
+    /// read to its end.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    ///
+    /// On error, `dst` may contain partially decoded data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(b"test", &mut enc)?;
+    ///     // Any `Read` implementation, for example a `File`.
+    ///     let reader = io::Cursor::new(enc);
+    ///     let mut dec = Vec::default();
+    ///     let n_bytes = LzfseDecoder::default().decode_reader(reader, &mut dec)?;
+    ///     assert_eq!(n_bytes, 4);
+    ///     assert_eq!(dec, b"test");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_reader<I: Read>(
+        &mut self,
+        mut reader: I,
+        dst: &mut Vec<u8>,
+    ) -> crate::Result<u64> {
+        let dst_len = dst.len();
+        self.n_payload_bytes = 0;
+        self.dst_mark = dst_len as u64;
+        let mut src = Vec::default();
+        let mut src_pos = 0;
+        let mut is_eof = false;
+        loop {
+            let block = &src[src_pos..];
+            if block.len() >= 4 {
+                let magic_bytes = MagicBytes::try_from(block.peek_u32())?;
+                if magic_bytes == MagicBytes::Eos {
+                    if is_eof {
+                        trailer::skip_eos(&mut &block[..])?;
+                        return Ok((dst.len() - dst_len) as u64);
+                    }
+                } else {
+                    match probe_block(block, magic_bytes) {
+                        Ok((n_payload_bytes, _)) if n_payload_bytes as usize <= block.len() => {
+                            let mut block = &block[..n_payload_bytes as usize];
+                            self.block_vec(dst, &mut block)?;
+                            if !block.is_empty() {
+                                return Err(Error::PayloadOverflow);
+                            }
+                            src_pos += n_payload_bytes as usize;
+                            continue;
+                        }
+                        // Raw and VN blocks may be huge, reject oversized payloads before
+                        // staging them.
+                        Ok((n_payload_bytes, n_raw_bytes)) => match magic_bytes {
+                            MagicBytes::Raw => self.check_block_payload(n_raw_bytes)?,
+                            MagicBytes::Vxn => {
+                                self.check_block_payload(n_payload_bytes - VN_HEADER_SIZE)?
+                            }
+                            _ => {}
+                        },
+                        Err(_) if block.len() < HEADER_LIMIT && !is_eof => {}
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+            if is_eof {
+                return Err(Error::PayloadUnderflow);
+            }
+            src.drain(..src_pos);
+            src_pos = 0;
+            let len = src.len();
+            src.resize(len + READ_LEN, 0);
+            let n = loop {
+                match reader.read(&mut src[len..]) {
+                    Ok(n) => break n,
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                    Err(err) => return Err(err.into()),
+                }
+            };
+            src.truncate(len + n);
+            is_eof = n == 0;
+        }
+    }
+
+    /// Decode `src`, encoded with a preset `dict` dictionary, into `dst` returning the number of
+    /// bytes written into `dst`.
+    ///
+    /// The counterpart to
+    /// [LzfseEncoder::encode_bytes_with_dict](crate::LzfseEncoder::encode_bytes_with_dict), `dict`
+    /// must be identical to that used when encoding. It is logically prepended to the decoded
+    /// output, so that matches may reference it, but is not itself written into `dst`.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let dict = b"a quick brown fox jumps over the lazy dog";
+    ///     let mut enc = Vec::default();
+    ///     LzfseEncoder::default().encode_bytes_with_dict(dict, b"the quick brown dog", &mut enc)?;
+    ///     let mut dec = Vec::default();
+    ///     let n_bytes = LzfseDecoder::default().decode_bytes_with_dict(dict, &enc, &mut dec)?;
+    ///     assert_eq!(n_bytes, 19);
+    ///     assert_eq!(dec, b"the quick brown dog");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_bytes_with_dict(
+        &mut self,
+        dict: &[u8],
+        src: &[u8],
+        dst: &mut Vec<u8>,
+    ) -> crate::Result<u64> {
+        let dict = &dict[dict.len().saturating_sub(Fse::MAX_MATCH_DISTANCE as usize)..];
+        let mark = dst.len();
+        dst.extend_from_slice(dict);
+        let result = self.decode_bytes(src, dst);
+        dst.drain(mark..mark + dict.len());
+        result
+    }
+
+    /// Decode `src`, which may hold multiple concatenated frames, into `dst` returning the total
+    /// number of bytes written into `dst`.
+    ///
+    /// Streams concatenated in the manner of `cat a.lzfse b.lzfse` are decoded in sequence. After
+    /// each end of stream block, and optional trailer, any remaining bytes must begin a new frame.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    /// * [Error::PayloadOverflow](crate::Error::PayloadOverflow) if bytes following a frame do
+    ///   not begin a new frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(b"abc", &mut enc)?;
+    ///     lzfse_rust::encode_bytes(b"def", &mut enc)?;
+    ///     let mut dec = Vec::default();
+    ///     let n_bytes = LzfseDecoder::default().decode_bytes_concatenated(&enc, &mut dec)?;
+    ///     assert_eq!(n_bytes, 6);
+    ///     assert_eq!(dec, b"abcdef");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_bytes_concatenated(
+        &mut self,
+        mut src: &[u8],
+        dst: &mut Vec<u8>,
+    ) -> crate::Result<u64> {
+        let result = match self.leniency {
+            DecodeLeniency::Strict => self.execute_concatenated(dst, &mut src),
+            DecodeLeniency::FillSentinel(u) => {
+                self.execute_concatenated(&mut SentinelWriter::new(dst, u), &mut src)
+            }
+        };
+        result.map(|u| u.1)
+    }
+
+    /// Decode `src` into the fixed buffer `dst` returning the number of bytes written into `dst`.
+    ///
+    /// In the manner of the reference `lzfse_decode_buffer`, no allocations are made for the
+    /// output. Writes are confined to `dst`. On success exactly the first `n` bytes of `dst` are
+    /// written, the remainder is untouched. On error the contents of `dst` are undefined, although
+    /// nothing is written beyond its bounds. [decode_bytes](Self::decode_bytes) is faster, its
+    /// byte copies are free to overrun into spare capacity.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::BufferOverflow](crate::Error::BufferOverflow) if the decoded output exceeds `dst`.
+    /// * [Error](crate::Error) detailing the nature of any other errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{Error, LzfseDecoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(b"test", &mut enc)?;
+    ///     let mut decoder = LzfseDecoder::default();
+    ///     let mut buf = [0u8; 8];
+    ///     let n = decoder.decode_bytes_into(&enc, &mut buf)?;
+    ///     assert_eq!(&buf[..n], b"test");
+    ///     match decoder.decode_bytes_into(&enc, &mut buf[..3]) {
+    ///         Err(Error::BufferOverflow) => {}
+    ///         _ => panic!(),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_bytes_into(&mut self, mut src: &[u8], dst: &mut [u8]) -> crate::Result<usize> {
+        let mut dst = SliceWriter::new(dst);
+        match self.leniency {
+            DecodeLeniency::Strict => self.execute(&mut dst, &mut src)?,
+            DecodeLeniency::FillSentinel(u) => {
+                self.execute(&mut SentinelWriter::new(&mut dst, u), &mut src)?
+            }
+        };
+        Ok(dst.len())
+    }
+
+    /// Decode `src` into `dst` returning a [DecodeReport] summarizing the decoded stream.
+    ///
+    /// Intended for validation and diagnostics, for example cross checking encoder statistics.
+    /// Decoding is otherwise identical to [decode_bytes](Self::decode_bytes).
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(&src, &mut enc)?;
+    ///     let mut dec = Vec::default();
+    ///     let report = LzfseDecoder::default().decode_bytes_report(&enc, &mut dec)?;
+    ///     assert_eq!(report.n_blocks, 1);
+    ///     assert_eq!(report.n_raw_bytes, src.len() as u64);
+    ///     assert!(report.n_literals < report.n_raw_bytes);
+    ///     assert_ne!(report.n_matches, 0);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_bytes_report(
+        &mut self,
+        mut src: &[u8],
+        dst: &mut Vec<u8>,
+    ) -> crate::Result<DecodeReport> {
+        let mut dst = ReportWriter::new(dst);
+        self.n_payload_bytes = 0;
+        self.dst_mark = dst.n_raw_bytes();
+        let mut n_blocks = 0;
+        while self.block(&mut dst, &mut src)? {
+            n_blocks += 1;
+        }
+        if !src.is_eof() {
+            return Err(Error::PayloadOverflow);
+        }
+        trailer::skip_eos(&mut src)?;
+        Ok(dst.report(n_blocks, dst.n_raw_bytes() - self.dst_mark))
+    }
+
+    /// Decode `src` into `dst` with an upper `max` output bound, returning the number of bytes
+    /// written into `dst`.
+    ///
+    /// Intended for untrusted input. The block headers are walked ahead of decoding to obtain the
+    /// claimed decoded size, which is rejected if it exceeds `max`. Exactly this number of bytes
+    /// is then reserved in `dst` and the input decoded. Headers with sizes that are inconsistent
+    /// with the payload are rejected by the header walk, before any memory is reserved. As block
+    /// outputs are validated against their declared sizes, the decoded size is the claimed size.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::OutputLimitExceeded](crate::Error::OutputLimitExceeded) if the claimed decoded
+    ///   size exceeds `max`.
+    /// * [Error](crate::Error) detailing the nature of any other errors.
+    ///
+    /// On error, `dst` may contain partially decoded data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{Error, LzfseDecoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // "test" string encoded.
+    ///     let enc = vec![
+    ///         0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76,
+    ///         0x78, 0x24,
+    ///     ];
+    ///     let mut decoder = LzfseDecoder::default();
+    ///     let mut dec = Vec::default();
+    ///     let n_bytes = decoder.decode_bounded(&enc, &mut dec, 4)?;
+    ///     assert_eq!(n_bytes, 4);
+    ///     assert_eq!(dec, b"test");
+    ///     dec.clear();
+    ///     match decoder.decode_bounded(&enc, &mut dec, 3) {
+    ///         Err(Error::OutputLimitExceeded) => {}
+    ///         _ => panic!(),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_bounded(
+        &mut self,
+        src: &[u8],
+        dst: &mut Vec<u8>,
+        max: u64,
+    ) -> crate::Result<u64> {
+        let n_raw_bytes = probe(src)?;
+        if n_raw_bytes > max {
+            return Err(Error::OutputLimitExceeded);
+        }
+        let n = n_raw_bytes.try_into().map_err(|_| Error::OutputLimitExceeded)?;
+        dst.reserve_exact(n);
+        let n_bytes = self.decode_bytes(src, dst)?;
+        debug_assert_eq!(n_bytes, n_raw_bytes);
+        Ok(n_bytes)
+    }
+
+    /// Decode `src` into `dst` given the known, `expected_len`, decoded size, returning the number
+    /// of bytes written into `dst`.
+    ///
+    /// Intended for trusted length scenarios where the decoded size is stored out of band. The
+    /// block headers are walked ahead of decoding and their claimed decoded size is rejected if it
+    /// differs from `expected_len`, before any payload is decoded. Exactly `expected_len` bytes are
+    /// then reserved in `dst` and the input decoded. The actual decoded size must match.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::OutputSizeMismatch](crate::Error::OutputSizeMismatch) if either the claimed or the
+    ///   actual decoded size does not match `expected_len`.
+    /// * [Error](crate::Error) detailing the nature of any other errors.
+    ///
+    /// On error, `dst` may contain partially decoded data. Should the claimed decoded size not
+    /// match, `dst` is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{Error, LzfseDecoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // "test" string encoded.
+    ///     let enc = vec![
+    ///         0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76,
+    ///         0x78, 0x24,
+    ///     ];
+    ///     let mut decoder = LzfseDecoder::default();
+    ///     let mut dec = Vec::default();
+    ///     let n_bytes = decoder.decode_bytes_expect(&enc, &mut dec, 4)?;
+    ///     assert_eq!(n_bytes, 4);
+    ///     assert_eq!(dec, b"test");
+    ///     dec.clear();
+    ///     match decoder.decode_bytes_expect(&enc, &mut dec, 5) {
+    ///         Err(Error::OutputSizeMismatch) => {}
+    ///         _ => panic!(),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_bytes_expect(
+        &mut self,
+        src: &[u8],
+        dst: &mut Vec<u8>,
+        expected_len: u64,
+    ) -> crate::Result<u64> {
+        if probe(src)? != expected_len {
+            return Err(Error::OutputSizeMismatch);
+        }
+        let n = expected_len.try_into().map_err(|_| Error::OutputLimitExceeded)?;
+        dst.reserve_exact(n);
+        let n_bytes = self.decode_bytes(src, dst)?;
+        if n_bytes != expected_len {
+            return Err(Error::OutputSizeMismatch);
+        }
+        Ok(n_bytes)
+    }
+
+    /// Decode a possibly truncated `src` into `dst` returning the number of valid bytes written
+    /// into `dst`.
+    ///
+    /// Intended for recovering data from cut off streams, for example interrupted downloads.
+    /// Decoding proceeds as per [decode_bytes](Self::decode_bytes) until `src` is exhausted.
+    /// Output decoded prior to the truncation is retained: raw and LZVN blocks recover their
+    /// complete operations up to the truncation point, whereas LZFSE blocks are recovered only if
+    /// complete. A bad bitstream discards the offending block's output. A complete frame decodes
+    /// exactly as per [decode_bytes](Self::decode_bytes).
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors other than
+    ///   [Error::PayloadUnderflow](crate::Error::PayloadUnderflow) and
+    ///   [Error::BadBitStream](crate::Error::BadBitStream).
+    ///
+    /// On error, `dst` may contain partially decoded data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // "test" string encoded.
+    ///     let enc = vec![
+    ///         0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76,
+    ///         0x78, 0x24,
+    ///     ];
+    ///     let mut decoder = LzfseDecoder::default();
+    ///     let mut dec = Vec::default();
+    ///     // Truncated mid raw block.
+    ///     let n_bytes = decoder.decode_bytes_partial(&enc[..10], &mut dec)?;
+    ///     assert_eq!(n_bytes, 2);
+    ///     assert_eq!(dec, b"te");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_bytes_partial(
+        &mut self,
+        mut src: &[u8],
+        dst: &mut Vec<u8>,
+    ) -> crate::Result<usize> {
+        let dst_len = dst.len();
+        self.n_payload_bytes = 0;
+        self.dst_mark = dst_len as u64;
+        loop {
+            let mark = dst.len();
+            match self.block_vec(dst, &mut src) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(Error::PayloadUnderflow) => return Ok(dst.len() - dst_len),
+                Err(Error::BadBitStream) => {
+                    dst.truncate(mark);
+                    return Ok(mark - dst_len);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        if !src.is_eof() {
+            return Err(Error::PayloadOverflow);
+        }
+        match trailer::skip_eos(&mut src) {
+            Ok(_) | Err(Error::PayloadUnderflow) => Ok(dst.len() - dst_len),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Trace `src` returning the number of bytes that decoding would write.
+    ///
+    /// Literal runs and matches are passed to `f` in stream order, as [DecodeEvent]s, without
+    /// reconstructing the decoded output. Replaying the events reconstructs the decoded output.
+    /// Consecutive literal runs, including those spanning block boundaries, are merged and then
+    /// split into runs of at most 4,096 bytes, so memory use is bounded regardless of the length of
+    /// the literal runs.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    ///
+    /// Events emitted prior to an error are not retracted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{DecodeEvent, LzfseDecoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // "test" string encoded.
+    ///     let enc = vec![
+    ///         0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76,
+    ///         0x78, 0x24,
+    ///     ];
+    ///     let mut decoder = LzfseDecoder::default();
+    ///     let mut literals = Vec::default();
+    ///     let n_bytes = decoder.trace(&enc, |event| {
+    ///         if let DecodeEvent::Literal(bytes) = event {
+    ///             literals.extend_from_slice(bytes);
+    ///         }
+    ///     })?;
+    ///     assert_eq!(n_bytes, 4);
+    ///     assert_eq!(literals, b"test");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn trace<F: FnMut(DecodeEvent)>(&mut self, mut src: &[u8], f: F) -> crate::Result<u64> {
+        let mut dst = TraceWriter::new(f);
+        let (_, n_raw_bytes) = self.execute(&mut dst, &mut src)?;
+        dst.flush();
+        Ok(n_raw_bytes)
+    }
+
+    /// Decode block `index` of `src` into `dst` returning the number of bytes written into `dst`.
+    ///
+    /// Intended for debugging, for example isolating a failing block. LZFSE blocks may reference
+    /// the decoded output of preceding blocks, so these are decoded into an internal buffer to
+    /// provide match history. Blocks following `index` are neither decoded nor validated.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::BlockIndexOutOfRange](crate::Error::BlockIndexOutOfRange) if `src` contains
+    ///   `index` or fewer blocks.
+    /// * [Error](crate::Error) detailing the nature of any other errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{Error, LzfseDecoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // "test" string encoded.
+    ///     let enc = vec![
+    ///         0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76,
+    ///         0x78, 0x24,
+    ///     ];
+    ///     let mut decoder = LzfseDecoder::default();
+    ///     let mut dec = Vec::default();
+    ///     let n_bytes = decoder.decode_block(&enc, 0, &mut dec)?;
+    ///     assert_eq!(n_bytes, 4);
+    ///     assert_eq!(dec, b"test");
+    ///     match decoder.decode_block(&enc, 1, &mut dec) {
+    ///         Err(Error::BlockIndexOutOfRange) => {}
+    ///         _ => panic!(),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_block(
+        &mut self,
+        mut src: &[u8],
+        index: usize,
+        dst: &mut Vec<u8>,
+    ) -> crate::Result<u64> {
+        let mut buf = Vec::default();
+        let mut mark = 0;
+        self.n_payload_bytes = 0;
+        self.dst_mark = 0;
+        for _ in 0..=index {
+            mark = buf.len();
+            if !self.block(&mut buf, &mut src)? {
+                return Err(Error::BlockIndexOutOfRange);
+            }
+        }
+        dst.extend_from_slice(&buf[mark..]);
+        Ok((buf.len() - mark) as u64)
+    }
+
+    /// Create a new [AsyncLzfseReader] decoder instance using the supplied `inner` tokio
+    /// [AsyncRead](tokio::io::AsyncRead) reader.
+    #[cfg(feature = "tokio")]
+    pub fn async_reader<I>(&mut self, inner: I) -> AsyncLzfseReader<'_, I>
+    where
+        I: tokio::io::AsyncRead + Unpin,
+    {
+        self.n_payload_bytes = 0;
+        self.dst_mark = 0;
+        AsyncLzfseReader::new(self, inner)
+    }
+
+    /// Returns the decoded size of `src` without decoding.
+    ///
+    /// The block headers are walked through to the end of stream block summing their raw byte
+    /// counts, no payloads are decoded. Useful to size a destination buffer ahead of
+    /// [decode_bytes](Self::decode_bytes). As payloads are unchecked, a successful result does
+    /// not imply that the stream is valid.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any header errors, as per
+    ///   [decode_bytes](Self::decode_bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(b"test", &mut enc)?;
+    ///     let mut decoder = LzfseDecoder::default();
+    ///     let n = decoder.decoded_len(&enc)?;
+    ///     let mut dec = Vec::with_capacity(n as usize);
+    ///     decoder.decode_bytes(&enc, &mut dec)?;
+    ///     assert_eq!(dec, b"test");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decoded_len(&mut self, src: &[u8]) -> crate::Result<u64> {
+        probe(src)
+    }
+
+    /// Quick check `src` integrity, returning the claimed decoded size.
+    ///
+    /// Intended as a cheap smoke test over large collections of streams. The block headers are
+    /// walked through to the end of stream block, validating the stream framing in full, after
+    /// which only the first and last blocks are decoded. Matches in the last block that reference
+    /// prior blocks are not verified. This catches the most common truncation and corruption
+    /// errors at a fraction of the cost of a full decode, however it is a probabilistic check and
+    /// NOT a full verification. Intermediate block payloads are unchecked, use
+    /// [decode_bytes](Self::decode_bytes) for a full verification.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(&src, &mut enc)?;
+    ///     let mut decoder = LzfseDecoder::default();
+    ///     assert_eq!(decoder.quick_verify(&enc)?, src.len() as u64);
+    ///     assert!(decoder.quick_verify(&enc[..enc.len() - 1]).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn quick_verify(&mut self, src: &[u8]) -> crate::Result<u64> {
+        let (n_raw_bytes, last) = probe_last(src)?;
+        let mut buf = Vec::default();
+        self.n_payload_bytes = 0;
+        self.dst_mark = 0;
+        self.block(&mut buf, &mut &src[..])?;
+        if let Some(index) = last.filter(|&u| u != 0) {
+            buf.clear();
+            self.block(&mut SentinelWriter::new(&mut buf, 0), &mut &src[index..])?;
+        }
+        Ok(n_raw_bytes)
+    }
+
+    /// Decode `src` returning the decoded output of each block in order.
+    ///
+    /// Intended for data processed with the same block granularity it was encoded with, for
+    /// example blocks aligned to logical records with
+    /// [set_block_boundaries](crate::LzfseEncoder::set_block_boundaries). Matches may reference
+    /// prior blocks, so the stream is decoded in full and then split at block boundaries. The
+    /// concatenated blocks equal the [decode_bytes](Self::decode_bytes) output.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // "test" string encoded.
+    ///     let enc = vec![
+    ///         0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76,
+    ///         0x78, 0x24,
+    ///     ];
+    ///     let blocks = LzfseDecoder::default().decode_blocks_vec(&enc)?;
+    ///     assert_eq!(blocks, vec![b"test".to_vec()]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_blocks_vec(&mut self, mut src: &[u8]) -> crate::Result<Vec<Vec<u8>>> {
+        let mut buf = Vec::default();
+        let mut marks = vec![0];
+        self.n_payload_bytes = 0;
+        self.dst_mark = 0;
+        while self.block(&mut buf, &mut src)? {
+            marks.push(buf.len());
+        }
+        if !src.is_eof() {
+            return Err(Error::PayloadOverflow);
+        }
+        trailer::skip_eos(&mut src)?;
+        Ok(marks.windows(2).map(|w| buf[w[0]..w[1]].to_vec()).collect())
+    }
+
+    /// Explain `src` returning a bit cost breakdown for each FSE compressed block.
+    ///
+    /// Intended for analysis, for example diagnosing poor compression. For each FSE block the
+    /// bits consumed by each decoded symbol are tallied into literal, literal length, match length
+    /// and match distance categories, see [BlockCost]. Raw and VN blocks are decoded, but not
+    /// reported.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(&src, &mut enc)?;
+    ///     let costs = LzfseDecoder::default().explain(&enc)?;
+    ///     assert_eq!(costs.len(), 1);
+    ///     assert!(costs[0].n_bits() <= costs[0].n_payload_bytes as u64 * 8);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn explain(&mut self, mut src: &[u8]) -> crate::Result<Vec<BlockCost>> {
+        let mut buf = Vec::default();
+        let mut costs = Vec::default();
+        self.n_payload_bytes = 0;
+        self.dst_mark = 0;
+        let mut index = 0;
+        loop {
+            let block = src;
+            let mark = self.n_payload_bytes;
+            if !self.block(&mut buf, &mut src)? {
+                break;
+            }
+            let magic_bytes: MagicBytes = block.peek_u32().try_into()?;
+            if magic_bytes == MagicBytes::Vx1 || magic_bytes == MagicBytes::Vx2 {
+                let n = (self.n_payload_bytes - mark) as usize;
+                let cost = self.fse_core.cost(&block[..n])?;
+                costs.push(BlockCost { index, ..cost });
+            }
+            index += 1;
+        }
+        if !src.is_eof() {
+            return Err(Error::PayloadOverflow);
+        }
+        trailer::skip_eos(&mut src)?;
+        Ok(costs)
+    }
+
+    /// Create a new [DecodedCursor] instance exposing [Read](std::io::Read) +
+    /// [Seek](std::io::Seek) over the decoded output of `src`, which is decoded lazily on first
+    /// access.
+    pub fn cursor<'a>(&'a mut self, src: &'a [u8]) -> DecodedCursor<'a> {
+        DecodedCursor::new(self, src)
+    }
+
+    /// Set the FSE bitstream [PadMode], the default is [PadMode::Strict].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseDecoder, PadMode};
+    ///
+    /// let mut decoder = LzfseDecoder::default();
+    /// decoder.set_pad_mode(PadMode::Lenient);
+    /// assert_eq!(decoder.pad_mode(), PadMode::Lenient);
+    /// ```
+    pub fn set_pad_mode(&mut self, pad_mode: PadMode) {
+        self.fse_core.set_pad_mode(pad_mode);
+    }
+
+    /// Returns the FSE bitstream [PadMode].
+    pub fn pad_mode(&self) -> PadMode {
+        self.fse_core.pad_mode()
+    }
+
+    /// Set strict FSE state verification, the default is `false`.
+    ///
+    /// Malformed FSE weights, those that do not fully populate the state tables, leave unpopulated
+    /// states that lock in place once entered. Such streams are always rejected, but only once a
+    /// block's final states are checked. With strict verification, entering one of these states is
+    /// reported immediately as [FseErrorKind::LatchState](crate::FseErrorKind::LatchState).
+    /// Slower.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseDecoder;
+    ///
+    /// let mut decoder = LzfseDecoder::default();
+    /// decoder.set_strict_fse(true);
+    /// assert!(decoder.strict_fse());
+    /// ```
+    pub fn set_strict_fse(&mut self, strict: bool) {
+        self.fse_core.set_strict(strict);
+    }
+
+    /// Returns true if strict FSE state verification is enabled.
+    pub fn strict_fse(&self) -> bool {
+        self.fse_core.strict()
+    }
+
+    /// Set the maximum block payload size, the default being `None`, unlimited.
+    ///
+    /// Intended for untrusted input. Block headers declaring a payload exceeding `limit` bytes are
+    /// rejected before the payload is processed, bounding the work per block independently of
+    /// the total decoded size. The payload excludes the block header. Raw block payloads equal
+    /// their decoded size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{Error, LzfseDecoder};
+    ///
+    /// // "test" string encoded.
+    /// let enc = vec![
+    ///     0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76,
+    ///     0x78, 0x24,
+    /// ];
+    /// let mut decoder = LzfseDecoder::default();
+    /// decoder.set_max_block_payload(Some(3));
+    /// let mut dec = Vec::default();
+    /// match decoder.decode_bytes(&enc, &mut dec) {
+    ///     Err(Error::PayloadOverflow) => {}
+    ///     _ => panic!(),
+    /// }
+    /// ```
+    pub fn set_max_block_payload(&mut self, limit: Option<u32>) {
+        self.max_block_payload = limit;
+    }
+
+    /// Returns the maximum block payload size.
+    pub fn max_block_payload(&self) -> Option<u32> {
+        self.max_block_payload
+    }
+
+    /// Set the [DecodeLeniency], the default is [DecodeLeniency::Strict].
+    ///
+    /// Applies to [decode_bytes](Self::decode_bytes) and the methods built upon it,
+    /// [decode_bounded](Self::decode_bounded) and [decode_bytes_expect](Self::decode_bytes_expect).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{DecodeLeniency, LzfseDecoder};
+    ///
+    /// let mut decoder = LzfseDecoder::default();
+    /// decoder.set_leniency(DecodeLeniency::FillSentinel(0xFF));
+    /// assert_eq!(decoder.leniency(), DecodeLeniency::FillSentinel(0xFF));
+    /// ```
+    pub fn set_leniency(&mut self, leniency: DecodeLeniency) {
+        self.leniency = leniency;
+    }
+
+    /// Returns the [DecodeLeniency].
+    pub fn leniency(&self) -> DecodeLeniency {
+        self.leniency
+    }
+
+    /// Warm the decoder's internal tables and buffers by decoding a small internal sample.
+    ///
+    /// Intended for latency sensitive applications. Internal allocations are lazily committed by
+    /// the operating system, so the first decoding operation may otherwise incur additional one
+    /// off costs. The sample is encoded on each call, warming is relatively expensive and should
+    /// be performed once, for example on construction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // "test" string encoded.
+    ///     let enc = vec![
+    ///         0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76,
+    ///         0x78, 0x24,
+    ///     ];
+    ///     let mut decoder = LzfseDecoder::default();
+    ///     decoder.warm();
+    ///     let mut dec = Vec::default();
+    ///     decoder.decode_bytes(&enc, &mut dec)?;
+    ///     assert_eq!(dec, b"test");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn warm(&mut self) {
+        let src = kit::warm_sample();
+        let mut enc = Vec::default();
+        let mut dec = Vec::with_capacity(src.len());
+        // Well formed in memory stream, so infallible.
+        let _ = crate::encode_bytes(&src, &mut enc);
+        let _ = self.decode_bytes(&enc, &mut dec);
+        debug_assert_eq!(dec, src);
+    }
+
+    /// Reset the decoder's internal state, ready to decode a new independent stream.
+    ///
+    /// Each decoding operation starts from a reset state, so instances may be reused across any
+    /// number of streams without calling this method. No allocations are made and settings are
+    /// unchanged.
+    pub fn reset(&mut self) {
+        self.n_payload_bytes = 0;
+        self.dst_mark = 0;
+    }
+
     #[inline(always)]
     pub(super) fn execute<I: for<'a> ByteReader<'a>, O: LzWriter>(
         &mut self,
         dst: &mut O,
         src: &mut I,
+    ) -> crate::Result<(u64, u64)> {
+        self.n_payload_bytes = 0;
+        self.dst_mark = dst.n_raw_bytes();
+        while self.block(dst, src)? {}
+        if !src.is_eof() {
+            return Err(Error::PayloadOverflow);
+        }
+        self.n_payload_bytes += trailer::skip_eos(src)?;
+        Ok((self.n_payload_bytes, dst.n_raw_bytes() - self.dst_mark))
+    }
+
+    // As `execute`, but continues across concatenated frames. Trailing bytes following an end of
+    // stream block must be empty or begin with a valid frame.
+    pub(super) fn execute_concatenated<I: for<'a> ByteReader<'a>, O: LzWriter>(
+        &mut self,
+        dst: &mut O,
+        src: &mut I,
     ) -> crate::Result<(u64, u64)> {
         self.n_payload_bytes = 0;
         self.dst_mark = dst.n_raw_bytes();
         loop {
+            while self.block(dst, src)? {}
+            self.n_payload_bytes += trailer::skip_eos_frame(src)?;
             src.fill()?;
-            if src.len() < 4 {
-                return Err(Error::PayloadUnderflow);
+            if src.len() == 0 {
+                break;
             }
-            let magic_bytes: MagicBytes = src.peek_u32().try_into()?;
-            match magic_bytes {
-                MagicBytes::Vx1 => self.vx1(dst, src)?,
-                MagicBytes::Vx2 => self.vx2(dst, src)?,
-                MagicBytes::Vxn => self.vxn(dst, src)?,
-                MagicBytes::Raw => self.raw(dst, src)?,
-                MagicBytes::Eos => break,
+            if src.len() < 4 || MagicBytes::try_from(src.peek_u32()).is_err() {
+                return Err(Error::PayloadOverflow);
             }
         }
-        if src.len() != 4 || !src.is_eof() {
-            return Err(Error::PayloadOverflow);
-        }
-        src.skip(4);
-        self.n_payload_bytes += 4;
         Ok((self.n_payload_bytes, dst.n_raw_bytes() - self.dst_mark))
     }
 
+    // Decode the next block, which must be complete in `src`, into `dst` as per the leniency.
+    pub(super) fn block_vec(&mut self, dst: &mut Vec<u8>, src: &mut &[u8]) -> crate::Result<bool> {
+        match self.leniency {
+            DecodeLeniency::Strict => self.block(dst, src),
+            DecodeLeniency::FillSentinel(u) => self.block(&mut SentinelWriter::new(dst, u), src),
+        }
+    }
+
+    // Decode the next block returning `false` on end of stream, which is not consumed.
+    #[inline(always)]
+    fn block<I: for<'a> ByteReader<'a>, O: LzWriter>(
+        &mut self,
+        dst: &mut O,
+        src: &mut I,
+    ) -> crate::Result<bool> {
+        src.fill()?;
+        if src.len() < 4 {
+            return Err(Error::PayloadUnderflow);
+        }
+        let magic_bytes: MagicBytes = src.peek_u32().try_into()?;
+        match magic_bytes {
+            MagicBytes::Vx1 => self.vx1(dst, src)?,
+            MagicBytes::Vx2 => self.vx2(dst, src)?,
+            MagicBytes::Vxn => self.vxn(dst, src)?,
+            MagicBytes::Raw => self.raw(dst, src)?,
+            MagicBytes::Eos => return Ok(false),
+        }
+        Ok(true)
+    }
+
     #[cold]
     fn vx1<I, O>(&mut self, dst: &mut O, src: &mut I) -> crate::Result<()>
     where
@@ -106,6 +1094,8 @@ impl LzfseDecoder {
     {
         let view = src.view();
         let n = self.fse_core.load_v1(view)?;
+        self.check_block_payload(self.fse_core.n_payload_bytes())?;
+        self.check_block_output(dst, self.fse_core.n_raw_bytes())?;
         src.skip(n as usize);
         self.n_payload_bytes += n as u64;
         self.vx1_vx2_cont(dst, src)
@@ -119,6 +1109,8 @@ impl LzfseDecoder {
     {
         let view = src.view();
         let n = self.fse_core.load_v2(view)?;
+        self.check_block_payload(self.fse_core.n_payload_bytes())?;
+        self.check_block_output(dst, self.fse_core.n_raw_bytes())?;
         src.skip(n as usize);
         self.n_payload_bytes += n as u64;
         self.vx1_vx2_cont(dst, src)
@@ -149,6 +1141,8 @@ impl LzfseDecoder {
         let mut core = VnCore::default();
         let view = src.view();
         let n = core.load_short(view)?;
+        self.check_block_payload(core.n_payload_bytes())?;
+        self.check_block_output(dst, core.n_raw_bytes())?;
         src.skip(n as usize);
         self.n_payload_bytes += n as u64;
         let n = core.decode(dst, src)?;
@@ -165,12 +1159,39 @@ impl LzfseDecoder {
         let mut block = RawBlock::default();
         let view = src.view();
         let n = block.load_short(view)?;
+        self.check_block_payload(block.n_raw_bytes())?;
+        self.check_block_output(dst, block.n_raw_bytes())?;
         src.skip(n as usize);
         self.n_payload_bytes += n as u64;
         let n = block.decode(dst, src)?;
         self.n_payload_bytes += n as u64;
         Ok(())
     }
+
+    #[inline(always)]
+    fn check_block_payload(&self, n_payload_bytes: u32) -> crate::Result<()> {
+        match self.max_block_payload {
+            Some(limit) if n_payload_bytes > limit => Err(Error::PayloadOverflow),
+            _ => Ok(()),
+        }
+    }
+
+    // Block headers declare their raw byte count, which is validated against the actual decoded
+    // output. Checking the declared count bounds the output before the block is decoded.
+    #[inline(always)]
+    fn check_block_output<O: LzWriter>(&self, dst: &O, n_raw_bytes: u32) -> crate::Result<()> {
+        match self.max_output {
+            Some(limit) if dst.n_raw_bytes() - self.dst_mark + n_raw_bytes as u64 > limit => {
+                Err(Error::OutputLimitExceeded)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Maximum cumulative decoded output for `execute`, see `LzfseRingDecoder::decode_with_limit`.
+    pub(super) fn set_max_output(&mut self, limit: Option<u64>) {
+        self.max_output = limit;
+    }
 }
 
 impl fmt::Debug for LzfseDecoder {
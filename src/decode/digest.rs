@@ -0,0 +1,173 @@
+use crate::base::TrailerKind;
+use crate::error::Error;
+use crate::trailer;
+
+use super::decoder::LzfseDecoder;
+
+use digest::Digest;
+
+/// Decode `src` into `dst`, as encoded by [encode_with_digest](crate::encode_with_digest),
+/// verifying the decoded output against the trailer digest. Returns the number of bytes written
+/// into `dst`.
+///
+/// Requires the `digest` feature.
+///
+/// # Errors
+///
+/// * [Error::BadTrailer](crate::Error::BadTrailer) if the trailers are malformed, none is a
+///   [TrailerKind::Digest](crate::TrailerKind::Digest) trailer or its length does not match the
+///   `hasher` digest length.
+/// * [Error::DigestMismatch](crate::Error::DigestMismatch) if the decoded output digest does not
+///   match the trailer digest.
+/// * [Error](crate::Error) detailing the nature of any other decoding errors.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::Error;
+/// use sha2::{Digest, Sha256};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     lzfse_rust::encode_with_digest(b"test", &mut enc, Sha256::new())?;
+///     // Tamper with the digest.
+///     *enc.last_mut().unwrap() ^= 1;
+///     let mut dec = Vec::default();
+///     match lzfse_rust::decode_verify_digest(&enc, &mut dec, Sha256::new()) {
+///         Err(Error::DigestMismatch) => {}
+///         _ => panic!(),
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn decode_verify_digest<H: Digest>(
+    src: &[u8],
+    dst: &mut Vec<u8>,
+    mut hasher: H,
+) -> crate::Result<u64> {
+    let trailer = trailer::find(src, TrailerKind::Digest).map_err(|_| Error::BadTrailer)?;
+    if trailer.len() != H::output_size() {
+        return Err(Error::BadTrailer);
+    }
+    let mark = dst.len();
+    let n_raw_bytes = LzfseDecoder::default().decode_bytes(src, dst)?;
+    hasher.update(&dst[mark..]);
+    if hasher.finalize()[..] != *trailer {
+        return Err(Error::DigestMismatch);
+    }
+    Ok(n_raw_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LzfseRingDecoder, LzfseRingEncoder};
+
+    use sha2::Sha256;
+    use test_kit::{Rng, Seq};
+
+    use super::*;
+
+    fn encode(src: &[u8]) -> crate::Result<Vec<u8>> {
+        let mut enc = Vec::default();
+        let digest = crate::encode_with_digest(src, &mut enc, Sha256::new())?;
+        assert!(digest == Sha256::digest(src));
+        Ok(enc)
+    }
+
+    #[test]
+    fn round_trip() -> crate::Result<()> {
+        for src in &[
+            Vec::default(),
+            b"test".to_vec(),
+            Seq::default().take(0x0001_0000).collect::<Vec<_>>(),
+            Rng::default().gen_vec(0x1000).unwrap(),
+        ] {
+            let enc = encode(src)?;
+            let mut dec = Vec::default();
+            let n = decode_verify_digest(&enc, &mut dec, Sha256::new())?;
+            assert_eq!(n, src.len() as u64);
+            assert!(&dec == src);
+            // Plain decoders ignore the trailer.
+            dec.clear();
+            crate::decode_bytes(&enc, &mut dec)?;
+            assert!(&dec == src);
+            dec.clear();
+            LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut dec)?;
+            assert!(&dec == src);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tamper() -> crate::Result<()> {
+        // Raw block, flipped literal bytes decode without error.
+        let src = b"test";
+        let enc = encode(src)?;
+        for index in 8..12 {
+            let mut enc = enc.clone();
+            enc[index] ^= 1;
+            let mut dec = Vec::default();
+            match decode_verify_digest(&enc, &mut dec, Sha256::new()) {
+                Err(Error::DigestMismatch) => {}
+                _ => panic!(),
+            }
+        }
+        // Compressed blocks, flipped bytes either fail to decode, fail verification or, as in the
+        // case of unused bits, decode correctly.
+        let src = Seq::default().take(0x4000).collect::<Vec<_>>();
+        let enc = encode(&src)?;
+        let mut dec = Vec::default();
+        for index in 0..enc.len() {
+            let mut enc = enc.clone();
+            enc[index] ^= 1;
+            dec.clear();
+            if decode_verify_digest(&enc, &mut dec, Sha256::new()).is_ok() {
+                assert!(dec == src);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bad_trailer() -> crate::Result<()> {
+        let mut enc = Vec::default();
+        crate::encode_bytes(b"test", &mut enc)?;
+        let mut dec = Vec::default();
+        match decode_verify_digest(&enc, &mut dec, Sha256::new()) {
+            Err(Error::BadTrailer) => {}
+            _ => panic!(),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn other_trailer_kind() -> crate::Result<()> {
+        let mut enc = encode(b"test")?;
+        let index = enc.len() - 8 - Sha256::output_size();
+        enc[index..index + 4].copy_from_slice(&TrailerKind::Crc32.magic_bytes());
+        let mut dec = Vec::default();
+        match decode_verify_digest(&enc, &mut dec, Sha256::new()) {
+            Err(Error::BadTrailer) => {}
+            _ => panic!(),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bad_trailer_len() -> crate::Result<()> {
+        let mut enc = Vec::default();
+        LzfseRingEncoder::default().encode_bytes(b"test", &mut enc)?;
+        enc.extend_from_slice(&u32::from(TrailerKind::Digest).to_le_bytes());
+        enc.extend_from_slice(&1u32.to_le_bytes());
+        enc.extend_from_slice(&[0]);
+        // Plain decoders reject inconsistent trailers.
+        let mut dec = Vec::default();
+        match crate::decode_bytes(&enc[..enc.len() - 1], &mut dec) {
+            Err(Error::PayloadOverflow) => {}
+            _ => panic!(),
+        }
+        crate::decode_bytes(&enc, &mut dec)?;
+        Ok(())
+    }
+}
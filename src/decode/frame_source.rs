@@ -0,0 +1,86 @@
+use crate::error::Error;
+
+use super::ring_decoder::LzfseRingDecoder;
+
+use std::fmt;
+use std::io::{self, Read};
+
+/// LZFSE length-delimited frame source.
+///
+/// Decodes frames, one at a time, from an inner reader. Each frame consists of a 4 byte
+/// little-endian length followed by an independent LZFSE stream of that length, as produced by
+/// [FrameSink](crate::FrameSink).
+///
+/// Instances are created using [LzfseRingDecoder::frame_source].
+pub struct FrameSource<'a, I: Read> {
+    decoder: &'a mut LzfseRingDecoder,
+    inner: I,
+    buffer: Vec<u8>,
+}
+
+impl<'a, I: Read> FrameSource<'a, I> {
+    #[inline(always)]
+    pub(super) fn new(decoder: &'a mut LzfseRingDecoder, inner: I) -> Self {
+        Self { decoder, inner, buffer: Vec::default() }
+    }
+
+    /// Decode the next frame into `dst` returning the number of bytes written into `dst`, or
+    /// `None` if the inner reader is exhausted on a frame boundary.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    /// * [Error::Io](crate::Error::Io) of kind [ErrorKind::OutOfMemory](std::io::ErrorKind) if the
+    ///   frame buffer or `dst` fails to allocate sufficient memory.
+    pub fn read_frame(&mut self, dst: &mut Vec<u8>) -> crate::Result<Option<u64>> {
+        let len = match self.read_len()? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+        self.buffer.clear();
+        self.buffer
+            .try_reserve(len as usize)
+            .map_err(|_| io::Error::from(io::ErrorKind::OutOfMemory))?;
+        (&mut self.inner).take(len as u64).read_to_end(&mut self.buffer)?;
+        if self.buffer.len() != len as usize {
+            return Err(Error::PayloadUnderflow);
+        }
+        self.decoder.decode_bytes(&self.buffer, dst).map(Some)
+    }
+
+    fn read_len(&mut self) -> crate::Result<Option<u32>> {
+        let mut bytes = [0u8; 4];
+        let mut n = 0;
+        while n < 4 {
+            match self.inner.read(&mut bytes[n..]) {
+                Ok(0) if n == 0 => return Ok(None),
+                Ok(0) => return Err(Error::PayloadUnderflow),
+                Ok(m) => n += m,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Some(u32::from_le_bytes(bytes)))
+    }
+
+    /// Gets a reference to the inner reader.
+    pub fn get_ref(&self) -> &I {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+
+    /// Unwraps this `FrameSource<I>`, returning the inner reader.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<'a, I: Read> fmt::Debug for FrameSource<'a, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameSource").finish()
+    }
+}
@@ -0,0 +1,103 @@
+use crate::kit::Width;
+use crate::lmd::{DMax, LiteralLen, MMax, MatchDistanceUnpack, MatchLen, Quad};
+use crate::lz::LzWriter;
+use crate::ops::{CopyLong, ShortLimit};
+use crate::types::ShortBytes;
+
+/// LZ match decode leniency.
+///
+/// Controls the handling of invalid matches, that is matches with a zero distance or a distance
+/// reaching beyond the start of the decoded output, as may be found in slightly corrupt streams.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum DecodeLeniency {
+    /// Reject invalid matches with [Error::BadDValue](crate::Error::BadDValue).
+    Strict,
+    /// Fill invalid matches with the specified sentinel byte and continue decoding.
+    ///
+    /// Intended for best effort forensic recovery. Damage is confined to the match length, so
+    /// the decoded size and all other decoded bytes are unaffected, with the caveat that later
+    /// matches referencing damaged regions propagate the sentinel. Other errors are reported as
+    /// usual.
+    FillSentinel(u8),
+}
+
+impl Default for DecodeLeniency {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+/// Fill invalid matches with a sentinel byte, forwarding to the inner writer.
+pub struct SentinelWriter<'a, O> {
+    inner: &'a mut O,
+    sentinel: [u8; 0x0100],
+}
+
+impl<'a, O: LzWriter> SentinelWriter<'a, O> {
+    #[inline(always)]
+    pub fn new(inner: &'a mut O, sentinel: u8) -> Self {
+        Self { inner, sentinel: [sentinel; 0x0100] }
+    }
+
+    #[cold]
+    fn fill(&mut self, mut len: usize) -> crate::Result<()> {
+        while len != 0 {
+            let n = len.min(self.sentinel.len());
+            self.inner.write_bytes_long(&self.sentinel[..n])?;
+            len -= n;
+        }
+        Ok(())
+    }
+}
+
+unsafe impl<'a, O: ShortLimit> ShortLimit for SentinelWriter<'a, O> {
+    const SHORT_LIMIT: u32 = O::SHORT_LIMIT;
+}
+
+impl<'a, O: LzWriter> LzWriter for SentinelWriter<'a, O> {
+    const MAX_MATCH_DISTANCE: u32 = O::MAX_MATCH_DISTANCE;
+
+    const MAX_MATCH_LEN: u32 = O::MAX_MATCH_LEN;
+
+    #[inline(always)]
+    fn write_bytes_long<T: CopyLong>(&mut self, bytes: T) -> crate::Result<()> {
+        self.inner.write_bytes_long(bytes)
+    }
+
+    #[inline(always)]
+    fn write_bytes_short<T: ShortLimit, W: Width>(
+        &mut self,
+        bytes: ShortBytes<T, W>,
+    ) -> crate::Result<()> {
+        self.inner.write_bytes_short(bytes)
+    }
+
+    #[inline(always)]
+    fn write_quad(&mut self, bytes: u32, len: LiteralLen<Quad>) -> crate::Result<()> {
+        self.inner.write_quad(bytes, len)
+    }
+
+    #[inline(always)]
+    fn write_match<T>(
+        &mut self,
+        len: MatchLen<T>,
+        distance: MatchDistanceUnpack<T>,
+    ) -> crate::Result<()>
+    where
+        T: DMax + MMax,
+    {
+        let d = distance.get();
+        if d == 0 || d as u64 > self.inner.n_raw_bytes() {
+            self.fill(len.get() as usize)
+        } else {
+            self.inner.write_match(len, distance)
+        }
+    }
+
+    #[inline(always)]
+    fn n_raw_bytes(&self) -> u64 {
+        self.inner.n_raw_bytes()
+    }
+}
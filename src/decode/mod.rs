@@ -1,15 +1,50 @@
+#[cfg(feature = "tokio")]
+mod async_reader;
+mod block_cost;
+mod chunk_sink;
+mod circular_file_sink;
 mod constants;
+mod decoded_cursor;
 mod decoder;
+#[cfg(feature = "digest")]
+mod digest;
+mod frame_source;
+mod leniency;
+mod output_sink;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod probe;
 mod reader_core;
+mod report;
 mod ring_decoder;
+mod ring_profile;
+mod slice_writer;
 mod take;
+mod trace;
 
+use crate::error::Error;
+
+#[cfg(feature = "tokio")]
+pub use async_reader::AsyncLzfseReader;
+pub use block_cost::BlockCost;
+pub use circular_file_sink::CircularFileSink;
+pub use decoded_cursor::DecodedCursor;
 pub use decoder::LzfseDecoder;
-pub use probe::probe;
+#[cfg(feature = "digest")]
+pub use digest::decode_verify_digest;
+pub use frame_source::FrameSource;
+pub use leniency::DecodeLeniency;
+pub use output_sink::OutputSink;
+#[cfg(feature = "rayon")]
+pub use parallel::verify_many;
+pub use probe::{probe, stream_len};
 pub use reader_core::ReaderCore;
+pub use report::DecodeReport;
 pub use ring_decoder::{LzfseReader, LzfseReaderBytes, LzfseRingDecoder};
+pub(crate) use ring_profile::Profile;
+pub use ring_profile::RingProfile;
 pub use take::Take;
+pub use trace::DecodeEvent;
 
 /// Decode `src` into `dst` returning the number of bytes written into `dst`.
 ///
@@ -49,3 +84,175 @@ pub use take::Take;
 pub fn decode_bytes(src: &[u8], dst: &mut Vec<u8>) -> crate::Result<u64> {
     LzfseDecoder::default().decode_bytes(src, dst)
 }
+
+/// Decode `src` into the fixed buffer `dst` returning the number of bytes written into `dst`.
+///
+/// This is a convenience method that constructs a temporary [LzfseDecoder] instance and then calls
+/// [decode_bytes_into](LzfseDecoder::decode_bytes_into).
+///
+/// # Errors
+///
+/// * [Error::BufferOverflow](crate::Error::BufferOverflow) if the decoded output exceeds `dst`.
+/// * [Error](crate::Error) detailing the nature of any other errors.
+pub fn decode_bytes_into(src: &[u8], dst: &mut [u8]) -> crate::Result<usize> {
+    LzfseDecoder::default().decode_bytes_into(src, dst)
+}
+
+/// Decode `src` into `dst` with an upper `max` output bound, returning the number of bytes written
+/// into `dst`.
+///
+/// This is a convenience method that constructs a temporary [LzfseDecoder] instance and then calls
+/// [decode_bounded](LzfseDecoder::decode_bounded). For multiple invocations, creating and reusing
+/// a [LzfseDecoder] instance is more efficient.
+///
+/// # Errors
+///
+/// * [Error::OutputLimitExceeded](crate::Error::OutputLimitExceeded) if the claimed decoded size
+///   exceeds `max`.
+/// * [Error](crate::Error) detailing the nature of any other errors.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     // "test" string encoded.
+///     let enc = vec![
+///         0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76,
+///         0x78, 0x24,
+///     ];
+///     let mut dec = Vec::default();
+///     let n_bytes = lzfse_rust::decode_bounded(&enc, &mut dec, 1024)?;
+///     assert_eq!(n_bytes, 4);
+///     assert_eq!(dec, b"test");
+///     Ok(())
+/// }
+/// ```
+pub fn decode_bounded(src: &[u8], dst: &mut Vec<u8>, max: u64) -> crate::Result<u64> {
+    LzfseDecoder::default().decode_bounded(src, dst, max)
+}
+
+/// Decode `src` into `dst` given the known, `expected_len`, decoded size, returning the number of
+/// bytes written into `dst`.
+///
+/// This is a convenience method that constructs a temporary [LzfseDecoder] instance and then calls
+/// [decode_bytes_expect](LzfseDecoder::decode_bytes_expect).
+///
+/// # Errors
+///
+/// * [Error::OutputSizeMismatch](crate::Error::OutputSizeMismatch) if either the claimed or the
+///   actual decoded size does not match `expected_len`.
+/// * [Error](crate::Error) detailing the nature of any other errors.
+pub fn decode_bytes_expect(src: &[u8], dst: &mut Vec<u8>, expected_len: u64) -> crate::Result<u64> {
+    LzfseDecoder::default().decode_bytes_expect(src, dst, expected_len)
+}
+
+/// Decode `src` passing the decoded output to `f` in `chunk_size` chunks, returning the number of
+/// decoded bytes.
+///
+/// This is a convenience method that constructs a temporary [LzfseRingDecoder] instance and then
+/// calls [decode_chunked](LzfseRingDecoder::decode_chunked).
+///
+/// # Errors
+///
+/// * [Error](crate::Error) detailing the nature of any errors.
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is zero.
+pub fn decode_chunked<F: FnMut(&[u8])>(src: &[u8], chunk_size: usize, f: F) -> crate::Result<u64> {
+    LzfseRingDecoder::default().decode_chunked(src, chunk_size, f)
+}
+
+/// Returns the decoded size of `src` without decoding.
+///
+/// This is a convenience method that calls [decoded_len](LzfseDecoder::decoded_len) without
+/// constructing a decoder instance.
+///
+/// # Errors
+///
+/// * [Error](crate::Error) detailing the nature of any header errors.
+pub fn decoded_len(src: &[u8]) -> crate::Result<u64> {
+    probe::probe(src)
+}
+
+/// Quick check `src` integrity, returning the claimed decoded size.
+///
+/// This is a convenience method that constructs a temporary [LzfseDecoder] instance and then calls
+/// [quick_verify](LzfseDecoder::quick_verify). This is a probabilistic check, NOT a full
+/// verification.
+///
+/// # Errors
+///
+/// * [Error](crate::Error) detailing the nature of any errors.
+pub fn quick_verify(src: &[u8]) -> crate::Result<u64> {
+    LzfseDecoder::default().quick_verify(src)
+}
+
+/// Decode block `index` of `src` into `dst` returning the number of bytes written into `dst`.
+///
+/// This is a convenience method that constructs a temporary [LzfseDecoder] instance and then calls
+/// [decode_block](LzfseDecoder::decode_block).
+///
+/// # Errors
+///
+/// * [Error::BlockIndexOutOfRange](crate::Error::BlockIndexOutOfRange) if `src` contains `index`
+///   or fewer blocks.
+/// * [Error](crate::Error) detailing the nature of any other errors.
+pub fn decode_block(src: &[u8], index: usize, dst: &mut Vec<u8>) -> crate::Result<u64> {
+    LzfseDecoder::default().decode_block(src, index, dst)
+}
+
+/// Decode `src` returning the decoded output of each block in order.
+///
+/// This is a convenience method that constructs a temporary [LzfseDecoder] instance and then calls
+/// [decode_blocks_vec](LzfseDecoder::decode_blocks_vec).
+///
+/// # Errors
+///
+/// * [Error](crate::Error) detailing the nature of any errors.
+pub fn decode_blocks_vec(src: &[u8]) -> crate::Result<Vec<Vec<u8>>> {
+    LzfseDecoder::default().decode_blocks_vec(src)
+}
+
+/// Explain `src` returning a bit cost breakdown for each FSE compressed block.
+///
+/// This is a convenience method that constructs a temporary [LzfseDecoder] instance and then calls
+/// [explain](LzfseDecoder::explain).
+///
+/// # Errors
+///
+/// * [Error](crate::Error) detailing the nature of any errors.
+pub fn explain(src: &[u8]) -> crate::Result<Vec<BlockCost>> {
+    LzfseDecoder::default().explain(src)
+}
+
+/// Decode `src` into a [String], validating that the decoded output is UTF-8.
+///
+/// This is a convenience method that constructs a temporary [LzfseDecoder] instance and then calls
+/// [decode_bytes](LzfseDecoder::decode_bytes).
+///
+/// # Errors
+///
+/// * [Error::Utf8](crate::Error::Utf8) if the decoded output is not valid UTF-8.
+/// * [Error](crate::Error) detailing the nature of any decoding errors.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     lzfse_rust::encode_str("ταχίστη αλώπηξ", &mut enc)?;
+///     let dec = lzfse_rust::decode_string(&enc)?;
+///     assert_eq!(dec, "ταχίστη αλώπηξ");
+///     Ok(())
+/// }
+/// ```
+pub fn decode_string(src: &[u8]) -> crate::Result<String> {
+    let mut dst = Vec::default();
+    LzfseDecoder::default().decode_bytes(src, &mut dst)?;
+    String::from_utf8(dst).map_err(|err| Error::Utf8(err.utf8_error()))
+}
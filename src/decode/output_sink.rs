@@ -0,0 +1,58 @@
+use std::io::{self, Write};
+
+/// LZFSE decoded output sink.
+///
+/// Decoded bytes are delivered in stream order, in chunks of arbitrary length, allowing them to be
+/// intercepted or transformed without an intermediate buffer. All [Write] implementations,
+/// including [Vec], are sinks.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{LzfseRingDecoder, OutputSink};
+/// use std::io;
+///
+/// // Counts decoded zero bytes.
+/// struct Zeros(u64);
+///
+/// impl OutputSink for Zeros {
+///     fn write_decoded(&mut self, bytes: &[u8]) -> io::Result<()> {
+///         self.0 += bytes.iter().filter(|&&u| u == 0).count() as u64;
+///         Ok(())
+///     }
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     lzfse_rust::encode_bytes(&[0u8; 0x1000], &mut enc)?;
+///     let mut zeros = Zeros(0);
+///     LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut zeros)?;
+///     assert_eq!(zeros.0, 0x1000);
+///     Ok(())
+/// }
+/// ```
+pub trait OutputSink {
+    /// Write all decoded `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) in case of sink errors, which terminate decoding.
+    fn write_decoded(&mut self, bytes: &[u8]) -> io::Result<()>;
+}
+
+impl<W: Write + ?Sized> OutputSink for W {
+    #[inline(always)]
+    fn write_decoded(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.write_all(bytes)
+    }
+}
+
+/// Mutable reference sink adapter, `&mut S` is not itself a sink.
+pub struct SinkMut<'a, S: ?Sized>(pub &'a mut S);
+
+impl<'a, S: OutputSink + ?Sized> OutputSink for SinkMut<'a, S> {
+    #[inline(always)]
+    fn write_decoded(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.0.write_decoded(bytes)
+    }
+}
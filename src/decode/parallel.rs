@@ -0,0 +1,93 @@
+use super::ring_decoder::LzfseRingDecoder;
+
+use rayon::prelude::*;
+
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+
+/// Verify the LZFSE files `paths` in parallel returning, for each file in order, the number of
+/// decoded bytes.
+///
+/// Each file is decoded into a null sink using the current [rayon](https://docs.rs/rayon) thread
+/// pool, the decoded output is discarded. Errors are reported per file and do not abort the
+/// remaining files.
+///
+/// Requires the `rayon` feature.
+///
+/// # Errors
+///
+/// Per file:
+///
+/// * [Error](crate::Error) in case of file IO errors.
+/// * [Error](crate::Error) detailing the nature of any decoding errors.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::PathBuf;
+///
+/// let paths = vec![PathBuf::from("a.txt.lzfse"), PathBuf::from("b.txt.lzfse")];
+/// for (path, result) in paths.iter().zip(lzfse_rust::verify_many(&paths)) {
+///     match result {
+///         Ok(n_raw_bytes) => println!("{}: ok, {} B", path.display(), n_raw_bytes),
+///         Err(err) => println!("{}: {}", path.display(), err),
+///     }
+/// }
+/// ```
+pub fn verify_many(paths: &[PathBuf]) -> Vec<crate::Result<u64>> {
+    paths
+        .par_iter()
+        .map_init(LzfseRingDecoder::default, |decoder, path| {
+            let mut src = File::open(path)?;
+            let (_, n_raw_bytes) = decoder.decode(&mut src, &mut io::sink())?;
+            Ok(n_raw_bytes)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    use test_kit::Seq;
+
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn verify_many_dir() -> io::Result<()> {
+        let dir =
+            std::env::temp_dir().join(format!("lzfse_rust_verify_many_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let mut paths = Vec::default();
+        for n in 0..8 {
+            let dec = Seq::default().take(n * 0x4000 + n).collect::<Vec<_>>();
+            let mut enc = Vec::default();
+            crate::encode_bytes(&dec, &mut enc)?;
+            if n == 5 {
+                // Corrupt, truncated.
+                enc.truncate(enc.len() / 2);
+            }
+            let path = dir.join(format!("{}.lzfse", n));
+            fs::write(&path, &enc)?;
+            paths.push(path);
+        }
+        paths.push(dir.join("missing.lzfse"));
+        let results = verify_many(&paths);
+        fs::remove_dir_all(&dir)?;
+        assert_eq!(results.len(), paths.len());
+        for (n, result) in results.iter().enumerate() {
+            match (n, result) {
+                (5, Err(Error::PayloadUnderflow)) => {}
+                (8, Err(Error::Io(err))) => assert_eq!(err.kind(), io::ErrorKind::NotFound),
+                (n, Ok(n_raw_bytes)) if n < 8 && n != 5 => {
+                    assert_eq!(*n_raw_bytes, (n * 0x4000 + n) as u64)
+                }
+                (n, result) => panic!("{}: {:?}", n, result),
+            }
+        }
+        Ok(())
+    }
+}
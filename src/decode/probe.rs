@@ -3,33 +3,64 @@ use crate::error::Error;
 use crate::fse;
 use crate::ops::{PeekData, Skip};
 use crate::raw;
+use crate::trailer;
 use crate::vn;
 
 use std::convert::TryInto;
 
-#[allow(dead_code)]
-pub fn probe(mut src: &[u8]) -> crate::Result<u64> {
+pub fn probe(src: &[u8]) -> crate::Result<u64> {
+    let (index, t_raw_bytes, _) = walk(src)?;
+    trailer::skip_eos(&mut &src[index..])?;
+    Ok(t_raw_bytes)
+}
+
+// As per `probe`, additionally returning the final block index, if any.
+pub(super) fn probe_last(src: &[u8]) -> crate::Result<(u64, Option<usize>)> {
+    let (index, t_raw_bytes, last) = walk(src)?;
+    trailer::skip_eos(&mut &src[index..])?;
+    Ok((t_raw_bytes, last))
+}
+
+/// Returns the length of the LZFSE stream in `src`, up to and including the end of stream block,
+/// without decoding. Any bytes that follow, such as a trailer, are not validated.
+pub fn stream_len(src: &[u8]) -> crate::Result<usize> {
+    let (index, _, _) = walk(src)?;
+    Ok(index + 4)
+}
+
+// Walk the block headers, returning the end of stream block index, the total raw byte count and
+// the final block index, if any.
+fn walk(mut src: &[u8]) -> crate::Result<(usize, u64, Option<usize>)> {
+    let len = src.len();
     let mut t_raw_bytes: u64 = 0;
+    let mut last = None;
     loop {
         if src.len() < 4 {
             return Err(Error::PayloadUnderflow);
         }
         let magic_bytes: MagicBytes = src.peek_u32().try_into()?;
-        let (n_payload_bytes, n_raw_bytes) = match magic_bytes {
-            MagicBytes::Vx1 => fse::v1_probe(src)?,
-            MagicBytes::Vx2 => fse::v2_probe(src)?,
-            MagicBytes::Vxn => vn::vn_probe(src)?,
-            MagicBytes::Raw => raw::raw_probe(src)?,
-            MagicBytes::Eos => break,
-        };
+        if magic_bytes == MagicBytes::Eos {
+            break;
+        }
+        let (n_payload_bytes, n_raw_bytes) = probe_block(src, magic_bytes)?;
         if n_payload_bytes as usize >= src.len() {
             return Err(Error::PayloadUnderflow);
         }
+        last = Some(len - src.len());
         src.skip(n_payload_bytes as usize);
         t_raw_bytes += n_raw_bytes as u64;
     }
-    if src.len() != 4 {
-        return Err(Error::PayloadOverflow);
+    Ok((len - src.len(), t_raw_bytes, last))
+}
+
+// Probe the block header at the head of `src`, returning the block length, header inclusive, and
+// the raw byte count. The end of stream block has no payload.
+pub(super) fn probe_block(src: &[u8], magic_bytes: MagicBytes) -> crate::Result<(u32, u32)> {
+    match magic_bytes {
+        MagicBytes::Vx1 => fse::v1_probe(src),
+        MagicBytes::Vx2 => fse::v2_probe(src),
+        MagicBytes::Vxn => vn::vn_probe(src),
+        MagicBytes::Raw => raw::raw_probe(src),
+        MagicBytes::Eos => Ok((4, 0)),
     }
-    Ok(t_raw_bytes)
 }
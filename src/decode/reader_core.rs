@@ -3,12 +3,11 @@ use crate::error::Error;
 use crate::fse::FseCore;
 use crate::ops::{Len, Pos};
 use crate::raw::RawBlock;
-use crate::ring::{RingBlock, RingLzWriter, RingSize};
+use crate::ring::{RingBlock, RingLzWriter};
+use crate::trailer;
 use crate::types::{ByteReader, Idx};
 use crate::vn::VnCore;
 
-use super::constants::*;
-
 use std::convert::TryInto;
 use std::io::{self, Read, Sink};
 
@@ -22,20 +21,21 @@ enum State {
     Err,
 }
 
-pub struct ReaderCore<'a, I: for<'b> ByteReader<'b>> {
-    ring: RingLzWriter<'a, Sink, Output>,
+pub struct ReaderCore<'a, I: for<'b> ByteReader<'b>, T> {
+    ring: RingLzWriter<'a, Sink, T>,
     inner: I,
     fse_core: &'a mut FseCore,
     vn_core: VnCore,
     raw_block: RawBlock,
     state: State,
     idx: Idx,
+    n_raw_bytes: u64,
 }
 
-impl<'a, I: for<'b> ByteReader<'b>> ReaderCore<'a, I> {
+impl<'a, I: for<'b> ByteReader<'b>, T: RingBlock> ReaderCore<'a, I, T> {
     #[allow(clippy::assertions_on_constants)]
-    pub fn new(ring: RingLzWriter<'a, Sink, Output>, inner: I, fse_core: &'a mut FseCore) -> Self {
-        assert!(Output::RING_BLK_SIZE <= Output::RING_SIZE / 4);
+    pub fn new(ring: RingLzWriter<'a, Sink, T>, inner: I, fse_core: &'a mut FseCore) -> Self {
+        assert!(T::RING_BLK_SIZE <= T::RING_SIZE / 4);
         Self {
             ring,
             inner,
@@ -44,15 +44,39 @@ impl<'a, I: for<'b> ByteReader<'b>> ReaderCore<'a, I> {
             raw_block: RawBlock::default(),
             state: State::None,
             idx: Idx::default(),
+            n_raw_bytes: 0,
         }
     }
 
     pub fn into_inner(self) -> I {
         self.inner
     }
+
+    #[inline(always)]
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Total number of decoded bytes read.
+    #[inline(always)]
+    pub fn n_raw_bytes(&self) -> u64 {
+        self.n_raw_bytes
+    }
+
+    /// Validate that the end of stream block was reached and all decoded data was read.
+    pub fn finish(&mut self) -> crate::Result<()> {
+        loop {
+            if self.idx != self.ring.pos() {
+                return Err(Error::PayloadOverflow);
+            }
+            if !self.fill()? {
+                return Ok(());
+            }
+        }
+    }
 }
 
-impl<'a, I: for<'b> ByteReader<'b>> ReaderCore<'a, I> {
+impl<'a, I: for<'b> ByteReader<'b>, T: RingBlock> ReaderCore<'a, I, T> {
     #[cold]
     #[rustfmt::skip]
     fn fill(&mut self) -> crate::Result<bool> {
@@ -67,13 +91,13 @@ impl<'a, I: for<'b> ByteReader<'b>> ReaderCore<'a, I> {
             State::Err  => return Err(Error::BadReaderState),
         };
         // TODO consider formalizing fill overflow limits.
-        assert!(self.ring.pos() - self.idx < Output::RING_SIZE as i32  / 2);
+        assert!(self.ring.pos() - self.idx < T::RING_SIZE as i32  / 2);
         Ok(true)
     }
 
     fn fill_fse(&mut self) -> crate::Result<()> {
         debug_assert_eq!(self.idx, self.ring.pos());
-        let len = Output::RING_BLK_SIZE;
+        let len = T::RING_BLK_SIZE;
         if !self.fse_core.decode_n(&mut self.ring, len)? {
             self.state = State::None;
         }
@@ -82,7 +106,7 @@ impl<'a, I: for<'b> ByteReader<'b>> ReaderCore<'a, I> {
 
     fn fill_vn(&mut self) -> crate::Result<()> {
         debug_assert_eq!(self.idx, self.ring.pos());
-        let len = Output::RING_BLK_SIZE;
+        let len = T::RING_BLK_SIZE;
         if !self.vn_core.decode_n(&mut self.ring, &mut self.inner, len)? {
             self.state = State::None;
         }
@@ -91,7 +115,7 @@ impl<'a, I: for<'b> ByteReader<'b>> ReaderCore<'a, I> {
 
     fn fill_raw(&mut self) -> crate::Result<()> {
         debug_assert_eq!(self.idx, self.ring.pos());
-        let len = Output::RING_BLK_SIZE;
+        let len = T::RING_BLK_SIZE;
         if !self.raw_block.decode_n(&mut self.ring, &mut self.inner, len)? {
             self.state = State::None;
         }
@@ -157,17 +181,24 @@ impl<'a, I: for<'b> ByteReader<'b>> ReaderCore<'a, I> {
     }
 
     fn init_eos(&mut self) -> crate::Result<()> {
-        if self.inner.len() != 4 || !self.inner.is_eof() {
+        if !self.inner.is_eof() {
             self.state = State::Err;
-            Err(crate::Error::PayloadOverflow)
-        } else {
-            self.state = State::Eos;
-            Ok(())
+            return Err(crate::Error::PayloadOverflow);
+        }
+        match trailer::skip_eos(&mut self.inner) {
+            Ok(_) => {
+                self.state = State::Eos;
+                Ok(())
+            }
+            Err(err) => {
+                self.state = State::Err;
+                Err(err)
+            }
         }
     }
 }
 
-impl<'a, I: for<'b> ByteReader<'b>> Read for ReaderCore<'a, I> {
+impl<'a, I: for<'b> ByteReader<'b>, T: RingBlock> Read for ReaderCore<'a, I, T> {
     fn read(&mut self, mut buf: &mut [u8]) -> io::Result<usize> {
         let mark = self.idx;
         loop {
@@ -183,6 +214,8 @@ impl<'a, I: for<'b> ByteReader<'b>> Read for ReaderCore<'a, I> {
                 break;
             }
         }
-        Ok((self.idx - mark) as usize)
+        let n = (self.idx - mark) as usize;
+        self.n_raw_bytes += n as u64;
+        Ok(n)
     }
 }
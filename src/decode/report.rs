@@ -0,0 +1,96 @@
+use crate::kit::Width;
+use crate::lmd::{DMax, LiteralLen, MMax, MatchDistanceUnpack, MatchLen, Quad};
+use crate::lz::LzWriter;
+use crate::ops::{CopyLong, ShortLimit};
+use crate::types::ShortBytes;
+
+/// LZFSE decode report.
+///
+/// Summarizes the LZ content of a decoded stream. Produced by
+/// [LzfseDecoder::decode_bytes_report](crate::LzfseDecoder::decode_bytes_report).
+///
+/// The literal bytes and match bytes together account for the decoded output in full. Raw block
+/// bytes are counted as literals.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DecodeReport {
+    /// Literal bytes.
+    pub n_literals: u64,
+    /// Matches.
+    pub n_matches: u64,
+    /// Blocks, counting all block types but excluding the end of stream block.
+    pub n_blocks: u64,
+    /// Decoded size in bytes.
+    pub n_raw_bytes: u64,
+}
+
+/// Tally LZ output, forwarding to the inner writer.
+pub struct ReportWriter<'a, O> {
+    inner: &'a mut O,
+    n_literals: u64,
+    n_matches: u64,
+}
+
+impl<'a, O: LzWriter> ReportWriter<'a, O> {
+    #[inline(always)]
+    pub fn new(inner: &'a mut O) -> Self {
+        Self { inner, n_literals: 0, n_matches: 0 }
+    }
+
+    #[inline(always)]
+    pub fn report(&self, n_blocks: u64, n_raw_bytes: u64) -> DecodeReport {
+        DecodeReport {
+            n_literals: self.n_literals,
+            n_matches: self.n_matches,
+            n_blocks,
+            n_raw_bytes,
+        }
+    }
+}
+
+unsafe impl<'a, O: ShortLimit> ShortLimit for ReportWriter<'a, O> {
+    const SHORT_LIMIT: u32 = O::SHORT_LIMIT;
+}
+
+impl<'a, O: LzWriter> LzWriter for ReportWriter<'a, O> {
+    const MAX_MATCH_DISTANCE: u32 = O::MAX_MATCH_DISTANCE;
+
+    const MAX_MATCH_LEN: u32 = O::MAX_MATCH_LEN;
+
+    fn write_bytes_long<T: CopyLong>(&mut self, bytes: T) -> crate::Result<()> {
+        self.n_literals += bytes.len() as u64;
+        self.inner.write_bytes_long(bytes)
+    }
+
+    #[inline(always)]
+    fn write_bytes_short<T: ShortLimit, W: Width>(
+        &mut self,
+        bytes: ShortBytes<T, W>,
+    ) -> crate::Result<()> {
+        self.n_literals += bytes.len() as u64;
+        self.inner.write_bytes_short(bytes)
+    }
+
+    #[inline(always)]
+    fn write_quad(&mut self, bytes: u32, len: LiteralLen<Quad>) -> crate::Result<()> {
+        self.n_literals += len.get() as u64;
+        self.inner.write_quad(bytes, len)
+    }
+
+    #[inline(always)]
+    fn write_match<T>(
+        &mut self,
+        len: MatchLen<T>,
+        distance: MatchDistanceUnpack<T>,
+    ) -> crate::Result<()>
+    where
+        T: DMax + MMax,
+    {
+        self.n_matches += 1;
+        self.inner.write_match(len, distance)
+    }
+
+    #[inline(always)]
+    fn n_raw_bytes(&self) -> u64 {
+        self.inner.n_raw_bytes()
+    }
+}
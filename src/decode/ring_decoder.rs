@@ -1,11 +1,19 @@
-use crate::ring::{RingBox, RingLzWriter, RingReader};
+use crate::fse::{FseCore, PadMode};
+use crate::ring::{RingBlock, RingBox, RingFnReader, RingLzWriter, RingReader, RingType};
+use crate::types::ByteReader;
 
+#[cfg(feature = "tokio")]
+use super::async_reader::AsyncLzfseReader;
+use super::chunk_sink::ChunkSink;
 use super::constants::*;
 use super::decoder::LzfseDecoder;
+use super::frame_source::FrameSource;
+use super::output_sink::{OutputSink, SinkMut};
 use super::reader_core::ReaderCore;
+use super::ring_profile::{Profile, RingProfile};
 
 use std::fmt;
-use std::io::{self, Read, Write};
+use std::io::{self, Read};
 
 /// LZFSE ring decoder.
 ///
@@ -13,24 +21,153 @@ use std::io::{self, Read, Write};
 /// This implementation builds upon [LzfseDecoder] with the addition of internal ring buffers that
 /// enable efficient IO operations. It can be converted to a mutable [LzfseDecoder] reference using
 /// [as_mut()](AsMut::as_mut).
+///
+/// The output ring size is determined by the decoder's [RingProfile], see
+/// [with_profile()](LzfseRingDecoder::with_profile).
 #[derive(Default)]
 pub struct LzfseRingDecoder {
     core: LzfseDecoder,
     input: RingBox<Input>,
-    output: RingBox<Output>,
+    output: OutputRing,
+}
+
+// Output ring variants share names with `Profile` variants, see `dispatch`.
+enum OutputRing {
+    R32K(RingBox<Output32K>),
+    R64K(RingBox<Output64K>),
+    R128K(RingBox<Output128K>),
+    R256K(RingBox<Output256K>),
+    R512K(RingBox<Output>),
+}
+
+impl OutputRing {
+    fn new(profile: RingProfile) -> Self {
+        match profile.0 {
+            Profile::R32K => Self::R32K(RingBox::default()),
+            Profile::R64K => Self::R64K(RingBox::default()),
+            Profile::R128K => Self::R128K(RingBox::default()),
+            Profile::R256K => Self::R256K(RingBox::default()),
+            Profile::R512K => Self::R512K(RingBox::default()),
+        }
+    }
+
+    fn profile(&self) -> RingProfile {
+        RingProfile(match self {
+            Self::R32K(_) => Profile::R32K,
+            Self::R64K(_) => Profile::R64K,
+            Self::R128K(_) => Profile::R128K,
+            Self::R256K(_) => Profile::R256K,
+            Self::R512K(_) => Profile::R512K,
+        })
+    }
+}
+
+impl Default for OutputRing {
+    fn default() -> Self {
+        Self::new(RingProfile::default())
+    }
+}
+
+macro_rules! dispatch {
+    ($value:expr, $kind:ident, $u:ident => $body:expr) => {
+        match $value {
+            $kind::R32K($u) => $body,
+            $kind::R64K($u) => $body,
+            $kind::R128K($u) => $body,
+            $kind::R256K($u) => $body,
+            $kind::R512K($u) => $body,
+        }
+    };
 }
 
 impl LzfseRingDecoder {
+    // Heap held by an instance with the specified output ring `profile`.
+    pub(crate) fn heap_size(profile: RingProfile) -> usize {
+        LzfseDecoder::HEAP_SIZE + Input::RING_CAPACITY as usize + profile.ring_capacity() as usize
+    }
+
+    /// Create a new decoder instance with the specified output ring `profile`.
+    ///
+    /// Streams containing matches beyond the profile's
+    /// [max_match_distance()](RingProfile::max_match_distance) are rejected with
+    /// [Error::BadDValue](crate::Error::BadDValue).
+    pub fn with_profile(profile: RingProfile) -> Self {
+        Self {
+            core: LzfseDecoder::default(),
+            input: RingBox::default(),
+            output: OutputRing::new(profile),
+        }
+    }
+
+    /// Returns the decoder's output ring profile.
+    pub fn profile(&self) -> RingProfile {
+        self.output.profile()
+    }
+
+    /// Set the FSE bitstream [PadMode], see [LzfseDecoder::set_pad_mode].
+    pub fn set_pad_mode(&mut self, pad_mode: PadMode) {
+        self.core.set_pad_mode(pad_mode);
+    }
+
+    /// Returns the FSE bitstream [PadMode].
+    pub fn pad_mode(&self) -> PadMode {
+        self.core.pad_mode()
+    }
+
+    /// Set strict FSE state verification, see [LzfseDecoder::set_strict_fse].
+    pub fn set_strict_fse(&mut self, strict: bool) {
+        self.core.set_strict_fse(strict);
+    }
+
+    /// Returns true if strict FSE state verification is enabled.
+    pub fn strict_fse(&self) -> bool {
+        self.core.strict_fse()
+    }
+
+    /// Set the maximum block payload size, see [LzfseDecoder::set_max_block_payload].
+    pub fn set_max_block_payload(&mut self, limit: Option<u32>) {
+        self.core.set_max_block_payload(limit);
+    }
+
+    /// Returns the maximum block payload size.
+    pub fn max_block_payload(&self) -> Option<u32> {
+        self.core.max_block_payload()
+    }
+
+    /// Warm the decoder's internal tables and ring buffers, see [LzfseDecoder::warm].
+    pub fn warm(&mut self) {
+        self.core.warm();
+        self.input.warm();
+        dispatch!(&mut self.output, OutputRing, output => output.warm());
+    }
+
+    /// Reset the decoder's internal state, see [LzfseDecoder::reset].
+    ///
+    /// The ring buffers are retained, no allocations are made.
+    pub fn reset(&mut self) {
+        self.core.reset();
+    }
+
     /// Decode `reader` into `writer` returning a tuple (u, v) where u is the number of encoded
     /// bytes read from the reader and v is the number of decoded bytes written into the writer.
     ///
     /// Both the `reader` and `writer` are accessed efficiently by internal ring buffers, there is
     /// no need to wrap them in [BufReader](std::io::BufReader) or
-    /// [BufWriter](std::io::BufWriter).
+    /// [BufWriter](std::io::BufWriter). The `writer` may be any [OutputSink], which includes all
+    /// [Write](std::io::Write) implementations.
+    ///
+    /// Each call decodes a new independent stream, the decoder may be reused across calls without
+    /// reallocation, see [reset](Self::reset).
+    ///
+    /// The `reader` must hold exactly one frame. It is read to its end, any bytes following the
+    /// end of stream block, other than an optional trailer, are rejected. On success the returned
+    /// value is therefore the exact frame size.
     ///
     /// # Errors
     ///
     /// * [Error](crate::Error) detailing the nature of any errors.
+    /// * [Error::PayloadOverflow](crate::Error::PayloadOverflow) if bytes follow the frame, see
+    ///   [decode_concatenated](Self::decode_concatenated) for multiple frame streams.
     ///
     /// # Examples
     ///
@@ -54,15 +191,227 @@ impl LzfseRingDecoder {
     ///     Ok(())
     /// }
     /// ```
-    pub fn decode<I: Read, O: Write>(
+    pub fn decode<I: Read, O: OutputSink>(
         &mut self,
         reader: &mut I,
         writer: &mut O,
     ) -> crate::Result<(u64, u64)> {
-        let mut dst = RingLzWriter::new((&mut self.output).into(), writer);
-        let mut src = RingReader::new((&mut self.input).into(), reader);
-        let n = self.core.execute(&mut dst, &mut src)?;
-        dst.into_inner()?;
+        let core = &mut self.core;
+        let input = &mut self.input;
+        dispatch!(&mut self.output, OutputRing, output => {
+            let mut dst = RingLzWriter::new(output.into(), SinkMut(&mut *writer));
+            let mut src = RingReader::new(input.into(), &mut *reader);
+            let n = core.execute(&mut dst, &mut src)?;
+            dst.into_inner()?;
+            Ok(n)
+        })
+    }
+
+    /// Decode encoded input supplied by `f` into `writer` returning a tuple (u, v) where u is the
+    /// number of encoded bytes supplied by `f` and v is the number of decoded bytes written into
+    /// the writer.
+    ///
+    /// Intended for sources that are not [Read](std::io::Read) implementations, for example DMA
+    /// or io_uring completions. `f` is handed a writable region of the internal input ring, which
+    /// it fills directly, returning the number of bytes written. A zero return marks the end of
+    /// the input. Otherwise this method is identical to [decode](Self::decode).
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` returns a count greater than the length of the region it was handed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseRingDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(b"test", &mut enc)?;
+    ///     let mut src = enc.as_slice();
+    ///     let mut decoder = LzfseRingDecoder::default();
+    ///     let mut writer = Vec::default();
+    ///     let (u, v) = decoder.decode_fill(
+    ///         |buf| {
+    ///             let n = buf.len().min(src.len());
+    ///             buf[..n].copy_from_slice(&src[..n]);
+    ///             src = &src[n..];
+    ///             n
+    ///         },
+    ///         &mut writer,
+    ///     )?;
+    ///     assert_eq!(u, enc.len() as u64);
+    ///     assert_eq!(v, 4);
+    ///     assert_eq!(writer, b"test");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_fill<F: FnMut(&mut [u8]) -> usize, O: OutputSink>(
+        &mut self,
+        f: F,
+        writer: &mut O,
+    ) -> crate::Result<(u64, u64)> {
+        let core = &mut self.core;
+        let input = &mut self.input;
+        dispatch!(&mut self.output, OutputRing, output => {
+            let mut dst = RingLzWriter::new(output.into(), SinkMut(&mut *writer));
+            let mut src = RingFnReader::new(input.into(), f);
+            let n = core.execute(&mut dst, &mut src)?;
+            dst.into_inner()?;
+            Ok(n)
+        })
+    }
+
+    /// Decode `reader` into `writer` with an upper `max_output` decoded size bound, returning a
+    /// tuple (u, v) as per [decode](Self::decode).
+    ///
+    /// Intended for untrusted input, guarding against small streams that decode to huge outputs.
+    /// Each block header's declared decoded size is checked against the remaining output budget
+    /// as the block is reached, and before any of its output is emitted. As block outputs are
+    /// validated against their declared sizes, no more than `max_output` bytes are ever written.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::OutputLimitExceeded](crate::Error::OutputLimitExceeded) if the cumulative
+    ///   decoded size would exceed `max_output`.
+    /// * [Error](crate::Error) detailing the nature of any other errors.
+    ///
+    /// On error, `writer` may hold the output of preceding blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{Error, LzfseRingDecoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(b"test", &mut enc)?;
+    ///     let mut decoder = LzfseRingDecoder::default();
+    ///     let mut writer = Vec::default();
+    ///     let (_, v) = decoder.decode_with_limit(&mut enc.as_slice(), &mut writer, 4)?;
+    ///     assert_eq!(v, 4);
+    ///     assert_eq!(writer, b"test");
+    ///     writer.clear();
+    ///     match decoder.decode_with_limit(&mut enc.as_slice(), &mut writer, 3) {
+    ///         Err(Error::OutputLimitExceeded) => {}
+    ///         _ => panic!(),
+    ///     }
+    ///     assert!(writer.is_empty());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_with_limit<I: Read, O: OutputSink>(
+        &mut self,
+        reader: &mut I,
+        writer: &mut O,
+        max_output: u64,
+    ) -> crate::Result<(u64, u64)> {
+        self.core.set_max_output(Some(max_output));
+        let result = self.decode(reader, writer);
+        self.core.set_max_output(None);
+        result
+    }
+
+    /// Decode `reader`, which may hold multiple concatenated frames, into `writer` returning a
+    /// tuple (u, v) where u is the total number of encoded bytes read from the reader and v is the
+    /// total number of decoded bytes written into the writer.
+    ///
+    /// Streams concatenated in the manner of `cat a.lzfse b.lzfse` are decoded in sequence into
+    /// the same `writer`, as with the gzip and zstd tools. After each end of stream block, and
+    /// optional trailer, any remaining bytes must begin a new frame. Otherwise this method is
+    /// identical to [decode](Self::decode).
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    /// * [Error::PayloadOverflow](crate::Error::PayloadOverflow) if bytes following a frame do
+    ///   not begin a new frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseRingDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(b"abc", &mut enc)?;
+    ///     lzfse_rust::encode_bytes(b"def", &mut enc)?;
+    ///     let mut decoder = LzfseRingDecoder::default();
+    ///     let mut reader = enc.as_slice();
+    ///     let mut writer = Vec::default();
+    ///     let (u, v) = decoder.decode_concatenated(&mut reader, &mut writer)?;
+    ///     assert_eq!(u, enc.len() as u64);
+    ///     assert_eq!(v, 6);
+    ///     assert_eq!(writer, b"abcdef");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_concatenated<I: Read, O: OutputSink>(
+        &mut self,
+        reader: &mut I,
+        writer: &mut O,
+    ) -> crate::Result<(u64, u64)> {
+        let core = &mut self.core;
+        let input = &mut self.input;
+        dispatch!(&mut self.output, OutputRing, output => {
+            let mut dst = RingLzWriter::new(output.into(), SinkMut(&mut *writer));
+            let mut src = RingReader::new(input.into(), &mut *reader);
+            let n = core.execute_concatenated(&mut dst, &mut src)?;
+            dst.into_inner()?;
+            Ok(n)
+        })
+    }
+
+    /// Decode `src` passing the decoded output to `f` in `chunk_size` chunks, returning the
+    /// number of decoded bytes.
+    ///
+    /// Intended for re-framing decoded output, for example into fixed size network packets,
+    /// without an intermediate buffer holding the entire decoded output. Each chunk is exactly
+    /// `chunk_size` bytes, with the exception of the last which may be smaller. Chunks are passed
+    /// as decoding produces them, assembled across block boundaries as required.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](crate::Error) detailing the nature of any errors.
+    ///
+    /// Chunks passed prior to an error are not retracted, an incomplete trailing chunk is
+    /// discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseRingDecoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(&[0u8; 1000], &mut enc)?;
+    ///     let mut lens = Vec::default();
+    ///     LzfseRingDecoder::default().decode_chunked(&enc, 256, |chunk| lens.push(chunk.len()))?;
+    ///     assert_eq!(lens, [256, 256, 256, 232]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_chunked<F: FnMut(&[u8])>(
+        &mut self,
+        mut src: &[u8],
+        chunk_size: usize,
+        f: F,
+    ) -> crate::Result<u64> {
+        let mut sink = ChunkSink::new(chunk_size, f);
+        let (_, n) = self.decode(&mut src, &mut sink)?;
+        sink.flush();
         Ok(n)
     }
 
@@ -72,11 +421,20 @@ impl LzfseRingDecoder {
         self.core.decode_bytes(src, dst)
     }
 
+    /// Create a new [AsyncLzfseReader] decoder instance using the supplied `inner` tokio
+    /// [AsyncRead](tokio::io::AsyncRead) reader, see [LzfseDecoder::async_reader].
+    #[cfg(feature = "tokio")]
+    pub fn async_reader<I>(&mut self, inner: I) -> AsyncLzfseReader<'_, I>
+    where
+        I: tokio::io::AsyncRead + Unpin,
+    {
+        self.core.async_reader(inner)
+    }
+
     /// Create a new [LzfseReader] decoder instance using the supplied `inner` reader.
     pub fn reader<I: Read>(&mut self, inner: I) -> LzfseReader<I> {
-        let dst = RingLzWriter::new((&mut self.output).into(), io::sink());
         let src = RingReader::new((&mut self.input).into(), inner);
-        LzfseReader(ReaderCore::new(dst, src, &mut self.core.fse_core))
+        LzfseReader(ReaderKind::new(&mut self.output, src, &mut self.core.fse_core))
     }
 
     /// Create a new [LzfseReaderBytes] decoder instance using the supplied `bytes`.
@@ -84,8 +442,14 @@ impl LzfseRingDecoder {
     /// This method offers greater efficiency in comparison to [LzfseRingDecoder::reader]
     /// when operating over byte slices.
     pub fn reader_bytes<'a>(&'a mut self, bytes: &'a [u8]) -> LzfseReaderBytes {
-        let dst = RingLzWriter::new((&mut self.output).into(), io::sink());
-        LzfseReaderBytes(ReaderCore::new(dst, bytes, &mut self.core.fse_core))
+        let len = bytes.len() as u64;
+        LzfseReaderBytes(ReaderKind::new(&mut self.output, bytes, &mut self.core.fse_core), len)
+    }
+
+    /// Create a new [FrameSource] length-delimited frame decoder instance using the supplied
+    /// `inner` reader.
+    pub fn frame_source<I: Read>(&mut self, inner: I) -> FrameSource<'_, I> {
+        FrameSource::new(self, inner)
     }
 }
 
@@ -102,6 +466,58 @@ impl fmt::Debug for LzfseRingDecoder {
     }
 }
 
+enum ReaderKind<'a, I: for<'b> ByteReader<'b>> {
+    R32K(ReaderCore<'a, I, Output32K>),
+    R64K(ReaderCore<'a, I, Output64K>),
+    R128K(ReaderCore<'a, I, Output128K>),
+    R256K(ReaderCore<'a, I, Output256K>),
+    R512K(ReaderCore<'a, I, Output>),
+}
+
+impl<'a, I: for<'b> ByteReader<'b>> ReaderKind<'a, I> {
+    fn new(output: &'a mut OutputRing, inner: I, fse_core: &'a mut FseCore) -> Self {
+        match output {
+            OutputRing::R32K(u) => Self::R32K(Self::core(u, inner, fse_core)),
+            OutputRing::R64K(u) => Self::R64K(Self::core(u, inner, fse_core)),
+            OutputRing::R128K(u) => Self::R128K(Self::core(u, inner, fse_core)),
+            OutputRing::R256K(u) => Self::R256K(Self::core(u, inner, fse_core)),
+            OutputRing::R512K(u) => Self::R512K(Self::core(u, inner, fse_core)),
+        }
+    }
+
+    fn core<T: RingBlock>(
+        output: &'a mut RingBox<T>,
+        inner: I,
+        fse_core: &'a mut FseCore,
+    ) -> ReaderCore<'a, I, T> {
+        let dst = RingLzWriter::new(output.into(), io::sink());
+        ReaderCore::new(dst, inner, fse_core)
+    }
+
+    fn into_inner(self) -> I {
+        dispatch!(self, ReaderKind, u => u.into_inner())
+    }
+
+    fn inner(&self) -> &I {
+        dispatch!(self, ReaderKind, u => u.inner())
+    }
+
+    fn n_raw_bytes(&self) -> u64 {
+        dispatch!(self, ReaderKind, u => u.n_raw_bytes())
+    }
+
+    fn finish(&mut self) -> crate::Result<()> {
+        dispatch!(self, ReaderKind, u => u.finish())
+    }
+}
+
+impl<'a, I: for<'b> ByteReader<'b>> Read for ReaderKind<'a, I> {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        dispatch!(self, ReaderKind, u => u.read(buf))
+    }
+}
+
 /// LZFSE decoding reader.
 ///
 /// Exposes a LZFSE decoder via the [Read](std::io::Read) interface that decodes from
@@ -110,6 +526,11 @@ impl fmt::Debug for LzfseRingDecoder {
 /// Instances are created using
 /// [LzfseRingDecoder::reader](super::LzfseRingDecoder::reader).
 ///
+/// The running totals [payload_bytes_consumed](Self::payload_bytes_consumed) and
+/// [raw_bytes_read](Self::raw_bytes_read) mirror the tuple returned by
+/// [LzfseRingDecoder::decode], [finish](Self::finish) validates that the stream was read to
+/// completion.
+///
 /// # Examples
 ///
 /// ```
@@ -132,7 +553,7 @@ impl fmt::Debug for LzfseRingDecoder {
 /// }
 /// ```
 
-pub struct LzfseReader<'a, I: Read>(ReaderCore<'a, RingReader<'a, I, Input>>);
+pub struct LzfseReader<'a, I: Read>(ReaderKind<'a, RingReader<'a, I, Input>>);
 
 impl<'a, I: Read> LzfseReader<'a, I> {
     /// Unwraps and returns the underlying reader.
@@ -142,6 +563,52 @@ impl<'a, I: Read> LzfseReader<'a, I> {
     pub fn into_inner(self) -> I {
         self.0.into_inner().into_inner()
     }
+
+    /// Returns the total number of encoded bytes consumed from the underlying reader.
+    ///
+    /// Note that the underlying reader is read ahead in blocks, this count excludes buffered
+    /// bytes that have yet to be decoded.
+    pub fn payload_bytes_consumed(&self) -> u64 {
+        self.0.inner().n_consumed()
+    }
+
+    /// Returns the total number of decoded bytes read.
+    pub fn raw_bytes_read(&self) -> u64 {
+        self.0.n_raw_bytes()
+    }
+
+    /// Validate that the end of stream block was reached, returning a tuple (u, v) where u is the
+    /// total number of encoded bytes consumed and v is the total number of decoded bytes read.
+    ///
+    /// Any remaining blocks are decoded as required to reach the end of stream block.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::PayloadOverflow](crate::Error::PayloadOverflow) if decoded data remains unread.
+    /// * [Error::PayloadUnderflow](crate::Error::PayloadUnderflow) if the stream ended early.
+    /// * [Error](crate::Error) detailing the nature of any other errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseRingDecoder;
+    /// use std::io::{self, Read};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     lzfse_rust::encode_bytes(b"test", &mut enc)?;
+    ///     let mut decoder = LzfseRingDecoder::default();
+    ///     let mut reader = decoder.reader(enc.as_slice());
+    ///     let mut dec = [0u8; 4];
+    ///     reader.read_exact(&mut dec)?;
+    ///     assert_eq!(reader.finish()?, (enc.len() as u64, 4));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn finish(&mut self) -> crate::Result<(u64, u64)> {
+        self.0.finish()?;
+        Ok((self.payload_bytes_consumed(), self.raw_bytes_read()))
+    }
 }
 
 impl<'a, I: Read> fmt::Debug for LzfseReader<'a, I> {
@@ -165,6 +632,9 @@ impl<'a, I: Read> Read for LzfseReader<'a, I> {
 /// Instances are created using
 /// [LzfseRingDecoder::reader_bytes](super::LzfseRingDecoder::reader_bytes).
 ///
+/// As with [LzfseReader], running totals are tracked and [finish](Self::finish) validates that
+/// the stream was read to completion.
+///
 /// # Examples
 ///
 /// ```
@@ -185,7 +655,25 @@ impl<'a, I: Read> Read for LzfseReader<'a, I> {
 ///     Ok(())
 /// }
 /// ```
-pub struct LzfseReaderBytes<'a>(ReaderCore<'a, &'a [u8]>);
+pub struct LzfseReaderBytes<'a>(ReaderKind<'a, &'a [u8]>, u64);
+
+impl<'a> LzfseReaderBytes<'a> {
+    /// Returns the total number of encoded bytes consumed.
+    pub fn payload_bytes_consumed(&self) -> u64 {
+        self.1 - self.0.inner().len() as u64
+    }
+
+    /// Returns the total number of decoded bytes read.
+    pub fn raw_bytes_read(&self) -> u64 {
+        self.0.n_raw_bytes()
+    }
+
+    /// Validate that the end of stream block was reached, see [LzfseReader::finish].
+    pub fn finish(&mut self) -> crate::Result<(u64, u64)> {
+        self.0.finish()?;
+        Ok((self.payload_bytes_consumed(), self.raw_bytes_read()))
+    }
+}
 
 impl<'a> Read for LzfseReaderBytes<'a> {
     #[inline(always)]
@@ -0,0 +1,126 @@
+use crate::ring::{RingSize, RingType};
+
+use super::constants::*;
+
+use std::fmt;
+
+/// LZFSE ring encoder input ring/ ring decoder output ring profile.
+///
+/// The default profile accommodates the maximum LZFSE match distance and is able to encode and
+/// decode any valid stream. Where the maximum match distance of our data is known in advance,
+/// smaller profiles reduce the decoder's memory footprint. Likewise smaller encoder profiles
+/// reduce the encoder's memory footprint at the cost of compression ratio.
+///
+/// Decoder: streams containing matches beyond the reach of the profile are rejected with
+/// [Error::BadDValue](crate::Error::BadDValue).
+///
+/// Encoder: match distances are limited to the reach of the profile. Streams are valid LZFSE
+/// streams and can be decoded by any decoder with the same, or a larger, profile.
+///
+/// Larger than default profiles are not offered, the default already spans the maximum LZFSE
+/// match distance.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{LzfseRingDecoder, RingProfile};
+///
+/// let profile = RingProfile::for_max_distance(0x4000);
+/// assert!(0x4000 <= profile.max_match_distance());
+/// assert!(profile.ring_size() < RingProfile::default().ring_size());
+/// let decoder = LzfseRingDecoder::with_profile(profile);
+/// assert_eq!(decoder.profile(), profile);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct RingProfile(pub(crate) Profile);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub(crate) enum Profile {
+    R32K,
+    R64K,
+    R128K,
+    R256K,
+    R512K,
+}
+
+const PROFILES: [Profile; 5] =
+    [Profile::R32K, Profile::R64K, Profile::R128K, Profile::R256K, Profile::R512K];
+
+impl RingProfile {
+    /// Returns the smallest profile whose maximum match distance is at least `d`, that is the
+    /// smallest profile with `ring_size() / 2 > d`.
+    ///
+    /// Distances beyond the LZFSE maximum map to the default profile.
+    pub fn for_max_distance(d: u32) -> Self {
+        PROFILES.iter().copied().map(Self).find(|u| d <= u.max_match_distance()).unwrap_or_default()
+    }
+
+    /// Output ring size in bytes.
+    pub fn ring_size(&self) -> u32 {
+        match self.0 {
+            Profile::R32K => Output32K::RING_SIZE,
+            Profile::R64K => Output64K::RING_SIZE,
+            Profile::R128K => Output128K::RING_SIZE,
+            Profile::R256K => Output256K::RING_SIZE,
+            Profile::R512K => Output::RING_SIZE,
+        }
+    }
+
+    // Output ring allocation size in bytes.
+    pub(super) fn ring_capacity(&self) -> u32 {
+        match self.0 {
+            Profile::R32K => Output32K::RING_CAPACITY,
+            Profile::R64K => Output64K::RING_CAPACITY,
+            Profile::R128K => Output128K::RING_CAPACITY,
+            Profile::R256K => Output256K::RING_CAPACITY,
+            Profile::R512K => Output::RING_CAPACITY,
+        }
+    }
+
+    /// Maximum supported match distance.
+    pub fn max_match_distance(&self) -> u32 {
+        self.ring_size() / 2 - 1
+    }
+}
+
+impl Default for RingProfile {
+    fn default() -> Self {
+        Self(Profile::R512K)
+    }
+}
+
+impl fmt::Display for RingProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}KiB", self.ring_size() / 0x400)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fse::Fse;
+    use crate::lmd::DMax;
+    use crate::vn::Vn;
+
+    use super::*;
+
+    #[test]
+    fn default_max_distance() {
+        let profile = RingProfile::default();
+        assert!(Fse::MAX_MATCH_DISTANCE <= profile.max_match_distance());
+        assert!(Vn::MAX_MATCH_DISTANCE <= profile.max_match_distance());
+        assert_eq!(RingProfile::for_max_distance(Fse::MAX_MATCH_DISTANCE), profile);
+        assert_eq!(RingProfile::for_max_distance(u32::MAX), profile);
+    }
+
+    #[test]
+    fn for_max_distance() {
+        assert_eq!(RingProfile::for_max_distance(0).0, Profile::R32K);
+        for window in PROFILES.windows(2) {
+            let u = RingProfile(window[0]);
+            let v = RingProfile(window[1]);
+            assert!(u.ring_size() < v.ring_size());
+            assert_eq!(RingProfile::for_max_distance(u.max_match_distance()), u);
+            assert_eq!(RingProfile::for_max_distance(u.max_match_distance() + 1), v);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::error::Error;
+use crate::kit::{Width, WIDE};
+use crate::lmd::{DMax, LiteralLen, MMax, MatchDistanceUnpack, MatchLen, Quad};
+use crate::lz::{self, LzWriter};
+use crate::ops::{CopyLong, ShortLimit};
+use crate::types::ShortBytes;
+
+/// Fixed slice output stage. Writes are exact, bytes beyond the decoded length are never written,
+/// and writes that would overflow the slice are rejected with
+/// [Error::BufferOverflow](crate::Error::BufferOverflow).
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    #[inline(always)]
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, len: usize) -> crate::Result<&mut [u8]> {
+        match self.buf.get_mut(self.len..).and_then(|u| u.get_mut(..len)) {
+            Some(u) => Ok(u),
+            None => Err(Error::BufferOverflow),
+        }
+    }
+}
+
+unsafe impl<'a> ShortLimit for SliceWriter<'a> {
+    const SHORT_LIMIT: u32 = i32::MAX as u32;
+}
+
+impl<'a> LzWriter for SliceWriter<'a> {
+    const MAX_MATCH_DISTANCE: u32 = u32::MAX;
+
+    const MAX_MATCH_LEN: u32 = u32::MAX;
+
+    fn write_bytes_long<T: CopyLong>(&mut self, mut bytes: T) -> crate::Result<()> {
+        // `copy_long_raw` may write up to `WIDE` bytes beyond `len`, the tail is staged.
+        let len = bytes.len();
+        let dst = self.reserve(len)?;
+        let n = len.saturating_sub(WIDE);
+        unsafe { bytes.copy_long_raw(dst.as_mut_ptr(), n) };
+        bytes.skip(n);
+        let mut tail = [0u8; WIDE * 2];
+        unsafe { bytes.copy_long_raw(tail.as_mut_ptr(), len - n) };
+        dst[n..].copy_from_slice(&tail[..len - n]);
+        self.len += len;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_bytes_short<T: ShortLimit, W: Width>(
+        &mut self,
+        bytes: ShortBytes<T, W>,
+    ) -> crate::Result<()> {
+        let len = bytes.len();
+        self.reserve(len)?.copy_from_slice(&bytes);
+        self.len += len;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_quad(&mut self, bytes: u32, len: LiteralLen<Quad>) -> crate::Result<()> {
+        let len = len.get() as usize;
+        self.reserve(len)?.copy_from_slice(&bytes.to_ne_bytes()[..len]);
+        self.len += len;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_match<T>(
+        &mut self,
+        len: MatchLen<T>,
+        distance: MatchDistanceUnpack<T>,
+    ) -> crate::Result<()>
+    where
+        T: DMax + MMax,
+    {
+        let len = len.get() as usize;
+        let src_index = self.len.checked_sub(distance.get() as usize).ok_or(Error::BadDValue)?;
+        self.reserve(len)?;
+        lz::write_match_checked(self.buf, src_index, self.len, len)?;
+        self.len += len;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn n_raw_bytes(&self) -> u64 {
+        self.len as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fse::Fse;
+
+    use super::*;
+
+    #[test]
+    fn exact() -> crate::Result<()> {
+        let mut buf = [0xFFu8; 0x40];
+        let mut wtr = SliceWriter::new(&mut buf[..0x20]);
+        wtr.write_bytes_long(b"abc".as_ref())?;
+        wtr.write_quad(u32::from_ne_bytes(*b"defg"), LiteralLen::new(2))?;
+        wtr.write_match::<Fse>(MatchLen::new(7), MatchDistanceUnpack::new(2))?;
+        assert_eq!(wtr.len(), 12);
+        assert_eq!(&buf[..12], b"abcdedededed");
+        assert!(buf[12..].iter().all(|&u| u == 0xFF));
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() -> crate::Result<()> {
+        let mut buf = [0xFFu8; 0x40];
+        let mut wtr = SliceWriter::new(&mut buf[..4]);
+        wtr.write_bytes_long(b"abc".as_ref())?;
+        match wtr.write_match::<Fse>(MatchLen::new(2), MatchDistanceUnpack::new(1)) {
+            Err(Error::BufferOverflow) => {}
+            res => panic!("{:?}", res),
+        }
+        match wtr.write_bytes_long(b"de".as_ref()) {
+            Err(Error::BufferOverflow) => {}
+            res => panic!("{:?}", res),
+        }
+        assert!(buf[3..].iter().all(|&u| u == 0xFF));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_distance() {
+        let mut buf = [0u8; 0x40];
+        let mut wtr = SliceWriter::new(&mut buf);
+        for d in [0, 1] {
+            match wtr.write_match::<Fse>(MatchLen::new(1), MatchDistanceUnpack::new(d)) {
+                Err(Error::BadDValue) => {}
+                res => panic!("{:?}", res),
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::error::Error;
+use crate::kit::{Width, WIDE};
+use crate::lmd::{DMax, LiteralLen, MMax, MatchDistanceUnpack, MatchLen, Quad};
+use crate::lz::LzWriter;
+use crate::ops::{CopyLong, ShortLimit};
+use crate::types::ShortBytes;
+
+/// Decode event.
+///
+/// Emitted in stream order by [LzfseDecoder::trace](crate::LzfseDecoder::trace).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DecodeEvent<'a> {
+    /// Literal run, raw block bytes are reported as literal runs. Runs are at most 4,096 bytes
+    /// long, longer runs are reported as consecutive literal runs.
+    Literal(&'a [u8]),
+    /// Match, copy `len` bytes from `distance` bytes behind the current position.
+    Match {
+        /// Match distance.
+        distance: u32,
+        /// Match length.
+        len: u32,
+    },
+}
+
+/// Literal run chunk size, longer literal runs are emitted as consecutive chunks.
+const LITERAL_CHUNK_SIZE: usize = 0x1000;
+
+/// Trace LZ output. Literal bytes are buffered into runs of at most `LITERAL_CHUNK_SIZE` bytes,
+/// matches are validated but not copied.
+pub struct TraceWriter<F> {
+    literals: Vec<u8>,
+    f: F,
+    index: u64,
+}
+
+impl<F: FnMut(DecodeEvent)> TraceWriter<F> {
+    #[inline(always)]
+    pub fn new(f: F) -> Self {
+        Self { literals: Vec::default(), f, index: 0 }
+    }
+
+    pub fn flush(&mut self) {
+        if !self.literals.is_empty() {
+            (self.f)(DecodeEvent::Literal(&self.literals));
+            self.literals.clear();
+        }
+    }
+
+    // Buffer `bytes`, emitting each literal chunk as it fills.
+    fn push_literals<T: CopyLong>(&mut self, mut bytes: T) {
+        self.index += bytes.len() as u64;
+        while !bytes.is_empty() {
+            debug_assert!(self.literals.len() < LITERAL_CHUNK_SIZE);
+            let len = (LITERAL_CHUNK_SIZE - self.literals.len()).min(bytes.len());
+            let index = self.literals.len();
+            self.literals.reserve(len + WIDE);
+            unsafe {
+                bytes.read_long_raw(self.literals.as_mut_ptr().add(index), len);
+                self.literals.set_len(index + len);
+            }
+            if self.literals.len() == LITERAL_CHUNK_SIZE {
+                self.flush();
+            }
+        }
+    }
+}
+
+unsafe impl<F> ShortLimit for TraceWriter<F> {
+    const SHORT_LIMIT: u32 = Vec::<u8>::SHORT_LIMIT;
+}
+
+impl<F: FnMut(DecodeEvent)> LzWriter for TraceWriter<F> {
+    const MAX_MATCH_DISTANCE: u32 = u32::MAX;
+
+    const MAX_MATCH_LEN: u32 = u32::MAX;
+
+    fn write_bytes_long<T: CopyLong>(&mut self, bytes: T) -> crate::Result<()> {
+        self.push_literals(bytes);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_bytes_short<T: ShortLimit, W: Width>(
+        &mut self,
+        bytes: ShortBytes<T, W>,
+    ) -> crate::Result<()> {
+        self.push_literals(bytes);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_quad(&mut self, bytes: u32, len: LiteralLen<Quad>) -> crate::Result<()> {
+        if self.literals.len() + 4 > LITERAL_CHUNK_SIZE {
+            self.flush();
+        }
+        self.index += len.get() as u64;
+        self.literals.write_quad(bytes, len)?;
+        if self.literals.len() == LITERAL_CHUNK_SIZE {
+            self.flush();
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_match<T>(
+        &mut self,
+        len: MatchLen<T>,
+        distance: MatchDistanceUnpack<T>,
+    ) -> crate::Result<()>
+    where
+        T: DMax + MMax,
+    {
+        let len = len.get();
+        let distance = distance.get();
+        if distance == 0 || distance as u64 > self.index {
+            return Err(Error::BadDValue);
+        }
+        self.flush();
+        (self.f)(DecodeEvent::Match { distance, len });
+        self.index += len as u64;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn n_raw_bytes(&self) -> u64 {
+        self.index
+    }
+}
@@ -0,0 +1,139 @@
+use crate::error::Error;
+use crate::fse::FseBackend;
+
+use super::frontend_kind::FrontendKind;
+
+use tokio::io::AsyncWrite;
+
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// LZFSE asynchronous encoding writer.
+///
+/// Exposes a LZFSE encoder via the tokio [AsyncWrite] interface, encoding into an inner
+/// [AsyncWrite]. Encoding itself is synchronous and never blocks, encoded output is staged in
+/// an internal buffer that is drained into the inner writer as it becomes ready. Input is only
+/// accepted once the staged output is fully drained, so memory use is bounded irrespective of
+/// the inner writer's speed.
+///
+/// Instances are created using
+/// [LzfseRingEncoder::async_writer](super::LzfseRingEncoder::async_writer).
+///
+/// **It is imperative that the writer is [shut down](AsyncWrite::poll_shutdown) after use to
+/// complete the encoding process, [flushing](AsyncWrite::poll_flush) is not sufficient.**
+/// Flushing drains the staged output, it does not flush the encoder's internal state.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::LzfseRingEncoder;
+/// use tokio::io::AsyncWriteExt;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let mut encoder = LzfseRingEncoder::default();
+/// let mut writer = encoder.async_writer(Vec::default());
+/// writer.write_all(b"test").await?;
+/// // It is IMPERATIVE that the writer is shut down.
+/// writer.shutdown().await?;
+/// let enc = writer.into_inner();
+/// // "test" string encoded.
+/// assert_eq!(enc, &[0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74,
+///                   0x62, 0x76, 0x78, 0x24]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncLzfseWriter<'a, O> {
+    frontend: FrontendKind<'a>,
+    backend: &'a mut FseBackend,
+    inner: O,
+    buf: Vec<u8>,
+    pos: usize,
+    is_finalized: bool,
+}
+
+impl<'a, O> AsyncLzfseWriter<'a, O> {
+    #[inline(always)]
+    pub(super) fn new(frontend: FrontendKind<'a>, backend: &'a mut FseBackend, inner: O) -> Self {
+        Self { frontend, backend, inner, buf: Vec::default(), pos: 0, is_finalized: false }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &O {
+        &self.inner
+    }
+
+    /// Returns the inner writer. Unless the writer was shut down, the encoded output is
+    /// incomplete.
+    pub fn into_inner(self) -> O {
+        self.inner
+    }
+}
+
+impl<'a, O: AsyncWrite + Unpin> AsyncLzfseWriter<'a, O> {
+    // Drain the staged output into the inner writer.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.pos < self.buf.len() {
+            match Pin::new(&mut self.inner).poll_write(cx, &self.buf[self.pos..]) {
+                Poll::Ready(Ok(0)) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                Poll::Ready(Ok(n)) => self.pos += n,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        self.buf.clear();
+        self.pos = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'a, O: AsyncWrite + Unpin> AsyncWrite for AsyncLzfseWriter<'a, O> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.is_finalized {
+            return Poll::Ready(Err(Error::BadWriterState.into()));
+        }
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => {}
+            u => return u.map_ok(|_| 0),
+        }
+        Poll::Ready(this.frontend.write(this.backend, buf, &mut this.buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.inner).poll_flush(cx),
+            u => u,
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if !this.is_finalized {
+            match this.poll_drain(cx) {
+                Poll::Ready(Ok(())) => {}
+                u => return u,
+            }
+            this.is_finalized = true;
+            if let Err(err) = this.frontend.flush(this.backend, &mut this.buf) {
+                return Poll::Ready(Err(err));
+            }
+        }
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.inner).poll_shutdown(cx),
+            u => u,
+        }
+    }
+}
+
+impl<'a, O> fmt::Debug for AsyncLzfseWriter<'a, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncLzfseWriter").finish()
+    }
+}
@@ -29,6 +29,13 @@ pub trait Backend {
         match_distance: MatchDistance<Self::Type>,
     ) -> io::Result<()>;
 
+    /// Close the current block, subsequent pushes open a new block. Empty blocks are not emitted.
+    ///
+    /// The default implementation does nothing, single block backends do not support boundaries.
+    fn close_block<O: ShortWriter>(&mut self, _: &mut O) -> io::Result<()> {
+        Ok(())
+    }
+
     /// Implementations should NOT flush `dst`.
     fn finalize<O: ShortWriter>(&mut self, dst: &mut O) -> io::Result<()>;
 }
@@ -0,0 +1,29 @@
+/// LZFSE encoder block strategy.
+///
+/// Selects the block types that the encoder may emit.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum BlockStrategy {
+    /// Raw blocks are emitted for tiny inputs and as a fallback for incompressible small inputs.
+    Auto,
+    /// Raw blocks are never emitted, all data is encoded as VN or FSE blocks.
+    ///
+    /// Incompressible data will expand. As an indication, random VN encoded data expands by
+    /// roughly 1/16th with an additional 12 byte block header overhead.
+    NeverRaw,
+    /// As [Auto](Self::Auto) but small inputs are trial encoded as both VN and FSE blocks with
+    /// the smallest retained.
+    ///
+    /// Intended for tiny, highly repetitive inputs where FSE table overhead dominates. Inputs
+    /// above the VN cutoff, 4KiB, are otherwise always encoded as FSE blocks. Trial encoding
+    /// is limited to inputs of 64KiB or less, larger inputs are encoded as per `Auto`. Encoding
+    /// trial inputs is up to three times slower.
+    Compact,
+}
+
+impl Default for BlockStrategy {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::Auto
+    }
+}
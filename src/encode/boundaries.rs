@@ -0,0 +1,88 @@
+use crate::types::ShortWriter;
+
+use super::backend::Backend;
+
+use std::io;
+
+/// Validate caller specified block boundaries, these must be non zero and strictly increasing.
+pub fn validate(offsets: &[u64]) -> io::Result<()> {
+    let mut prev = 0;
+    for &offset in offsets {
+        if offset <= prev {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "bad block boundary"));
+        }
+        prev = offset;
+    }
+    Ok(())
+}
+
+/// Caller specified block boundaries as raw byte offsets, see `validate`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Boundaries<'a> {
+    offsets: &'a [u64],
+    index: usize,
+    offset: u64,
+}
+
+impl<'a> Boundaries<'a> {
+    #[inline(always)]
+    pub fn new(offsets: &'a [u64]) -> Self {
+        debug_assert!(validate(offsets).is_ok());
+        Self { offsets, index: 0, offset: 0 }
+    }
+
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.index = 0;
+        self.offset = 0;
+    }
+
+    #[inline(always)]
+    pub fn is_active(&self) -> bool {
+        !self.offsets.is_empty()
+    }
+
+    /// Close the current block if we sit on a boundary. Returns the number of raw bytes that may be
+    /// pushed before the next boundary, which is non zero.
+    pub fn limit<B, O>(&mut self, backend: &mut B, dst: &mut O) -> io::Result<u64>
+    where
+        B: Backend,
+        O: ShortWriter,
+    {
+        if self.offsets.get(self.index) == Some(&self.offset) {
+            backend.close_block(dst)?;
+            self.index += 1;
+        }
+        Ok(self.offsets.get(self.index).map_or(u64::MAX, |&u| u - self.offset))
+    }
+
+    #[inline(always)]
+    pub fn advance(&mut self, n: u64) {
+        self.offset += n;
+        debug_assert!(!matches!(self.offsets.get(self.index), Some(&u) if self.offset > u));
+    }
+
+    /// Reject boundaries that lie beyond the end of the input.
+    pub fn finalize(&self) -> io::Result<()> {
+        match self.offsets.last() {
+            Some(&u) if u > self.offset => {
+                Err(io::Error::new(io::ErrorKind::InvalidInput, "block boundary out of range"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_offsets() {
+        assert!(validate(&[]).is_ok());
+        assert!(validate(&[1, 2, 0x1000]).is_ok());
+        assert!(validate(&[0]).is_err());
+        assert!(validate(&[2, 2]).is_err());
+        assert!(validate(&[3, 2]).is_err());
+    }
+}
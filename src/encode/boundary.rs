@@ -0,0 +1,35 @@
+use test_kit::{Rng, Seq};
+
+use super::constants::*;
+
+use crate::ring::{RingBlock, RingSize};
+
+/// Input sizes either side of, and exactly at, frontend and ring boundaries.
+pub fn sizes() -> Vec<usize> {
+    let mut sizes: Vec<usize> = [
+        RAW_CUTOFF,
+        VN_CUTOFF,
+        Input::RING_BLK_SIZE,
+        Input::RING_SIZE / 2,
+        Input::RING_SIZE,
+        Output::RING_BLK_SIZE,
+        Output::RING_SIZE / 2,
+        Output::RING_SIZE,
+    ]
+    .iter()
+    .flat_map(|&n| vec![n as usize - 1, n as usize, n as usize + 1])
+    .collect();
+    sizes.sort_unstable();
+    sizes.dedup();
+    sizes
+}
+
+/// Zero, random, low entropy and text data of length `n`.
+pub fn data(n: usize) -> Vec<Vec<u8>> {
+    vec![
+        vec![0u8; n],
+        Rng::default().gen_vec(n).unwrap(),
+        Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), n).collect(),
+        b"a quick brown fox jumps over the lazy dog ".iter().copied().cycle().take(n).collect(),
+    ]
+}
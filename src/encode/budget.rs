@@ -0,0 +1,128 @@
+use crate::bits::BitDst;
+use crate::error::Error;
+use crate::ops::{
+    Allocate, CopyLong, Flush, FlushLimit, PatchInto, Pos, ShortLimit, Truncate, WriteLong,
+    WriteShort,
+};
+use crate::types::{Idx, ShortWriter};
+
+use std::io;
+
+/// Budgeted output. The output length is checked against `limit` on flush, that is after each
+/// block is emitted, as such encoding is abandoned within one block of the budget being exceeded.
+/// Blocks may be speculatively written and then truncated, so mid block checks are not possible.
+pub struct BudgetWriter<'a> {
+    inner: &'a mut Vec<u8>,
+    limit: usize,
+    is_exceeded: bool,
+}
+
+impl<'a> BudgetWriter<'a> {
+    #[inline(always)]
+    pub fn new(inner: &'a mut Vec<u8>, max_bytes: usize) -> Self {
+        let limit = inner.len().saturating_add(max_bytes);
+        Self { inner, limit, is_exceeded: false }
+    }
+
+    #[inline(always)]
+    pub fn is_exceeded(&self) -> bool {
+        self.is_exceeded
+    }
+
+    #[inline(always)]
+    pub fn n_bytes(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Check the output length against `limit`.
+    #[inline(always)]
+    pub fn check(&mut self) -> crate::Result<()> {
+        if self.inner.len() > self.limit {
+            self.is_exceeded = true;
+            Err(Error::BufferOverflow)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'a> Allocate for BudgetWriter<'a> {
+    #[inline(always)]
+    fn allocate(&mut self, len: usize) -> io::Result<()> {
+        self.inner.allocate(len)
+    }
+
+    #[inline(always)]
+    fn is_allocated(&mut self, len: usize) -> bool {
+        self.inner.is_allocated(len)
+    }
+}
+
+impl<'a> BitDst for BudgetWriter<'a> {
+    #[inline(always)]
+    unsafe fn push_bytes_unchecked(&mut self, bytes: usize, n_bytes: usize) {
+        self.inner.push_bytes_unchecked(bytes, n_bytes)
+    }
+
+    #[inline(always)]
+    fn finalize(&mut self) -> io::Result<()> {
+        BitDst::finalize(self.inner)
+    }
+}
+
+impl<'a> Flush for BudgetWriter<'a> {
+    #[inline(always)]
+    fn flush(&mut self, _: bool) -> crate::Result<()> {
+        self.check()
+    }
+}
+
+impl<'a> FlushLimit for BudgetWriter<'a> {
+    const FLUSH_LIMIT: u32 = Vec::<u8>::FLUSH_LIMIT;
+}
+
+impl<'a> PatchInto for BudgetWriter<'a> {
+    #[inline(always)]
+    fn patch_into(&mut self, pos: Idx, len: usize) -> &mut [u8] {
+        self.inner.patch_into(pos, len)
+    }
+}
+
+impl<'a> Pos for BudgetWriter<'a> {
+    #[inline(always)]
+    fn pos(&self) -> Idx {
+        self.inner.pos()
+    }
+}
+
+unsafe impl<'a> ShortLimit for BudgetWriter<'a> {
+    const SHORT_LIMIT: u32 = Vec::<u8>::SHORT_LIMIT;
+}
+
+impl<'a> Truncate for BudgetWriter<'a> {
+    #[inline(always)]
+    fn truncate(&mut self, idx: Idx) -> bool {
+        Truncate::truncate(self.inner, idx)
+    }
+}
+
+impl<'a> WriteLong for BudgetWriter<'a> {
+    #[inline(always)]
+    fn write_long<I: CopyLong>(&mut self, src: I) -> io::Result<()> {
+        self.inner.write_long(src)
+    }
+}
+
+impl<'a> WriteShort for BudgetWriter<'a> {
+    #[inline(always)]
+    unsafe fn short_set(&mut self, len: u32) {
+        self.inner.short_set(len)
+    }
+
+    #[inline(always)]
+    unsafe fn short_ptr(&mut self) -> *mut u8 {
+        self.inner.short_ptr()
+    }
+}
+
+impl<'a> ShortWriter for BudgetWriter<'a> {}
@@ -0,0 +1,99 @@
+use super::constants::GOOD_MATCH_LEN;
+use super::encoder::LzfseEncoder;
+use super::history::{HASH_WIDTH, MAX_SEARCH_DEPTH};
+use super::ring_encoder::LzfseRingEncoder;
+
+/// LZFSE encoder builder.
+///
+/// Configures the match finder effort, trading encoding speed for compression ratio. Encoded
+/// output remains standard LZFSE regardless of the settings, only the compressed size and the
+/// encoding speed differ. The defaults are identical to those of [LzfseEncoder::default].
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{LzfseDecoder, LzfseEncoderBuilder};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+///     let mut encoder = LzfseEncoderBuilder::new().good_match_len(16).search_depth(1).build();
+///     let mut enc = Vec::default();
+///     encoder.encode_bytes(&src, &mut enc)?;
+///     let mut dec = Vec::default();
+///     LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+///     assert_eq!(dec, src);
+///     Ok(())
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LzfseEncoderBuilder {
+    good_match_len: u32,
+    search_depth: usize,
+}
+
+impl LzfseEncoderBuilder {
+    /// The maximum match search depth.
+    pub const MAX_SEARCH_DEPTH: usize = MAX_SEARCH_DEPTH;
+
+    /// Create a new builder with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the good match length, the default being 40 bytes.
+    ///
+    /// Matches of at least this length are selected immediately, without deferring to a
+    /// potentially longer match at the following position. Lower values encode faster, higher
+    /// values may improve the compression ratio.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero.
+    pub fn good_match_len(mut self, len: u32) -> Self {
+        assert!(len != 0);
+        self.good_match_len = len;
+        self
+    }
+
+    /// Set the match search depth, the number of history candidates examined at each position,
+    /// the default being 4.
+    ///
+    /// Lower values encode faster, higher values may improve the compression ratio. Depths beyond
+    /// the default, up to [MAX_SEARCH_DEPTH](Self::MAX_SEARCH_DEPTH), are intended for archival
+    /// use, with each additional 4 candidates allocating a further 512 KiB of history.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `depth` is zero or exceeds [MAX_SEARCH_DEPTH](Self::MAX_SEARCH_DEPTH).
+    pub fn search_depth(mut self, depth: usize) -> Self {
+        assert!(depth != 0 && depth <= Self::MAX_SEARCH_DEPTH);
+        self.search_depth = depth;
+        self
+    }
+
+    /// Build an [LzfseEncoder].
+    pub fn build(self) -> LzfseEncoder {
+        let mut encoder = LzfseEncoder::default();
+        self.configure(&mut encoder);
+        encoder
+    }
+
+    /// Build an [LzfseRingEncoder].
+    pub fn build_ring(self) -> LzfseRingEncoder {
+        let mut encoder = LzfseRingEncoder::default();
+        self.configure(encoder.as_mut());
+        encoder
+    }
+
+    fn configure(self, encoder: &mut LzfseEncoder) {
+        encoder.good_match_len = self.good_match_len;
+        encoder.search_depth = self.search_depth;
+    }
+}
+
+impl Default for LzfseEncoderBuilder {
+    fn default() -> Self {
+        Self { good_match_len: GOOD_MATCH_LEN, search_depth: HASH_WIDTH }
+    }
+}
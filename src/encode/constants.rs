@@ -9,6 +9,17 @@ pub const RAW_LIMIT: u32 = 0x4000;
 
 pub const VN_CUTOFF: u32 = 0x1000;
 
+// MatchStrategy::DecodeOptimized limits.
+pub const NEAR_MATCH_DISTANCE: u32 = 0x0001_0000;
+pub const NEAR_MATCH_LEN: u32 = 0x0100;
+
+// Sparse scan zero run match length threshold and retained history tail length.
+pub const SPARSE_RUN_LEN: u32 = 0x0100;
+pub const SPARSE_TAIL_LEN: u32 = 0x0010;
+
+// Compact strategy VN/ FSE trial limit. Conservative value.
+pub const COMPACT_LIMIT: u32 = 0x0001_0000;
+
 // Fixed constants. Do NOT change.
 // u32::MAX quarter bounds
 #[allow(dead_code)]
@@ -32,6 +43,69 @@ unsafe impl RingBlock for Input {
     const RING_BLK_SIZE: u32 = 0x0000_4000;
 }
 
+// Reduced input rings, see `RingProfile`. Match distances are clamped to `RING_SIZE / 2 - 1`.
+// `RING_BLK_SIZE` is scaled to `RING_SIZE / 32` in line with `Input`.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Input32K;
+
+unsafe impl RingSize for Input32K {
+    const RING_SIZE: u32 = 0x0000_8000;
+}
+
+unsafe impl RingType for Input32K {
+    const RING_LIMIT: u32 = 0x0140;
+}
+
+unsafe impl RingBlock for Input32K {
+    const RING_BLK_SIZE: u32 = 0x0400;
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Input64K;
+
+unsafe impl RingSize for Input64K {
+    const RING_SIZE: u32 = 0x0001_0000;
+}
+
+unsafe impl RingType for Input64K {
+    const RING_LIMIT: u32 = 0x0140;
+}
+
+unsafe impl RingBlock for Input64K {
+    const RING_BLK_SIZE: u32 = 0x0800;
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Input128K;
+
+unsafe impl RingSize for Input128K {
+    const RING_SIZE: u32 = 0x0002_0000;
+}
+
+unsafe impl RingType for Input128K {
+    const RING_LIMIT: u32 = 0x0140;
+}
+
+unsafe impl RingBlock for Input128K {
+    const RING_BLK_SIZE: u32 = 0x1000;
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Input256K;
+
+unsafe impl RingSize for Input256K {
+    const RING_SIZE: u32 = 0x0004_0000;
+}
+
+unsafe impl RingType for Input256K {
+    const RING_LIMIT: u32 = 0x0140;
+}
+
+unsafe impl RingBlock for Input256K {
+    const RING_BLK_SIZE: u32 = 0x2000;
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct Output;
 
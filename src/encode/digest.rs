@@ -0,0 +1,52 @@
+use crate::base::TrailerKind;
+use crate::trailer;
+
+use super::encoder::LzfseEncoder;
+
+use digest::{Digest, Output};
+
+use std::io;
+
+/// Encode `src` into `dst` followed by a trailer containing the `hasher` digest of `src`,
+/// returning the digest.
+///
+/// The trailer follows the end of stream block and is ignored by this crate's decoders. Use
+/// [decode_verify_digest](crate::decode_verify_digest) to decode and verify. Intended for content
+/// addressed storage where the decoded data digest doubles as its address.
+///
+/// Requires the `digest` feature.
+///
+/// # Errors
+///
+/// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` or `dst` buffer overflow.
+///
+/// # Examples
+///
+/// ```
+/// use sha2::{Digest, Sha256};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     let digest = lzfse_rust::encode_with_digest(b"test", &mut enc, Sha256::new())?;
+///     let mut dec = Vec::default();
+///     lzfse_rust::decode_verify_digest(&enc, &mut dec, Sha256::new())?;
+///     assert_eq!(dec, b"test");
+///     // Plain decoding ignores the trailer.
+///     dec.clear();
+///     lzfse_rust::decode_bytes(&enc, &mut dec)?;
+///     assert_eq!(dec, b"test");
+///     Ok(())
+/// }
+/// ```
+pub fn encode_with_digest<H: Digest>(
+    src: &[u8],
+    dst: &mut Vec<u8>,
+    mut hasher: H,
+) -> io::Result<Output<H>> {
+    hasher.update(src);
+    let digest = hasher.finalize();
+    LzfseEncoder::default().encode_bytes(src, dst)?;
+    trailer::write(dst, TrailerKind::Digest, Some(&digest[..]))?;
+    Ok(digest)
+}
@@ -1,34 +1,81 @@
-use crate::fse::FseBackend;
+use crate::base::{MagicBytes, TrailerKind};
+use crate::decode::LzfseDecoder;
+use crate::error::Error;
+use crate::fse::{Fse, FseBackend, NormalizationStrategy};
+use crate::kit;
+use crate::lmd::DMax;
+use crate::ops::PeekData;
+use crate::raw;
+use crate::trailer;
+use crate::types::ShortWriter;
 
+use super::backend::Backend;
+use super::block_strategy::BlockStrategy;
+use super::boundaries;
+use super::budget::BudgetWriter;
+use super::constants::GOOD_MATCH_LEN;
 use super::frontend_bytes::FrontendBytes;
-use super::history::HistoryTable;
+use super::histogram::{self, HistogramBackend, N_BUCKETS};
+use super::history::{HistoryTable, HASH_WIDTH};
+use super::match_strategy::MatchStrategy;
+use super::segmented::{self, SegmentEntry};
+use super::sink::{LzfseSink, SinkWriter};
 
+use std::convert::TryFrom;
 use std::fmt;
 use std::io;
+use std::mem;
 
 /// LZFSE encoder.
 ///
 ///
-/// This basic implementation encodes byte slices into byte vectors.
-#[derive(Default)]
+/// This basic implementation encodes byte slices into byte vectors. Match finder effort may be
+/// configured with [LzfseEncoderBuilder](crate::LzfseEncoderBuilder).
 pub struct LzfseEncoder {
     pub(super) backend: FseBackend,
     pub(super) table: HistoryTable,
-    dst_mark: u64,
+    pub(super) strategy: BlockStrategy,
+    pub(super) match_strategy: MatchStrategy,
+    pub(super) sparse_scan: bool,
+    pub(super) rle_bias: bool,
+    pub(super) skip_if_compressed: bool,
+    pub(super) good_match_len: u32,
+    pub(super) search_depth: usize,
+    pub(super) pad_output_to: Option<u32>,
+    pub(super) boundaries: Vec<u64>,
 }
 
 impl LzfseEncoder {
+    pub(crate) const HEAP_SIZE: usize = FseBackend::HEAP_SIZE + HistoryTable::HEAP_SIZE;
+
+    pub(super) fn with_history_table(mut table: HistoryTable) -> Self {
+        table.set_hash_seed(0);
+        Self {
+            backend: FseBackend::default(),
+            table,
+            strategy: BlockStrategy::default(),
+            match_strategy: MatchStrategy::default(),
+            sparse_scan: false,
+            rle_bias: false,
+            skip_if_compressed: false,
+            good_match_len: GOOD_MATCH_LEN,
+            search_depth: HASH_WIDTH,
+            pad_output_to: None,
+            boundaries: Vec::default(),
+        }
+    }
+
     /// Encode `src` into `dst` returning the number of bytes written into `dst`.
     ///
+    /// The output is padded as per [set_pad_output_to](Self::set_pad_output_to). Input that
+    /// appears to be already LZFSE encoded may be stored rather than encoded, see
+    /// [set_skip_if_compressed](Self::set_skip_if_compressed).
+    ///
     /// # Errors
     ///
     /// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` or `dst` buffer overflow.
-    ///
-    /// # Aborts
-    ///
-    /// With limited system memory [Vec] may abort when attempting to allocate sufficient memory.
-    /// This issue will be resolved in future releases when [try_reserve()](Vec::try_reserve) is
-    /// stabilized.
+    /// * [ErrorKind::OutOfMemory](std::io::ErrorKind) if `dst` fails to allocate sufficient
+    ///   memory, in which case `dst` contains partially encoded data.
     ///
     /// # Examples
     ///
@@ -47,9 +94,843 @@ impl LzfseEncoder {
     /// }
     /// ```
     pub fn encode_bytes(&mut self, src: &[u8], dst: &mut Vec<u8>) -> io::Result<u64> {
-        self.dst_mark = dst.len() as u64;
-        FrontendBytes::new(&mut self.table, src).execute(&mut self.backend, dst)?;
-        Ok(dst.len() as u64 - self.dst_mark)
+        if self.skip_if_compressed && is_compressed(src) {
+            return self.encode_stored(src, dst);
+        }
+        self.encode_prefixed(src, 0, dst)
+    }
+
+    /// As [encode_bytes](Self::encode_bytes), additionally decoding the encoded output and
+    /// comparing it against `src` before returning.
+    ///
+    /// Intended for critical writes, for example archival, where an encoder defect must be caught
+    /// before the output is persisted. The output is decoded in memory by an internal
+    /// [LzfseDecoder](crate::LzfseDecoder), roughly doubling the CPU cost. The encoded output is
+    /// identical to that of [encode_bytes](Self::encode_bytes).
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::InvalidData](std::io::ErrorKind) wrapping
+    ///   [Error::VerificationFailed](crate::Error::VerificationFailed) if the encoded output fails
+    ///   to decode, or decodes to bytes other than `src`. `dst` is restored to its initial length.
+    /// * As per [encode_bytes](Self::encode_bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseEncoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     let n_bytes = encoder.encode_bytes_verified(b"test", &mut enc)?;
+    ///     assert_eq!(n_bytes, 16);
+    ///     let mut dec = Vec::default();
+    ///     lzfse_rust::decode_bytes(&enc, &mut dec)?;
+    ///     assert_eq!(dec, b"test");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encode_bytes_verified(&mut self, src: &[u8], dst: &mut Vec<u8>) -> io::Result<u64> {
+        let mark = dst.len();
+        let n_bytes = self.encode_bytes(src, dst)?;
+        if let Err(err) = verify(src, &dst[mark..]) {
+            dst.truncate(mark);
+            return Err(err.into());
+        }
+        Ok(n_bytes)
+    }
+
+    // Encode `src` verbatim as raw blocks.
+    fn encode_stored(&mut self, src: &[u8], dst: &mut Vec<u8>) -> io::Result<u64> {
+        let mark = dst.len() as u64;
+        store(src, dst)?;
+        if let Some(multiple) = self.pad_output_to {
+            pad_output(dst, dst.len() as u64 - mark, multiple)?;
+        }
+        Ok(dst.len() as u64 - mark)
+    }
+
+    /// Encode `src` into `dst`, with matches referencing a preset `dict` dictionary, returning
+    /// the number of bytes written into `dst`.
+    ///
+    /// Each frame otherwise starts with an empty history, so the leading bytes of small inputs
+    /// never match. Priming the history with a dictionary of representative data, for example a
+    /// sample record, allows matches from the first byte onwards.
+    ///
+    /// The output is a standard LZFSE stream, but with match distances that reach back past the
+    /// start of the stream into `dict`. It decodes with a standard LZFSE decoder only when the
+    /// same `dict` is logically prepended to the decoded output, see
+    /// [LzfseDecoder::decode_bytes_with_dict](crate::LzfseDecoder::decode_bytes_with_dict). As
+    /// match distances are limited to 262,139 bytes, only the trailing 262,139 bytes of `dict`
+    /// are referenced, for VN blocks, the block type used for inputs up to 4,096 bytes, this
+    /// limit is 65,535 bytes. Inputs of 20 bytes or less are stored as raw blocks and do not
+    /// reference `dict`.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` or `dst` buffer overflow.
+    /// * [ErrorKind::OutOfMemory](std::io::ErrorKind) if `dst` fails to allocate sufficient
+    ///   memory, in which case `dst` contains partially encoded data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let dict = br#"{"id":0,"name":"","email":"@example.com","active":true}"#;
+    ///     let src = br#"{"id":7,"name":"ann","email":"ann@example.com","active":true}"#;
+    ///     let mut enc = Vec::default();
+    ///     LzfseEncoder::default().encode_bytes_with_dict(dict, src, &mut enc)?;
+    ///     let mut dec = Vec::default();
+    ///     LzfseDecoder::default().decode_bytes_with_dict(dict, &enc, &mut dec)?;
+    ///     assert_eq!(dec, src);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encode_bytes_with_dict(
+        &mut self,
+        dict: &[u8],
+        src: &[u8],
+        dst: &mut Vec<u8>,
+    ) -> io::Result<u64> {
+        let dict = &dict[dict.len().saturating_sub(Fse::MAX_MATCH_DISTANCE as usize)..];
+        if dict.is_empty() {
+            return self.encode_bytes(src, dst);
+        }
+        let mut buf = Vec::with_capacity(dict.len() + src.len());
+        buf.extend_from_slice(dict);
+        buf.extend_from_slice(src);
+        self.encode_prefixed(&buf, dict.len() as u32, dst)
+    }
+
+    fn encode_prefixed(
+        &mut self,
+        src: &[u8],
+        prefix_len: u32,
+        dst: &mut Vec<u8>,
+    ) -> io::Result<u64> {
+        let mark = dst.len() as u64;
+        self.execute(src, prefix_len, dst)?;
+        if let Some(multiple) = self.pad_output_to {
+            pad_output(dst, dst.len() as u64 - mark, multiple)?;
+        }
+        Ok(dst.len() as u64 - mark)
+    }
+
+    fn execute<O: ShortWriter>(
+        &mut self,
+        src: &[u8],
+        prefix_len: u32,
+        dst: &mut O,
+    ) -> io::Result<()> {
+        let mut frontend = FrontendBytes::new(&mut self.table, src);
+        frontend.set_prefix_len(prefix_len);
+        frontend.set_block_strategy(self.strategy);
+        frontend.set_match_strategy(self.match_strategy);
+        frontend.set_sparse_scan(self.sparse_scan);
+        frontend.set_rle_bias(self.rle_bias);
+        frontend.set_good_match_len(self.good_match_len);
+        frontend.set_search_depth(self.search_depth);
+        frontend.set_block_boundaries(&self.boundaries);
+        frontend.execute(&mut self.backend, dst)
+    }
+
+    /// Encode `src` into the user supplied `dst` sink returning the number of bytes written into
+    /// `dst`.
+    ///
+    /// As [encode_bytes](Self::encode_bytes), but encoding directly into any [LzfseSink], for
+    /// example a fixed size buffer or memory mapped region. The encoded output is identical to
+    /// that of [encode_bytes](Self::encode_bytes).
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) as returned by [LzfseSink::reserve], for example fixed size
+    ///   buffer overflow.
+    /// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` buffer overflow.
+    ///
+    /// On error, `dst` is restored to its initial number of bytes.
+    ///
+    /// # Examples
+    ///
+    /// See [LzfseSink].
+    pub fn encode_to_sink<S: LzfseSink + ?Sized>(
+        &mut self,
+        src: &[u8],
+        dst: &mut S,
+    ) -> io::Result<u64> {
+        let mark = dst.n_bytes();
+        match self.encode_internal(src, &mut SinkWriter::new(dst), SinkWriter::n_bytes) {
+            Ok(()) => Ok((dst.n_bytes() - mark) as u64),
+            Err(err) => {
+                dst.set_n_bytes(mark);
+                Err(err)
+            }
+        }
+    }
+
+    // Encode `src` into `dst` as per the encoder settings, `n_bytes` returns the number of bytes
+    // written into `dst`.
+    fn encode_internal<O: ShortWriter>(
+        &mut self,
+        src: &[u8],
+        dst: &mut O,
+        n_bytes: fn(&O) -> usize,
+    ) -> io::Result<()> {
+        let mark = n_bytes(dst);
+        if self.skip_if_compressed && is_compressed(src) {
+            store(src, dst)?;
+        } else {
+            self.execute(src, 0, dst)?;
+        }
+        if let Some(multiple) = self.pad_output_to {
+            pad_output(dst, (n_bytes(dst) - mark) as u64, multiple)?;
+        }
+        Ok(())
+    }
+
+    /// Encode `src` into `dst` with an upper `max_bytes` output budget, returning the number of
+    /// bytes written into `dst`.
+    ///
+    /// The output size is tracked during encoding, which is abandoned as soon as the budget is
+    /// exceeded. Tracking is block granular, large inputs are abandoned within one block of the
+    /// budget being exceeded as opposed to being encoded in full. Otherwise the output is identical
+    /// to that of [encode_bytes](Self::encode_bytes), including padding, see
+    /// [set_pad_output_to](Self::set_pad_output_to), and the storing of already LZFSE encoded
+    /// input, see [set_skip_if_compressed](Self::set_skip_if_compressed).
+    ///
+    /// # Errors
+    ///
+    /// * [Error::BufferOverflow](crate::Error::BufferOverflow) if the encoded output exceeds
+    ///   `max_bytes`, in which case `dst` is restored to its original length.
+    /// * [Error](crate::Error) detailing the nature of any other errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{Error, LzfseEncoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     let n_bytes = encoder.encode_within(b"test", &mut enc, 16)?;
+    ///     assert_eq!(n_bytes, 16);
+    ///     enc.clear();
+    ///     match encoder.encode_within(b"test", &mut enc, 15) {
+    ///         Err(Error::BufferOverflow) => {}
+    ///         _ => panic!(),
+    ///     }
+    ///     assert!(enc.is_empty());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encode_within(
+        &mut self,
+        src: &[u8],
+        dst: &mut Vec<u8>,
+        max_bytes: usize,
+    ) -> crate::Result<usize> {
+        let mark = dst.len();
+        let mut wtr = BudgetWriter::new(dst, max_bytes);
+        let result = self.encode_internal(src, &mut wtr, BudgetWriter::n_bytes);
+        let result = result.map_err(Error::from).and_then(|()| wtr.check());
+        match result {
+            Ok(()) => Ok(dst.len() - mark),
+            Err(err) => {
+                let is_exceeded = wtr.is_exceeded();
+                dst.truncate(mark);
+                if is_exceeded {
+                    Err(Error::BufferOverflow)
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    /// Encode `src` as independent `block_size` byte chunks, the final chunk may be shorter,
+    /// returning the raw/ encoded size ratio of each chunk in order.
+    ///
+    /// Intended to inform the choice of chunk size for seekable, independently encoded data.
+    /// Smaller chunks permit finer grained access at the expense of ratio. The encoded size
+    /// includes stream headers and end of stream markers. Weighting each ratio by its encoded size
+    /// yields the overall ratio.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` buffer overflow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseEncoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"testtesttesttest".repeat(0x0100);
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     let ratios = encoder.block_ratios(&src, 0x0400)?;
+    ///     assert_eq!(ratios.len(), 4);
+    ///     assert!(ratios.iter().all(|&u| u > 1.0));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn block_ratios(&mut self, src: &[u8], block_size: usize) -> io::Result<Vec<f64>> {
+        assert!(block_size != 0);
+        let mut dst = Vec::default();
+        let mut ratios = Vec::default();
+        for chunk in src.chunks(block_size) {
+            dst.clear();
+            let n_bytes = self.encode_bytes(chunk, &mut dst)?;
+            ratios.push(chunk.len() as f64 / n_bytes as f64);
+        }
+        Ok(ratios)
+    }
+
+    /// Scan `src` with the match finder returning a logarithmic match distance histogram, see
+    /// [N_BUCKETS](crate::N_BUCKETS).
+    ///
+    /// No entropy coding takes place. At most `sample` bytes are scanned as evenly spaced 1MiB
+    /// windows, or a single smaller window, with inputs no longer than `sample` scanned whole.
+    /// Windows are scanned independently, so distances spanning windows are not represented.
+    /// The current match strategy, sparse scan and RLE bias settings apply. Intended to inform
+    /// the choice of [MatchStrategy] and decoder [RingProfile](crate::RingProfile) prior to
+    /// encoding.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` buffer overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseEncoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"test".repeat(0x0100);
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     let histogram = encoder.match_distance_histogram(&src, usize::MAX)?;
+    ///     // Distance 4 matches.
+    ///     assert_eq!(histogram[2], 1);
+    ///     assert_eq!(histogram.iter().sum::<u64>(), 1);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn match_distance_histogram(
+        &mut self,
+        src: &[u8],
+        sample: usize,
+    ) -> io::Result<[u64; N_BUCKETS]> {
+        let mut backend = HistogramBackend::default();
+        for window in histogram::windows(src, sample) {
+            self.scan(window, &mut backend)?;
+        }
+        Ok(backend.buckets())
+    }
+
+    /// Encode `src` into `dst` as roughly `target_segments` independent LZFSE streams, returning
+    /// the segment index.
+    ///
+    /// Intended to prepare data for parallel or random access decoding, each segment being a
+    /// complete LZFSE stream. Segments are roughly balanced in size. Rather than splitting at
+    /// fixed offsets, each boundary is moved, within a small search radius, to a low match point
+    /// detected by the match finder, where fewer matches are severed. Segment stream offsets are
+    /// relative to the start of `dst`, segments are appended in order and the concatenated
+    /// decoded segments reconstruct `src`. Inputs too small to split encode as a single segment.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` or `dst` buffer overflow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target_segments` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseEncoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x1000);
+    ///     let mut enc = Vec::default();
+    ///     let segments = LzfseEncoder::default().encode_segmented(&src, 4, &mut enc)?;
+    ///     assert_eq!(segments.len(), 4);
+    ///     let mut dec = Vec::default();
+    ///     for segment in segments {
+    ///         let offset = segment.offset as usize;
+    ///         let enc = &enc[offset..offset + segment.n_payload_bytes as usize];
+    ///         lzfse_rust::decode_bytes(enc, &mut dec)?;
+    ///     }
+    ///     assert!(dec == src);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encode_segmented(
+        &mut self,
+        src: &[u8],
+        target_segments: usize,
+        dst: &mut Vec<u8>,
+    ) -> io::Result<Vec<SegmentEntry>> {
+        let boundaries = segmented::boundaries(self, src, target_segments)?;
+        let mut segments = Vec::with_capacity(boundaries.len() + 1);
+        let mut src_offset = 0;
+        for end in boundaries.into_iter().chain(Some(src.len())) {
+            let offset = dst.len() as u64;
+            let n_payload_bytes = self.encode_bytes(&src[src_offset..end], dst)?;
+            segments.push(SegmentEntry {
+                src_offset: src_offset as u64,
+                n_raw_bytes: (end - src_offset) as u64,
+                offset,
+                n_payload_bytes,
+            });
+            src_offset = end;
+        }
+        Ok(segments)
+    }
+
+    // Run the match finder, with the current match settings, over `src` into `backend`.
+    pub(super) fn scan<B: Backend>(&mut self, src: &[u8], backend: &mut B) -> io::Result<()> {
+        let mut frontend = FrontendBytes::new(&mut self.table, src);
+        frontend.set_match_strategy(self.match_strategy);
+        frontend.set_sparse_scan(self.sparse_scan);
+        frontend.set_rle_bias(self.rle_bias);
+        frontend.set_good_match_len(self.good_match_len);
+        frontend.set_search_depth(self.search_depth);
+        frontend.scan(backend, &mut Vec::default())
+    }
+
+    /// Set the [BlockStrategy], the default being [BlockStrategy::Auto].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{BlockStrategy, LzfseEncoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     encoder.set_block_strategy(BlockStrategy::NeverRaw);
+    ///     encoder.encode_bytes(b"test", &mut enc)?;
+    ///     // VN block magic, as opposed to raw block magic.
+    ///     assert_eq!(&enc[..4], b"bvxn");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_block_strategy(&mut self, strategy: BlockStrategy) {
+        self.strategy = strategy;
+    }
+
+    /// Returns the [BlockStrategy].
+    pub fn block_strategy(&self) -> BlockStrategy {
+        self.strategy
+    }
+
+    /// Set the block boundaries as strictly increasing raw byte offsets, the default being none.
+    ///
+    /// Intended for record oriented data, for example fixed size records in a columnar store. FSE
+    /// blocks are closed at each boundary, so no block spans a boundary. Blocks may also be closed
+    /// elsewhere, for example once full. Boundaries apply to each subsequent encoding operation,
+    /// which is then restricted to FSE blocks. An empty slice clears the boundaries.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::InvalidInput](std::io::ErrorKind) if the offsets are zero or not strictly
+    ///   increasing, in which case the boundaries are unchanged. Subsequent encoding operations
+    ///   also fail with this error if a boundary lies beyond the end of the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     encoder.set_block_boundaries(&[0x1000, 0x2000])?;
+    ///     let mut enc = Vec::default();
+    ///     encoder.encode_bytes(&src, &mut enc)?;
+    ///     let mut decoder = LzfseDecoder::default();
+    ///     let mut block = Vec::default();
+    ///     decoder.decode_block(&enc, 1, &mut block)?;
+    ///     assert_eq!(block, &src[0x1000..0x2000]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_block_boundaries(&mut self, offsets: &[u64]) -> io::Result<()> {
+        boundaries::validate(offsets)?;
+        self.boundaries.clear();
+        self.boundaries.extend_from_slice(offsets);
+        Ok(())
+    }
+
+    /// Returns the block boundaries.
+    pub fn block_boundaries(&self) -> &[u64] {
+        &self.boundaries
+    }
+
+    /// Set the FSE [NormalizationStrategy], the default being [NormalizationStrategy::Fast].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseDecoder, LzfseEncoder, NormalizationStrategy};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     encoder.set_normalization_strategy(NormalizationStrategy::Accurate);
+    ///     encoder.encode_bytes(&src, &mut enc)?;
+    ///     let mut dec = Vec::default();
+    ///     LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+    ///     assert_eq!(dec, src);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_normalization_strategy(&mut self, strategy: NormalizationStrategy) {
+        self.backend.set_normalization_strategy(strategy);
+    }
+
+    /// Returns the FSE [NormalizationStrategy].
+    pub fn normalization_strategy(&self) -> NormalizationStrategy {
+        self.backend.normalization_strategy()
+    }
+
+    /// Set the match finder hash seed, the default being zero.
+    ///
+    /// Intended for research into hash collision behavior, for example crafted inputs that degrade
+    /// compression ratios or speed through worst case bucket collisions. Match finder history is
+    /// indexed by hashing input bytes, the seed perturbs this hash. Zero selects the reference
+    /// hash. Other seeds produce differing, although equally valid, encoded output. Decoding is
+    /// unaffected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     encoder.set_hash_seed(0x1234_5678);
+    ///     encoder.encode_bytes(&src, &mut enc)?;
+    ///     let mut dec = Vec::default();
+    ///     LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+    ///     assert_eq!(dec, src);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_hash_seed(&mut self, seed: u32) {
+        self.table.set_hash_seed(seed);
+    }
+
+    /// Returns the match finder hash seed.
+    pub fn hash_seed(&self) -> u32 {
+        self.table.hash_seed()
+    }
+
+    /// Set the [MatchStrategy], the default being [MatchStrategy::Auto].
+    ///
+    /// [MatchStrategy::DecodeOptimized] limits match distances and lengths, trading compression
+    /// ratio for decode cache locality. Encoded output remains standard LZFSE.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseDecoder, LzfseEncoder, MatchStrategy};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     encoder.set_match_strategy(MatchStrategy::DecodeOptimized);
+    ///     encoder.encode_bytes(&src, &mut enc)?;
+    ///     let mut dec = Vec::default();
+    ///     LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+    ///     assert_eq!(dec, src);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_match_strategy(&mut self, strategy: MatchStrategy) {
+        self.match_strategy = strategy;
+    }
+
+    /// Returns the [MatchStrategy].
+    pub fn match_strategy(&self) -> MatchStrategy {
+        self.match_strategy
+    }
+
+    /// Enable the sparse scan fast path, the default being disabled.
+    ///
+    /// Intended for sparse data such as disk images, with long zero runs interleaved with data.
+    /// Zero runs are matched as usual, but the match finder history is only updated with the tail
+    /// of each long run rather than every position within it. This considerably speeds up
+    /// encoding across large zero spans. Encoded output may differ slightly from the default,
+    /// but remains standard LZFSE.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut src = vec![0; 0x0010_0000];
+    ///     src[0x8000..0x8010].copy_from_slice(b"sparse test data");
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     encoder.set_sparse_scan(true);
+    ///     encoder.encode_bytes(&src, &mut enc)?;
+    ///     let mut dec = Vec::default();
+    ///     LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+    ///     assert_eq!(dec, src);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_sparse_scan(&mut self, sparse_scan: bool) {
+        self.sparse_scan = sparse_scan;
+    }
+
+    /// Returns `true` if the sparse scan fast path is enabled.
+    pub fn sparse_scan(&self) -> bool {
+        self.sparse_scan
+    }
+
+    /// Strengthen the match finder's preference for distance 1 matches, the default being
+    /// disabled.
+    ///
+    /// Intended for output that is subsequently run length encoded downstream. By default, of
+    /// equal length match candidates the nearest is selected, which is distance 1 for byte runs
+    /// only when the preceding position is retained in the match finder history. When enabled, a
+    /// distance 1 match is explicitly considered at each position and selected over any farther
+    /// match of equal or shorter length. Longer matches are still preferred. Encoded output may
+    /// differ slightly from the default, but remains standard LZFSE.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"aaaaaaaabbbbbbbbaaaaaaaaaaaabbbbbbbbbbbb".repeat(0x0100);
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     encoder.set_rle_bias(true);
+    ///     encoder.encode_bytes(&src, &mut enc)?;
+    ///     let mut dec = Vec::default();
+    ///     LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+    ///     assert_eq!(dec, src);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_rle_bias(&mut self, rle_bias: bool) {
+        self.rle_bias = rle_bias;
+    }
+
+    /// Returns `true` if the distance 1 match preference is strengthened.
+    pub fn rle_bias(&self) -> bool {
+        self.rle_bias
+    }
+
+    /// Store input that appears to be already LZFSE encoded, the default is `false`.
+    ///
+    /// Intended for pipelines that may inadvertently encode data twice. Encoded data is high
+    /// entropy and does not compress further, a second encoding pass only costs time and
+    /// potentially expands the data. When enabled, [encode_bytes](Self::encode_bytes) inspects the
+    /// first four bytes of the input and, if they are LZFSE block magic bytes, `bvx1`, `bvx2`,
+    /// `bvxn`, `bvx-` or `bvx$`, emits the input verbatim as raw blocks without match finding.
+    ///
+    /// The output is always a valid LZFSE stream that decodes to the input. The check is
+    /// heuristic, only the magic bytes are inspected. Unencoded input that coincidentally begins
+    /// with magic bytes is also stored, and encoded input preceded by other data is encoded as
+    /// usual.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"test".repeat(0x0100);
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     encoder.encode_bytes(&src, &mut enc)?;
+    ///     // Accidental second pass.
+    ///     encoder.set_skip_if_compressed(true);
+    ///     let mut enc_enc = Vec::default();
+    ///     encoder.encode_bytes(&enc, &mut enc_enc)?;
+    ///     // Raw block header and end of stream overhead only.
+    ///     assert_eq!(enc_enc.len(), enc.len() + 12);
+    ///     let mut dec = Vec::default();
+    ///     LzfseDecoder::default().decode_bytes(&enc_enc, &mut dec)?;
+    ///     assert_eq!(dec, enc);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_skip_if_compressed(&mut self, skip_if_compressed: bool) {
+        self.skip_if_compressed = skip_if_compressed;
+    }
+
+    /// Returns `true` if input that appears to be already LZFSE encoded is stored.
+    pub fn skip_if_compressed(&self) -> bool {
+        self.skip_if_compressed
+    }
+
+    /// Pad [encode_bytes](Self::encode_bytes) output to a multiple of `multiple` bytes, the
+    /// default being `None`, unpadded.
+    ///
+    /// Intended for output that is subsequently encrypted with a block cipher. Padding takes the
+    /// form of a trailer following the end of stream block: the trailer magic bytes `bvxt`, a
+    /// little endian u32 padding length and that many zero bytes. Trailers are ignored by this
+    /// crate's decoders, although third party decoders may reject them. A trailer occupies at
+    /// least 8 bytes, so the padding may exceed `multiple`. Output whose length is already a
+    /// multiple is not padded. The output length is measured from the initial length of `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `multiple` is `Some(0)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseEncoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     encoder.set_pad_output_to(Some(16));
+    ///     assert_eq!(encoder.pad_output_to(), Some(16));
+    ///     assert_eq!(encoder.encode_bytes(b"test!", &mut enc)?, 32);
+    ///     let mut dec = Vec::default();
+    ///     lzfse_rust::decode_bytes(&enc, &mut dec)?;
+    ///     assert_eq!(dec, b"test!");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_pad_output_to(&mut self, multiple: Option<u32>) {
+        assert!(multiple != Some(0));
+        self.pad_output_to = multiple;
+    }
+
+    /// Returns the output padding multiple.
+    pub fn pad_output_to(&self) -> Option<u32> {
+        self.pad_output_to
+    }
+
+    /// Returns the good match length, see
+    /// [LzfseEncoderBuilder::good_match_len](crate::LzfseEncoderBuilder::good_match_len).
+    pub fn good_match_len(&self) -> u32 {
+        self.good_match_len
+    }
+
+    /// Returns the match search depth, see
+    /// [LzfseEncoderBuilder::search_depth](crate::LzfseEncoderBuilder::search_depth).
+    pub fn search_depth(&self) -> usize {
+        self.search_depth
+    }
+
+    /// Warm the encoder's internal tables and buffers by encoding a small internal sample.
+    ///
+    /// Intended for latency sensitive applications. Internal allocations are lazily committed by
+    /// the operating system, so the first encoding operation may otherwise incur additional one
+    /// off costs. Settings are unchanged and subsequent output is unaffected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseEncoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut encoder = LzfseEncoder::default();
+    ///     encoder.warm();
+    ///     let mut enc = Vec::default();
+    ///     encoder.encode_bytes(b"test", &mut enc)?;
+    ///     assert_eq!(enc, &[0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74,
+    ///                       0x62, 0x76, 0x78, 0x24]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn warm(&mut self) {
+        let strategy = mem::take(&mut self.strategy);
+        let boundaries = mem::take(&mut self.boundaries);
+        let src = kit::warm_sample();
+        let mut dst = Vec::with_capacity(src.len());
+        // In memory with the default strategy and no boundaries, so infallible.
+        let _ = self.encode_bytes(&src, &mut dst);
+        self.strategy = strategy;
+        self.boundaries = boundaries;
+    }
+
+    /// Reset the encoder's internal state, ready to encode a new independent stream.
+    ///
+    /// Each encoding operation starts from a reset state, so instances may be reused across any
+    /// number of streams without calling this method. It is provided to explicitly discard prior
+    /// history, for example after abandoning a writer mid stream. No allocations are made and
+    /// settings are unchanged.
+    pub fn reset(&mut self) {
+        self.table.reset();
+    }
+}
+
+// Store `src` verbatim as raw blocks followed by an end of stream block.
+fn store<O: ShortWriter>(src: &[u8], dst: &mut O) -> io::Result<()> {
+    for chunk in src.chunks(i32::MAX as usize) {
+        raw::raw_compress(dst, chunk)?;
+    }
+    dst.write_short_bytes(&u32::from(MagicBytes::Eos).to_le_bytes())
+}
+
+// Pad `len` encoded bytes to a multiple of `multiple` bytes with a zero filled trailer.
+fn pad_output<O: ShortWriter>(dst: &mut O, len: u64, multiple: u32) -> io::Result<()> {
+    const U32: u64 = mem::size_of::<u32>() as u64;
+    let multiple = multiple as u64;
+    let rem = len % multiple;
+    if rem == 0 {
+        return Ok(());
+    }
+    let n = (multiple - (len + U32 * 2) % multiple) % multiple;
+    trailer::write_zeros(dst, TrailerKind::Opaque, n as u32)
+}
+
+// Decode `enc` and compare against `src`. As `enc` is our own output, decoding errors are also
+// verification failures.
+fn verify(src: &[u8], enc: &[u8]) -> crate::Result<()> {
+    let mut dec = Vec::default();
+    match LzfseDecoder::default().decode_bounded(enc, &mut dec, src.len() as u64) {
+        Ok(_) if dec == src => Ok(()),
+        _ => Err(Error::VerificationFailed),
+    }
+}
+
+// Heuristic, `src` begins with LZFSE block magic bytes.
+fn is_compressed(src: &[u8]) -> bool {
+    src.len() >= 4 && MagicBytes::try_from(src.peek_u32()).is_ok()
+}
+
+impl Default for LzfseEncoder {
+    fn default() -> Self {
+        Self::with_history_table(HistoryTable::default())
     }
 }
 
@@ -58,3 +939,67 @@ impl fmt::Debug for LzfseEncoder {
         f.debug_struct("LzfseEncoder").finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(src: &[u8]) -> Vec<u8> {
+        let mut enc = Vec::default();
+        LzfseEncoder::default().encode_bytes(src, &mut enc).unwrap();
+        enc
+    }
+
+    fn check_fail(src: &[u8], enc: &[u8]) {
+        assert!(matches!(verify(src, enc), Err(Error::VerificationFailed)));
+    }
+
+    #[test]
+    fn verify_ok() {
+        for src in [&b""[..], b"test", &[0u8; 0x0001_0000], &b"abcdefgh".repeat(0x1000)] {
+            verify(src, &encode(src)).unwrap();
+        }
+    }
+
+    // Corrupt raw block literal: the stream decodes, but to different bytes.
+    #[test]
+    fn verify_bad_literal() {
+        let mut enc = encode(b"test");
+        assert_eq!(&enc[..4], b"bvx-");
+        enc[8] ^= 0x01;
+        check_fail(b"test", &enc);
+    }
+
+    // Corrupt magic bytes: the stream fails to decode.
+    #[test]
+    fn verify_bad_magic() {
+        let src = b"abcdefgh".repeat(0x1000);
+        let mut enc = encode(&src);
+        enc[0] ^= 0x01;
+        check_fail(&src, &enc);
+    }
+
+    // Every single byte corruption that alters the decoded output is detected.
+    #[test]
+    fn verify_flip() {
+        let src = b"abcdefgh".repeat(0x0100);
+        let enc = encode(&src);
+        for i in 0..enc.len() {
+            let mut bad = enc.clone();
+            bad[i] ^= 0x80;
+            let mut dec = Vec::default();
+            match LzfseDecoder::default().decode_bytes(&bad, &mut dec) {
+                Ok(_) if dec == src => verify(&src, &bad).unwrap(),
+                _ => check_fail(&src, &bad),
+            }
+        }
+    }
+
+    #[test]
+    fn verify_mismatch() {
+        let enc = encode(b"test");
+        check_fail(b"tesT", &enc);
+        check_fail(b"tes", &enc);
+        check_fail(b"tests", &enc);
+    }
+}
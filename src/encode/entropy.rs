@@ -0,0 +1,143 @@
+use crate::decode::{DecodeEvent, LzfseDecoder};
+
+use super::encoder::LzfseEncoder;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io;
+
+/// Order-0 byte entropy of `src` in bits per byte.
+///
+/// Intended as a lower bound reference when evaluating compression ratios. The order-0 entropy
+/// multiplied by `src.len()` is the minimum encoded size, in bits, achievable by any coder that
+/// models bytes independently. Dictionary coders such as LZFSE may undercut it by exploiting
+/// repetition, see [lz_entropy_floor].
+///
+/// Returns 0.0 for empty inputs.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(lzfse_rust::entropy_floor(&[0; 1024]), 0.0);
+/// assert_eq!(lzfse_rust::entropy_floor(&[0, 1, 2, 3]), 2.0);
+/// ```
+pub fn entropy_floor(src: &[u8]) -> f64 {
+    let mut counts = [0u64; 256];
+    src.iter().for_each(|&u| counts[u as usize] += 1);
+    per_byte(entropy(counts.iter().copied()), src.len())
+}
+
+/// LZ model entropy of `src` in bits per byte.
+///
+/// `src` is parsed into literal runs and matches using the LZFSE match finder. The LZ floor is the
+/// sum of the order-0 entropies of the literal, literal length, match length and match distance
+/// streams, each modelled independently, divided by `src.len()`. Repeated match distances are
+/// modelled as a single symbol. This approximates the size achievable by an ideal entropy coder
+/// given the LZFSE parse, excluding block and table overheads. Compare with [entropy_floor] and
+/// the actual encoded size.
+///
+/// Returns 0.0 for empty inputs.
+///
+/// # Errors
+///
+/// * [Error](std::io::Error) in case of internal encoding errors.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+///     let lz = lzfse_rust::lz_entropy_floor(&src)?;
+///     assert!(lz < lzfse_rust::entropy_floor(&src));
+///     Ok(())
+/// }
+/// ```
+pub fn lz_entropy_floor(src: &[u8]) -> io::Result<f64> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(src, &mut enc)?;
+    let mut model = LzModel::default();
+    LzfseDecoder::default().trace(&enc, |event| model.push(event))?;
+    Ok(per_byte(model.n_bits(), src.len()))
+}
+
+#[derive(Default)]
+struct LzModel {
+    literals: HashMap<u8, u64>,
+    literal_lens: HashMap<u32, u64>,
+    match_lens: HashMap<u32, u64>,
+    match_distances: HashMap<Option<u32>, u64>,
+    literal_len: u32,
+    distance: u32,
+}
+
+impl LzModel {
+    fn push(&mut self, event: DecodeEvent) {
+        match event {
+            DecodeEvent::Literal(bytes) => {
+                bytes.iter().for_each(|&u| *self.literals.entry(u).or_default() += 1);
+                self.literal_len += bytes.len() as u32;
+            }
+            DecodeEvent::Match { distance, len } => {
+                *self.literal_lens.entry(self.literal_len).or_default() += 1;
+                *self.match_lens.entry(len).or_default() += 1;
+                // Repeated distances as `None`.
+                let key = if distance == self.distance { None } else { Some(distance) };
+                *self.match_distances.entry(key).or_default() += 1;
+                self.literal_len = 0;
+                self.distance = distance;
+            }
+        }
+    }
+
+    fn n_bits(&self) -> f64 {
+        map_entropy(&self.literals)
+            + map_entropy(&self.literal_lens)
+            + map_entropy(&self.match_lens)
+            + map_entropy(&self.match_distances)
+    }
+}
+
+fn map_entropy<K: Eq + Hash>(map: &HashMap<K, u64>) -> f64 {
+    entropy(map.values().copied())
+}
+
+// Total order-0 entropy in bits given symbol counts.
+fn entropy(counts: impl Iterator<Item = u64> + Clone) -> f64 {
+    let total = counts.clone().sum::<u64>() as f64;
+    counts.filter(|&n| n != 0).map(|n| n as f64 * (total / n as f64).log2()).sum()
+}
+
+#[inline(always)]
+fn per_byte(n_bits: f64, len: usize) -> f64 {
+    if len == 0 {
+        0.0
+    } else {
+        n_bits / len as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entropy_uniform() {
+        assert_eq!(entropy([1u64, 1, 1, 1].iter().copied()), 8.0);
+        assert_eq!(entropy([0u64, 4, 0].iter().copied()), 0.0);
+        assert_eq!(entropy([0u64; 4].iter().copied()), 0.0);
+    }
+
+    #[test]
+    fn repeated_distance() {
+        let mut model = LzModel::default();
+        model.push(DecodeEvent::Literal(b"ab"));
+        model.push(DecodeEvent::Match { distance: 2, len: 4 });
+        model.push(DecodeEvent::Literal(b"c"));
+        model.push(DecodeEvent::Match { distance: 2, len: 4 });
+        assert_eq!(model.match_distances.get(&None), Some(&1));
+        assert_eq!(model.match_distances.get(&Some(2)), Some(&1));
+        assert_eq!(model.literal_lens.len(), 2);
+    }
+}
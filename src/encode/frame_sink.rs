@@ -0,0 +1,97 @@
+use super::ring_encoder::LzfseRingEncoder;
+
+use std::convert::TryInto;
+use std::fmt;
+use std::io::{self, Write};
+
+/// LZFSE length-delimited frame sink.
+///
+/// Encodes discrete messages into an inner writer as a sequence of frames. Each frame consists of
+/// a 4 byte little-endian length followed by an independent LZFSE stream of that length. Frames
+/// may be decoded independently of each other, see [FrameSource](crate::FrameSource).
+///
+/// Instances are created using [LzfseRingEncoder::frame_sink].
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{LzfseRingDecoder, LzfseRingEncoder};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut encoder = LzfseRingEncoder::default();
+///     let mut sink = encoder.frame_sink(Vec::default());
+///     sink.write_frame(b"alpha")?;
+///     sink.write_frame(b"beta")?;
+///     let enc = sink.into_inner();
+///     let mut decoder = LzfseRingDecoder::default();
+///     let mut source = decoder.frame_source(enc.as_slice());
+///     let mut dec = Vec::default();
+///     source.read_frame(&mut dec)?;
+///     assert_eq!(dec, b"alpha");
+///     dec.clear();
+///     source.read_frame(&mut dec)?;
+///     assert_eq!(dec, b"beta");
+///     dec.clear();
+///     assert_eq!(source.read_frame(&mut dec)?, None);
+///     Ok(())
+/// }
+/// ```
+pub struct FrameSink<'a, O: Write> {
+    encoder: &'a mut LzfseRingEncoder,
+    inner: O,
+    buffer: Vec<u8>,
+}
+
+impl<'a, O: Write> FrameSink<'a, O> {
+    #[inline(always)]
+    pub(super) fn new(encoder: &'a mut LzfseRingEncoder, inner: O) -> Self {
+        Self { encoder, inner, buffer: Vec::default() }
+    }
+
+    /// Encode `msg` as a single frame returning the total number of bytes written into the inner
+    /// writer, including the 4 byte length prefix.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) in case of inner writer IO errors, or if the encoded frame
+    ///   exceeds `u32::MAX` bytes.
+    pub fn write_frame(&mut self, msg: &[u8]) -> io::Result<u64> {
+        self.buffer.clear();
+        self.encoder.encode_bytes(msg, &mut self.buffer)?;
+        let len: u32 = self
+            .buffer
+            .len()
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame overflow"))?;
+        self.inner.write_all(&len.to_le_bytes())?;
+        self.inner.write_all(&self.buffer)?;
+        Ok(4 + len as u64)
+    }
+
+    /// Flush the inner writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Gets a reference to the inner writer.
+    pub fn get_ref(&self) -> &O {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner writer.
+    pub fn get_mut(&mut self) -> &mut O {
+        &mut self.inner
+    }
+
+    /// Unwraps this `FrameSink<O>`, returning the inner writer.
+    pub fn into_inner(self) -> O {
+        self.inner
+    }
+}
+
+impl<'a, O: Write> fmt::Debug for FrameSink<'a, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameSink").finish()
+    }
+}
@@ -8,9 +8,12 @@ use crate::vn::VnBackend;
 
 use super::backend::Backend;
 use super::backend_type::BackendType;
+use super::block_strategy::BlockStrategy;
+use super::boundaries::Boundaries;
 use super::constants::*;
-use super::history::{History, HistoryTable, Item};
+use super::history::{History, HistoryTable, Item, HASH_WIDTH, MAX_SEARCH_DEPTH};
 use super::match_object::Match;
+use super::match_strategy::MatchStrategy;
 use super::match_unit::MatchUnit;
 
 use std::io;
@@ -29,12 +32,83 @@ pub struct FrontendBytes<'a> {
     pending: Match,
     literal_index: u32,
     index: u32,
+    strategy: BlockStrategy,
+    match_strategy: MatchStrategy,
+    sparse_scan: bool,
+    rle_bias: bool,
+    good_match_len: u32,
+    search_depth: usize,
+    deep_depth: usize,
+    boundaries: Boundaries<'a>,
+    prefix_len: u32,
 }
 
 impl<'a> FrontendBytes<'a> {
     #[inline(always)]
     pub fn new(table: &'a mut HistoryTable, src: &'a [u8]) -> Self {
-        Self { table, src, block: &[], pending: Match::default(), literal_index: 0, index: 0 }
+        table.set_search_depth(HASH_WIDTH);
+        Self {
+            table,
+            src,
+            block: &[],
+            pending: Match::default(),
+            literal_index: 0,
+            index: 0,
+            strategy: BlockStrategy::default(),
+            match_strategy: MatchStrategy::default(),
+            sparse_scan: false,
+            rle_bias: false,
+            good_match_len: GOOD_MATCH_LEN,
+            search_depth: HASH_WIDTH,
+            deep_depth: 0,
+            boundaries: Boundaries::default(),
+            prefix_len: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_block_strategy(&mut self, strategy: BlockStrategy) {
+        self.strategy = strategy;
+    }
+
+    #[inline(always)]
+    pub fn set_match_strategy(&mut self, strategy: MatchStrategy) {
+        self.match_strategy = strategy;
+    }
+
+    #[inline(always)]
+    pub fn set_sparse_scan(&mut self, sparse_scan: bool) {
+        self.sparse_scan = sparse_scan;
+    }
+
+    #[inline(always)]
+    pub fn set_rle_bias(&mut self, rle_bias: bool) {
+        self.rle_bias = rle_bias;
+    }
+
+    #[inline(always)]
+    pub fn set_good_match_len(&mut self, good_match_len: u32) {
+        self.good_match_len = good_match_len;
+    }
+
+    #[inline(always)]
+    pub fn set_search_depth(&mut self, search_depth: usize) {
+        debug_assert!((1..=MAX_SEARCH_DEPTH).contains(&search_depth));
+        self.search_depth = search_depth.min(HASH_WIDTH);
+        self.deep_depth = search_depth - self.search_depth;
+        self.table.set_search_depth(search_depth);
+    }
+
+    #[inline(always)]
+    pub fn set_block_boundaries(&mut self, offsets: &'a [u64]) {
+        self.boundaries = Boundaries::new(offsets);
+    }
+
+    // Leading `src` bytes that prime the history as a dictionary, they are not encoded.
+    #[inline(always)]
+    pub fn set_prefix_len(&mut self, prefix_len: u32) {
+        debug_assert!(prefix_len as usize <= self.src.len());
+        self.prefix_len = prefix_len;
     }
 
     #[inline(always)]
@@ -42,11 +116,44 @@ impl<'a> FrontendBytes<'a> {
     where
         O: ShortWriter,
     {
+        if self.is_raw_only() {
+            return self.flush_tiny(dst);
+        }
         self.init();
         self.flush(backend, dst)?;
         Ok(())
     }
 
+    // Match finder only, no block selection or end of stream marker.
+    #[inline(always)]
+    pub fn scan<B, O>(&mut self, backend: &mut B, dst: &mut O) -> io::Result<()>
+    where
+        B: Backend,
+        O: ShortWriter,
+    {
+        self.init();
+        self.flush_backend::<_, _, false>(backend, dst)
+    }
+
+    // Tiny inputs always encode as raw blocks.
+    #[inline(always)]
+    fn is_raw_only(&self) -> bool {
+        self.payload_len() <= RAW_CUTOFF as usize
+            && self.strategy != BlockStrategy::NeverRaw
+            && !self.boundaries.is_active()
+    }
+
+    // Fast path, bypass the match finder and history table reset.
+    fn flush_tiny<O>(&mut self, dst: &mut O) -> io::Result<()>
+    where
+        O: ShortWriter,
+    {
+        self.flush_raw(dst)?;
+        dst.write_short_u32(MagicBytes::Eos.into())?;
+        dst.flush(true)?;
+        Ok(())
+    }
+
     fn flush<O>(&mut self, backend: &mut FseBackend, dst: &mut O) -> io::Result<()>
     where
         O: ShortWriter,
@@ -54,6 +161,7 @@ impl<'a> FrontendBytes<'a> {
         // Select.
         self.flush_select(backend, dst)?;
         debug_assert_eq!(self.literal_index as usize, self.src.len());
+        self.boundaries.finalize()?;
         // Eos.
         dst.write_short_u32(MagicBytes::Eos.into())?;
         dst.flush(true)?;
@@ -64,11 +172,17 @@ impl<'a> FrontendBytes<'a> {
     where
         O: ShortWriter,
     {
-        let len = self.src.len();
-        if len > VN_CUTOFF as usize {
+        let len = self.payload_len();
+        if self.boundaries.is_active() {
+            // Only Fse blocks may be closed at caller specified boundaries.
+            self.flush_backend::<_, _, false>(backend, dst)
+        } else if len > VN_CUTOFF as usize {
+            if self.strategy == BlockStrategy::Compact && len <= COMPACT_LIMIT as usize {
+                return self.flush_compact(backend, dst);
+            }
             // Fse
             self.flush_backend::<_, _, false>(backend, dst)
-        } else if len > RAW_CUTOFF as usize {
+        } else if len > RAW_CUTOFF as usize || self.strategy == BlockStrategy::NeverRaw {
             // Vn
             self.flush_backend::<_, _, true>(&mut VnBackend::default(), dst)
         } else {
@@ -76,6 +190,27 @@ impl<'a> FrontendBytes<'a> {
         }
     }
 
+    // Trial Vn and Fse, retaining the smallest.
+    fn flush_compact<O>(&mut self, backend: &mut FseBackend, dst: &mut O) -> io::Result<()>
+    where
+        O: ShortWriter,
+    {
+        let mark = dst.pos();
+        self.flush_backend::<_, _, true>(&mut VnBackend::default(), dst)?;
+        let vn_len = (dst.pos() - mark) as u32;
+        if !dst.truncate(mark) {
+            return Ok(());
+        }
+        self.init();
+        self.flush_backend::<_, _, false>(backend, dst)?;
+        let fse_len = (dst.pos() - mark) as u32;
+        if vn_len < fse_len && dst.truncate(mark) {
+            self.init();
+            self.flush_backend::<_, _, true>(&mut VnBackend::default(), dst)?;
+        }
+        Ok(())
+    }
+
     fn flush_backend<B, O, const VN: bool>(
         &mut self,
         backend: &mut B,
@@ -85,11 +220,11 @@ impl<'a> FrontendBytes<'a> {
         B: Backend,
         O: ShortWriter,
     {
-        let src_len = self.src.len();
+        let src_len = self.payload_len();
         let mark = dst.pos();
         backend.init(dst, Some(src_len))?;
         self.finalize(backend, dst)?;
-        if VN && src_len < RAW_LIMIT as usize {
+        if VN && src_len < RAW_LIMIT as usize && self.strategy != BlockStrategy::NeverRaw {
             let dst_len = (dst.pos() - mark) as usize;
             if src_len + RAW_HEADER_SIZE as usize <= dst_len && dst.truncate(mark) {
                 // The compressed length is NOT shorter than raw block length AND we have a
@@ -104,8 +239,8 @@ impl<'a> FrontendBytes<'a> {
     where
         O: ShortWriter,
     {
-        assert!(self.src.len() <= i32::MAX as usize);
-        raw::raw_compress(dst, self.src)?;
+        assert!(self.payload_len() <= i32::MAX as usize);
+        raw::raw_compress(dst, &self.src[self.prefix_len as usize..])?;
         self.literal_index = self.src.len() as u32;
         Ok(())
     }
@@ -114,8 +249,14 @@ impl<'a> FrontendBytes<'a> {
         self.table.reset();
         self.block = &[];
         self.pending = Match::default();
-        self.literal_index = 0;
+        self.literal_index = self.prefix_len;
         self.index = 0;
+        self.boundaries.reset();
+    }
+
+    #[inline(always)]
+    fn payload_len(&self) -> usize {
+        self.src.len() - self.prefix_len as usize
     }
 
     fn finalize<B, O>(&mut self, backend: &mut B, dst: &mut O) -> io::Result<()>
@@ -123,8 +264,13 @@ impl<'a> FrontendBytes<'a> {
         B: Backend,
         O: ShortWriter,
     {
-        self.match_blocks(backend, dst)?;
-        self.flush_pending(backend, dst)?;
+        if self.payload_len() < mem::size_of::<u32>() {
+            // Tiny inputs with `BlockStrategy::NeverRaw`, literals only.
+            self.block = self.src;
+        } else {
+            self.match_blocks(backend, dst)?;
+            self.flush_pending(backend, dst)?;
+        }
         self.flush_literals(backend, dst)?;
         backend.finalize(dst)?;
         Ok(())
@@ -136,6 +282,8 @@ impl<'a> FrontendBytes<'a> {
         O: ShortWriter,
     {
         debug_assert!(self.is_init());
+        // Prime the history with the prefix, if any.
+        self.index = unsafe { self.sync_history::<B::Type>(0) };
         while self.match_block(backend, dst)? {}
         Ok(())
     }
@@ -186,13 +334,16 @@ impl<'a> FrontendBytes<'a> {
             let item = Item::new(val, index.into());
             let queue = self.table.push::<B::Type>(item);
             let incoming = unsafe { self.find_match::<B::Type>(queue, item) };
-            if let Some(select) = self.pending.select::<GOOD_MATCH_LEN>(incoming) {
+            if let Some(select) = self.pending.select(incoming, self.good_match_len) {
                 unsafe { self.push_match(backend, dst, select)? };
                 if self.literal_index >= self.index {
                     // Unlikely.
                     break;
                 }
                 index += 1;
+                if self.sparse_scan {
+                    index = unsafe { self.sparse_skip(select, index) };
+                }
                 index = unsafe { self.sync_history::<B::Type>(index) };
                 if index >= self.index {
                     // Unlikely
@@ -210,23 +361,25 @@ impl<'a> FrontendBytes<'a> {
         Ok(is_short)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline)]
     unsafe fn find_match<B>(&self, queue: History, item: Item) -> Match
     where
         B: BackendType,
     {
+        // Distances are non-decreasing, we break on the first distant item.
+        debug_assert!(queue.is_ordered(item.idx));
+        let max_distance = B::MAX_MATCH_DISTANCE.min(self.match_strategy.max_match_distance());
         let mut m = Match::default();
-        for &match_idx_val in queue.iter() {
-            let distance = (item.idx - match_idx_val.idx) as u32;
-            debug_assert!(distance <= Q2);
-            if distance > B::MAX_MATCH_DISTANCE {
-                break;
-            }
-            let match_len_inc = self.match_unit::<B>(item, match_idx_val);
-            if match_len_inc > m.match_len {
-                m.match_len = match_len_inc;
-                m.match_idx = match_idx_val.idx;
-            }
+        if self.scan_queue::<B>(&queue[..self.search_depth], item, max_distance, &mut m)
+            && self.deep_depth != 0
+        {
+            // Unlikely.
+            let queue = &self.table.deep_queue()[..self.deep_depth];
+            self.scan_queue::<B>(queue, item, max_distance, &mut m);
+        }
+        if self.rle_bias {
+            self.rle_match::<B>(item, &mut m);
         }
         if m.match_len == 0 {
             // Likely.
@@ -238,12 +391,60 @@ impl<'a> FrontendBytes<'a> {
             m.idx -= match_len_dec;
             m.match_idx -= match_len_dec;
             m.match_len += match_len_dec;
+            m.match_len = m.match_len.min(self.match_strategy.max_match_len());
             debug_assert!(self.validate_match::<B>(m));
             m
         }
     }
 
-    #[inline(always)]
+    // Returns false if a distant item was encountered, older items being no nearer.
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline)]
+    unsafe fn scan_queue<B>(
+        &self,
+        queue: &[Item],
+        item: Item,
+        max_distance: u32,
+        m: &mut Match,
+    ) -> bool
+    where
+        B: BackendType,
+    {
+        for &match_idx_val in queue.iter() {
+            let distance = (item.idx - match_idx_val.idx) as u32;
+            debug_assert!(distance <= Q2);
+            if distance > max_distance {
+                return false;
+            }
+            let match_len_inc = self.match_unit::<B>(item, match_idx_val);
+            if match_len_inc > m.match_len {
+                m.match_len = match_len_inc;
+                m.match_idx = match_idx_val.idx;
+            }
+        }
+        true
+    }
+
+    // Within byte runs candidates are compared by the run length they cover, ties resolved in favour
+    // of distance 1. Run heads are left as literals, the run tail is then matched at distance 1.
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline)]
+    unsafe fn rle_match<B: BackendType>(&self, item: Item, m: &mut Match) {
+        let index = u32::from(item.idx);
+        if item.val.rotate_left(8) != item.val {
+            return;
+        }
+        if index == 0 || get_u32(self.block, index - 1) != item.val {
+            m.match_len = 0;
+            return;
+        }
+        let match_item = Item::new(item.val, item.idx - 1);
+        m.match_len = self.match_unit::<B>(item, match_item);
+        m.match_idx = match_item.idx;
+    }
+
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline)]
     unsafe fn match_unit<M: MatchUnit>(&self, item: Item, match_item: Item) -> u32 {
         debug_assert!(self.validate_match_items::<M>(item, match_item));
         let len = M::match_us((item.val, match_item.val));
@@ -257,7 +458,8 @@ impl<'a> FrontendBytes<'a> {
         }
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline)]
     unsafe fn match_dec<M: MatchUnit>(&self, idx: Idx, match_idx: Idx) -> u32 {
         debug_assert!(self.validate_match_idxs::<M>(idx, match_idx));
         let index = usize::from(idx);
@@ -296,6 +498,10 @@ impl<'a> FrontendBytes<'a> {
         let match_index = usize::from(m.idx);
         debug_assert!(literal_index <= self.block.len());
         debug_assert!(match_index <= self.block.len());
+        if self.boundaries.is_active() {
+            let literal_len = (match_index - literal_index) as u32;
+            return self.push_bounded(backend, dst, literal_len, m.match_len, match_distance);
+        }
         let literals = self.block.get_unchecked(literal_index..match_index);
         self.literal_index = u32::from(m.idx) + m.match_len;
         backend.push_match(dst, literals, m.match_len, match_distance)
@@ -325,12 +531,60 @@ impl<'a> FrontendBytes<'a> {
         debug_assert_ne!(len, 0);
         debug_assert_eq!(self.pending.match_len, 0);
         debug_assert!(self.literal_index as usize + len as usize <= self.block.len());
+        if self.boundaries.is_active() {
+            let match_distance = MatchDistance::new_unchecked(1);
+            return self.push_bounded(backend, dst, len, 0, match_distance);
+        }
         let literal_index = self.literal_index as usize;
         let literals = self.block.get_unchecked(literal_index..literal_index + len as usize);
         self.literal_index += len;
         backend.push_literals(dst, literals)
     }
 
+    // Push literals and match, splitting them and closing blocks at caller specified boundaries.
+    #[cold]
+    unsafe fn push_bounded<B: Backend, O: ShortWriter>(
+        &mut self,
+        backend: &mut B,
+        dst: &mut O,
+        mut literal_len: u32,
+        mut match_len: u32,
+        match_distance: MatchDistance<B::Type>,
+    ) -> io::Result<()> {
+        loop {
+            let limit = self.boundaries.limit(backend, dst)?;
+            let literal_index = self.literal_index as usize;
+            let len = literal_len + match_len;
+            if len as u64 <= limit {
+                let literals =
+                    self.block.get_unchecked(literal_index..literal_index + literal_len as usize);
+                self.literal_index += len;
+                self.boundaries.advance(len as u64);
+                return if match_len == 0 {
+                    backend.push_literals(dst, literals)
+                } else {
+                    backend.push_match(dst, literals, match_len, match_distance)
+                };
+            }
+            let limit = limit as u32;
+            if limit <= literal_len {
+                let literals =
+                    self.block.get_unchecked(literal_index..literal_index + limit as usize);
+                self.literal_index += limit;
+                literal_len -= limit;
+                backend.push_literals(dst, literals)?;
+            } else {
+                let literals =
+                    self.block.get_unchecked(literal_index..literal_index + literal_len as usize);
+                self.literal_index += limit;
+                match_len -= limit - literal_len;
+                backend.push_match(dst, literals, limit - literal_len, match_distance)?;
+                literal_len = 0;
+            }
+            self.boundaries.advance(limit as u64);
+        }
+    }
+
     #[inline(always)]
     #[must_use]
     unsafe fn sync_history<B: BackendType>(&mut self, mut index: u32) -> u32 {
@@ -343,6 +597,21 @@ impl<'a> FrontendBytes<'a> {
         index
     }
 
+    // Zero run matches, distance <= 4 with a zero source u32, skip history synchronization over
+    // all but the run tail.
+    #[inline(always)]
+    #[must_use]
+    unsafe fn sparse_skip(&self, m: Match, index: u32) -> u32 {
+        if m.match_len >= SPARSE_RUN_LEN
+            && (m.idx - m.match_idx) as u32 <= 4
+            && get_u32(self.block, u32::from(m.match_idx)) == 0
+        {
+            index.max(self.literal_index - SPARSE_TAIL_LEN)
+        } else {
+            index
+        }
+    }
+
     #[allow(clippy::absurd_extreme_comparisons)]
     #[allow(clippy::assertions_on_constants)]
     fn reposition<B, O>(&mut self, backend: &mut B, dst: &mut O) -> io::Result<()>
@@ -377,13 +646,13 @@ impl<'a> FrontendBytes<'a> {
     fn is_init(&self) -> bool {
         self.block.is_empty()
             && self.pending == Match::default()
-            && self.literal_index == 0
+            && self.literal_index == self.prefix_len
             && self.index == 0
     }
 
     fn is_any<B: BackendType>(&self) -> bool {
         self.literal_index <= self.index
-            && (self.index == 0 || self.index == B::MAX_MATCH_DISTANCE)
+            && (self.index == self.prefix_len || self.index == B::MAX_MATCH_DISTANCE)
             && self.src.len() >= 4 + self.index as usize
     }
 
@@ -413,10 +682,11 @@ impl<'a> FrontendBytes<'a> {
     }
 }
 
+// Little endian, as per `Ring::get_u32`, for platform independent output.
 #[inline(always)]
 unsafe fn get_u32(bytes: &[u8], index: u32) -> u32 {
     debug_assert!(index as usize + mem::size_of::<u32>() <= bytes.len());
-    bytes.as_ptr().add(index as usize).cast::<u32>().read_unaligned()
+    bytes.as_ptr().add(index as usize).cast::<u32>().read_unaligned().to_le()
 }
 
 #[cfg(test)]
@@ -426,6 +696,7 @@ mod tests {
     use crate::lmd::Lmd;
     use crate::ops::PeekData;
 
+    use super::super::boundary;
     use super::super::dummy::{Dummy, DummyBackend};
     use super::*;
 
@@ -494,6 +765,27 @@ mod tests {
         )
     }
 
+    // Tiny input fast path, byte identical to the standard path.
+    #[test]
+    fn tiny_fast_path() -> io::Result<()> {
+        let mut table = HistoryTable::default();
+        let mut backend = FseBackend::default();
+        for n in 0..=RAW_CUTOFF as usize {
+            let src = Rng::default().gen_vec(n).unwrap();
+            let mut frontend = FrontendBytes::new(&mut table, &src);
+            assert!(frontend.is_raw_only());
+            let mut fast = Vec::default();
+            frontend.execute(&mut backend, &mut fast)?;
+            let mut standard = Vec::default();
+            frontend.init();
+            frontend.flush(&mut backend, &mut standard)?;
+            assert_eq!(fast, standard);
+        }
+        let src = [0; RAW_CUTOFF as usize + 1];
+        assert!(!FrontendBytes::new(&mut table, &src).is_raw_only());
+        Ok(())
+    }
+
     // Vxn, assumes the defaults (RAW_CUTOFF: 0x0014, VN_CUTOFF: 0x1000)
     #[test]
     fn zero_4096() -> io::Result<()> {
@@ -530,6 +822,69 @@ mod tests {
         )
     }
 
+    // Inputs either side of, and exactly at, the cutoff and ring boundaries.
+    #[test]
+    fn boundary_matrix() -> io::Result<()> {
+        for n in boundary::sizes() {
+            for src in boundary::data(n) {
+                let enc = compress(&src)?;
+                let mut dec = Vec::default();
+                crate::decode_bytes(&enc, &mut dec)?;
+                assert!(dec == src, "n: {}", n);
+            }
+        }
+        Ok(())
+    }
+
+    // Block types either side of, and exactly at, the raw and VN cutoffs.
+    #[test]
+    fn boundary_magic() -> io::Result<()> {
+        for &(n, magic) in &[
+            (RAW_CUTOFF - 1, MagicBytes::Raw),
+            (RAW_CUTOFF, MagicBytes::Raw),
+            (RAW_CUTOFF + 1, MagicBytes::Vxn),
+            (VN_CUTOFF - 1, MagicBytes::Vxn),
+            (VN_CUTOFF, MagicBytes::Vxn),
+            (VN_CUTOFF + 1, MagicBytes::Vx2),
+        ] {
+            check_magic(&vec![0u8; n as usize], magic)?;
+        }
+        Ok(())
+    }
+
+    // Inputs either side of, and exactly at, the block guide. Requires 2GB+ of memory.
+    #[test]
+    #[ignore = "expensive"]
+    fn boundary_block_guide() -> io::Result<()> {
+        let mut table = HistoryTable::default();
+        let mut backend = FseBackend::default();
+        let src = vec![0u8; BLOCK_GUIDE as usize + 4];
+        let mut dst = Vec::default();
+        for &n in &[-1, 0, 1, 3, 4] {
+            let len = (BLOCK_GUIDE as i64 + n) as usize;
+            dst.clear();
+            FrontendBytes::new(&mut table, &src[..len]).execute(&mut backend, &mut dst)?;
+            let mut sink = ZeroSink(0);
+            crate::LzfseRingDecoder::default().decode(&mut dst.as_slice(), &mut sink)?;
+            assert_eq!(sink.0, len as u64);
+        }
+        Ok(())
+    }
+
+    struct ZeroSink(u64);
+
+    impl io::Write for ZeroSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            assert!(buf.iter().all(|&u| u == 0));
+            self.0 += buf.len() as u64;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
     // Random non-compressible data (<= VN_CUTOFF) should fall back to a Raw block (more efficient).
     #[test]
     fn rand_vn_cutoff() -> io::Result<()> {
@@ -623,4 +978,14 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn get_u32_le() {
+        let bytes: Vec<u8> = (0..0x20).collect();
+        for index in 0..0x1C {
+            let u = unsafe { get_u32(&bytes, index) };
+            let i = index as usize;
+            assert_eq!(u, u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]));
+        }
+    }
 }
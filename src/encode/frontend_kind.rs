@@ -0,0 +1,175 @@
+use crate::decode::{Profile, RingProfile};
+use crate::fse::FseBackend;
+use crate::ring::{RingBox, RingType};
+use crate::types::ShortWriter;
+
+use super::constants::*;
+use super::encoder::LzfseEncoder;
+use super::frontend_ring::FrontendRing;
+
+use std::io::{self, Read};
+
+// Input ring variants share names with `Profile` variants, see `dispatch`.
+pub enum InputRing {
+    R32K(RingBox<Input32K>),
+    R64K(RingBox<Input64K>),
+    R128K(RingBox<Input128K>),
+    R256K(RingBox<Input256K>),
+    R512K(RingBox<Input>),
+}
+
+macro_rules! dispatch {
+    ($value:expr, $kind:ident, $u:ident => $body:expr) => {
+        match $value {
+            $kind::R32K($u) => $body,
+            $kind::R64K($u) => $body,
+            $kind::R128K($u) => $body,
+            $kind::R256K($u) => $body,
+            $kind::R512K($u) => $body,
+        }
+    };
+}
+
+impl InputRing {
+    pub fn new(profile: RingProfile) -> Self {
+        match profile.0 {
+            Profile::R32K => Self::R32K(RingBox::default()),
+            Profile::R64K => Self::R64K(RingBox::default()),
+            Profile::R128K => Self::R128K(RingBox::default()),
+            Profile::R256K => Self::R256K(RingBox::default()),
+            Profile::R512K => Self::R512K(RingBox::default()),
+        }
+    }
+
+    /// Default profile ring over `vec`.
+    pub fn from_vec(vec: Vec<u8>) -> io::Result<Self> {
+        RingBox::from_vec(vec).map(Self::R512K)
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        dispatch!(self, InputRing, u => u.into_vec())
+    }
+
+    pub fn profile(&self) -> RingProfile {
+        RingProfile(match self {
+            Self::R32K(_) => Profile::R32K,
+            Self::R64K(_) => Profile::R64K,
+            Self::R128K(_) => Profile::R128K,
+            Self::R256K(_) => Profile::R256K,
+            Self::R512K(_) => Profile::R512K,
+        })
+    }
+
+    // Input ring allocation size in bytes.
+    pub fn ring_capacity(profile: RingProfile) -> u32 {
+        match profile.0 {
+            Profile::R32K => Input32K::RING_CAPACITY,
+            Profile::R64K => Input64K::RING_CAPACITY,
+            Profile::R128K => Input128K::RING_CAPACITY,
+            Profile::R256K => Input256K::RING_CAPACITY,
+            Profile::R512K => Input::RING_CAPACITY,
+        }
+    }
+
+    pub fn warm(&mut self) {
+        dispatch!(self, InputRing, u => u.warm())
+    }
+}
+
+impl Default for InputRing {
+    fn default() -> Self {
+        Self::new(RingProfile::default())
+    }
+}
+
+pub enum FrontendKind<'a> {
+    R32K(FrontendRing<'a, Input32K>),
+    R64K(FrontendRing<'a, Input64K>),
+    R128K(FrontendRing<'a, Input128K>),
+    R256K(FrontendRing<'a, Input256K>),
+    R512K(FrontendRing<'a, Input>),
+}
+
+impl<'a> FrontendKind<'a> {
+    /// Initialized frontend over `input` configured as per `core`, along with `core`'s backend.
+    pub fn new(input: &'a mut InputRing, core: &'a mut LzfseEncoder) -> (Self, &'a mut FseBackend) {
+        let table = &mut core.table;
+        let mut frontend = match input {
+            InputRing::R32K(u) => Self::R32K(FrontendRing::new(u.into(), table)),
+            InputRing::R64K(u) => Self::R64K(FrontendRing::new(u.into(), table)),
+            InputRing::R128K(u) => Self::R128K(FrontendRing::new(u.into(), table)),
+            InputRing::R256K(u) => Self::R256K(FrontendRing::new(u.into(), table)),
+            InputRing::R512K(u) => Self::R512K(FrontendRing::new(u.into(), table)),
+        };
+        dispatch!(&mut frontend, FrontendKind, u => {
+            u.set_block_strategy(core.strategy);
+            u.set_match_strategy(core.match_strategy);
+            u.set_sparse_scan(core.sparse_scan);
+            u.set_rle_bias(core.rle_bias);
+            u.set_good_match_len(core.good_match_len);
+            u.set_search_depth(core.search_depth);
+            u.set_block_boundaries(&core.boundaries);
+            u.init();
+        });
+        (frontend, &mut core.backend)
+    }
+
+    /// See [FrontendRing::copy].
+    pub fn copy<I, O>(
+        &mut self,
+        backend: &mut FseBackend,
+        dst: &mut O,
+        src: &mut I,
+    ) -> io::Result<u64>
+    where
+        I: Read,
+        O: ShortWriter,
+    {
+        dispatch!(self, FrontendKind, u => u.copy(backend, dst, src))
+    }
+
+    /// See [FrontendRing::copy_with].
+    pub fn copy_with<I, O, F>(
+        &mut self,
+        backend: &mut FseBackend,
+        dst: &mut O,
+        src: &mut I,
+        f: F,
+    ) -> io::Result<u64>
+    where
+        I: Read,
+        O: ShortWriter,
+        F: FnMut(u64, &O),
+    {
+        dispatch!(self, FrontendKind, u => u.copy_with(backend, dst, src, f))
+    }
+
+    /// See [FrontendRing::write].
+    pub fn write<O>(
+        &mut self,
+        backend: &mut FseBackend,
+        src: &[u8],
+        dst: &mut O,
+    ) -> io::Result<usize>
+    where
+        O: ShortWriter,
+    {
+        dispatch!(self, FrontendKind, u => u.write(backend, src, dst))
+    }
+
+    /// See [FrontendRing::flush_blocks].
+    pub fn flush_blocks<O>(&mut self, dst: &mut O) -> io::Result<()>
+    where
+        O: ShortWriter,
+    {
+        dispatch!(self, FrontendKind, u => u.flush_blocks(dst))
+    }
+
+    /// See [FrontendRing::flush].
+    pub fn flush<O>(&mut self, backend: &mut FseBackend, dst: &mut O) -> io::Result<()>
+    where
+        O: ShortWriter,
+    {
+        dispatch!(self, FrontendKind, u => u.flush(backend, dst))
+    }
+}
@@ -1,18 +1,21 @@
 use crate::base::MagicBytes;
-use crate::fse::{Fse, FseBackend};
+use crate::error::Error;
+use crate::fse::FseBackend;
 use crate::kit::ReadExtFully;
-use crate::lmd::DMax;
 use crate::lmd::MatchDistance;
 use crate::raw::{self, RAW_HEADER_SIZE};
 use crate::ring::{self, Ring, RingBlock, RingType};
 use crate::types::{Idx, ShortWriter};
-use crate::vn::{Vn, VnBackend};
+use crate::vn::VnBackend;
 
 use super::backend::Backend;
 use super::backend_type::BackendType;
+use super::block_strategy::BlockStrategy;
+use super::boundaries::Boundaries;
 use super::constants::*;
-use super::history::{History, HistoryTable, Item};
+use super::history::{History, HistoryTable, Item, HASH_WIDTH, MAX_SEARCH_DEPTH};
 use super::match_object::Match;
+use super::match_strategy::MatchStrategy;
 use super::match_unit::MatchUnit;
 
 use std::io::{self, Read};
@@ -39,6 +42,16 @@ pub struct FrontendRing<'a, T> {
     mark: Idx,
     clamp: Idx,
     n_raw_bytes: u64,
+    strategy: BlockStrategy,
+    match_strategy: MatchStrategy,
+    sparse_scan: bool,
+    rle_bias: bool,
+    good_match_len: u32,
+    search_depth: usize,
+    deep_depth: usize,
+    boundaries: Boundaries<'a>,
+    error: Option<io::Error>,
+    is_poisoned: bool,
 }
 
 // Implementation notes:
@@ -109,14 +122,16 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
     // Non flush max match len that doesn't overshoot our tail.
     const LONG_MATCH_LEN: u32 = T::RING_SIZE / 2 - T::RING_BLK_SIZE - OVERMATCH_SLACK;
 
+    // Max match distance within our ring, reduced rings clamp the backend max match distance.
+    const MAX_RING_DISTANCE: u32 = T::RING_SIZE / 2 - 1;
+
     pub fn new(ring: Ring<'a, T>, table: &'a mut HistoryTable) -> Self {
         assert!(T::RING_BLK_SIZE.is_power_of_two());
-        assert!(Vn::MAX_MATCH_DISTANCE < T::RING_SIZE / 2);
-        assert!(Fse::MAX_MATCH_DISTANCE < T::RING_SIZE / 2);
         assert!(T::RING_SIZE <= Q1 / 4);
         assert!(0x100 < T::RING_BLK_SIZE as usize);
         assert!(T::RING_BLK_SIZE <= T::RING_SIZE / 4);
         assert!(OVERMATCH_SLACK < T::RING_LIMIT);
+        table.set_search_depth(HASH_WIDTH);
         let zero = Idx::default();
         Self {
             table,
@@ -130,9 +145,58 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
             mark: zero,
             clamp: zero,
             n_raw_bytes: 0,
+            strategy: BlockStrategy::default(),
+            match_strategy: MatchStrategy::default(),
+            sparse_scan: false,
+            rle_bias: false,
+            good_match_len: GOOD_MATCH_LEN,
+            search_depth: HASH_WIDTH,
+            deep_depth: 0,
+            boundaries: Boundaries::default(),
+            error: None,
+            is_poisoned: false,
         }
     }
 
+    #[inline(always)]
+    pub fn set_block_strategy(&mut self, strategy: BlockStrategy) {
+        self.strategy = strategy;
+    }
+
+    #[inline(always)]
+    pub fn set_match_strategy(&mut self, strategy: MatchStrategy) {
+        self.match_strategy = strategy;
+    }
+
+    #[inline(always)]
+    pub fn set_sparse_scan(&mut self, sparse_scan: bool) {
+        self.sparse_scan = sparse_scan;
+    }
+
+    #[inline(always)]
+    pub fn set_rle_bias(&mut self, rle_bias: bool) {
+        self.rle_bias = rle_bias;
+    }
+
+    #[inline(always)]
+    pub fn set_good_match_len(&mut self, good_match_len: u32) {
+        self.good_match_len = good_match_len;
+    }
+
+    #[inline(always)]
+    pub fn set_search_depth(&mut self, search_depth: usize) {
+        debug_assert!((1..=MAX_SEARCH_DEPTH).contains(&search_depth));
+        self.search_depth = search_depth.min(HASH_WIDTH);
+        self.deep_depth = search_depth - self.search_depth;
+        self.table.set_search_depth(search_depth);
+    }
+
+    /// Call before init.
+    #[inline(always)]
+    pub fn set_block_boundaries(&mut self, offsets: &'a [u64]) {
+        self.boundaries = Boundaries::new(offsets);
+    }
+
     /// Call after init, otherwise behavior is undefined.
     #[inline(always)]
     pub fn copy<B, I, O>(&mut self, backend: &mut B, dst: &mut O, src: &mut I) -> io::Result<u64>
@@ -150,6 +214,30 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
         Ok(self.n_raw_bytes)
     }
 
+    /// As `copy`, invoking `f` with the number of bytes read and `dst` after each block.
+    pub fn copy_with<B, I, O, F>(
+        &mut self,
+        backend: &mut B,
+        dst: &mut O,
+        src: &mut I,
+        mut f: F,
+    ) -> io::Result<u64>
+    where
+        B: Backend,
+        I: Read,
+        O: ShortWriter,
+        F: FnMut(u64, &O),
+    {
+        loop {
+            if !self.copy_block(src)? {
+                break;
+            }
+            self.match_block(backend, dst)?;
+            f(self.n_raw_bytes, dst);
+        }
+        Ok(self.n_raw_bytes)
+    }
+
     #[inline(always)]
     fn copy_block<I: Read>(&mut self, src: &mut I) -> io::Result<bool> {
         debug_assert!(self.validate_global());
@@ -173,16 +261,53 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
     where
         O: ShortWriter,
     {
+        self.check_state()?;
         let total_len = src.len();
         loop {
             if !self.write_block(&mut src) {
                 break;
             }
-            self.match_block(backend, dst)?;
+            if let Err(err) = self.match_block(backend, dst) {
+                // Bytes copied into the ring are accepted, the error is deferred to the next call.
+                self.is_poisoned = true;
+                let n = total_len - src.len();
+                if n == 0 {
+                    return Err(err);
+                }
+                self.error = Some(err);
+                return Ok(n);
+            }
         }
         Ok(total_len)
     }
 
+    /// Hard flush `dst`, passing completed blocks to its inner writer. The in-progress block,
+    /// staged in the input ring and the backend, is retained.
+    pub fn flush_blocks<O>(&mut self, dst: &mut O) -> io::Result<()>
+    where
+        O: ShortWriter,
+    {
+        self.check_state()?;
+        if let Err(err) = dst.flush(true) {
+            // The inner writer may have accepted a partial write, we cannot safely retry.
+            self.is_poisoned = true;
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    // Once `write` fails, or defers an error, our state is undefined and we reject further calls.
+    #[inline(always)]
+    fn check_state(&mut self) -> io::Result<()> {
+        if let Some(err) = self.error.take() {
+            Err(err)
+        } else if self.is_poisoned {
+            Err(Error::BadWriterState.into())
+        } else {
+            Ok(())
+        }
+    }
+
     #[inline(always)]
     fn write_block(&mut self, src: &mut &[u8]) -> bool {
         debug_assert!(self.validate_global());
@@ -238,10 +363,13 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
         B: Backend,
         O: ShortWriter,
     {
-        debug_assert!(self.commit == Commit::None || self.commit == commit);
         if self.commit == Commit::None {
             self.commit = commit;
             backend.init(dst, len)?;
+        } else if self.commit != commit {
+            // Mixed Fse/ Vn history, only reachable by continuing to use the frontend after a
+            // flush without an intervening init.
+            return Err(Error::BadWriterState.into());
         }
         Ok(())
     }
@@ -276,6 +404,7 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
     where
         O: ShortWriter,
     {
+        self.check_state()?;
         self.validate_global();
         self.manage_ring_zones();
         // Select.
@@ -284,11 +413,14 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
                 self.finalize(backend, dst)?;
             }
             Commit::Vn => {
-                panic!("internal error: invalid commit state: {:?}", self.commit);
+                // Vn blocks are committed and finalized within a single flush, we are being
+                // flushed twice without an intervening init.
+                return Err(Error::BadWriterState.into());
             }
             Commit::None => self.flush_select(backend, dst)?,
         };
         debug_assert!(self.is_done());
+        self.boundaries.finalize()?;
         // Eos.
         dst.write_short_u32(MagicBytes::Eos.into())?;
         Ok(())
@@ -300,10 +432,17 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
     {
         debug_assert!(self.is_uncommitted());
         let len = (self.tail - self.idx) as u32;
-        if len > VN_CUTOFF {
+        if self.boundaries.is_active() {
+            // Only Fse blocks may be closed at caller specified boundaries.
+            self.commit(backend, dst, Commit::Fse, None)?;
+            self.flush_backend(backend, dst)
+        } else if len > VN_CUTOFF {
+            if self.strategy == BlockStrategy::Compact && len <= COMPACT_LIMIT {
+                return self.flush_compact(backend, dst);
+            }
             self.commit(backend, dst, Commit::Fse, None)?;
             self.flush_backend(backend, dst)
-        } else if len > RAW_CUTOFF {
+        } else if len > RAW_CUTOFF || self.strategy == BlockStrategy::NeverRaw {
             self.commit(backend, dst, Commit::Vn, Some(len as usize))?;
             self.flush_backend(&mut VnBackend::default(), dst)
         } else {
@@ -311,6 +450,41 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
         }
     }
 
+    // Trial Vn and Fse, retaining the smallest.
+    fn flush_compact<O>(&mut self, backend: &mut FseBackend, dst: &mut O) -> io::Result<()>
+    where
+        O: ShortWriter,
+    {
+        let len = (self.tail - self.idx) as usize;
+        let mark = dst.pos();
+        self.commit(backend, dst, Commit::Vn, Some(len))?;
+        self.flush_backend(&mut VnBackend::default(), dst)?;
+        let vn_len = (dst.pos() - mark) as u32;
+        if !dst.truncate(mark) {
+            return Ok(());
+        }
+        self.uncommit();
+        self.commit(backend, dst, Commit::Fse, None)?;
+        self.flush_backend(backend, dst)?;
+        let fse_len = (dst.pos() - mark) as u32;
+        if vn_len < fse_len && dst.truncate(mark) {
+            self.uncommit();
+            self.commit(backend, dst, Commit::Vn, Some(len))?;
+            self.flush_backend(&mut VnBackend::default(), dst)?;
+        }
+        Ok(())
+    }
+
+    // Rewind a flush of uncommitted data.
+    fn uncommit(&mut self) {
+        self.table.reset();
+        self.commit = Commit::None;
+        self.pending = Match::default();
+        self.literal_idx = self.head;
+        self.idx = self.head;
+        debug_assert!(self.is_uncommitted());
+    }
+
     fn flush_backend<B, O>(&mut self, backend: &mut B, dst: &mut O) -> io::Result<()>
     where
         B: Backend,
@@ -320,7 +494,10 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
         let mark = dst.pos();
         backend.init(dst, Some(src_len))?;
         self.finalize(backend, dst)?;
-        if self.commit == Commit::Vn && src_len < RAW_LIMIT as usize {
+        if self.commit == Commit::Vn
+            && src_len < RAW_LIMIT as usize
+            && self.strategy != BlockStrategy::NeverRaw
+        {
             let dst_len = (dst.pos() - mark) as usize;
             if src_len + RAW_HEADER_SIZE as usize <= dst_len && dst.truncate(mark) {
                 // The compressed length is NOT shorter than raw block length AND we have a
@@ -370,9 +547,12 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
             let u_idx = Item::new(u, idx);
             let queue = self.table.push::<B::Type>(u_idx);
             let incoming = self.find_match::<B::Type, false>(queue, u_idx, Self::LONG_MATCH_LEN);
-            if let Some(select) = self.pending.select::<GOOD_MATCH_LEN>(incoming) {
+            if let Some(select) = self.pending.select(incoming, self.good_match_len) {
                 unsafe { self.push_match(backend, dst, select)? };
                 idx += 1;
+                if self.sparse_scan {
+                    idx = self.sparse_skip(select, idx);
+                }
                 for _ in 0..(self.literal_idx - idx) {
                     let u = self.get_u32::<B::Type>(idx);
                     let u_idx = Item::new(u, idx);
@@ -417,7 +597,7 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
             let queue = self.table.push::<B::Type>(u_idx);
             let max = (self.tail - idx) as u32;
             let incoming = self.find_match::<B::Type, true>(queue, u_idx, max);
-            if let Some(select) = self.pending.select::<GOOD_MATCH_LEN>(incoming) {
+            if let Some(select) = self.pending.select(incoming, self.good_match_len) {
                 unsafe { self.push_match(backend, dst, select)? };
                 if self.literal_idx >= self.idx {
                     // Unlikely.
@@ -426,6 +606,9 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
                     break;
                 }
                 idx += 1;
+                if self.sparse_scan {
+                    idx = self.sparse_skip(select, idx);
+                }
                 for _ in 0..(self.literal_idx - idx) {
                     let u = self.get_u32::<B::Type>(idx);
                     let u_idx = Item::new(u, idx);
@@ -449,25 +632,59 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
         Ok(())
     }
 
+    // Zero run matches, distance <= 4 with a zero source u32, skip history synchronization over
+    // all but the run tail.
+    #[inline(always)]
+    #[must_use]
+    fn sparse_skip(&self, m: Match, idx: Idx) -> Idx {
+        if m.match_len >= SPARSE_RUN_LEN
+            && (m.idx - m.match_idx) as u32 <= 4
+            && self.ring.get_u32(m.match_idx) == 0
+        {
+            let tail_idx = self.literal_idx - SPARSE_TAIL_LEN;
+            if idx < tail_idx {
+                tail_idx
+            } else {
+                idx
+            }
+        } else {
+            idx
+        }
+    }
+
     #[inline(always)]
     fn find_match<B, const F: bool>(&self, queue: History, item: Item, max: u32) -> Match
+    where
+        B: BackendType,
+    {
+        self.find_match_any::<B>(queue, item, max, F)
+    }
+
+    // `size-opt` shares a single body between the long and short (final) match loops, otherwise
+    // `f` is constant propagated into each.
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline(never))]
+    fn find_match_any<B>(&self, queue: History, item: Item, max: u32, f: bool) -> Match
     where
         B: BackendType,
     {
         debug_assert!(B::MATCH_UNIT <= max);
-        debug_assert!(item.idx + max <= self.tail - if F { 0 } else { OVERMATCH_SLACK });
+        debug_assert!(item.idx + max <= self.tail - if f { 0 } else { OVERMATCH_SLACK });
+        // Distances are non-decreasing, we break on the first distant item.
+        debug_assert!(queue.is_ordered(item.idx));
+        let max_distance = B::MAX_MATCH_DISTANCE
+            .min(self.match_strategy.max_match_distance())
+            .min(Self::MAX_RING_DISTANCE);
         let mut m = Match::default();
-        for &match_idx_val in queue.iter() {
-            let distance = (item.idx - match_idx_val.idx) as u32;
-            debug_assert!(distance < Q3);
-            if distance > B::MAX_MATCH_DISTANCE {
-                break;
-            }
-            let match_len_inc = self.match_unit_coarse::<B>(item, match_idx_val, max);
-            if match_len_inc > m.match_len {
-                m.match_len = match_len_inc;
-                m.match_idx = match_idx_val.idx;
-            }
+        if self.scan_queue::<B>(&queue[..self.search_depth], item, max, max_distance, &mut m)
+            && self.deep_depth != 0
+        {
+            // Unlikely.
+            let queue = &self.table.deep_queue()[..self.deep_depth];
+            self.scan_queue::<B>(queue, item, max, max_distance, &mut m);
+        }
+        if self.rle_bias {
+            self.rle_match::<B>(item, max, &mut m);
         }
         if m.match_len == 0 {
             // Likely.
@@ -476,7 +693,7 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
             // Unlikely.
             let literal_len = (item.idx - self.literal_idx) as u32;
             m.idx = item.idx;
-            if F {
+            if f {
                 m.match_len = m.match_len.min(max);
             }
             let max = ((m.match_idx - self.head) as u32).min(literal_len);
@@ -484,12 +701,59 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
             m.idx -= match_len_dec;
             m.match_idx -= match_len_dec;
             m.match_len += match_len_dec;
+            m.match_len = m.match_len.min(self.match_strategy.max_match_len());
             debug_assert!(self.validate_match::<B>(m));
             m
         }
     }
 
+    // Returns false if a distant item was encountered, older items being no nearer.
     #[inline(always)]
+    fn scan_queue<B>(
+        &self,
+        queue: &[Item],
+        item: Item,
+        max: u32,
+        max_distance: u32,
+        m: &mut Match,
+    ) -> bool
+    where
+        B: BackendType,
+    {
+        for &match_idx_val in queue.iter() {
+            let distance = (item.idx - match_idx_val.idx) as u32;
+            debug_assert!(distance < Q3);
+            if distance > max_distance {
+                return false;
+            }
+            let match_len_inc = self.match_unit_coarse::<B>(item, match_idx_val, max);
+            if match_len_inc > m.match_len {
+                m.match_len = match_len_inc;
+                m.match_idx = match_idx_val.idx;
+            }
+        }
+        true
+    }
+
+    // Within byte runs candidates are compared by the run length they cover, ties resolved in favour
+    // of distance 1. Run heads are left as literals, the run tail is then matched at distance 1.
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline)]
+    fn rle_match<B: BackendType>(&self, item: Item, max: u32, m: &mut Match) {
+        if item.val.rotate_left(8) != item.val || item.idx <= self.head {
+            return;
+        }
+        if self.ring.get_u32(item.idx - 1) != item.val {
+            m.match_len = 0;
+            return;
+        }
+        let match_item = Item::new(item.val, item.idx - 1);
+        m.match_len = self.match_unit_coarse::<B>(item, match_item, max);
+        m.match_idx = match_item.idx;
+    }
+
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline)]
     fn match_unit_coarse<M: MatchUnit>(&self, item: Item, match_item: Item, max: u32) -> u32 {
         debug_assert!(self.validate_match_items::<M>(item, match_item));
         let len = M::match_us((item.val, match_item.val));
@@ -500,7 +764,8 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
         }
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline)]
     fn match_dec_coarse<M: MatchUnit>(&self, idx: Idx, match_idx: Idx, literal_len: u32) -> u32 {
         debug_assert!(self.validate_match_idxs::<M>(idx, match_idx));
         self.ring.match_dec_coarse::<0>((idx, match_idx), literal_len as usize) as u32
@@ -529,6 +794,10 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
         debug_assert!(self.validate_match::<B::Type>(m));
         let match_len = m.match_len;
         let match_distance = MatchDistance::new_unchecked((m.idx - m.match_idx) as u32);
+        if self.boundaries.is_active() {
+            let literal_len = (m.idx - self.literal_idx) as u32;
+            return self.push_bounded(backend, dst, literal_len, match_len, match_distance);
+        }
         let literals = self.ring.view(self.literal_idx, m.idx);
         self.literal_idx = m.idx + m.match_len;
         backend.push_match(dst, literals, match_len, match_distance)
@@ -556,11 +825,55 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
         debug_assert_ne!(len, 0);
         debug_assert_eq!(self.pending.match_len, 0);
         debug_assert!(self.literal_idx + len <= self.tail);
+        if self.boundaries.is_active() {
+            let match_distance = unsafe { MatchDistance::new_unchecked(1) };
+            return self.push_bounded(backend, dst, len, 0, match_distance);
+        }
         let literals = self.ring.view(self.literal_idx, self.literal_idx + len);
         self.literal_idx += len;
         backend.push_literals(dst, literals)
     }
 
+    // Push literals and match, splitting them and closing blocks at caller specified boundaries.
+    #[cold]
+    fn push_bounded<B: Backend, O: ShortWriter>(
+        &mut self,
+        backend: &mut B,
+        dst: &mut O,
+        mut literal_len: u32,
+        mut match_len: u32,
+        match_distance: MatchDistance<B::Type>,
+    ) -> io::Result<()> {
+        loop {
+            let limit = self.boundaries.limit(backend, dst)?;
+            let len = literal_len + match_len;
+            if len as u64 <= limit {
+                let literals = self.ring.view(self.literal_idx, self.literal_idx + literal_len);
+                self.literal_idx += len;
+                self.boundaries.advance(len as u64);
+                return if match_len == 0 {
+                    backend.push_literals(dst, literals)
+                } else {
+                    backend.push_match(dst, literals, match_len, match_distance)
+                };
+            }
+            let limit = limit as u32;
+            if limit <= literal_len {
+                let literals = self.ring.view(self.literal_idx, self.literal_idx + limit);
+                self.literal_idx += limit;
+                literal_len -= limit;
+                backend.push_literals(dst, literals)?;
+            } else {
+                let literals = self.ring.view(self.literal_idx, self.literal_idx + literal_len);
+                self.literal_idx += limit;
+                match_len -= limit - literal_len;
+                backend.push_match(dst, literals, limit - literal_len, match_distance)?;
+                literal_len = 0;
+            }
+            self.boundaries.advance(limit as u64);
+        }
+    }
+
     pub fn init(&mut self) {
         self.table.reset();
         self.commit = Commit::None;
@@ -571,6 +884,10 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
         self.tail = Idx::Q0;
         self.mark = Idx::Q0 + T::RING_BLK_SIZE;
         self.clamp = Idx::Q0 + Q1;
+        self.n_raw_bytes = 0;
+        self.boundaries.reset();
+        self.error = None;
+        self.is_poisoned = false;
         debug_assert!(self.is_init());
     }
 
@@ -665,6 +982,7 @@ impl<'a, T: Copy + RingBlock> FrontendRing<'a, T> {
             && self.literal_idx <= m.idx
             && m.match_idx < m.idx
             && (m.idx - m.match_idx) as u32 <= B::MAX_MATCH_DISTANCE
+            && (m.idx - m.match_idx) as u32 <= Self::MAX_RING_DISTANCE
             && m.idx + m.match_len <= self.tail
     }
 
@@ -695,6 +1013,7 @@ mod tests {
     use crate::ring::RingBox;
     use crate::ring::RingSize;
 
+    use super::super::boundary;
     use super::super::dummy::{Dummy, DummyBackend};
     use super::*;
 
@@ -738,6 +1057,16 @@ mod tests {
             clamp: zero,
             commit: Commit::None,
             n_raw_bytes: 0,
+            strategy: BlockStrategy::default(),
+            match_strategy: MatchStrategy::default(),
+            sparse_scan: false,
+            rle_bias: false,
+            good_match_len: GOOD_MATCH_LEN,
+            search_depth: HASH_WIDTH,
+            deep_depth: 0,
+            boundaries: Boundaries::default(),
+            error: None,
+            is_poisoned: false,
         }
     }
 
@@ -753,6 +1082,48 @@ mod tests {
         Ok(dst)
     }
 
+    // Copy from a reader yielding at most `chunk` bytes per read.
+    fn compress_chunked(src: &[u8], chunk: usize) -> io::Result<Vec<u8>> {
+        let mut ring_box = RingBox::<Input>::default();
+        let mut table = HistoryTable::default();
+        let mut frontend = build((&mut ring_box).into(), &mut table);
+        let mut backend = FseBackend::default();
+        let mut dst = Vec::with_capacity(32 + src.len() * 2);
+        frontend.init();
+        frontend.copy(&mut backend, &mut dst, &mut ChunkReader(src, chunk))?;
+        frontend.flush(&mut backend, &mut dst)?;
+        Ok(dst)
+    }
+
+    // Write in `chunk` byte slices.
+    fn compress_write(src: &[u8], chunk: usize) -> io::Result<Vec<u8>> {
+        let mut ring_box = RingBox::<Input>::default();
+        let mut table = HistoryTable::default();
+        let mut frontend = build((&mut ring_box).into(), &mut table);
+        let mut backend = FseBackend::default();
+        let mut dst = Vec::with_capacity(32 + src.len() * 2);
+        frontend.init();
+        for bytes in src.chunks(chunk) {
+            let mut bytes = bytes;
+            while !bytes.is_empty() {
+                let n = frontend.write(&mut backend, bytes, &mut dst)?;
+                bytes = &bytes[n..];
+            }
+        }
+        frontend.flush(&mut backend, &mut dst)?;
+        Ok(dst)
+    }
+
+    struct ChunkReader<'a>(&'a [u8], usize);
+
+    impl<'a> Read for ChunkReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.1);
+            let n = (&mut self.0).take(n as u64).read(buf)?;
+            Ok(n)
+        }
+    }
+
     fn check_output(src: &[u8], expected: &[u8]) -> io::Result<()> {
         let dst = compress(src)?;
         assert_eq!(dst, expected);
@@ -843,6 +1214,43 @@ mod tests {
         )
     }
 
+    // Inputs either side of, and exactly at, the cutoff and ring boundaries.
+    #[test]
+    fn boundary_matrix() -> io::Result<()> {
+        for n in boundary::sizes() {
+            for src in boundary::data(n) {
+                let enc = compress(&src)?;
+                let mut dec = Vec::default();
+                crate::decode_bytes(&enc, &mut dec)?;
+                assert!(dec == src, "n: {}", n);
+                for &chunk in &[Input::RING_BLK_SIZE as usize - 1, Input::RING_BLK_SIZE as usize] {
+                    assert!(compress_chunked(&src, chunk)? == enc, "n: {}", n);
+                    let enc = compress_write(&src, chunk)?;
+                    dec.clear();
+                    crate::decode_bytes(&enc, &mut dec)?;
+                    assert!(dec == src, "n: {}", n);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Block types either side of, and exactly at, the raw and VN cutoffs.
+    #[test]
+    fn boundary_magic() -> io::Result<()> {
+        for &(n, magic) in &[
+            (RAW_CUTOFF - 1, MagicBytes::Raw),
+            (RAW_CUTOFF, MagicBytes::Raw),
+            (RAW_CUTOFF + 1, MagicBytes::Vxn),
+            (VN_CUTOFF - 1, MagicBytes::Vxn),
+            (VN_CUTOFF, MagicBytes::Vxn),
+            (VN_CUTOFF + 1, MagicBytes::Vx2),
+        ] {
+            check_magic(&vec![0u8; n as usize], magic)?;
+        }
+        Ok(())
+    }
+
     // Random non-compressible data (<= VN_CUTOFF) should fall back to a Raw block (more efficient).
     #[test]
     fn rand_vn_cutoff() -> io::Result<()> {
@@ -990,4 +1398,53 @@ mod tests {
         }
         Ok(())
     }
+
+    fn check_bad_writer_state(result: io::Result<()>) {
+        match result.unwrap_err().into_inner().map(|u| u.downcast::<Error>()) {
+            Some(Ok(err)) => assert!(matches!(*err, Error::BadWriterState)),
+            _ => panic!(),
+        }
+    }
+
+    // Vn flush followed by a second flush without init.
+    #[test]
+    fn commit_vn_flush() -> io::Result<()> {
+        let mut ring_box = RingBox::<Input>::default();
+        let mut table = HistoryTable::default();
+        let mut frontend = build((&mut ring_box).into(), &mut table);
+        let mut backend = FseBackend::default();
+        let mut dst = Vec::default();
+        frontend.init();
+        frontend.copy(&mut backend, &mut dst, &mut [0u8; 0x0100].as_ref())?;
+        frontend.flush(&mut backend, &mut dst)?;
+        assert_eq!(frontend.commit, Commit::Vn);
+        check_bad_writer_state(frontend.flush(&mut backend, &mut dst));
+        // Init restores a valid state.
+        frontend.init();
+        frontend.copy(&mut backend, &mut dst, &mut [0u8; 0x0100].as_ref())?;
+        frontend.flush(&mut backend, &mut dst)?;
+        Ok(())
+    }
+
+    // Vn flush followed by an Fse commit without init.
+    #[test]
+    fn commit_vn_fse() -> io::Result<()> {
+        let mut ring_box = RingBox::<Input>::default();
+        let mut table = HistoryTable::default();
+        let mut frontend = build((&mut ring_box).into(), &mut table);
+        let mut backend = FseBackend::default();
+        let mut dst = Vec::default();
+        frontend.init();
+        frontend.copy(&mut backend, &mut dst, &mut [0u8; 0x0100].as_ref())?;
+        frontend.flush(&mut backend, &mut dst)?;
+        assert_eq!(frontend.commit, Commit::Vn);
+        let src = vec![0u8; Input::RING_SIZE as usize * 2];
+        // The error may be deferred to the next call.
+        let result = match frontend.write(&mut backend, &src, &mut dst) {
+            Ok(_) => frontend.write(&mut backend, &src, &mut dst),
+            err => err,
+        };
+        check_bad_writer_state(result.map(|_| ()));
+        Ok(())
+    }
 }
@@ -0,0 +1,135 @@
+use crate::fse::Fse;
+use crate::lmd::MatchDistance;
+use crate::types::{ShortBuffer, ShortWriter};
+
+use super::backend::Backend;
+use super::encoder::LzfseEncoder;
+
+use std::io;
+
+/// Number of [match_distance_histogram] buckets.
+///
+/// Bucket `i` counts match distances in `2^i..2^(i + 1)`, the LZFSE maximum match distance of
+/// 262139 bytes falls within the final bucket.
+pub const N_BUCKETS: usize = 18;
+
+// Sample window length, 4x the LZFSE maximum match distance.
+const WINDOW_LEN: usize = 0x0010_0000;
+
+/// Scan `src` with the LZFSE match finder returning a logarithmic match distance histogram, see
+/// [N_BUCKETS].
+///
+/// This is a convenience method that constructs a temporary [LzfseEncoder] instance and then calls
+/// [match_distance_histogram](LzfseEncoder::match_distance_histogram).
+///
+/// # Errors
+///
+/// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` buffer overflow.
+pub fn match_distance_histogram(src: &[u8], sample: usize) -> io::Result<[u64; N_BUCKETS]> {
+    LzfseEncoder::default().match_distance_histogram(src, sample)
+}
+
+// Evenly spaced, non overlapping windows totalling at most `sample` bytes. Inputs no longer than
+// `sample` are returned whole.
+pub(super) fn windows(src: &[u8], sample: usize) -> impl Iterator<Item = &[u8]> {
+    let len = src.len();
+    let (window_len, n) = if len <= sample {
+        (len, 1)
+    } else if sample == 0 {
+        (0, 0)
+    } else {
+        let window_len = sample.min(WINDOW_LEN);
+        (window_len, sample / window_len)
+    };
+    let span = (len - window_len) as u64;
+    (0..n).map(move |i| {
+        let off = if n == 1 { span / 2 } else { span * i as u64 / (n - 1) as u64 } as usize;
+        &src[off..off + window_len]
+    })
+}
+
+// Match distance only backend, literals are discarded and nothing is written to `dst`.
+#[derive(Debug, Default)]
+pub(super) struct HistogramBackend {
+    buckets: [u64; N_BUCKETS],
+}
+
+impl HistogramBackend {
+    #[inline(always)]
+    pub fn buckets(&self) -> [u64; N_BUCKETS] {
+        self.buckets
+    }
+}
+
+impl Backend for HistogramBackend {
+    type Type = Fse;
+
+    // Buckets accumulate across invocations.
+    #[inline(always)]
+    fn init<O: ShortWriter>(&mut self, _: &mut O, _: Option<usize>) -> io::Result<()> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn push_literals<I: ShortBuffer, O: ShortWriter>(&mut self, _: &mut O, _: I) -> io::Result<()> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn push_match<I: ShortBuffer, O: ShortWriter>(
+        &mut self,
+        _: &mut O,
+        _: I,
+        match_len: u32,
+        match_distance: MatchDistance<Self::Type>,
+    ) -> io::Result<()> {
+        if match_len != 0 {
+            let distance = match_distance.get();
+            self.buckets[(31 - distance.leading_zeros()) as usize] += 1;
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn finalize<O: ShortWriter>(&mut self, _: &mut O) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offsets(src: &[u8], sample: usize) -> Vec<(usize, usize)> {
+        windows(src, sample)
+            .map(|u| (u.as_ptr() as usize - src.as_ptr() as usize, u.len()))
+            .collect()
+    }
+
+    #[test]
+    fn windows_whole() {
+        let src = vec![0u8; 0x1000];
+        assert_eq!(offsets(&src, 0x1000), vec![(0, 0x1000)]);
+        assert_eq!(offsets(&src, usize::MAX), vec![(0, 0x1000)]);
+        assert_eq!(offsets(&[], 0), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn windows_none() {
+        assert!(offsets(&[0u8; 0x1000], 0).is_empty());
+    }
+
+    #[test]
+    fn windows_single() {
+        assert_eq!(offsets(&[0u8; 0x1000], 0x0100), vec![(0x0780, 0x0100)]);
+    }
+
+    #[test]
+    fn windows_spread() {
+        let src = vec![0u8; WINDOW_LEN * 8];
+        assert_eq!(
+            offsets(&src, WINDOW_LEN * 3 + 1),
+            vec![(0, WINDOW_LEN), (WINDOW_LEN * 7 / 2, WINDOW_LEN), (WINDOW_LEN * 7, WINDOW_LEN)]
+        );
+    }
+}
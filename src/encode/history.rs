@@ -2,6 +2,8 @@ use crate::encode::constants::{Q1, Q3};
 use crate::encode::MatchUnit;
 use crate::types::Idx;
 
+use std::fmt;
+use std::mem;
 use std::ops::Deref;
 
 #[cfg(test)]
@@ -12,27 +14,72 @@ pub const HASH_BITS: u32 = 14;
 // Aligned/ power of two values. Minimum 4.
 pub const HASH_WIDTH: usize = 4;
 
-pub struct HistoryTable(Box<[History]>, #[cfg(test)] Ward);
+// Search depths beyond `HASH_WIDTH` are served by extension levels, see `Deep`.
+pub const MAX_SEARCH_DEPTH: usize = HASH_WIDTH * 4;
+
+const DEEP_WIDTH: usize = MAX_SEARCH_DEPTH - HASH_WIDTH;
+
+/// LZ match history table.
+///
+/// The table is the largest single allocation held by an encoder. It may be transferred between
+/// [LzfseRingEncoder](crate::LzfseRingEncoder) instances, see
+/// [with_history_table](crate::LzfseRingEncoder::with_history_table). The table is reset at the
+/// start of each encoding operation, prior contents do not affect the encoded output.
+///
+/// Search depths beyond the default allocate additional history levels on demand, see
+/// [LzfseEncoderBuilder::search_depth](crate::LzfseEncoderBuilder::search_depth).
+pub struct HistoryTable(Box<[History]>, u32, Deep, #[cfg(test)] Ward);
 
 impl HistoryTable {
     const SIZE: usize = 1 << HASH_BITS;
 
+    pub(crate) const HEAP_SIZE: usize = Self::SIZE * mem::size_of::<History>();
+
     /// Push a new history item.
     ///
     /// Items must be pushed in strict sequential order and must not wrap around.
     #[inline(always)]
-    pub fn push<M: MatchUnit>(&mut self, item: Item) -> History {
+    pub(crate) fn push<M: MatchUnit>(&mut self, item: Item) -> History {
         #[cfg(test)]
-        debug_assert!(self.1.push(item));
-        let queue = self.get_mut::<M>(item.val);
+        debug_assert!(self.3.push(item));
+        let index = index::<M>(item.val ^ self.1);
+        let queue = unsafe { self.0.get_unchecked_mut(index) };
         let copy = *queue;
         queue.push(item);
+        if self.2.n_levels != 0 {
+            // Unlikely.
+            self.2.push(index, copy[HASH_WIDTH - 1]);
+        }
         copy
     }
 
+    /// Extension level items, older than those of the [History] returned by the preceding
+    /// [push](Self::push), ordered new to old. Empty unless the search depth exceeds `HASH_WIDTH`.
     #[inline(always)]
+    pub(crate) fn deep_queue(&self) -> &[Item] {
+        &self.2.queue[..self.2.n_levels * HASH_WIDTH]
+    }
+
+    /// Set the search depth, allocating extension levels as required. Call before reset.
+    pub(crate) fn set_search_depth(&mut self, search_depth: usize) {
+        debug_assert!((1..=MAX_SEARCH_DEPTH).contains(&search_depth));
+        self.2.set_n_levels((search_depth - 1) / HASH_WIDTH);
+    }
+
+    #[cfg(test)]
     fn get_mut<M: MatchUnit>(&mut self, val: u32) -> &mut History {
-        unsafe { self.0.get_unchecked_mut(index::<M>(val)) }
+        unsafe { self.0.get_unchecked_mut(index::<M>(val ^ self.1)) }
+    }
+
+    /// Hash seed, see [LzfseEncoder::set_hash_seed](crate::LzfseEncoder::set_hash_seed).
+    #[inline(always)]
+    pub(crate) fn hash_seed(&self) -> u32 {
+        self.1
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_hash_seed(&mut self, seed: u32) {
+        self.1 = seed;
     }
 
     /// Clamp all history `idx` values to a maximum of `idx - Q1` with respect to the specified
@@ -44,10 +91,11 @@ impl HistoryTable {
     /// Allows us to push a maximum of 0x8000_0000 items, with sequential `idx` values, without
     /// additional clamping.
     #[cold]
-    pub fn clamp(&mut self, idx: Idx) {
+    pub(crate) fn clamp(&mut self, idx: Idx) {
         #[cfg(test)]
-        debug_assert!(self.1.clamp(idx));
+        debug_assert!(self.3.clamp(idx));
         self.0.iter_mut().for_each(|u| u.clamp_rebias(idx, 0));
+        self.2.levels_mut().iter_mut().for_each(|u| u.clamp_rebias(idx, 0));
     }
 
     /// Clamp all history `idx` values to a maximum of `idx - Q1` with respect to the specified
@@ -59,34 +107,45 @@ impl HistoryTable {
     /// Allows us to push a maximum of 0x8000_0000 items, with sequential `idx - delta ` values,
     /// without additional clamping.
     #[cold]
-    pub fn clamp_rebias(&mut self, idx: Idx, delta: u32) {
+    pub(crate) fn clamp_rebias(&mut self, idx: Idx, delta: u32) {
         #[cfg(test)]
-        debug_assert!(self.1.clamp_rebias(idx, delta));
+        debug_assert!(self.3.clamp_rebias(idx, delta));
         self.0.iter_mut().for_each(|u| u.clamp_rebias(idx, delta));
+        self.2.levels_mut().iter_mut().for_each(|u| u.clamp_rebias(idx, delta));
     }
 
     /// All history `idx` values are set to `Idx::Q3`.
     ///
     /// Allows us to push a maximum of 0x8000_0000 items, with sequential `idx` values starting from
     /// `Idx::Q0`, without additional clamping.
-    pub fn reset(&mut self) {
+    pub(crate) fn reset(&mut self) {
         self.reset_with_idx(Idx::Q0)
     }
 
     #[cold]
-    pub fn reset_with_idx(&mut self, idx: Idx) {
-        self.0.iter_mut().for_each(|u| *u = History::new(Item::new(0, idx - Q1)));
+    pub(crate) fn reset_with_idx(&mut self, idx: Idx) {
+        let history = History::new(Item::new(0, idx - Q1));
+        self.0.iter_mut().for_each(|u| *u = history);
+        self.2.levels_mut().iter_mut().for_each(|u| *u = history);
         #[cfg(test)]
         {
-            self.1 = Ward::new(idx);
+            self.3 = Ward::new(idx);
         }
     }
 }
 
+impl fmt::Debug for HistoryTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HistoryTable").finish()
+    }
+}
+
 impl Default for HistoryTable {
     fn default() -> Self {
         Self(
             vec![History::default(); Self::SIZE].into_boxed_slice(),
+            0,
+            Deep::default(),
             #[cfg(test)]
             Ward::default(),
         )
@@ -117,8 +176,10 @@ impl History {
         self.0[0] = item;
     }
 
+    // Clamped items are equalized at distance Q1, so ordering is preserved.
     #[inline(always)]
     fn clamp_rebias(&mut self, idx: Idx, delta: u32) {
+        debug_assert!(self.is_ordered(idx));
         for item in self.0.iter_mut() {
             debug_assert!(!is_wrapping(idx, item.idx));
             if (idx - item.idx) as u32 > Q1 {
@@ -127,6 +188,14 @@ impl History {
                 item.idx -= delta;
             }
         }
+        debug_assert!(self.is_ordered(idx - delta));
+    }
+
+    /// Returns `true` if item distances with respect to `idx` are non-decreasing, that is items are
+    /// ordered new to old. Match finders rely on this to break on the first distant item.
+    #[inline(always)]
+    pub fn is_ordered(&self, idx: Idx) -> bool {
+        self.0.windows(2).all(|u| (idx - u[0].idx) as u32 <= (idx - u[1].idx) as u32)
     }
 }
 
@@ -153,6 +222,45 @@ impl Item {
     }
 }
 
+/// History extension levels.
+///
+/// Level `n` holds the items evicted from level `n - 1`, the primary table being level zero. Levels
+/// are only pushed when enabled, default search depths incur no cost beyond a branch.
+#[derive(Default)]
+struct Deep {
+    levels: Vec<History>,
+    n_levels: usize,
+    queue: [Item; DEEP_WIDTH],
+}
+
+impl Deep {
+    fn set_n_levels(&mut self, n_levels: usize) {
+        debug_assert!(n_levels * HASH_WIDTH <= DEEP_WIDTH);
+        let len = n_levels * HistoryTable::SIZE;
+        if self.levels.len() < len {
+            self.levels.resize(len, History::default());
+        }
+        self.n_levels = n_levels;
+    }
+
+    #[inline(always)]
+    fn levels_mut(&mut self) -> &mut [History] {
+        &mut self.levels[..self.n_levels * HistoryTable::SIZE]
+    }
+
+    // Cascade `item`, evicted from `index` in the primary table, through the levels recording the
+    // prior level contents into `queue`.
+    fn push(&mut self, index: usize, mut item: Item) {
+        for level in 0..self.n_levels {
+            let history = &mut self.levels[level * HistoryTable::SIZE + index];
+            self.queue[level * HASH_WIDTH..][..HASH_WIDTH].copy_from_slice(&history.0);
+            let evicted = history.0[HASH_WIDTH - 1];
+            history.push(item);
+            item = evicted;
+        }
+    }
+}
+
 /// Ward. Test/ debug assistant.
 #[cfg(test)]
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
@@ -179,14 +287,9 @@ impl Ward {
             return false;
         }
         if let Some(val) = self.opt_val {
-            #[cfg(target_endian = "little")]
             if (val >> 8) != item.val & 0x00FF_FFFF {
                 return false;
             }
-            #[cfg(target_endian = "big")]
-            if (val << 8) != item.val & 0xFFFF_FF00 {
-                return false;
-            }
         }
         self.opt_val = Some(item.val);
         self.idx += 1;
@@ -245,7 +348,7 @@ mod tests {
         for val in 0..Q2 {
             // Bypass Ward protection as item values are not sequential.
             table.get_mut::<Dummy>(val).push(Item::new(val, val.into()));
-            table.1.idx += 1;
+            table.3.idx += 1;
         }
         table.clamp(Idx::Q2);
         for history in table.0.iter() {
@@ -268,7 +371,7 @@ mod tests {
         for val in 0..Q2 {
             // Bypass Ward protection as item values are not sequential.
             table.get_mut::<Dummy>(val).push(Item::new(val, val.into()));
-            table.1.idx += 1;
+            table.3.idx += 1;
         }
         table.clamp_rebias(Idx::Q2, Q1);
         for history in table.0.iter() {
@@ -283,6 +386,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn history_clamp_rebias_ordered() {
+        let step = Q1 / 4;
+        for &delta in &[0, step, Q1] {
+            for n in 0..HASH_WIDTH as u32 * 2 {
+                for k in 1..5 {
+                    let mut history = History::new(Item::new(0, Idx::Q0));
+                    for i in 1..=n {
+                        history.push(Item::new(i, Idx::Q0 + i * step));
+                    }
+                    let idx = Idx::Q0 + (n + k) * step;
+                    assert!(history.is_ordered(idx));
+                    history.clamp_rebias(idx, delta);
+                    let idx = idx - delta;
+                    assert!(history.is_ordered(idx));
+                    assert!(history.iter().all(|u| (idx - u.idx) as u32 <= Q1));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn history_is_ordered() {
+        let history = History([
+            Item::new(0, Idx::Q0 + 3),
+            Item::new(0, Idx::Q0 + 2),
+            Item::new(0, Idx::Q0 + 2),
+            Item::new(0, Idx::Q0),
+        ]);
+        assert!(history.is_ordered(Idx::Q0 + 4));
+        let history = History([
+            Item::new(0, Idx::Q0 + 2),
+            Item::new(0, Idx::Q0 + 3),
+            Item::new(0, Idx::Q0 + 1),
+            Item::new(0, Idx::Q0),
+        ]);
+        assert!(!history.is_ordered(Idx::Q0 + 4));
+    }
+
     #[test]
     fn history_clamp_rebias_q0_0() {
         let mut history = History::default();
@@ -9,11 +9,11 @@ pub struct Match {
 
 impl Match {
     #[inline(always)]
-    pub fn select<const T: u32>(&mut self, incoming: Match) -> Option<Match> {
+    pub fn select(&mut self, incoming: Match, good_match_len: u32) -> Option<Match> {
         let select;
         if incoming.match_len == 0 {
             select = None;
-        } else if incoming.match_len >= T {
+        } else if incoming.match_len >= good_match_len {
             select = Some(incoming);
             self.match_len = 0;
         } else if self.match_len == 0 {
@@ -0,0 +1,44 @@
+use super::constants::{NEAR_MATCH_DISTANCE, NEAR_MATCH_LEN};
+
+/// LZFSE encoder match strategy.
+///
+/// Selects the trade off between compression ratio and decoding work that the match finder
+/// targets.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum MatchStrategy {
+    /// Matches are selected for compression ratio.
+    Auto,
+    /// Matches are restricted to recent history, 64KiB, with lengths capped at 256 bytes.
+    ///
+    /// Intended for assets decoded on memory bandwidth limited devices. Match sources remain
+    /// cache local to the decoded output, trading compression ratio for decoding throughput.
+    DecodeOptimized,
+}
+
+impl MatchStrategy {
+    /// Maximum match distance.
+    #[inline(always)]
+    pub(crate) fn max_match_distance(self) -> u32 {
+        match self {
+            Self::Auto => u32::MAX,
+            Self::DecodeOptimized => NEAR_MATCH_DISTANCE,
+        }
+    }
+
+    /// Maximum match length.
+    #[inline(always)]
+    pub(crate) fn max_match_len(self) -> u32 {
+        match self {
+            Self::Auto => u32::MAX,
+            Self::DecodeOptimized => NEAR_MATCH_LEN,
+        }
+    }
+}
+
+impl Default for MatchStrategy {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::Auto
+    }
+}
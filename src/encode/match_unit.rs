@@ -8,7 +8,7 @@ pub unsafe trait MatchUnit {
     /// Minimum match length, range 1..=4
     const MATCH_UNIT: u32;
 
-    /// Native endian `MATCH_UNIT `bit mask
+    /// Little endian `MATCH_UNIT `bit mask
     const MATCH_MASK: u32;
 
     fn hash_u(u: u32) -> u32;
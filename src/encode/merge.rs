@@ -0,0 +1,97 @@
+use crate::decode::LzfseDecoder;
+
+use super::block_strategy::BlockStrategy;
+use super::encoder::LzfseEncoder;
+
+use std::io;
+
+/// Merge the LZFSE streams `a` and `b` into a single re-encoded stream appended to `dst`,
+/// returning a tuple (u, v) where u is the number of decoded bytes and v is the number of bytes
+/// written into `dst`.
+///
+/// Both streams are decoded into a single internal buffer which is then encoded in one pass, so
+/// matches spanning the `a`/`b` boundary are found. The merged stream is typically, although not
+/// necessarily, smaller than the combined inputs. Useful for compaction jobs.
+///
+/// # Errors
+///
+/// * [Error](crate::Error) detailing the nature of any `a` or `b` decoding errors, in which case
+///   `dst` is unmodified.
+/// * [Error::Io](crate::Error::Io) of kind [ErrorKind::OutOfMemory](std::io::ErrorKind) if the
+///   internal buffer or `dst` fails to allocate sufficient memory, in which case `dst` may
+///   contain partially encoded data.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut a = Vec::default();
+///     let mut b = Vec::default();
+///     lzfse_rust::encode_bytes(b"test", &mut a)?;
+///     lzfse_rust::encode_bytes(b"test", &mut b)?;
+///     let mut enc = Vec::default();
+///     let (u, v) = lzfse_rust::merge_reencode(&a, &b, &mut enc)?;
+///     assert_eq!(u, 8);
+///     assert!(v <= (a.len() + b.len()) as u64);
+///     let mut dec = Vec::default();
+///     lzfse_rust::decode_bytes(&enc, &mut dec)?;
+///     assert_eq!(dec, b"testtest");
+///     Ok(())
+/// }
+/// ```
+pub fn merge_reencode(a: &[u8], b: &[u8], dst: &mut Vec<u8>) -> crate::Result<(u64, u64)> {
+    let mut decoder = LzfseDecoder::default();
+    let mut buf = Vec::default();
+    decoder.decode_bytes(a, &mut buf)?;
+    decoder.decode_bytes(b, &mut buf)?;
+    let n_bytes = LzfseEncoder::default().encode_bytes(&buf, dst)?;
+    Ok((buf.len() as u64, n_bytes))
+}
+
+/// Repack the LZFSE stream `src` into `dst` returning the number of bytes written into `dst`.
+///
+/// `src` is decoded and re-encoded at maximum effort, with [BlockStrategy::Compact]. Should the
+/// re-encoded stream fail to shrink `src`, then `src` is copied verbatim. The output is therefore
+/// never larger than `src` and always decodes to the same content. Useful for squeezing out
+/// padding or the slack left by fast encoders.
+///
+/// # Errors
+///
+/// * [Error](crate::Error) detailing the nature of any `src` decoding errors, in which case
+///   `dst` is unmodified.
+/// * [Error::Io](crate::Error::Io) of kind [ErrorKind::OutOfMemory](std::io::ErrorKind) if the
+///   internal buffers or `dst` fail to allocate sufficient memory, in which case `dst` is
+///   unmodified.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     lzfse_rust::encode_bytes(b"test test test test test", &mut enc)?;
+///     let mut rep = Vec::default();
+///     let n = lzfse_rust::repack(&enc, &mut rep)?;
+///     assert_eq!(n, rep.len() as u64);
+///     assert!(rep.len() <= enc.len());
+///     let mut dec = Vec::default();
+///     lzfse_rust::decode_bytes(&rep, &mut dec)?;
+///     assert_eq!(dec, b"test test test test test");
+///     Ok(())
+/// }
+/// ```
+pub fn repack(src: &[u8], dst: &mut Vec<u8>) -> crate::Result<u64> {
+    let mut buf = Vec::default();
+    LzfseDecoder::default().decode_bytes(src, &mut buf)?;
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_block_strategy(BlockStrategy::Compact);
+    let mut enc = Vec::default();
+    encoder.encode_bytes(&buf, &mut enc)?;
+    let best = if enc.len() < src.len() { enc.as_slice() } else { src };
+    dst.try_reserve(best.len()).map_err(|_| io::Error::from(io::ErrorKind::OutOfMemory))?;
+    dst.extend_from_slice(best);
+    Ok(best.len() as u64)
+}
@@ -1,24 +1,66 @@
+#[cfg(feature = "tokio")]
+mod async_writer;
 mod backend;
 mod backend_type;
+mod block_strategy;
+mod boundaries;
+mod budget;
+mod builder;
 mod constants;
+#[cfg(feature = "digest")]
+mod digest;
 mod encoder;
+mod entropy;
+mod frame_sink;
 mod frontend_bytes;
+mod frontend_kind;
 mod frontend_ring;
+mod histogram;
 mod history;
 mod match_object;
+mod match_strategy;
 mod match_unit;
+mod merge;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod replay;
 mod ring_encoder;
+mod segmented;
+mod sink;
+mod stats;
+mod stream;
 mod writer;
 mod writer_bytes;
 
+#[cfg(test)]
+mod boundary;
 #[cfg(test)]
 mod dummy;
 
+#[cfg(feature = "tokio")]
+pub use async_writer::AsyncLzfseWriter;
 pub use backend::Backend;
 pub use backend_type::BackendType;
+pub use block_strategy::BlockStrategy;
+pub use builder::LzfseEncoderBuilder;
+#[cfg(feature = "digest")]
+pub use digest::encode_with_digest;
 pub use encoder::LzfseEncoder;
+pub use entropy::{entropy_floor, lz_entropy_floor};
+pub use frame_sink::FrameSink;
+pub use histogram::{match_distance_histogram, N_BUCKETS};
+pub use history::HistoryTable;
+pub use match_strategy::MatchStrategy;
 pub use match_unit::MatchUnit;
+pub use merge::{merge_reencode, repack};
+#[cfg(feature = "rayon")]
+pub use parallel::encode_bytes_parallel;
+pub use replay::{ReplayEncoder, ReplayPoint};
 pub use ring_encoder::LzfseRingEncoder;
+pub use segmented::{encode_segmented, SegmentEntry};
+pub use sink::LzfseSink;
+pub use stats::{BlockStats, EncodeStats};
+pub use stream::compress_reader_to_writer;
 pub use writer::LzfseWriter;
 pub use writer_bytes::LzfseWriterBytes;
 
@@ -33,12 +75,8 @@ use std::io;
 /// # Errors
 ///
 /// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` or `dst` buffer overflow.
-///
-/// # Aborts
-///
-/// With limited system memory [Vec] may abort when attempting to allocate sufficient memory.
-/// This issue will be resolved in future releases when [try_reserve()](Vec::try_reserve) is
-/// stabilized.
+/// * [ErrorKind::OutOfMemory](std::io::ErrorKind) if `dst` fails to allocate sufficient memory,
+///   in which case `dst` contains partially encoded data.
 ///
 /// # Examples
 ///
@@ -58,3 +96,61 @@ use std::io;
 pub fn encode_bytes(src: &[u8], dst: &mut Vec<u8>) -> io::Result<u64> {
     LzfseEncoder::default().encode_bytes(src, dst)
 }
+
+/// Encode `src` into `dst` with an upper `max_bytes` output budget, returning the number of bytes
+/// written into `dst`.
+///
+/// This is a convenience method that constructs a temporary [LzfseEncoder] instance and then calls
+/// [encode_within](LzfseEncoder::encode_within). For multiple invocations, creating and reusing a
+/// [LzfseEncoder] instance is more efficient.
+///
+/// # Errors
+///
+/// * [Error::BufferOverflow](crate::Error::BufferOverflow) if the encoded output exceeds
+///   `max_bytes`, in which case `dst` is restored to its original length.
+/// * [Error](crate::Error) detailing the nature of any other errors.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     let n_bytes = lzfse_rust::encode_within(b"test", &mut enc, 1024)?;
+///     assert_eq!(n_bytes, 16);
+///     Ok(())
+/// }
+/// ```
+pub fn encode_within(src: &[u8], dst: &mut Vec<u8>, max_bytes: usize) -> crate::Result<usize> {
+    LzfseEncoder::default().encode_within(src, dst, max_bytes)
+}
+
+/// Encode `src` as independent `block_size` byte chunks returning the raw/ encoded size ratio of
+/// each chunk in order.
+///
+/// This is a convenience method that constructs a temporary [LzfseEncoder] instance and then calls
+/// [block_ratios](LzfseEncoder::block_ratios).
+///
+/// # Errors
+///
+/// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` buffer overflow.
+///
+/// # Panics
+///
+/// Panics if `block_size` is zero.
+pub fn block_ratios(src: &[u8], block_size: usize) -> io::Result<Vec<f64>> {
+    LzfseEncoder::default().block_ratios(src, block_size)
+}
+
+/// Encode the UTF-8 string `src` into `dst` returning the number of bytes written into `dst`.
+///
+/// This is a convenience method that constructs a temporary [LzfseEncoder] instance and then calls
+/// [encode_bytes](LzfseEncoder::encode_bytes). Use [decode_string](crate::decode_string) to decode.
+///
+/// # Errors
+///
+/// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` or `dst` buffer overflow.
+pub fn encode_str(src: &str, dst: &mut Vec<u8>) -> io::Result<u64> {
+    LzfseEncoder::default().encode_bytes(src.as_bytes(), dst)
+}
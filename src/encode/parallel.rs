@@ -0,0 +1,143 @@
+use crate::base::MagicBytes;
+
+use super::encoder::LzfseEncoder;
+
+use rayon::prelude::*;
+
+use std::io;
+
+/// Encode `src` into `dst` in parallel returning the number of bytes written into `dst`.
+///
+/// `src` is split into consecutive `segment_size` byte segments, the last segment may be shorter,
+/// that are encoded independently using the current [rayon](https://docs.rs/rayon) thread pool.
+/// The encoded segments are then joined into a single LZFSE stream that is decodable by any LZFSE
+/// decoder.
+///
+/// Matches cannot span segment boundaries, smaller segments trade compression ratio for
+/// parallelism.
+///
+/// Output is deterministic. Segment boundaries are fixed by `segment_size` alone and not by
+/// thread scheduling, as such for a given `src` and `segment_size` the output is byte identical
+/// regardless of the thread pool size. Output is suitable for caches that key on the output hash.
+///
+/// Segments are joined into a single stream rather than concatenated as independent frames, there
+/// is no per frame overhead and decoders need not support multi-frame streams.
+///
+/// The thread count is that of the current thread pool. To fix the thread count, call from within
+/// a dedicated [ThreadPool](rayon::ThreadPool), see the examples below.
+///
+/// Requires the `rayon` feature.
+///
+/// # Panics
+///
+/// Panics if `segment_size` is zero.
+///
+/// # Errors
+///
+/// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` or `dst` buffer overflow.
+/// * [ErrorKind::OutOfMemory](std::io::ErrorKind) if a segment buffer or `dst` fails to allocate
+///   sufficient memory, in which case `dst` is unmodified.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let src = vec![0u8; 0x0010_0000];
+///     let mut enc = Vec::default();
+///     lzfse_rust::encode_bytes_parallel(&src, &mut enc, 0x0004_0000)?;
+///     let mut dec = Vec::default();
+///     lzfse_rust::decode_bytes(&enc, &mut dec)?;
+///     assert!(dec == src);
+///     Ok(())
+/// }
+/// ```
+///
+/// Encoding with a fixed thread count.
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let src = vec![0u8; 0x0010_0000];
+///     let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+///     let mut enc = Vec::default();
+///     pool.install(|| lzfse_rust::encode_bytes_parallel(&src, &mut enc, 0x0004_0000))?;
+///     let mut dec = Vec::default();
+///     lzfse_rust::decode_bytes(&enc, &mut dec)?;
+///     assert!(dec == src);
+///     Ok(())
+/// }
+/// ```
+pub fn encode_bytes_parallel(
+    src: &[u8],
+    dst: &mut Vec<u8>,
+    segment_size: usize,
+) -> io::Result<u64> {
+    assert!(segment_size != 0);
+    if src.is_empty() {
+        return LzfseEncoder::default().encode_bytes(src, dst);
+    }
+    let segments = src
+        .par_chunks(segment_size)
+        .map_init(LzfseEncoder::default, |encoder, segment| {
+            let mut enc = Vec::default();
+            encoder.encode_bytes(segment, &mut enc)?;
+            strip_eos(&mut enc);
+            Ok(enc)
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    let mark = dst.len();
+    dst.try_reserve(segments.iter().map(Vec::len).sum::<usize>() + 4)
+        .map_err(|_| io::Error::from(io::ErrorKind::OutOfMemory))?;
+    segments.iter().for_each(|segment| dst.extend_from_slice(segment));
+    dst.extend_from_slice(&u32::from(MagicBytes::Eos).to_le_bytes());
+    Ok((dst.len() - mark) as u64)
+}
+
+fn strip_eos(enc: &mut Vec<u8>) {
+    let n = enc.len() - 4;
+    debug_assert_eq!(enc[n..], u32::from(MagicBytes::Eos).to_le_bytes());
+    enc.truncate(n);
+}
+
+#[cfg(test)]
+mod tests {
+    use test_kit::{Rng, Seq};
+
+    use super::*;
+
+    fn encode_threads(src: &[u8], segment_size: usize, n_threads: usize) -> io::Result<Vec<u8>> {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(n_threads).build().unwrap();
+        let mut enc = Vec::default();
+        pool.install(|| encode_bytes_parallel(src, &mut enc, segment_size))?;
+        Ok(enc)
+    }
+
+    #[test]
+    fn deterministic() -> io::Result<()> {
+        let src = Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), 0x0010_0123)
+            .collect::<Vec<_>>();
+        for &segment_size in &[0x0000_1000, 0x0001_0000, 0x0004_0000, 0x0100_0000] {
+            let enc = encode_threads(&src, segment_size, 1)?;
+            for &n_threads in &[2, 8] {
+                assert!(enc == encode_threads(&src, segment_size, n_threads)?);
+            }
+            let mut dec = Vec::default();
+            crate::decode_bytes(&enc, &mut dec)?;
+            assert!(dec == src);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn empty() -> io::Result<()> {
+        let mut enc = Vec::default();
+        encode_bytes_parallel(&[], &mut enc, 0x1000)?;
+        let mut dec = Vec::default();
+        crate::encode_bytes(&[], &mut dec)?;
+        assert_eq!(enc, dec);
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use super::ring_encoder::LzfseRingEncoder;
+
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+/// [ReplayEncoder] output position.
+///
+/// A replay point consists of the source start position and the number of encoded bytes accepted
+/// by the writer. It captures no encoder state. Both values are exposed so that replay points may
+/// be persisted and later recreated using [new](Self::new), for example across client restarts.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReplayPoint {
+    src_pos: u64,
+    n_payload_bytes: u64,
+}
+
+impl ReplayPoint {
+    /// Create a new replay point from the source start position `src_pos` and the number of encoded
+    /// bytes `n_payload_bytes` already accepted by the writer.
+    #[inline(always)]
+    pub fn new(src_pos: u64, n_payload_bytes: u64) -> Self {
+        Self { src_pos, n_payload_bytes }
+    }
+
+    /// The source start position.
+    #[inline(always)]
+    pub fn src_pos(&self) -> u64 {
+        self.src_pos
+    }
+
+    /// The number of encoded bytes accepted by the writer.
+    #[inline(always)]
+    pub fn n_payload_bytes(&self) -> u64 {
+        self.n_payload_bytes
+    }
+}
+
+/// LZFSE replay encoder.
+///
+/// Encodes a [Read] + [Seek] source into a writer, tracking the number of encoded bytes accepted
+/// by the writer. Should the writer fail, for example an interrupted upload, the current
+/// [replay point](Self::replay_point) may be used to [resume](Self::resume) output, in this or a
+/// later process, producing the identical remaining encoded stream.
+///
+/// Output is resumed by replaying the encoding, not by restoring encoder state. Encoding is
+/// deterministic: the source is rewound to the replay point start position and encoded again in
+/// full, with encoded bytes preceding the replay point discarded. As such replay points are tiny,
+/// although each resumption costs a full re-encode of the source up to the replay point.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::ReplayEncoder;
+/// use std::io::{self, Cursor, Write};
+///
+/// // Upload that fails once `limit` bytes are accepted.
+/// struct Upload {
+///     buf: Vec<u8>,
+///     limit: usize,
+/// }
+///
+/// impl Write for Upload {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         let n = buf.len().min(self.limit - self.buf.len());
+///         if n == 0 {
+///             return Err(io::ErrorKind::ConnectionReset.into());
+///         }
+///         self.buf.extend_from_slice(&buf[..n]);
+///         Ok(n)
+///     }
+///
+///     fn flush(&mut self) -> io::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+///     let mut encoder = ReplayEncoder::new(Cursor::new(&src))?;
+///     let mut upload = Upload { buf: Vec::default(), limit: 0x20 };
+///     assert!(encoder.encode(&mut upload).is_err());
+///     // The replay point records the bytes accepted prior to the failure.
+///     let point = encoder.replay_point();
+///     assert_eq!(point.n_payload_bytes(), 0x20);
+///     // Resume, in this or a later process, writing only the remaining bytes.
+///     let mut resumed = ReplayEncoder::resume(Cursor::new(&src), point);
+///     upload.limit = usize::MAX;
+///     let n = resumed.encode(&mut upload)?;
+///     assert_eq!(point.n_payload_bytes() + n, upload.buf.len() as u64);
+///     let mut dec = Vec::default();
+///     lzfse_rust::decode_bytes(&upload.buf, &mut dec)?;
+///     assert_eq!(dec, src);
+///     Ok(())
+/// }
+/// ```
+pub struct ReplayEncoder<R> {
+    encoder: LzfseRingEncoder,
+    inner: R,
+    point: ReplayPoint,
+}
+
+impl<R: Read + Seek> ReplayEncoder<R> {
+    /// Create a new instance encoding `inner` from its current position.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) in case of `inner` seek errors.
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let src_pos = inner.stream_position()?;
+        Ok(Self::resume(inner, ReplayPoint::new(src_pos, 0)))
+    }
+
+    /// Create a new instance resuming output from a prior replay `point`, replaying the encoding
+    /// of `inner` from the replay point start position.
+    ///
+    /// The source contents must be unchanged, otherwise the resumed encoded stream is undefined.
+    pub fn resume(inner: R, point: ReplayPoint) -> Self {
+        Self { encoder: LzfseRingEncoder::default(), inner, point }
+    }
+
+    /// Encode the remainder of the source into `writer` returning the number of encoded bytes
+    /// written into `writer`.
+    ///
+    /// The source is encoded from the replay point start position, with encoded bytes preceding
+    /// the replay point discarded. Encoded bytes accepted by `writer` are reflected by the
+    /// [replay point](Self::replay_point), including in case of errors. Should the replay point
+    /// lie beyond the end of the encoded stream, no bytes are written.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) in case of source or `writer` IO errors.
+    pub fn encode<W: Write>(&mut self, writer: &mut W) -> io::Result<u64> {
+        self.inner.seek(SeekFrom::Start(self.point.src_pos))?;
+        let mut skip = SkipWriter {
+            inner: writer,
+            n_skip: self.point.n_payload_bytes,
+            n_bytes: 0,
+            n_written: 0,
+        };
+        let result = self.encoder.encode(&mut self.inner, &mut skip);
+        self.point.n_payload_bytes += skip.n_written;
+        result.map(|_| skip.n_written)
+    }
+
+    /// The current replay point.
+    #[inline(always)]
+    pub fn replay_point(&self) -> ReplayPoint {
+        self.point
+    }
+
+    /// Unwraps this `ReplayEncoder<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> fmt::Debug for ReplayEncoder<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplayEncoder").field("point", &self.point).finish()
+    }
+}
+
+// Discards the first `n_skip` bytes, counting bytes accepted by the inner writer.
+struct SkipWriter<'a, W> {
+    inner: &'a mut W,
+    n_skip: u64,
+    n_bytes: u64,
+    n_written: u64,
+}
+
+impl<'a, W: Write> Write for SkipWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.n_bytes < self.n_skip {
+            let n = (self.n_skip - self.n_bytes).min(buf.len() as u64) as usize;
+            self.n_bytes += n as u64;
+            return Ok(n);
+        }
+        let n = self.inner.write(buf)?;
+        self.n_bytes += n as u64;
+        self.n_written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_writer() -> io::Result<()> {
+        let mut vec = Vec::default();
+        let mut skip = SkipWriter { inner: &mut vec, n_skip: 3, n_bytes: 0, n_written: 0 };
+        skip.write_all(b"ab")?;
+        skip.write_all(b"cdef")?;
+        assert_eq!(skip.n_bytes, 6);
+        assert_eq!(skip.n_written, 3);
+        assert_eq!(vec, b"def");
+        Ok(())
+    }
+}
@@ -1,10 +1,20 @@
-use crate::fse::{V1_MAX_BLOCK_LEN, V2_MAX_BLOCK_LEN};
+use crate::decode::RingProfile;
+use crate::fse::{Fse, NormalizationStrategy, V1_MAX_BLOCK_LEN, V2_MAX_BLOCK_LEN};
+use crate::lmd::DMax;
 use crate::ops::FlushLimit;
-use crate::ring::{RingBox, RingShortWriter};
+use crate::ring::{RingBox, RingShortWriter, RingSize, RingType};
+use crate::vn::Vn;
 
+#[cfg(feature = "tokio")]
+use super::async_writer::AsyncLzfseWriter;
+use super::block_strategy::BlockStrategy;
 use super::constants::*;
 use super::encoder::LzfseEncoder;
-use super::frontend_ring::FrontendRing;
+use super::frame_sink::FrameSink;
+use super::frontend_kind::{FrontendKind, InputRing};
+use super::history::HistoryTable;
+use super::match_strategy::MatchStrategy;
+use super::stats::{EncodeStats, StatsWriter};
 use super::writer::LzfseWriter;
 use super::writer_bytes::LzfseWriterBytes;
 
@@ -19,11 +29,144 @@ use std::io::{self, Read, Write};
 /// [as_mut()](AsMut::as_mut).
 pub struct LzfseRingEncoder {
     core: LzfseEncoder,
-    input: RingBox<Input>,
+    input: InputRing,
     output: RingBox<Output>,
 }
 
 impl LzfseRingEncoder {
+    /// Required [from_buffers](Self::from_buffers) input buffer length in bytes.
+    pub const INPUT_BUFFER_LEN: usize = Input::RING_CAPACITY as usize;
+
+    /// Required [from_buffers](Self::from_buffers) output buffer length in bytes.
+    pub const OUTPUT_BUFFER_LEN: usize = Output::RING_CAPACITY as usize;
+
+    // Heap held by an instance with the specified input ring `profile`.
+    pub(crate) fn heap_size(profile: RingProfile) -> usize {
+        LzfseEncoder::HEAP_SIZE
+            + InputRing::ring_capacity(profile) as usize
+            + Self::OUTPUT_BUFFER_LEN
+    }
+
+    /// Create a new encoder instance with the specified input ring `profile`.
+    ///
+    /// Smaller profiles reduce the encoder's memory footprint, with match distances limited to the
+    /// profile's [max_match_distance()](RingProfile::max_match_distance). This typically comes at
+    /// the cost of compression ratio. The output is a valid LZFSE stream decodable by any decoder,
+    /// including a [LzfseRingDecoder](crate::LzfseRingDecoder) with the same profile.
+    ///
+    /// The output ring is fixed, it accommodates the largest LZFSE block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{LzfseRingDecoder, LzfseRingEncoder, RingProfile};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let profile = RingProfile::for_max_distance(0x4000);
+    ///     let mut encoder = LzfseRingEncoder::with_profile(profile);
+    ///     assert_eq!(encoder.profile(), profile);
+    ///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0400);
+    ///     let mut enc = Vec::default();
+    ///     encoder.encode(&mut src.as_slice(), &mut enc)?;
+    ///     let mut decoder = LzfseRingDecoder::with_profile(profile);
+    ///     let mut dec = Vec::default();
+    ///     decoder.decode(&mut enc.as_slice(), &mut dec)?;
+    ///     assert_eq!(dec, src);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_profile(profile: RingProfile) -> Self {
+        Self {
+            core: LzfseEncoder::default(),
+            input: InputRing::new(profile),
+            output: RingBox::default(),
+        }
+    }
+
+    /// Returns the encoder's input ring profile.
+    pub fn profile(&self) -> RingProfile {
+        self.input.profile()
+    }
+
+    /// Create a new instance reusing the allocations of the supplied `input` and `output` buffers,
+    /// with the default [RingProfile]. Buffer contents are zeroed. Use [into_buffers](Self::into_buffers) to recover them.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::InvalidInput](std::io::ErrorKind) if the buffer lengths do not equal
+    ///   [INPUT_BUFFER_LEN](Self::INPUT_BUFFER_LEN) and
+    ///   [OUTPUT_BUFFER_LEN](Self::OUTPUT_BUFFER_LEN) respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseRingEncoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let input = vec![0u8; LzfseRingEncoder::INPUT_BUFFER_LEN];
+    ///     let output = vec![0u8; LzfseRingEncoder::OUTPUT_BUFFER_LEN];
+    ///     let mut encoder = LzfseRingEncoder::from_buffers(input, output)?;
+    ///     let mut enc = Vec::default();
+    ///     encoder.encode_bytes(b"test", &mut enc)?;
+    ///     let (input, output) = encoder.into_buffers();
+    ///     // Recycle the buffers.
+    ///     let encoder = LzfseRingEncoder::from_buffers(input, output)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_buffers(input: Vec<u8>, output: Vec<u8>) -> io::Result<Self> {
+        Ok(Self {
+            core: LzfseEncoder::default(),
+            input: InputRing::from_vec(input)?,
+            output: RingBox::from_vec(output)?,
+        })
+    }
+
+    /// Consume the encoder returning the (input, output) buffers for reuse.
+    pub fn into_buffers(self) -> (Vec<u8>, Vec<u8>) {
+        (self.input.into_vec(), self.output.into_vec())
+    }
+
+    /// Create a new instance taking ownership of the supplied history `table`. Use
+    /// [into_history_table](Self::into_history_table) to recover it.
+    ///
+    /// Intended for pools of short lived encoders used sequentially on a single thread. A single
+    /// [HistoryTable] may be passed from one encoder to the next, amortizing the allocation. The
+    /// table is reset at the start of each encoding operation, so encoders never share state. The
+    /// [hash seed](Self::set_hash_seed) is reset to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::{HistoryTable, LzfseRingEncoder};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut table = HistoryTable::default();
+    ///     for _ in 0..4 {
+    ///         let mut encoder = LzfseRingEncoder::with_history_table(table);
+    ///         let mut enc = Vec::default();
+    ///         encoder.encode(&mut b"test".as_ref(), &mut enc)?;
+    ///         table = encoder.into_history_table();
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_history_table(table: HistoryTable) -> Self {
+        Self {
+            core: LzfseEncoder::with_history_table(table),
+            input: InputRing::default(),
+            output: RingBox::<Output>::default(),
+        }
+    }
+
+    /// Consume the encoder returning the [HistoryTable] for reuse.
+    pub fn into_history_table(self) -> HistoryTable {
+        self.core.table
+    }
+
     /// Encode `reader` into `writer` returning a tuple (u, v) where u is the number of unencoded
     /// bytes read from the reader and v is the number of encoded bytes written into the writer.
     ///
@@ -31,6 +174,8 @@ impl LzfseRingEncoder {
     /// no need to wrap them in [BufReader](std::io::BufReader) or
     /// [BufWriter](std::io::BufWriter).
     ///
+    /// Each call encodes a new independent stream, the encoder may be reused across calls without
+    /// reallocation, see [reset](Self::reset).
     ///
     /// # Errors
     ///
@@ -57,15 +202,112 @@ impl LzfseRingEncoder {
         I: Read,
         O: Write,
     {
-        let mut frontend = FrontendRing::new((&mut self.input).into(), &mut self.core.table);
-        frontend.init();
+        let (mut frontend, backend) = FrontendKind::new(&mut self.input, &mut self.core);
         let mut writer = RingShortWriter::new((&mut self.output).into(), writer);
-        let n_raw_bytes = frontend.copy(&mut self.core.backend, &mut writer, reader)?;
-        frontend.flush(&mut self.core.backend, &mut writer)?;
+        let n_raw_bytes = frontend.copy(backend, &mut writer, reader)?;
+        frontend.flush(backend, &mut writer)?;
         let (_, n_payload_bytes) = writer.into_inner()?;
         Ok((n_raw_bytes, n_payload_bytes))
     }
 
+    /// As [encode](Self::encode), additionally invoking `on_progress` with a running tuple (u, v)
+    /// where u is the number of unencoded bytes read from the reader and v is the number of
+    /// encoded bytes written into the writer.
+    ///
+    /// Intended to drive progress reporting for long running encodes. `on_progress` is invoked
+    /// after each input ring block is read and matched, with non-decreasing values, and once more
+    /// on completion with the final totals, which equal the returned tuple. Encoded output is
+    /// buffered, so v trails u. The encoded output is identical to that of
+    /// [encode](Self::encode), which remains the zero overhead path.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) in case of `reader` or `writer` IO errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseRingEncoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = vec![0u8; 0x0010_0000];
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseRingEncoder::default();
+    ///     let mut last = (0, 0);
+    ///     let totals = encoder.encode_with_progress(&mut src.as_slice(), &mut enc, |u, v| {
+    ///         last = (u, v);
+    ///     })?;
+    ///     assert_eq!(last, totals);
+    ///     assert_eq!(totals.0, 0x0010_0000);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encode_with_progress<I, O, F>(
+        &mut self,
+        reader: &mut I,
+        writer: &mut O,
+        mut on_progress: F,
+    ) -> io::Result<(u64, u64)>
+    where
+        I: Read,
+        O: Write,
+        F: FnMut(u64, u64),
+    {
+        let (mut frontend, backend) = FrontendKind::new(&mut self.input, &mut self.core);
+        let mut writer = RingShortWriter::new((&mut self.output).into(), writer);
+        let n_raw_bytes = frontend.copy_with(backend, &mut writer, reader, |u, dst| {
+            on_progress(u, dst.n_payload_bytes())
+        })?;
+        frontend.flush(backend, &mut writer)?;
+        let (_, n_payload_bytes) = writer.into_inner()?;
+        on_progress(n_raw_bytes, n_payload_bytes);
+        Ok((n_raw_bytes, n_payload_bytes))
+    }
+
+    /// As [encode](Self::encode), additionally returning [EncodeStats] detailing the emitted
+    /// blocks.
+    ///
+    /// Statistics are gathered by inspecting block headers as they are written, encoded output
+    /// is identical and the match finder is unaffected. Intended for diagnosing the compression
+    /// ratio of a given corpus.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::Other](std::io::ErrorKind) in case of `reader` or `writer` IO errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lzfse_rust::LzfseRingEncoder;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0400);
+    ///     let mut enc = Vec::default();
+    ///     let mut encoder = LzfseRingEncoder::default();
+    ///     let (n_raw_bytes, n_payload_bytes, stats) =
+    ///         encoder.encode_with_stats(&mut src.as_slice(), &mut enc)?;
+    ///     assert_eq!(stats.fse.n_blocks, 1);
+    ///     assert_eq!(stats.fse.n_raw_bytes, n_raw_bytes);
+    ///     assert_eq!(stats.fse.n_payload_bytes + 4, n_payload_bytes);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn encode_with_stats<I, O>(
+        &mut self,
+        reader: &mut I,
+        writer: &mut O,
+    ) -> io::Result<(u64, u64, EncodeStats)>
+    where
+        I: Read,
+        O: Write,
+    {
+        let mut writer = StatsWriter::new(writer);
+        let (n_raw_bytes, n_payload_bytes) = self.encode(reader, &mut writer)?;
+        Ok((n_raw_bytes, n_payload_bytes, writer.stats()))
+    }
+
     /// This method bypasses the internal ring buffers and operates over the supplied buffers,
     /// it is functionally identical to [LzfseEncoder::encode_bytes].
     pub fn encode_bytes(&mut self, src: &[u8], dst: &mut Vec<u8>) -> io::Result<u64> {
@@ -74,13 +316,14 @@ impl LzfseRingEncoder {
 
     /// Create a new [LzfseWriter] encoder instance using the supplied `inner` writer.
     ///
-    /// **It is imperative that the writer is [finalized](LzfseWriterBytes::finalize) after use to
+    /// See [LzfseWriter] for the incremental encoding contract.
+    ///
+    /// **It is imperative that the writer is [finalized](LzfseWriter::finalize) after use to
     /// complete the encoding process, [flushing](std::io::Write::flush) is not sufficient.**
     pub fn writer<O: Write>(&mut self, inner: O) -> LzfseWriter<O> {
-        let mut frontend = FrontendRing::new((&mut self.input).into(), &mut self.core.table);
-        frontend.init();
+        let (frontend, backend) = FrontendKind::new(&mut self.input, &mut self.core);
         let writer = RingShortWriter::new((&mut self.output).into(), inner);
-        LzfseWriter::new(frontend, &mut self.core.backend, writer)
+        LzfseWriter::new(frontend, backend, writer)
     }
 
     /// Create a new [LzfseWriterBytes] decoder instance using the supplied `vec`.
@@ -91,9 +334,125 @@ impl LzfseRingEncoder {
     /// **It is imperative that the writer is [finalized](LzfseWriterBytes::finalize) after use to
     /// complete the encoding process, [flushing](std::io::Write::flush) is not sufficient.**
     pub fn writer_bytes(&mut self, vec: Vec<u8>) -> LzfseWriterBytes {
-        let mut frontend = FrontendRing::new((&mut self.input).into(), &mut self.core.table);
-        frontend.init();
-        LzfseWriterBytes::new(frontend, &mut self.core.backend, vec)
+        let (frontend, backend) = FrontendKind::new(&mut self.input, &mut self.core);
+        LzfseWriterBytes::new(frontend, backend, vec)
+    }
+
+    /// Create a new [AsyncLzfseWriter] encoder instance using the supplied `inner` tokio
+    /// [AsyncWrite](tokio::io::AsyncWrite) writer.
+    ///
+    /// **It is imperative that the writer is
+    /// [shut down](tokio::io::AsyncWrite::poll_shutdown) after use to complete the encoding
+    /// process, [flushing](tokio::io::AsyncWrite::poll_flush) is not sufficient.**
+    #[cfg(feature = "tokio")]
+    pub fn async_writer<O>(&mut self, inner: O) -> AsyncLzfseWriter<'_, O>
+    where
+        O: tokio::io::AsyncWrite + Unpin,
+    {
+        let (frontend, backend) = FrontendKind::new(&mut self.input, &mut self.core);
+        AsyncLzfseWriter::new(frontend, backend, inner)
+    }
+
+    /// Set the [BlockStrategy], see [LzfseEncoder::set_block_strategy].
+    pub fn set_block_strategy(&mut self, strategy: BlockStrategy) {
+        self.core.set_block_strategy(strategy);
+    }
+
+    /// Returns the [BlockStrategy].
+    pub fn block_strategy(&self) -> BlockStrategy {
+        self.core.block_strategy()
+    }
+
+    /// Set the block boundaries, see [LzfseEncoder::set_block_boundaries].
+    pub fn set_block_boundaries(&mut self, offsets: &[u64]) -> io::Result<()> {
+        self.core.set_block_boundaries(offsets)
+    }
+
+    /// Returns the block boundaries.
+    pub fn block_boundaries(&self) -> &[u64] {
+        self.core.block_boundaries()
+    }
+
+    /// Set the FSE [NormalizationStrategy], see [LzfseEncoder::set_normalization_strategy].
+    pub fn set_normalization_strategy(&mut self, strategy: NormalizationStrategy) {
+        self.core.set_normalization_strategy(strategy);
+    }
+
+    /// Returns the FSE [NormalizationStrategy].
+    pub fn normalization_strategy(&self) -> NormalizationStrategy {
+        self.core.normalization_strategy()
+    }
+
+    /// Set the match finder hash seed, see [LzfseEncoder::set_hash_seed].
+    pub fn set_hash_seed(&mut self, seed: u32) {
+        self.core.set_hash_seed(seed);
+    }
+
+    /// Returns the match finder hash seed.
+    pub fn hash_seed(&self) -> u32 {
+        self.core.hash_seed()
+    }
+
+    /// Set the [MatchStrategy], see [LzfseEncoder::set_match_strategy].
+    pub fn set_match_strategy(&mut self, strategy: MatchStrategy) {
+        self.core.set_match_strategy(strategy);
+    }
+
+    /// Returns the [MatchStrategy].
+    pub fn match_strategy(&self) -> MatchStrategy {
+        self.core.match_strategy()
+    }
+
+    /// Enable the sparse scan fast path, see [LzfseEncoder::set_sparse_scan].
+    pub fn set_sparse_scan(&mut self, sparse_scan: bool) {
+        self.core.set_sparse_scan(sparse_scan);
+    }
+
+    /// Returns `true` if the sparse scan fast path is enabled.
+    pub fn sparse_scan(&self) -> bool {
+        self.core.sparse_scan()
+    }
+
+    /// Strengthen the distance 1 match preference, see [LzfseEncoder::set_rle_bias].
+    pub fn set_rle_bias(&mut self, rle_bias: bool) {
+        self.core.set_rle_bias(rle_bias);
+    }
+
+    /// Returns `true` if the distance 1 match preference is strengthened.
+    pub fn rle_bias(&self) -> bool {
+        self.core.rle_bias()
+    }
+
+    /// Returns the good match length, see
+    /// [LzfseEncoderBuilder::good_match_len](crate::LzfseEncoderBuilder::good_match_len).
+    pub fn good_match_len(&self) -> u32 {
+        self.core.good_match_len()
+    }
+
+    /// Returns the match search depth, see
+    /// [LzfseEncoderBuilder::search_depth](crate::LzfseEncoderBuilder::search_depth).
+    pub fn search_depth(&self) -> usize {
+        self.core.search_depth()
+    }
+
+    /// Warm the encoder's internal tables and ring buffers, see [LzfseEncoder::warm].
+    pub fn warm(&mut self) {
+        self.core.warm();
+        self.input.warm();
+        self.output.warm();
+    }
+
+    /// Reset the encoder's internal state, see [LzfseEncoder::reset].
+    ///
+    /// The ring buffers are retained, no allocations are made.
+    pub fn reset(&mut self) {
+        self.core.reset();
+    }
+
+    /// Create a new [FrameSink] length-delimited frame encoder instance using the supplied
+    /// `inner` writer.
+    pub fn frame_sink<O: Write>(&mut self, inner: O) -> FrameSink<'_, O> {
+        FrameSink::new(self, inner)
     }
 }
 
@@ -102,14 +461,24 @@ impl Default for LzfseRingEncoder {
     fn default() -> Self {
         assert!(V1_MAX_BLOCK_LEN + 64 < RingShortWriter::<(), Output>::FLUSH_LIMIT);
         assert!(V2_MAX_BLOCK_LEN + 64 < RingShortWriter::<(), Output>::FLUSH_LIMIT);
+        // The default input ring spans the maximum match distance.
+        assert!(Fse::MAX_MATCH_DISTANCE < Input::RING_SIZE / 2);
+        assert!(Vn::MAX_MATCH_DISTANCE < Input::RING_SIZE / 2);
         Self {
             core: LzfseEncoder::default(),
-            input: RingBox::<Input>::default(),
+            input: InputRing::default(),
             output: RingBox::<Output>::default(),
         }
     }
 }
 
+impl AsMut<LzfseEncoder> for LzfseRingEncoder {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut LzfseEncoder {
+        &mut self.core
+    }
+}
+
 impl fmt::Debug for LzfseRingEncoder {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("LzfseRingEncoder").finish()
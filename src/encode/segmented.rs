@@ -0,0 +1,184 @@
+use crate::fse::Fse;
+use crate::lmd::MatchDistance;
+use crate::types::{ShortBuffer, ShortWriter};
+
+use super::backend::Backend;
+use super::encoder::LzfseEncoder;
+
+use std::io;
+
+// Boundary search radius upper limit.
+const SEARCH_RADIUS: usize = 0x0001_0000;
+
+// History scanned ahead of the boundary search region.
+const LOOKBACK_LEN: usize = 0x0001_0000;
+
+/// Segmented encode index entry, see [encode_segmented].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SegmentEntry {
+    /// Offset of the segment's decoded data from the start of the input.
+    pub src_offset: u64,
+    /// Decoded segment size.
+    pub n_raw_bytes: u64,
+    /// Offset of the segment LZFSE stream within the output.
+    pub offset: u64,
+    /// Encoded segment size.
+    pub n_payload_bytes: u64,
+}
+
+/// Encode `src` into `dst` as roughly `target_segments` independent LZFSE streams, returning the
+/// segment index.
+///
+/// This is a convenience method that constructs a temporary [LzfseEncoder] instance and then calls
+/// [encode_segmented](LzfseEncoder::encode_segmented).
+///
+/// # Errors
+///
+/// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` or `dst` buffer overflow.
+///
+/// # Panics
+///
+/// Panics if `target_segments` is zero.
+pub fn encode_segmented(
+    src: &[u8],
+    target_segments: usize,
+    dst: &mut Vec<u8>,
+) -> io::Result<Vec<SegmentEntry>> {
+    LzfseEncoder::default().encode_segmented(src, target_segments, dst)
+}
+
+// Segment boundaries, excluding zero and `src.len()`, in ascending order. Each nominal, evenly
+// spaced, boundary moves to the start of the longest literal run found by the match finder within
+// the search radius, where fresh content begins and the fewest matches are severed.
+pub(super) fn boundaries(
+    encoder: &mut LzfseEncoder,
+    src: &[u8],
+    target_segments: usize,
+) -> io::Result<Vec<usize>> {
+    assert!(target_segments != 0);
+    let len = src.len();
+    let segment_len = len / target_segments;
+    let radius = (segment_len / 8).min(SEARCH_RADIUS);
+    let mut vec = Vec::with_capacity(target_segments);
+    if segment_len == 0 {
+        return Ok(vec);
+    }
+    for i in 1..target_segments {
+        let nominal = i * segment_len;
+        let lo = nominal - radius;
+        let hi = nominal + radius;
+        let base = lo.saturating_sub(LOOKBACK_LEN);
+        let mut backend = LiteralRunBackend::new(lo - base);
+        encoder.scan(&src[base..hi], &mut backend)?;
+        let boundary = match backend.longest() {
+            Some(run_idx) => base + run_idx,
+            None => nominal,
+        };
+        debug_assert!(lo <= boundary && boundary <= hi);
+        vec.push(boundary);
+    }
+    Ok(vec)
+}
+
+// Track the longest literal run starting at or beyond `min_idx`.
+struct LiteralRunBackend {
+    min_idx: usize,
+    idx: usize,
+    run_idx: usize,
+    run_len: usize,
+    best: Option<(usize, usize)>,
+}
+
+impl LiteralRunBackend {
+    fn new(min_idx: usize) -> Self {
+        Self { min_idx, idx: 0, run_idx: 0, run_len: 0, best: None }
+    }
+
+    fn longest(&mut self) -> Option<usize> {
+        self.close_run();
+        self.best.map(|u| u.0)
+    }
+
+    fn close_run(&mut self) {
+        if self.run_len != 0
+            && self.run_idx >= self.min_idx
+            && !matches!(self.best, Some(u) if u.1 >= self.run_len)
+        {
+            self.best = Some((self.run_idx, self.run_len));
+        }
+        self.run_len = 0;
+    }
+
+    fn push_literal_len(&mut self, len: usize) {
+        if self.run_len == 0 {
+            self.run_idx = self.idx;
+        }
+        self.run_len += len;
+        self.idx += len;
+    }
+}
+
+impl Backend for LiteralRunBackend {
+    type Type = Fse;
+
+    fn init<O: ShortWriter>(&mut self, _: &mut O, _: Option<usize>) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn push_literals<I: ShortBuffer, O: ShortWriter>(
+        &mut self,
+        _: &mut O,
+        literals: I,
+    ) -> io::Result<()> {
+        self.push_literal_len(literals.len());
+        Ok(())
+    }
+
+    fn push_match<I: ShortBuffer, O: ShortWriter>(
+        &mut self,
+        _: &mut O,
+        literals: I,
+        match_len: u32,
+        _: MatchDistance<Self::Type>,
+    ) -> io::Result<()> {
+        if !literals.is_empty() {
+            self.push_literal_len(literals.len());
+        }
+        self.close_run();
+        self.idx += match_len as usize;
+        Ok(())
+    }
+
+    fn finalize<O: ShortWriter>(&mut self, _: &mut O) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_run_backend() -> io::Result<()> {
+        let mut backend = LiteralRunBackend::new(8);
+        let dst = &mut Vec::default();
+        let md = MatchDistance::new(1);
+        // idx 0: 16 literals, below `min_idx`.
+        backend.push_match(dst, [0u8; 16].as_ref(), 4, md)?;
+        // idx 20: 3 literals.
+        backend.push_match(dst, [0u8; 3].as_ref(), 4, md)?;
+        // idx 27: 5 + 2 literals, coalesced.
+        backend.push_literals(dst, [0u8; 5].as_ref())?;
+        backend.push_match(dst, [0u8; 2].as_ref(), 4, md)?;
+        // idx 38: 7 literals, not longer.
+        backend.push_literals(dst, [0u8; 7].as_ref())?;
+        assert_eq!(backend.longest(), Some(27));
+        Ok(())
+    }
+
+    #[test]
+    fn literal_run_backend_none() {
+        let mut backend = LiteralRunBackend::new(0);
+        assert_eq!(backend.longest(), None);
+    }
+}
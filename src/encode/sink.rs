@@ -0,0 +1,236 @@
+use crate::bits::BitDst;
+use crate::kit::WIDE;
+use crate::ops::{
+    Allocate, CopyLong, Flush, FlushLimit, PatchInto, Pos, ShortLimit, Truncate, WriteLong,
+    WriteShort,
+};
+use crate::types::{Idx, ShortWriter};
+
+use std::io;
+use std::mem;
+use std::ptr;
+
+/// LZFSE encoded output sink.
+///
+/// A contiguous byte buffer that [LzfseEncoder::encode_to_sink](crate::LzfseEncoder::encode_to_sink)
+/// encodes directly into, for example a memory mapped region, as opposed to a [Vec]. The buffer
+/// holds the encoded bytes, [n_bytes](Self::n_bytes) in total, followed by spare bytes.
+///
+/// The encoder reserves spare bytes ahead of writing them, and may reserve several kilobytes more
+/// than it finally writes. Already written bytes are revisited, block headers are patched once the
+/// block is complete and blocks may be rolled back. Fixed size buffers should allow headroom
+/// accordingly.
+///
+/// # Safety
+///
+/// The encoder writes into the buffer via raw pointers. Implementations must ensure that:
+///
+/// * [buffer](Self::buffer) contents are preserved between calls.
+/// * Following a successful [reserve](Self::reserve) of `n` bytes, [buffer](Self::buffer) is at
+///   least [n_bytes](Self::n_bytes) + `n` bytes long. The buffer may move during calls to
+///   [reserve](Self::reserve), but never shrinks.
+/// * [n_bytes](Self::n_bytes) returns the value last passed to [set_n_bytes](Self::set_n_bytes),
+///   and is initially no greater than the buffer length.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{LzfseEncoder, LzfseSink};
+/// use std::io;
+///
+/// // Fixed size sink over a mutable slice.
+/// struct SliceSink<'a> {
+///     buf: &'a mut [u8],
+///     n: usize,
+/// }
+///
+/// unsafe impl<'a> LzfseSink for SliceSink<'a> {
+///     fn buffer(&mut self) -> &mut [u8] {
+///         self.buf
+///     }
+///
+///     fn n_bytes(&self) -> usize {
+///         self.n
+///     }
+///
+///     fn set_n_bytes(&mut self, n_bytes: usize) {
+///         self.n = n_bytes;
+///     }
+///
+///     fn reserve(&mut self, additional: usize) -> io::Result<()> {
+///         if additional <= self.buf.len() - self.n {
+///             Ok(())
+///         } else {
+///             Err(io::ErrorKind::WriteZero.into())
+///         }
+///     }
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+///     let mut encoder = LzfseEncoder::default();
+///     let mut buf = [0u8; 0x1000];
+///     let mut sink = SliceSink { buf: &mut buf, n: 0 };
+///     let n_bytes = encoder.encode_to_sink(&src, &mut sink)?;
+///     let mut dec = Vec::default();
+///     lzfse_rust::decode_bytes(&buf[..n_bytes as usize], &mut dec)?;
+///     assert_eq!(dec, src);
+///     // Overflow.
+///     let mut buf = [0u8; 0x0010];
+///     let mut sink = SliceSink { buf: &mut buf, n: 0 };
+///     let err = encoder.encode_to_sink(&src, &mut sink).unwrap_err();
+///     assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+///     assert_eq!(sink.n, 0);
+///     Ok(())
+/// }
+/// ```
+pub unsafe trait LzfseSink {
+    /// The entire buffer, encoded bytes followed by spare bytes.
+    fn buffer(&mut self) -> &mut [u8];
+
+    /// Number of encoded bytes.
+    fn n_bytes(&self) -> usize;
+
+    /// Set the number of encoded bytes, never greater than the buffer length.
+    fn set_n_bytes(&mut self, n_bytes: usize);
+
+    /// Reserve at least `additional` spare bytes.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) if the bytes cannot be reserved, for example fixed size buffer
+    ///   overflow, which terminates encoding.
+    fn reserve(&mut self, additional: usize) -> io::Result<()>;
+}
+
+/// `LzfseSink` output adapter.
+pub struct SinkWriter<'a, S: ?Sized> {
+    inner: &'a mut S,
+}
+
+impl<'a, S: LzfseSink + ?Sized> SinkWriter<'a, S> {
+    #[inline(always)]
+    pub fn new(inner: &'a mut S) -> Self {
+        Self { inner }
+    }
+
+    #[inline(always)]
+    pub fn n_bytes(&self) -> usize {
+        self.inner.n_bytes()
+    }
+
+    #[inline(always)]
+    fn index(&self, pos: Idx) -> usize {
+        let delta = self.pos() - pos;
+        (self.inner.n_bytes() as isize - delta as isize) as usize
+    }
+}
+
+impl<'a, S: LzfseSink + ?Sized> Allocate for SinkWriter<'a, S> {
+    fn allocate(&mut self, len: usize) -> io::Result<()> {
+        if !self.is_allocated(len) {
+            self.inner.reserve(len)?;
+            if !self.is_allocated(len) {
+                return Err(io::ErrorKind::Other.into());
+            }
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn is_allocated(&mut self, len: usize) -> bool {
+        let n_bytes = self.inner.n_bytes();
+        len <= self.inner.buffer().len().wrapping_sub(n_bytes)
+    }
+}
+
+impl<'a, S: LzfseSink + ?Sized> BitDst for SinkWriter<'a, S> {
+    #[inline(always)]
+    unsafe fn push_bytes_unchecked(&mut self, bytes: usize, n_bytes: usize) {
+        debug_assert!(n_bytes <= mem::size_of::<usize>());
+        assert!(self.is_allocated(mem::size_of::<usize>()));
+        let index = self.inner.n_bytes();
+        let src = bytes.to_le_bytes();
+        let dst = self.inner.buffer().as_mut_ptr().add(index);
+        ptr::copy_nonoverlapping(src.as_ptr(), dst, mem::size_of::<usize>());
+        self.inner.set_n_bytes(index + n_bytes);
+    }
+
+    #[inline(always)]
+    fn finalize(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, S: LzfseSink + ?Sized> Flush for SinkWriter<'a, S> {
+    #[inline(always)]
+    fn flush(&mut self, _: bool) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, S: LzfseSink + ?Sized> FlushLimit for SinkWriter<'a, S> {
+    const FLUSH_LIMIT: u32 = Vec::<u8>::FLUSH_LIMIT;
+}
+
+impl<'a, S: LzfseSink + ?Sized> PatchInto for SinkWriter<'a, S> {
+    #[inline(always)]
+    fn patch_into(&mut self, pos: Idx, len: usize) -> &mut [u8] {
+        let index = self.index(pos);
+        &mut self.inner.buffer()[index..index + len]
+    }
+}
+
+impl<'a, S: LzfseSink + ?Sized> Pos for SinkWriter<'a, S> {
+    #[inline(always)]
+    fn pos(&self) -> Idx {
+        (self.inner.n_bytes() as u32).into()
+    }
+}
+
+unsafe impl<'a, S: LzfseSink + ?Sized> ShortLimit for SinkWriter<'a, S> {
+    const SHORT_LIMIT: u32 = Vec::<u8>::SHORT_LIMIT;
+}
+
+impl<'a, S: LzfseSink + ?Sized> Truncate for SinkWriter<'a, S> {
+    fn truncate(&mut self, idx: Idx) -> bool {
+        let index = self.index(idx);
+        if index <= self.inner.n_bytes() {
+            self.inner.set_n_bytes(index);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<'a, S: LzfseSink + ?Sized> WriteLong for SinkWriter<'a, S> {
+    #[inline(always)]
+    fn write_long<I: CopyLong>(&mut self, src: I) -> io::Result<()> {
+        let len = src.len();
+        let index = self.inner.n_bytes();
+        self.allocate(len + WIDE)?;
+        let dst = unsafe { self.inner.buffer().as_mut_ptr().add(index) };
+        unsafe { src.copy_long_raw(dst, len) };
+        self.inner.set_n_bytes(index + len);
+        Ok(())
+    }
+}
+
+impl<'a, S: LzfseSink + ?Sized> WriteShort for SinkWriter<'a, S> {
+    #[inline(always)]
+    unsafe fn short_set(&mut self, len: u32) {
+        debug_assert!(len as usize <= i32::MAX as usize);
+        debug_assert!(self.is_allocated(len as usize));
+        let index = self.inner.n_bytes();
+        self.inner.set_n_bytes(index + len as usize);
+    }
+
+    #[inline(always)]
+    unsafe fn short_ptr(&mut self) -> *mut u8 {
+        let index = self.inner.n_bytes();
+        self.inner.buffer().as_mut_ptr().add(index)
+    }
+}
+
+impl<'a, S: LzfseSink + ?Sized> ShortWriter for SinkWriter<'a, S> {}
@@ -0,0 +1,163 @@
+use crate::base::MagicBytes;
+use crate::fse;
+use crate::ops::PeekData;
+use crate::raw;
+use crate::vn;
+
+use std::convert::TryFrom;
+use std::io::{self, Write};
+
+// Sufficient to probe any block header.
+const HEADER_LIMIT: usize = 0x40;
+
+/// LZFSE encode block statistics.
+///
+/// Block statistics, per block type, as part of [EncodeStats].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BlockStats {
+    /// Blocks.
+    pub n_blocks: u64,
+    /// Decoded size in bytes.
+    pub n_raw_bytes: u64,
+    /// Encoded size in bytes, including block headers.
+    pub n_payload_bytes: u64,
+}
+
+impl BlockStats {
+    #[inline(always)]
+    fn push(&mut self, n_raw_bytes: u32, n_payload_bytes: u32) {
+        self.n_blocks += 1;
+        self.n_raw_bytes += n_raw_bytes as u64;
+        self.n_payload_bytes += n_payload_bytes as u64;
+    }
+}
+
+/// LZFSE encode statistics.
+///
+/// Summarizes the blocks of an encoded stream. Produced by
+/// [LzfseRingEncoder::encode_with_stats](crate::LzfseRingEncoder::encode_with_stats).
+///
+/// Literal and LMD counts are read from FSE block headers and are tallied for FSE blocks only,
+/// VN blocks interleave literals and matches within their payload. An LMD is a literal length,
+/// match length, match distance triple, it does not necessarily describe a match.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct EncodeStats {
+    /// Raw blocks.
+    pub raw: BlockStats,
+    /// VN blocks.
+    pub vn: BlockStats,
+    /// FSE blocks, both v1 and v2.
+    pub fse: BlockStats,
+    /// FSE block literal bytes, including up to 3 bytes of alignment padding per block.
+    pub n_literals: u64,
+    /// FSE block LMD triples.
+    pub n_lmds: u64,
+}
+
+/// Tally encoded blocks by inspecting block headers, forwarding to the inner writer.
+///
+/// The match finder is untouched, the cost is limited to header inspection as bytes are written.
+pub struct StatsWriter<'a, O> {
+    inner: &'a mut O,
+    stats: EncodeStats,
+    header: [u8; HEADER_LIMIT],
+    n_header: usize,
+    n_skip: u64,
+    is_done: bool,
+}
+
+impl<'a, O: Write> StatsWriter<'a, O> {
+    #[inline(always)]
+    pub fn new(inner: &'a mut O) -> Self {
+        Self {
+            inner,
+            stats: EncodeStats::default(),
+            header: [0; HEADER_LIMIT],
+            n_header: 0,
+            n_skip: 0,
+            is_done: false,
+        }
+    }
+
+    #[inline(always)]
+    pub fn stats(&self) -> EncodeStats {
+        self.stats
+    }
+
+    fn observe(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() && !self.is_done {
+            if self.n_skip != 0 {
+                let n = (bytes.len() as u64).min(self.n_skip) as usize;
+                self.n_skip -= n as u64;
+                bytes = &bytes[n..];
+                continue;
+            }
+            let n = bytes.len().min(HEADER_LIMIT - self.n_header);
+            self.header[self.n_header..self.n_header + n].copy_from_slice(&bytes[..n]);
+            self.n_header += n;
+            bytes = &bytes[n..];
+            self.parse();
+        }
+    }
+
+    // Consume complete block headers from the header buffer.
+    fn parse(&mut self) {
+        while self.n_header >= 4 && !self.is_done {
+            let src = &self.header[..self.n_header];
+            let magic_bytes = match MagicBytes::try_from(src.peek_u32()) {
+                Ok(MagicBytes::Eos) | Err(_) => {
+                    self.is_done = true;
+                    return;
+                }
+                Ok(u) => u,
+            };
+            let probe = match magic_bytes {
+                MagicBytes::Vx1 => fse::v1_probe_lz(src),
+                MagicBytes::Vx2 => fse::v2_probe_lz(src),
+                MagicBytes::Vxn => vn::vn_probe(src).map(|(u, v)| (u, v, 0, 0)),
+                MagicBytes::Raw => raw::raw_probe(src).map(|(u, v)| (u, v, 0, 0)),
+                MagicBytes::Eos => unreachable!(),
+            };
+            let (n_payload_bytes, n_raw_bytes, n_literals, n_lmds) = match probe {
+                Ok(u) => u,
+                Err(_) => {
+                    // Incomplete header, or otherwise invalid which we do not expect.
+                    debug_assert!(self.n_header < HEADER_LIMIT);
+                    self.is_done = self.n_header == HEADER_LIMIT;
+                    return;
+                }
+            };
+            match magic_bytes {
+                MagicBytes::Vx1 | MagicBytes::Vx2 => {
+                    self.stats.fse.push(n_raw_bytes, n_payload_bytes);
+                    self.stats.n_literals += n_literals as u64;
+                    self.stats.n_lmds += n_lmds as u64;
+                }
+                MagicBytes::Vxn => self.stats.vn.push(n_raw_bytes, n_payload_bytes),
+                _ => self.stats.raw.push(n_raw_bytes, n_payload_bytes),
+            }
+            let n_payload_bytes = n_payload_bytes as usize;
+            if n_payload_bytes < self.n_header {
+                self.header.copy_within(n_payload_bytes..self.n_header, 0);
+                self.n_header -= n_payload_bytes;
+            } else {
+                self.n_skip = (n_payload_bytes - self.n_header) as u64;
+                self.n_header = 0;
+            }
+        }
+    }
+}
+
+impl<'a, O: Write> Write for StatsWriter<'a, O> {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.observe(&buf[..n]);
+        Ok(n)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
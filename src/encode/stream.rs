@@ -0,0 +1,157 @@
+use super::ring_encoder::LzfseRingEncoder;
+
+use std::cell::RefCell;
+use std::io::{self, Read, Write};
+
+thread_local! {
+    static ENCODER: RefCell<LzfseRingEncoder> = RefCell::new(LzfseRingEncoder::default());
+}
+
+/// Encode `reader` into `writer` returning a tuple (u, v) where u is the number of unencoded
+/// bytes read from the reader and v is the number of encoded bytes written into the writer.
+///
+/// This is a convenience method that reuses a thread local [LzfseRingEncoder], amortizing the
+/// ring buffer and history table allocations across invocations on the same thread. There is no
+/// need to wrap `reader` or `writer` in [BufReader](std::io::BufReader) or
+/// [BufWriter](std::io::BufWriter).
+///
+/// Should `writer` fail with [ErrorKind::BrokenPipe](std::io::ErrorKind), for example a
+/// downstream process exiting early, encoding stops and the bytes read and written up to that
+/// point are returned. The encoded output is then truncated.
+///
+/// # Errors
+///
+/// * [Error](std::io::Error) in case of other `reader` or `writer` IO errors.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     let (u, v) = lzfse_rust::compress_reader_to_writer(&mut b"test".as_ref(), &mut enc)?;
+///     assert_eq!(u, 4);
+///     assert_eq!(v, 16);
+///     // "test" string encoded.
+///     assert_eq!(enc, &[0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74,
+///                       0x62, 0x76, 0x78, 0x24]);
+///     Ok(())
+/// }
+/// ```
+pub fn compress_reader_to_writer<R, W>(reader: &mut R, writer: &mut W) -> io::Result<(u64, u64)>
+where
+    R: Read,
+    W: Write,
+{
+    let mut reader = CountReader { inner: reader, n: 0 };
+    let mut writer = PipeWriter { inner: writer, n: 0, broken: false };
+    let result = ENCODER.with(|cell| match cell.try_borrow_mut() {
+        Ok(mut encoder) => encoder.encode(&mut reader, &mut writer),
+        // Re-entrant call from within `reader` or `writer`.
+        Err(_) => LzfseRingEncoder::default().encode(&mut reader, &mut writer),
+    });
+    match result {
+        Err(_) if writer.broken => Ok((reader.n, writer.n)),
+        result => result,
+    }
+}
+
+struct CountReader<'a, R> {
+    inner: &'a mut R,
+    n: u64,
+}
+
+impl<'a, R: Read> Read for CountReader<'a, R> {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.n += n as u64;
+        Ok(n)
+    }
+}
+
+// Counts bytes accepted by the inner writer, flagging broken pipes.
+struct PipeWriter<'a, W> {
+    inner: &'a mut W,
+    n: u64,
+    broken: bool,
+}
+
+impl<'a, W: Write> PipeWriter<'a, W> {
+    #[inline(always)]
+    fn check<T>(&mut self, result: io::Result<T>) -> io::Result<T> {
+        if let Err(err) = &result {
+            self.broken |= err.kind() == io::ErrorKind::BrokenPipe;
+        }
+        result
+    }
+}
+
+impl<'a, W: Write> Write for PipeWriter<'a, W> {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let result = self.inner.write(buf);
+        let n = self.check(result)?;
+        self.n += n as u64;
+        Ok(n)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        let result = self.inner.flush();
+        self.check(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Pipe {
+        limit: usize,
+        vec: Vec<u8>,
+    }
+
+    impl Write for Pipe {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.vec.len() == self.limit {
+                return Err(io::ErrorKind::BrokenPipe.into());
+            }
+            let n = buf.len().min(self.limit - self.vec.len());
+            self.vec.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn broken_pipe() -> io::Result<()> {
+        let src = vec![0u8; 0x0010_0000];
+        let mut enc = Vec::default();
+        crate::encode_bytes(&src, &mut enc)?;
+        let mut pipe = Pipe { limit: 8, vec: Vec::default() };
+        let (u, v) = compress_reader_to_writer(&mut src.as_slice(), &mut pipe)?;
+        assert!(u <= src.len() as u64);
+        assert_eq!(v, 8);
+        assert_eq!(pipe.vec, &enc[..8]);
+        Ok(())
+    }
+
+    #[test]
+    fn reuse() -> io::Result<()> {
+        for &src in [b"test".as_ref(), &[0u8; 0x1000], b"test"].iter() {
+            let mut expected = Vec::default();
+            crate::encode_bytes(src, &mut expected)?;
+            let mut enc = Vec::default();
+            let (u, v) = compress_reader_to_writer(&mut &*src, &mut enc)?;
+            assert_eq!(u, src.len() as u64);
+            assert_eq!(v, enc.len() as u64);
+            assert_eq!(enc, expected);
+        }
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use crate::fse::FseBackend;
 use crate::ring::RingShortWriter;
 
 use super::constants::*;
-use super::frontend_ring::FrontendRing;
+use super::frontend_kind::FrontendKind;
 
 use std::fmt;
 use std::io::{self, Write};
@@ -10,12 +10,47 @@ use std::io::{self, Write};
 /// LZFSE encoding writer.
 ///
 /// Exposes, in part, a LZFSE encoder via the [Write](std::io::Write) interface that encodes into
-/// an inner writer. Due to the nature of LZFSE streams [Write::flush](std::io::Write::flush) has
-/// no effect, instead it is imperative that we call [LzfseWriter::finalize] after use to complete
-/// the encoding process.
+/// an inner writer. It is imperative that we call [LzfseWriter::finalize] after use to complete the
+/// encoding process.
 ///
-/// Instances are created using
-/// [LzfseRingEncoder::writer_bytes](super::LzfseRingEncoder::writer).
+/// Short writes by the inner writer are retried. Should the inner writer fail, bytes already
+/// accepted are reported by [write](std::io::Write::write) and the error is returned by the next
+/// call, after which the writer rejects further calls with
+/// [Error::BadWriterState](crate::Error::BadWriterState).
+///
+/// Instances are created using [LzfseRingEncoder::writer](super::LzfseRingEncoder::writer).
+///
+/// # Incremental encoding
+///
+/// The writer is suited to sources of unknown length that produce data over time:
+///
+/// * The total input length need not be known in advance.
+/// * The encoded output is independent of how the input is chunked across
+///   [write](std::io::Write::write) calls, any chunking, including empty writes, produces the
+///   same valid stream.
+/// * Input is staged in the encoder's fixed size input ring. Blocks are encoded and passed to the
+///   inner writer, via the fixed size output ring, as the input ring fills. Internal memory is
+///   bounded by [memory_footprint](crate::memory_footprint) irrespective of the input length,
+///   writes perform no further allocations.
+/// * Output therefore trails input by a bounded amount.
+/// * [finalize](LzfseWriter::finalize) encodes any remaining input and writes the end of stream
+///   marker. Until then the inner writer holds an incomplete stream.
+///
+/// # Flush
+///
+/// [Write::flush](std::io::Write::flush) passes all completed blocks to the inner writer and then
+/// flushes the inner writer. The in-progress block remains buffered, flushing does not force
+/// encoding. After a successful flush:
+///
+/// * The inner writer holds a prefix of the final stream that ends on a block boundary, no block is
+///   split. Appending an end of stream marker `bvx$` yields a valid stream that decodes to a
+///   prefix of the input written so far.
+/// * The end of stream marker is not written, only [finalize](LzfseWriter::finalize) does that.
+/// * The encoded output is identical regardless of when, or how often, we flush.
+///
+/// Input accepted by [write](std::io::Write::write) but not yet encoded is not made visible. For
+/// example, a log writer that flushes after each record sees its output in whole blocks as they
+/// complete, rather than record by record.
 ///
 /// # Examples
 ///
@@ -37,7 +72,7 @@ use std::io::{self, Write};
 /// }
 /// ```
 pub struct LzfseWriter<'a, O> {
-    frontend: FrontendRing<'a, Input>,
+    frontend: FrontendKind<'a>,
     backend: &'a mut FseBackend,
     writer: RingShortWriter<'a, O, Output>,
 }
@@ -45,7 +80,7 @@ pub struct LzfseWriter<'a, O> {
 impl<'a, O> LzfseWriter<'a, O> {
     #[inline(always)]
     pub(super) fn new(
-        frontend: FrontendRing<'a, Input>,
+        frontend: FrontendKind<'a>,
         backend: &'a mut FseBackend,
         writer: RingShortWriter<'a, O, Output>,
     ) -> Self {
@@ -70,7 +105,7 @@ impl<'a, O: Write> Write for LzfseWriter<'a, O> {
 
     #[inline(always)]
     fn flush(&mut self) -> io::Result<()> {
-        Ok(())
+        self.frontend.flush_blocks(&mut self.writer)
     }
 }
 
@@ -1,7 +1,6 @@
 use crate::fse::FseBackend;
 
-use super::constants::*;
-use super::frontend_ring::FrontendRing;
+use super::frontend_kind::FrontendKind;
 
 use std::fmt;
 use std::io::{self, Write};
@@ -9,18 +8,22 @@ use std::io::{self, Write};
 /// LZFSE encoding byte writer.
 ///
 /// Exposes, in part, a LZFSE encoder via the [Write](std::io::Write) interface that encodes into
-/// an inner [Vec]. Due to the nature of LZFSE streams [Write::flush](std::io::Write::flush) has
-/// no effect, instead it is imperative that we call [LzfseWriterBytes::finalize] after use to
-/// complete the encoding process.
+/// an inner [Vec]. It is imperative that we call [LzfseWriterBytes::finalize] after use to complete
+/// the encoding process.
+///
+/// Completed blocks are encoded directly into the inner [Vec], there is no intermediate output
+/// buffer. [Write::flush](std::io::Write::flush) therefore has nothing to pass on. As with
+/// [LzfseWriter](super::LzfseWriter), the in-progress block remains buffered until further input
+/// completes it or we finalize, and the end of stream marker is only written by
+/// [finalize](LzfseWriterBytes::finalize).
 ///
 /// Instances are created using
 /// [LzfseRingEncoder::writer_bytes](super::LzfseRingEncoder::writer_bytes).
 ///
-/// # Aborts
+/// # Errors
 ///
-/// With limited system memory [Vec] may abort when attempting to allocate sufficient memory.
-/// This issue will be resolved in future releases when [try_reserve()](Vec::try_reserve) is
-/// stabilized.
+/// * [ErrorKind::OutOfMemory](std::io::ErrorKind) if the inner [Vec] fails to allocate
+///   sufficient memory.
 ///
 /// # Examples
 ///
@@ -42,7 +45,7 @@ use std::io::{self, Write};
 /// }
 /// ```
 pub struct LzfseWriterBytes<'a> {
-    frontend: FrontendRing<'a, Input>,
+    frontend: FrontendKind<'a>,
     backend: &'a mut FseBackend,
     vec: Vec<u8>,
 }
@@ -50,7 +53,7 @@ pub struct LzfseWriterBytes<'a> {
 impl<'a> LzfseWriterBytes<'a> {
     #[inline(always)]
     pub(super) fn new(
-        frontend: FrontendRing<'a, Input>,
+        frontend: FrontendKind<'a>,
         backend: &'a mut FseBackend,
         vec: Vec<u8>,
     ) -> Self {
@@ -73,7 +76,7 @@ impl<'a> Write for LzfseWriterBytes<'a> {
 
     #[inline(always)]
     fn flush(&mut self) -> io::Result<()> {
-        Ok(())
+        self.frontend.flush_blocks(&mut self.vec)
     }
 }
 
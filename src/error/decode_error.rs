@@ -0,0 +1,234 @@
+use crate::fse;
+use crate::vn;
+
+use super::Error;
+
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::io;
+use std::str;
+
+/// Lightweight decoding errors.
+///
+/// Mirrors the non [IO](std::io) [Error] variants. Unlike [Error], [DecodeError] is [Copy], [Eq]
+/// and compact, making it suitable for embedding in other error types. Conversions are
+/// lossless: [Error::decode_error] and [TryFrom] extract a [DecodeError], with [Error::Io] errors
+/// returned as is, and [From] converts back.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{DecodeError, Error};
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug)]
+/// enum MyError {
+///     Decode(DecodeError),
+///     Io(std::io::ErrorKind),
+/// }
+///
+/// impl From<Error> for MyError {
+///     fn from(err: Error) -> Self {
+///         match DecodeError::try_from(err) {
+///             Ok(err) => MyError::Decode(err),
+///             Err(err) => MyError::Io(err.kind()),
+///         }
+///     }
+/// }
+///
+/// let err: MyError = lzfse_rust::decode_bytes(b"bvx", &mut Vec::default()).unwrap_err().into();
+/// assert!(matches!(err, MyError::Decode(DecodeError::PayloadUnderflow)));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// FSE specific errors.
+    Fse(fse::FseErrorKind),
+    /// VN specific errors.
+    Vn(vn::VnErrorKind),
+    /// Unknown or unsupported block type (magic bytes).
+    BadBlock(u32),
+    /// Bad bitstream.
+    BadBitStream,
+    /// Bad LZ distance value.
+    BadDValue,
+    /// Reader state is invalid, likely the user attempted to use after an error was encountered.
+    BadReaderState,
+    /// Writer state is invalid, likely the user attempted to use after an error was encountered.
+    BadWriterState,
+    /// Buffer overflow.
+    BufferOverflow,
+    /// Input has more bytes than expected.
+    PayloadOverflow,
+    /// Input has less bytes than expected.
+    PayloadUnderflow,
+    /// Decoded output exceeds the specified limit.
+    OutputLimitExceeded,
+    /// Decoded output size does not match the expected size.
+    OutputSizeMismatch,
+    /// Block index exceeds the number of blocks in the stream.
+    BlockIndexOutOfRange,
+    /// Decoded output is not valid UTF-8.
+    Utf8(str::Utf8Error),
+    /// Trailer is missing or malformed.
+    BadTrailer,
+    /// Decoded output digest does not match the trailer digest.
+    DigestMismatch,
+    /// Decoded output checksum or length does not match the trailer.
+    ChecksumMismatch,
+    /// Metadata frame is missing or malformed.
+    BadMetadata,
+    /// Archive or mux footer or index is missing or malformed.
+    BadArchive,
+    /// Archive member or mux channel not found.
+    MemberNotFound,
+    /// Encoded output does not decode to the source.
+    VerificationFailed,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> std::result::Result<(), fmt::Error> {
+        fmt::Display::fmt(&Error::from(*self), f)
+    }
+}
+
+impl error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Utf8(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<DecodeError> for Error {
+    fn from(err: DecodeError) -> Self {
+        match err {
+            DecodeError::Fse(e) => Self::Fse(e),
+            DecodeError::Vn(e) => Self::Vn(e),
+            DecodeError::BadBlock(u) => Self::BadBlock(u),
+            DecodeError::BadBitStream => Self::BadBitStream,
+            DecodeError::BadDValue => Self::BadDValue,
+            DecodeError::BadReaderState => Self::BadReaderState,
+            DecodeError::BadWriterState => Self::BadWriterState,
+            DecodeError::BufferOverflow => Self::BufferOverflow,
+            DecodeError::PayloadOverflow => Self::PayloadOverflow,
+            DecodeError::PayloadUnderflow => Self::PayloadUnderflow,
+            DecodeError::OutputLimitExceeded => Self::OutputLimitExceeded,
+            DecodeError::OutputSizeMismatch => Self::OutputSizeMismatch,
+            DecodeError::BlockIndexOutOfRange => Self::BlockIndexOutOfRange,
+            DecodeError::Utf8(e) => Self::Utf8(e),
+            DecodeError::BadTrailer => Self::BadTrailer,
+            DecodeError::DigestMismatch => Self::DigestMismatch,
+            DecodeError::ChecksumMismatch => Self::ChecksumMismatch,
+            DecodeError::BadMetadata => Self::BadMetadata,
+            DecodeError::BadArchive => Self::BadArchive,
+            DecodeError::MemberNotFound => Self::MemberNotFound,
+            DecodeError::VerificationFailed => Self::VerificationFailed,
+        }
+    }
+}
+
+impl TryFrom<Error> for DecodeError {
+    type Error = io::Error;
+
+    fn try_from(err: Error) -> std::result::Result<Self, Self::Error> {
+        match err {
+            Error::Io(e) => Err(e),
+            Error::Fse(e) => Ok(Self::Fse(e)),
+            Error::Vn(e) => Ok(Self::Vn(e)),
+            Error::BadBlock(u) => Ok(Self::BadBlock(u)),
+            Error::BadBitStream => Ok(Self::BadBitStream),
+            Error::BadDValue => Ok(Self::BadDValue),
+            Error::BadReaderState => Ok(Self::BadReaderState),
+            Error::BadWriterState => Ok(Self::BadWriterState),
+            Error::BufferOverflow => Ok(Self::BufferOverflow),
+            Error::PayloadOverflow => Ok(Self::PayloadOverflow),
+            Error::PayloadUnderflow => Ok(Self::PayloadUnderflow),
+            Error::OutputLimitExceeded => Ok(Self::OutputLimitExceeded),
+            Error::OutputSizeMismatch => Ok(Self::OutputSizeMismatch),
+            Error::BlockIndexOutOfRange => Ok(Self::BlockIndexOutOfRange),
+            Error::Utf8(e) => Ok(Self::Utf8(e)),
+            Error::BadTrailer => Ok(Self::BadTrailer),
+            Error::DigestMismatch => Ok(Self::DigestMismatch),
+            Error::ChecksumMismatch => Ok(Self::ChecksumMismatch),
+            Error::BadMetadata => Ok(Self::BadMetadata),
+            Error::BadArchive => Ok(Self::BadArchive),
+            Error::MemberNotFound => Ok(Self::MemberNotFound),
+            Error::VerificationFailed => Ok(Self::VerificationFailed),
+        }
+    }
+}
+
+impl From<DecodeError> for io::Error {
+    fn from(err: DecodeError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all() -> Vec<DecodeError> {
+        let bytes = vec![0xFF];
+        vec![
+            DecodeError::Fse(fse::FseErrorKind::BadLmdPayload),
+            DecodeError::Vn(vn::VnErrorKind::BadPayload),
+            DecodeError::BadBlock(0x1234_5678),
+            DecodeError::BadBitStream,
+            DecodeError::BadDValue,
+            DecodeError::BadReaderState,
+            DecodeError::BadWriterState,
+            DecodeError::BufferOverflow,
+            DecodeError::PayloadOverflow,
+            DecodeError::PayloadUnderflow,
+            DecodeError::OutputLimitExceeded,
+            DecodeError::OutputSizeMismatch,
+            DecodeError::BlockIndexOutOfRange,
+            DecodeError::Utf8(str::from_utf8(&bytes).unwrap_err()),
+            DecodeError::BadTrailer,
+            DecodeError::DigestMismatch,
+            DecodeError::ChecksumMismatch,
+            DecodeError::BadMetadata,
+            DecodeError::BadArchive,
+            DecodeError::MemberNotFound,
+            DecodeError::VerificationFailed,
+        ]
+    }
+
+    #[test]
+    fn round_trip() {
+        for err in all() {
+            let error = Error::from(err);
+            assert_eq!(error.to_string(), err.to_string());
+            assert_eq!(error.decode_error(), Some(err));
+            assert_eq!(DecodeError::try_from(error).unwrap(), err);
+        }
+    }
+
+    #[test]
+    fn io() {
+        let error = Error::Io(io::ErrorKind::BrokenPipe.into());
+        assert_eq!(error.decode_error(), None);
+        let err = DecodeError::try_from(error).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn source() {
+        use std::error::Error as _;
+        for err in all() {
+            assert_eq!(err.source().is_some(), Error::from(err).source().is_some());
+        }
+    }
+
+    #[test]
+    fn into_io() {
+        let err: io::Error = DecodeError::BadDValue.into();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let inner = err.into_inner().unwrap().downcast::<DecodeError>().unwrap();
+        assert_eq!(*inner, DecodeError::BadDValue);
+    }
+}
@@ -1,9 +1,14 @@
+mod decode_error;
+
 use crate::fse;
 use crate::vn;
 
+pub use decode_error::DecodeError;
+
 use std::error;
 use std::fmt;
 use std::io;
+use std::str;
 
 /// Decoding error Result.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -15,6 +20,9 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// conversion. [Error::Io] errors are flattened whilst other errors are boxed into an
 /// [InvalidData](std::io::ErrorKind::InvalidData) variant [io::Error](std::io::Error).
 ///
+/// The non [Error::Io] variants are mirrored by the lightweight [DecodeError], see
+/// [decode_error](Self::decode_error).
+///
 /// # Examples
 ///
 /// ```
@@ -52,12 +60,67 @@ pub enum Error {
     BadDValue,
     /// Reader state is invalid, likely the user attempted to use after an error was encountered.
     BadReaderState,
+    /// Writer state is invalid, likely the user attempted to use after an error was encountered.
+    BadWriterState,
     /// Buffer overflow.
     BufferOverflow,
     /// Input has more bytes than expected.
     PayloadOverflow,
     /// Input has less bytes than expected.
     PayloadUnderflow,
+    /// Decoded output exceeds the specified limit.
+    OutputLimitExceeded,
+    /// Decoded output size does not match the expected size.
+    OutputSizeMismatch,
+    /// Block index exceeds the number of blocks in the stream.
+    BlockIndexOutOfRange,
+    /// Decoded output is not valid UTF-8.
+    Utf8(str::Utf8Error),
+    /// Trailer is missing or malformed.
+    BadTrailer,
+    /// Decoded output digest does not match the trailer digest.
+    DigestMismatch,
+    /// Decoded output checksum or length does not match the trailer.
+    ChecksumMismatch,
+    /// Metadata frame is missing or malformed.
+    BadMetadata,
+    /// Archive or mux footer or index is missing or malformed.
+    BadArchive,
+    /// Archive member or mux channel not found.
+    MemberNotFound,
+    /// Encoded output does not decode to the source, see
+    /// [LzfseEncoder::encode_bytes_verified](crate::LzfseEncoder::encode_bytes_verified).
+    VerificationFailed,
+}
+
+impl Error {
+    /// Returns the equivalent [DecodeError], or `None` for [Error::Io] errors.
+    pub fn decode_error(&self) -> Option<DecodeError> {
+        match self {
+            Self::Io(_) => None,
+            Self::Fse(e) => Some(DecodeError::Fse(*e)),
+            Self::Vn(e) => Some(DecodeError::Vn(*e)),
+            Self::BadBlock(u) => Some(DecodeError::BadBlock(*u)),
+            Self::BadBitStream => Some(DecodeError::BadBitStream),
+            Self::BadDValue => Some(DecodeError::BadDValue),
+            Self::BadReaderState => Some(DecodeError::BadReaderState),
+            Self::BadWriterState => Some(DecodeError::BadWriterState),
+            Self::BufferOverflow => Some(DecodeError::BufferOverflow),
+            Self::PayloadOverflow => Some(DecodeError::PayloadOverflow),
+            Self::PayloadUnderflow => Some(DecodeError::PayloadUnderflow),
+            Self::OutputLimitExceeded => Some(DecodeError::OutputLimitExceeded),
+            Self::OutputSizeMismatch => Some(DecodeError::OutputSizeMismatch),
+            Self::BlockIndexOutOfRange => Some(DecodeError::BlockIndexOutOfRange),
+            Self::Utf8(e) => Some(DecodeError::Utf8(*e)),
+            Self::BadTrailer => Some(DecodeError::BadTrailer),
+            Self::DigestMismatch => Some(DecodeError::DigestMismatch),
+            Self::ChecksumMismatch => Some(DecodeError::ChecksumMismatch),
+            Self::BadMetadata => Some(DecodeError::BadMetadata),
+            Self::BadArchive => Some(DecodeError::BadArchive),
+            Self::MemberNotFound => Some(DecodeError::MemberNotFound),
+            Self::VerificationFailed => Some(DecodeError::VerificationFailed),
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -70,9 +133,21 @@ impl fmt::Display for Error {
             Self::BadDValue => write!(f, "bad D value"),
             Self::BadBlock(u) => write!(f, "bad block: 0x{u:08X}"),
             Self::BadReaderState => write!(f, "bad reader state"),
+            Self::BadWriterState => write!(f, "bad writer state"),
             Self::BufferOverflow => write!(f, "buffer overflow"),
             Self::PayloadOverflow => write!(f, "bad payload overflow"),
             Self::PayloadUnderflow => write!(f, "bad payload underflow"),
+            Self::OutputLimitExceeded => write!(f, "output limit exceeded"),
+            Self::OutputSizeMismatch => write!(f, "output size mismatch"),
+            Self::BlockIndexOutOfRange => write!(f, "block index out of range"),
+            Self::Utf8(e) => write!(f, "UTF-8: {e}"),
+            Self::BadTrailer => write!(f, "bad trailer"),
+            Self::DigestMismatch => write!(f, "digest mismatch"),
+            Self::ChecksumMismatch => write!(f, "checksum mismatch"),
+            Self::BadMetadata => write!(f, "bad metadata"),
+            Self::BadArchive => write!(f, "bad archive"),
+            Self::MemberNotFound => write!(f, "member not found"),
+            Self::VerificationFailed => write!(f, "verification failed"),
         }
     }
 }
@@ -81,6 +156,7 @@ impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Self::Io(e) => Some(e),
+            Self::Utf8(e) => Some(e),
             _ => None,
         }
     }
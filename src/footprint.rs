@@ -0,0 +1,44 @@
+use crate::decode::{LzfseRingDecoder, RingProfile};
+use crate::encode::LzfseRingEncoder;
+
+/// Ring encoder/ decoder role, see [memory_footprint].
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Role {
+    /// [LzfseRingEncoder].
+    Encoder,
+    /// [LzfseRingDecoder].
+    Decoder,
+}
+
+/// Returns the heap in bytes held by a ring encoder/ decoder instance for the specified ring
+/// `profile`: the input ring, the output ring, the history table and the FSE block buffers.
+///
+/// The footprint is fixed from construction, it does not grow with the workload. The `profile`
+/// selects the encoder's input ring and the decoder's output ring. LZVN blocks use no heap.
+///
+/// Excludes the decoder's [PadMode::Lenient](crate::PadMode::Lenient) scratch buffer, which grows on
+/// demand to the largest LMD payload encountered.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{RingProfile, Role};
+///
+/// let small = RingProfile::for_max_distance(0x4000);
+/// let default = RingProfile::default();
+/// assert!(
+///     lzfse_rust::memory_footprint(small, Role::Decoder)
+///         < lzfse_rust::memory_footprint(default, Role::Decoder)
+/// );
+/// assert!(
+///     lzfse_rust::memory_footprint(small, Role::Encoder)
+///         < lzfse_rust::memory_footprint(default, Role::Encoder)
+/// );
+/// ```
+pub fn memory_footprint(profile: RingProfile, role: Role) -> usize {
+    match role {
+        Role::Encoder => LzfseRingEncoder::heap_size(profile),
+        Role::Decoder => LzfseRingDecoder::heap_size(profile),
+    }
+}
@@ -0,0 +1,150 @@
+use crate::base::TrailerKind;
+use crate::decode::stream_len;
+use crate::error::Error;
+use crate::metadata::Metadata;
+use crate::trailer;
+
+/// Framing surrounding a bare LZFSE stream.
+///
+/// A framed stream consists of an optional [Metadata] frame, the bare LZFSE stream and any number of
+/// chained trailers following the end of stream block, see
+/// [encode_with_digest](crate::encode_with_digest).
+/// Use [strip_frame] and [add_frame] to convert between framed and bare streams.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FrameInfo {
+    /// Optional metadata frame preceding the stream.
+    pub metadata: Option<Metadata>,
+    /// Trailer kinds and payloads following the end of stream block, in stream order.
+    pub trailers: Vec<(TrailerKind, Vec<u8>)>,
+}
+
+/// Split a framed stream into its [FrameInfo] and the bare LZFSE stream.
+///
+/// The bare stream is Apple compatible and may be decoded by any LZFSE decoder. Only block headers
+/// are inspected, the stream itself is not decoded and may still contain errors.
+///
+/// # Errors
+///
+/// * [Error::BadMetadata](crate::Error::BadMetadata) or [Error::Utf8](crate::Error::Utf8) if the
+///   metadata frame is malformed.
+/// * [Error::BadTrailer](crate::Error::BadTrailer) if the bytes following the end of stream block
+///   are not a sequence of well formed trailers of known [TrailerKind].
+/// * [Error](crate::Error) detailing the nature of any block header errors.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{FrameInfo, Metadata, TrailerKind};
+///
+/// fn main() -> lzfse_rust::Result<()> {
+///     let mut bare = Vec::default();
+///     lzfse_rust::encode_bytes(b"test", &mut bare)?;
+///     let info = FrameInfo {
+///         metadata: Some(Metadata { name: "a.txt".to_owned(), mtime: 0 }),
+///         trailers: vec![(TrailerKind::Opaque, b"note".to_vec())],
+///     };
+///     let mut framed = Vec::default();
+///     lzfse_rust::add_frame(&bare, &info, &mut framed)?;
+///     let (stripped_info, stripped) = lzfse_rust::strip_frame(&framed)?;
+///     assert_eq!(stripped_info, info);
+///     assert_eq!(stripped, bare.as_slice());
+///     Ok(())
+/// }
+/// ```
+pub fn strip_frame(mut src: &[u8]) -> crate::Result<(FrameInfo, &[u8])> {
+    let metadata =
+        if Metadata::is_metadata(src) { Some(Metadata::read_from(&mut src)?) } else { None };
+    let trailers =
+        trailer::split(src)?.into_iter().map(|(kind, payload)| (kind, payload.to_vec())).collect();
+    let bare = &src[..stream_len(src)?];
+    Ok((FrameInfo { metadata, trailers }, bare))
+}
+
+/// Frame the bare LZFSE stream `bare` as described by `info`, appending the result into `dst`.
+/// Returns the number of bytes written into `dst`.
+///
+/// # Errors
+///
+/// * [Error::PayloadOverflow](crate::Error::PayloadOverflow) if `bare` is followed by bytes
+///   beyond the end of stream block.
+/// * [Error::BadTrailer](crate::Error::BadTrailer) if a trailer exceeds `u32::MAX` bytes.
+/// * [Error](crate::Error) detailing the nature of any block header or metadata errors.
+pub fn add_frame(bare: &[u8], info: &FrameInfo, dst: &mut Vec<u8>) -> crate::Result<u64> {
+    if stream_len(bare)? != bare.len() {
+        return Err(Error::PayloadOverflow);
+    }
+    if info.trailers.iter().any(|(_, trailer)| trailer.len() > u32::MAX as usize) {
+        return Err(Error::BadTrailer);
+    }
+    let mark = dst.len();
+    if let Some(metadata) = &info.metadata {
+        if let Err(err) = metadata.write_to(dst) {
+            dst.truncate(mark);
+            return Err(err.into());
+        }
+    }
+    dst.extend_from_slice(bare);
+    for (kind, trailer) in &info.trailers {
+        trailer::write(dst, *kind, Some(&trailer[..]))?;
+    }
+    Ok((dst.len() - mark) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bare() -> crate::Result<Vec<u8>> {
+        let mut bare = Vec::default();
+        crate::encode_bytes(b"test test test test", &mut bare)?;
+        Ok(bare)
+    }
+
+    #[test]
+    fn bare_only() -> crate::Result<()> {
+        let bare = bare()?;
+        let (info, stripped) = strip_frame(&bare)?;
+        assert_eq!(info, FrameInfo::default());
+        assert_eq!(stripped, bare.as_slice());
+        let mut dst = Vec::default();
+        assert_eq!(add_frame(&bare, &info, &mut dst)?, bare.len() as u64);
+        assert_eq!(dst, bare);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_trailer() -> crate::Result<()> {
+        let mut enc = bare()?;
+        enc.extend_from_slice(&u32::from(TrailerKind::Crc32).to_le_bytes());
+        assert!(matches!(strip_frame(&enc), Err(Error::BadTrailer)));
+        enc.extend_from_slice(&1u32.to_le_bytes());
+        assert!(matches!(strip_frame(&enc), Err(Error::BadTrailer)));
+        enc.push(0);
+        assert_eq!(strip_frame(&enc)?.0.trailers, vec![(TrailerKind::Crc32, vec![0])]);
+        enc.push(0);
+        assert!(matches!(strip_frame(&enc), Err(Error::BadTrailer)));
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_trailer_kind() -> crate::Result<()> {
+        let mut enc = bare()?;
+        enc.extend_from_slice(b"bvx?");
+        enc.extend_from_slice(&0u32.to_le_bytes());
+        assert!(matches!(strip_frame(&enc), Err(Error::BadTrailer)));
+        Ok(())
+    }
+
+    #[test]
+    fn add_frame_trailing_bytes() -> crate::Result<()> {
+        let mut bare = bare()?;
+        bare.push(0);
+        let mut dst = Vec::default();
+        assert!(matches!(
+            add_frame(&bare, &FrameInfo::default(), &mut dst),
+            Err(Error::PayloadOverflow)
+        ));
+        assert!(dst.is_empty());
+        Ok(())
+    }
+}
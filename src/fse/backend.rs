@@ -4,6 +4,7 @@ use crate::lmd::MatchDistance;
 use crate::types::{ShortBuffer, ShortWriter};
 
 use super::constants::*;
+use super::normalization_strategy::NormalizationStrategy;
 use super::object::Fse;
 
 use std::io;
@@ -13,16 +14,29 @@ pub struct FseBackend {
     buffer: Buffer,
     weights: Weights,
     encoder: Encoder,
+    strategy: NormalizationStrategy,
 }
 
 impl FseBackend {
+    pub const HEAP_SIZE: usize = Buffer::HEAP_SIZE;
+
+    #[inline(always)]
+    pub fn set_normalization_strategy(&mut self, strategy: NormalizationStrategy) {
+        self.strategy = strategy;
+    }
+
+    #[inline(always)]
+    pub fn normalization_strategy(&self) -> NormalizationStrategy {
+        self.strategy
+    }
+
     #[allow(dead_code)]
     #[cold]
     fn emit_block_v1<O: ShortWriter>(&mut self, dst: &mut O, flush: bool) -> io::Result<()> {
         let mark = dst.pos();
         dst.write_short_bytes(&[0u8; V1_HEADER_SIZE as usize])?;
         self.buffer.pad();
-        self.buffer.init_weights(&mut self.weights);
+        self.buffer.init_weights(&mut self.weights, self.strategy);
         self.weights.store_v1_short(dst)?;
         self.encoder.init(&self.weights);
         let block = self.buffer.store(dst, &self.encoder)?;
@@ -40,7 +54,7 @@ impl FseBackend {
         let mark = dst.pos();
         dst.write_short_bytes(&[0u8; V2_HEADER_SIZE as usize])?;
         self.buffer.pad();
-        self.buffer.init_weights(&mut self.weights);
+        self.buffer.init_weights(&mut self.weights, self.strategy);
         let n_weight_payload_bytes = self.weights.store_v2_short(dst)?;
         self.encoder.init(&self.weights);
         let block = self.buffer.store(dst, &self.encoder)?;
@@ -89,6 +103,13 @@ impl Backend for FseBackend {
         Ok(())
     }
 
+    fn close_block<O: ShortWriter>(&mut self, dst: &mut O) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            self.emit_block_v2(dst, true)?;
+        }
+        Ok(())
+    }
+
     fn finalize<O: ShortWriter>(&mut self, dst: &mut O) -> io::Result<()> {
         self.emit_block_v2(dst, false)?;
         Ok(())
@@ -24,6 +24,17 @@ pub fn literal_n_payload_bytes_limit(num: u32) -> u32 {
     1024 + (num * MAX_U_BITS + 7) / 8
 }
 
+// Implementation notes:
+//
+// FSE block layout, V1 and V2:
+//
+// | header | weight payload | literal payload | LMD payload |
+//
+// The section order is fixed by the LZFSE format, there is no header flag that permits an
+// alternative order. The literal payload always precedes the LMD payload. The decoder loads and
+// decodes all literals into an internal buffer before decoding the LMDs, see `FseCore`, as such
+// literal bytes are already available whilst LMDs are being parsed and a literals-first encoder
+// toggle would offer nothing beyond the current layout.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct FseBlock {
     literal: LiteralParam,
@@ -96,10 +107,13 @@ impl FseBlock {
         self.lmd.state[0]             = src.read_u16();
         self.lmd.state[1]             = src.read_u16();
         self.lmd.state[2]             = src.read_u16();
-        if n_payload_bytes < self.literal.n_payload_bytes.wrapping_add(self.lmd.n_payload_bytes) {
+        self.validate()?;
+        if n_payload_bytes < self.n_payload_bytes()
+            || n_payload_bytes
+                > literal_n_payload_bytes_limit(self.literal.num) + lmd_n_payload_bytes_limit(self.lmd.num)
+        {
             return Err(FseErrorKind::BadPayloadCount.into());
         }
-        self.validate()?;
         Ok((V1_HEADER_SIZE, V1_WEIGHT_PAYLOAD_BYTES))
     }
 
@@ -127,10 +141,10 @@ impl FseBlock {
         self.lmd.state[0]            =     p.get_bits(32, 10) as u16;
         self.lmd.state[1]            =     p.get_bits(42, 10) as u16;
         self.lmd.state[2]            =     p.get_bits(52, 10) as u16;
-        let n_weight_payload_bytes = header_size.wrapping_sub(V2_HEADER_SIZE);
-        if n_weight_payload_bytes > V2_WEIGHT_PAYLOAD_BYTES_MAX {
-            return Err(FseErrorKind::BadWeightPayload.into());
-        }
+        let n_weight_payload_bytes = match header_size.checked_sub(V2_HEADER_SIZE) {
+            Some(u) if u <= V2_WEIGHT_PAYLOAD_BYTES_MAX => u,
+            _ => return Err(FseErrorKind::BadWeightPayload.into()),
+        };
         self.validate()?;
         Ok((V2_HEADER_SIZE, n_weight_payload_bytes))
     }
@@ -8,6 +8,7 @@ use super::constants::*;
 use super::encoder::Encoder;
 use super::literals::Literals;
 use super::lmds::Lmds;
+use super::normalization_strategy::NormalizationStrategy;
 use super::weights::Weights;
 use super::Fse;
 
@@ -23,12 +24,14 @@ pub struct Buffer {
 }
 
 impl Buffer {
+    pub const HEAP_SIZE: usize = Literals::HEAP_SIZE + Lmds::HEAP_SIZE;
+
     pub fn pad(&mut self) {
         self.literals.pad();
     }
 
-    pub fn init_weights(&self, weights: &mut Weights) -> u8 {
-        weights.load(self.lmds.as_ref(), self.literals.as_ref())
+    pub fn init_weights(&self, weights: &mut Weights, strategy: NormalizationStrategy) -> u8 {
+        weights.load(self.lmds.as_ref(), self.literals.as_ref(), strategy)
     }
 
     pub fn store<O>(&self, dst: &mut O, encoder: &Encoder) -> io::Result<FseBlock>
@@ -124,6 +127,11 @@ impl Buffer {
         self.match_distance = 0;
     }
 
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.lmds.len() == 0
+    }
+
     #[inline(always)]
     fn n_raw_bytes(&self) -> u32 {
         self.literals.len() as u32 + self.n_match_bytes
@@ -15,12 +15,38 @@ use std::fmt::{self, Debug, Formatter};
 pub struct Decoder(
     [VEntry; (L_STATES + M_STATES + D_STATES) as usize],
     [UEntry; U_STATES as usize],
+    // L, M, D, U latch region starts, see `build_v_table_block`.
+    [usize; 4],
 );
 
 impl Decoder {
     pub fn init(&mut self, weights: &Weights) {
         self.init_v_table(weights);
         self.init_u_table(weights);
+        self.init_latches(weights);
+    }
+
+    fn init_latches(&mut self, weights: &Weights) {
+        let total = |block: &[u16]| block.iter().map(|&u| u as usize).sum::<usize>();
+        self.2 = [
+            total(weights.l_block()),
+            L_STATES as usize + total(weights.m_block()),
+            L_STATES as usize + M_STATES as usize + total(weights.d_block()),
+            total(weights.u_block()),
+        ];
+    }
+
+    /// Returns true if any of the L, M, D states are latched. Latch states are unpopulated states
+    /// that are reachable only with malformed weights.
+    #[inline(always)]
+    pub fn is_lmd_latched(&self, state: &(L, M, D)) -> bool {
+        state.0 .0 >= self.2[0] || state.1 .0 >= self.2[1] || state.2 .0 >= self.2[2]
+    }
+
+    /// Returns true if the U `state` is latched.
+    #[inline(always)]
+    pub fn is_u_latched(&self, state: &U) -> bool {
+        state.0 >= self.2[3]
     }
 
     fn init_v_table(&mut self, weights: &Weights) {
@@ -121,9 +147,28 @@ impl Decoder {
     }
 }
 
+impl Decoder {
+    /// Bits consumed decoding an L, M or D `state`.
+    #[inline(always)]
+    pub fn v_bits(&self, state: usize) -> u32 {
+        let e = self.0[state];
+        e.k as u32 + e.v_bits as u32
+    }
+
+    /// Bits consumed decoding a U `state`.
+    #[inline(always)]
+    pub fn u_bits(&self, state: usize) -> u32 {
+        self.1[state].k as u32
+    }
+}
+
 impl Debug for Decoder {
     fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), fmt::Error> {
-        f.debug_tuple("Decoder").field(&self.0.as_ref()).field(&self.1.as_ref()).finish()
+        f.debug_tuple("Decoder")
+            .field(&self.0.as_ref())
+            .field(&self.1.as_ref())
+            .field(&self.2)
+            .finish()
     }
 }
 
@@ -132,6 +177,7 @@ impl Default for Decoder {
         Self(
             [VEntry::default(); L_STATES as usize + M_STATES as usize + D_STATES as usize],
             [UEntry::default(); U_STATES as usize],
+            [0; 4],
         )
     }
 }
@@ -33,6 +33,8 @@ pub enum FseErrorKind {
     BadWeightPayload,
     /// Bad weight payload count.
     BadWeightPayloadCount,
+    /// Latch state entered, the weights are malformed. Reported in strict FSE mode only.
+    LatchState,
     /// Weight payload overflow.    
     WeightPayloadOverflow,
     /// Weight payload underflow.
@@ -55,6 +57,7 @@ impl fmt::Display for FseErrorKind {
             Self::BadReaderState => write!(f, "bad reader state"),
             Self::BadWeightPayload => write!(f, "bad weight payload"),
             Self::BadWeightPayloadCount => write!(f, "bad weight payload count"),
+            Self::LatchState => write!(f, "latch state"),
             Self::WeightPayloadOverflow => write!(f, "weight payload overflow"),
             Self::WeightPayloadUnderflow => write!(f, "weight payload underflow"),
         }
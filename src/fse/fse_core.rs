@@ -1,8 +1,10 @@
 use crate::bits::{BitReader, BitSrc};
-use crate::decode::Take;
+use crate::decode::{BlockCost, Take};
+use crate::error::Error;
 use crate::kit::W00;
 use crate::lmd::{LiteralLen, LmdPack, MatchDistanceUnpack, MatchLen};
 use crate::lz::LzWriter;
+use crate::ops::{CopyLong, WriteLong};
 use crate::types::{ShortBuffer, ShortBytes};
 
 use super::block::FseBlock;
@@ -12,6 +14,7 @@ use super::error_kind::FseErrorKind;
 use super::literals::Literals;
 use super::lmds::Lmds;
 use super::object::Fse;
+use super::pad_mode::PadMode;
 use super::weights::Weights;
 
 #[derive(Default)]
@@ -25,14 +28,57 @@ pub struct FseCore {
     lmd_index: u32,
     mark: u64,
     match_distance: MatchDistanceUnpack<Fse>,
+    pad_mode: PadMode,
+    pad: Vec<u8>,
+    strict: bool,
 }
 
 // Implementation notes:
 //
 // BitSrc requires an 8 byte pad. The LMD payload is padded. The literal payload is not padded, so
 // we borrow 8 bytes from the header.
+//
+// In lenient pad mode the LMD payload is copied into `pad` behind a synthesized 8 byte zero pad.
+// Payload bytes may then be consumed in full, whilst consuming synthesized bytes is an underflow.
+// In either mode the LMD payload is at least 8 bytes, as validated by `LmdParam`.
 
 impl FseCore {
+    /// Heap held from construction. Excludes the lenient pad mode `pad` buffer, which grows on
+    /// demand to the largest LMD payload encountered.
+    pub const HEAP_SIZE: usize = Literals::HEAP_SIZE + Lmds::HEAP_SIZE;
+
+    #[inline(always)]
+    pub fn set_pad_mode(&mut self, pad_mode: PadMode) {
+        self.pad_mode = pad_mode;
+    }
+
+    #[inline(always)]
+    pub fn pad_mode(&self) -> PadMode {
+        self.pad_mode
+    }
+
+    #[inline(always)]
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    #[inline(always)]
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Loaded block literal and LMD payload length, excluding the header.
+    #[inline(always)]
+    pub fn n_payload_bytes(&self) -> u32 {
+        self.block.n_payload_bytes()
+    }
+
+    /// Loaded block declared raw byte count.
+    #[inline(always)]
+    pub fn n_raw_bytes(&self) -> u32 {
+        self.block.n_raw_bytes()
+    }
+
     pub fn load_v1<I>(&mut self, mut src: I) -> crate::Result<u32>
     where
         I: Copy + ShortBuffer,
@@ -64,7 +110,11 @@ impl FseCore {
         I: BitSrc + Copy + ShortBuffer,
     {
         let payload = src.take(self.n_literal_payload_bytes())?;
-        self.literals.load(payload, &self.decoder, self.block.literal())?;
+        if self.strict {
+            self.literals.load::<_, true>(payload, &self.decoder, self.block.literal())?;
+        } else {
+            self.literals.load::<_, false>(payload, &self.decoder, self.block.literal())?;
+        }
         Ok(self.n_literal_payload_bytes())
     }
 
@@ -73,7 +123,20 @@ impl FseCore {
         I: BitSrc + Copy + ShortBuffer,
     {
         let payload = src.take(self.n_lmd_payload_bytes())?;
-        self.lmds.load(payload, &self.decoder, self.block.lmd())?;
+        if self.pad_mode == PadMode::Lenient {
+            self.load_pad(payload)?;
+        }
+        let lmd = self.block.lmd();
+        match (self.pad_mode, self.strict) {
+            (PadMode::Lenient, false) => {
+                self.lmds.load::<_, false>(self.pad.as_slice(), &self.decoder, lmd)?
+            }
+            (PadMode::Lenient, true) => {
+                self.lmds.load::<_, true>(self.pad.as_slice(), &self.decoder, lmd)?
+            }
+            (_, false) => self.lmds.load::<_, false>(payload, &self.decoder, lmd)?,
+            (_, true) => self.lmds.load::<_, true>(payload, &self.decoder, lmd)?,
+        }
         Ok(self.n_lmd_payload_bytes())
     }
 
@@ -83,12 +146,34 @@ impl FseCore {
         I: BitSrc + Copy + ShortBuffer,
     {
         let payload = src.take(self.n_lmd_payload_bytes())?;
-        self.decode_internal(dst, payload)?;
+        match (self.pad_mode, self.strict) {
+            (PadMode::Lenient, false) => {
+                self.load_pad(payload)?;
+                self.decode_internal::<_, _, false>(dst, self.pad.as_slice())?;
+            }
+            (PadMode::Lenient, true) => {
+                self.load_pad(payload)?;
+                self.decode_internal::<_, _, true>(dst, self.pad.as_slice())?;
+            }
+            (_, false) => self.decode_internal::<_, _, false>(dst, payload)?,
+            (_, true) => self.decode_internal::<_, _, true>(dst, payload)?,
+        }
         Ok(self.n_lmd_payload_bytes())
     }
 
+    fn load_pad<I: CopyLong>(&mut self, payload: I) -> crate::Result<()> {
+        self.pad.clear();
+        self.pad.extend_from_slice(&[0u8; 8]);
+        self.pad.write_long(payload)?;
+        Ok(())
+    }
+
     #[inline(always)]
-    fn decode_internal<O: LzWriter, T: BitSrc>(&self, dst: &mut O, src: T) -> crate::Result<()> {
+    fn decode_internal<O: LzWriter, T: BitSrc, const STRICT: bool>(
+        &self,
+        dst: &mut O,
+        src: T,
+    ) -> crate::Result<()> {
         let mut reader = BitReader::new(src, self.block.lmd().bits() as usize)?;
         let state = self.block.lmd().state();
         let mut state = (
@@ -112,6 +197,9 @@ impl FseCore {
             reader.flush();
             let match_distance_pack = unsafe { self.decoder.d(&mut reader, &mut state.2) };
             reader.flush();
+            if STRICT && self.decoder.is_lmd_latched(&state) {
+                return Err(FseErrorKind::LatchState.into());
+            }
             match_distance.substitute(match_distance_pack);
             let ptr = unsafe { self.literals.as_ptr().add(literal_index as usize) };
             let bytes = unsafe { ShortBytes::from_raw_parts(ptr, literal_len.get() as usize) };
@@ -140,6 +228,95 @@ impl FseCore {
         }
     }
 
+    /// Walk the loaded block's literal and LMD payloads tallying the bits spent on each component.
+    /// `src` holds exactly the block bytes as previously loaded and decoded.
+    pub fn cost(&self, src: &[u8]) -> crate::Result<BlockCost> {
+        let n_lmd_payload_bytes = self.n_lmd_payload_bytes() as usize;
+        let n_literal_payload_bytes = self.n_literal_payload_bytes() as usize;
+        if src.len() < n_lmd_payload_bytes + n_literal_payload_bytes {
+            return Err(Error::PayloadUnderflow);
+        }
+        let (head, lmd_src) = src.split_at(src.len() - n_lmd_payload_bytes);
+        let literal_src = &head[head.len() - n_literal_payload_bytes..];
+        let literal_bits = self.literal_cost(literal_src)?;
+        let [l_bits, m_bits, d_bits] = if self.pad_mode == PadMode::Lenient {
+            let mut pad = vec![0u8; 8];
+            pad.extend_from_slice(lmd_src);
+            self.lmd_cost(pad.as_slice())?
+        } else {
+            self.lmd_cost(lmd_src)?
+        };
+        // The literal payload borrows 8 bytes from the header.
+        let n_header_bytes = head.len() + 8 - n_literal_payload_bytes;
+        let mut cost = BlockCost {
+            index: 0,
+            n_raw_bytes: self.block.n_raw_bytes(),
+            n_payload_bytes: src.len() as u32,
+            header_bits: n_header_bytes as u64 * 8,
+            literal_bits,
+            literal_len_bits: l_bits,
+            match_len_bits: m_bits,
+            match_distance_bits: d_bits,
+            pad_bits: 0,
+        };
+        cost.pad_bits = src.len() as u64 * 8 - cost.n_bits();
+        Ok(cost)
+    }
+
+    #[allow(clippy::identity_op)]
+    fn literal_cost<T: BitSrc>(&self, src: T) -> crate::Result<u64> {
+        let param = self.block.literal();
+        let mut reader = BitReader::new(src, param.bits() as usize)?;
+        let state = param.state();
+        let mut state = [
+            decoder::U::new(state[0] as usize),
+            decoder::U::new(state[1] as usize),
+            decoder::U::new(state[2] as usize),
+            decoder::U::new(state[3] as usize),
+        ];
+        let mut n_bits = 0;
+        let mut i = 0;
+        while i != param.num() {
+            for u in state.iter_mut() {
+                n_bits += self.decoder.u_bits(u.get()) as u64;
+                unsafe { self.decoder.u(&mut reader, u) };
+                #[cfg(target_pointer_width = "32")]
+                reader.flush();
+            }
+            reader.flush();
+            i += 4;
+        }
+        reader.finalize()?;
+        Ok(n_bits)
+    }
+
+    fn lmd_cost<T: BitSrc>(&self, src: T) -> crate::Result<[u64; 3]> {
+        let param = self.block.lmd();
+        let mut reader = BitReader::new(src, param.bits() as usize)?;
+        let state = param.state();
+        let mut state = (
+            decoder::L::new(state[0] as usize),
+            decoder::M::new(state[1] as usize),
+            decoder::D::new(state[2] as usize),
+        );
+        let mut n_bits = [0u64; 3];
+        for _ in 0..param.num() {
+            n_bits[0] += self.decoder.v_bits(state.0.get()) as u64;
+            unsafe { self.decoder.l(&mut reader, &mut state.0) };
+            #[cfg(target_pointer_width = "32")]
+            reader.flush();
+            n_bits[1] += self.decoder.v_bits(state.1.get()) as u64;
+            unsafe { self.decoder.m(&mut reader, &mut state.1) };
+            #[cfg(target_pointer_width = "32")]
+            reader.flush();
+            n_bits[2] += self.decoder.v_bits(state.2.get()) as u64;
+            unsafe { self.decoder.d(&mut reader, &mut state.2) };
+            reader.flush();
+        }
+        reader.finalize()?;
+        Ok(n_bits)
+    }
+
     pub fn decode_n_init<O: LzWriter>(&mut self, dst: &O) {
         self.literal_index = 0;
         self.lmd_index = 0;
@@ -19,6 +19,8 @@ const BUF_LEN: usize = LITERALS_PER_BLOCK as usize + MAX_L_VALUE as usize + WIDE
 pub struct Literals(Box<[u8]>, pub usize);
 
 impl Literals {
+    pub const HEAP_SIZE: usize = BUF_LEN;
+
     #[inline(always)]
     pub unsafe fn push_unchecked_max<I>(&mut self, literals: &mut I)
     where
@@ -46,7 +48,13 @@ impl Literals {
     }
 
     #[allow(clippy::identity_op)]
-    pub fn load<T>(&mut self, src: T, decoder: &Decoder, param: &LiteralParam) -> crate::Result<()>
+    /// With `STRICT` latched states are rejected as soon as they are entered.
+    pub fn load<T, const STRICT: bool>(
+        &mut self,
+        src: T,
+        decoder: &Decoder,
+        param: &LiteralParam,
+    ) -> crate::Result<()>
     where
         T: BitSrc,
     {
@@ -73,6 +81,14 @@ impl Literals {
             unsafe { *ptr.add(i + 2) = decoder.u(&mut reader, &mut state.2) };
             unsafe { *ptr.add(i + 3) = decoder.u(&mut reader, &mut state.3) };
             reader.flush();
+            if STRICT
+                && (decoder.is_u_latched(&state.0)
+                    || decoder.is_u_latched(&state.1)
+                    || decoder.is_u_latched(&state.2)
+                    || decoder.is_u_latched(&state.3))
+            {
+                return Err(FseErrorKind::LatchState.into());
+            }
             i += 4;
         }
         reader.finalize()?;
@@ -178,7 +194,7 @@ impl Default for Literals {
 
 #[cfg(test)]
 mod tests {
-    use crate::fse::Weights;
+    use crate::fse::{NormalizationStrategy, Weights};
 
     use test_kit::{Rng, Seq};
 
@@ -208,7 +224,7 @@ mod tests {
         }
 
         fn encode(&mut self) -> io::Result<()> {
-            let u = self.weights.load(&[], self.src.as_ref());
+            let u = self.weights.load(&[], self.src.as_ref(), NormalizationStrategy::default());
             self.src.pad_u(u);
             self.encoder.init(&self.weights);
             self.enc.clear();
@@ -220,7 +236,7 @@ mod tests {
 
         fn decode(&mut self) -> io::Result<()> {
             self.decoder.init(&self.weights);
-            self.dst.load(self.enc.as_slice(), &self.decoder, &self.param)?;
+            self.dst.load::<_, false>(self.enc.as_slice(), &self.decoder, &self.param)?;
             Ok(())
         }
 
@@ -10,6 +10,7 @@ use super::error_kind::FseErrorKind;
 use super::object::Fse;
 
 use std::io;
+use std::mem;
 
 const BUF_LEN: usize = LMDS_PER_BLOCK as usize;
 
@@ -17,6 +18,8 @@ const BUF_LEN: usize = LMDS_PER_BLOCK as usize;
 pub struct Lmds(Box<[LmdPack<Fse>]>, usize);
 
 impl Lmds {
+    pub const HEAP_SIZE: usize = BUF_LEN * mem::size_of::<LmdPack<Fse>>();
+
     #[inline(always)]
     pub unsafe fn push_unchecked(&mut self, lmd: LmdPack<Fse>) {
         debug_assert!(self.1 < LMDS_PER_BLOCK as usize);
@@ -24,7 +27,13 @@ impl Lmds {
         self.1 += 1;
     }
 
-    pub fn load<T>(&mut self, src: T, decoder: &Decoder, param: &LmdParam) -> crate::Result<()>
+    /// With `STRICT` latched states are rejected as soon as they are entered.
+    pub fn load<T, const STRICT: bool>(
+        &mut self,
+        src: T,
+        decoder: &Decoder,
+        param: &LmdParam,
+    ) -> crate::Result<()>
     where
         T: BitSrc,
     {
@@ -49,6 +58,9 @@ impl Lmds {
             reader.flush();
             let match_distance_zeroed = unsafe { decoder.d(&mut reader, &mut state.2) };
             reader.flush();
+            if STRICT && decoder.is_lmd_latched(&state) {
+                return Err(FseErrorKind::LatchState.into());
+            }
             *lmd = LmdPack(literal_len.into(), match_len.into(), match_distance_zeroed);
         }
         reader.finalize()?;
@@ -121,7 +133,7 @@ impl Default for Lmds {
 
 #[cfg(test)]
 mod tests {
-    use crate::fse::Weights;
+    use crate::fse::{NormalizationStrategy, Weights};
 
     use test_kit::Rng;
 
@@ -148,7 +160,7 @@ mod tests {
         }
 
         fn encode(&mut self) -> io::Result<()> {
-            self.weights.load(self.src.as_ref(), &[]);
+            self.weights.load(self.src.as_ref(), &[], NormalizationStrategy::default());
             self.encoder.init(&self.weights);
             self.enc.clear();
             self.param = self.src.store(&mut self.enc, &self.encoder)?;
@@ -158,7 +170,7 @@ mod tests {
 
         fn decode(&mut self) -> io::Result<()> {
             self.decoder.init(&self.weights);
-            self.dst.load(self.enc.as_slice(), &self.decoder, &self.param)?;
+            self.dst.load::<_, false>(self.enc.as_slice(), &self.decoder, &self.param)?;
             Ok(())
         }
 
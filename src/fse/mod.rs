@@ -8,7 +8,9 @@ mod error_kind;
 mod fse_core;
 mod literals;
 mod lmds;
+mod normalization_strategy;
 mod object;
+mod pad_mode;
 mod probe;
 mod weight_encoder;
 mod weights;
@@ -25,6 +27,8 @@ pub use decoder::Decoder;
 pub use encoder::Encoder;
 pub use error_kind::FseErrorKind;
 pub use fse_core::FseCore;
+pub use normalization_strategy::NormalizationStrategy;
 pub use object::Fse;
-pub use probe::{v1_probe, v2_probe};
+pub use pad_mode::PadMode;
+pub use probe::{v1_probe, v1_probe_lz, v2_probe, v2_probe_lz};
 pub use weights::Weights;
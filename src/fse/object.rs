@@ -37,8 +37,7 @@ unsafe impl MatchUnit for Fse {
     #[inline(always)]
     fn hash_u(u: u32) -> u32 {
         // Donald Knuth's multiplicative hash as per LZFSE reference.
-        // As we don't swap bytes, big/ little endian implementations will produce different,
-        // although presumably statistically equivalent outputs.
+        // Values are little endian, hence outputs are platform independent.
         u.wrapping_mul(0x9E37_79B1)
     }
 }
@@ -0,0 +1,24 @@
+/// FSE bitstream pad mode.
+///
+/// Reference encoders precede each LZ bitstream with an 8 byte zero pad. Some third-party encoders
+/// may emit streams with less padding.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum PadMode {
+    /// Require the full 8 byte pad.
+    Strict,
+    /// Tolerate missing pad bytes, which are synthesized as zeros. Slower.
+    ///
+    /// LMD payloads shorter than 8 bytes are rejected in either mode.
+    ///
+    /// Streams that genuinely lack data are rejected with
+    /// [Error::PayloadUnderflow](crate::Error::PayloadUnderflow).
+    Lenient,
+}
+
+impl Default for PadMode {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::Strict
+    }
+}
@@ -3,23 +3,41 @@ use crate::types::ShortBuffer;
 use super::block::FseBlock;
 
 pub fn v1_probe<I>(src: I) -> crate::Result<(u32, u32)>
+where
+    I: Copy + ShortBuffer,
+{
+    v1_probe_lz(src).map(|u| (u.0, u.1))
+}
+
+pub fn v2_probe<I>(src: I) -> crate::Result<(u32, u32)>
+where
+    I: Copy + ShortBuffer,
+{
+    v2_probe_lz(src).map(|u| (u.0, u.1))
+}
+
+/// As `v1_probe`, additionally returning the literal count and the match count.
+pub fn v1_probe_lz<I>(src: I) -> crate::Result<(u32, u32, u32, u32)>
 where
     I: Copy + ShortBuffer,
 {
     let mut block = FseBlock::default();
     let (n_header_payload_bytes, n_weight_payload_bytes) = block.load_v1_short(src)?;
-    let n_payload_bytes = n_header_payload_bytes + n_weight_payload_bytes + block.n_payload_bytes();
-    let n_raw_bytes = block.n_raw_bytes();
-    Ok((n_payload_bytes, n_raw_bytes))
+    Ok(lz(&block, n_header_payload_bytes + n_weight_payload_bytes))
 }
 
-pub fn v2_probe<I>(src: I) -> crate::Result<(u32, u32)>
+/// As `v2_probe`, additionally returning the literal count and the match count.
+pub fn v2_probe_lz<I>(src: I) -> crate::Result<(u32, u32, u32, u32)>
 where
     I: Copy + ShortBuffer,
 {
     let mut block = FseBlock::default();
     let (n_header_payload_bytes, n_weight_payload_bytes) = block.load_v2_short(src)?;
-    let n_payload_bytes = n_header_payload_bytes + n_weight_payload_bytes + block.n_payload_bytes();
-    let n_raw_bytes = block.n_raw_bytes();
-    Ok((n_payload_bytes, n_raw_bytes))
+    Ok(lz(&block, n_header_payload_bytes + n_weight_payload_bytes))
+}
+
+#[inline(always)]
+fn lz(block: &FseBlock, n_header_bytes: u32) -> (u32, u32, u32, u32) {
+    let n_payload_bytes = n_header_bytes + block.n_payload_bytes();
+    (n_payload_bytes, block.n_raw_bytes(), block.literal().num(), block.lmd().num())
 }
@@ -9,6 +9,7 @@ use test_kit::{Rng, Seq};
 
 use super::block::{FseBlock, LiteralParam, LmdParam};
 use super::fse_core::FseCore;
+use super::pad_mode::PadMode;
 use super::Fse;
 use super::{constants::*, FseBackend};
 
@@ -148,6 +149,25 @@ impl Buddy {
             Ok(false)
         }
     }
+
+    // Remove `n` leading LMD pad bytes.
+    fn trim_lmd_pad(&mut self, n: u32) -> crate::Result<()> {
+        let mut src = self.enc.as_slice();
+        let n_header_bytes = self.core.load_v2(src)?;
+        src.skip(n_header_bytes as usize);
+        let n_literal_bytes = self.core.load_literals(src)?;
+        let index = (n_header_bytes + n_literal_bytes) as usize;
+        let mut block = FseBlock::default();
+        let (n_header_bytes, n_weight_payload_bytes) =
+            block.load_v2_short(self.enc.as_slice().short_bytes())?;
+        let lmd = *block.lmd();
+        let lmd = LmdParam::new(lmd.num(), lmd.n_payload_bytes() - n, lmd.bits(), *lmd.state())?;
+        let block = FseBlock::new(block.n_raw_bytes(), *block.literal(), lmd)?;
+        let bytes = self.enc.patch_into(Idx::default(), n_header_bytes as usize);
+        block.store_v2(bytes, n_weight_payload_bytes);
+        self.enc.drain(index..index + n as usize);
+        Ok(())
+    }
 }
 
 // Quote.
@@ -561,3 +581,122 @@ fn fuzz_noise() -> crate::Result<()> {
     }
     Ok(())
 }
+
+// Max valued v1 header fields. In all cases the decoder should reject the header via `Err(error)`
+// with explicit range checks rather than wrapping arithmetic. The bits fields are excluded as
+// their max values are valid post negation.
+#[test]
+fn header_max_v1() -> crate::Result<()> {
+    let bytes = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let mut buddy = Buddy::default();
+    let mut lmds = Vec::default();
+    lmd::split_lmd(&mut lmds, bytes.len() as u32, 0x1000, 1);
+    buddy.encode_lmds(&bytes, &lmds)?;
+    let mut block = FseBlock::default();
+    block.load_v2_short(buddy.enc.as_slice().short_bytes())?;
+    let mut header = [0u8; V1_HEADER_SIZE as usize];
+    block.store_v1(&mut header);
+    FseBlock::default().load_v1(&header)?;
+    for &(index, len) in &[
+        (0x04, 4),
+        (0x08, 4),
+        (0x0C, 4),
+        (0x10, 4),
+        (0x14, 4),
+        (0x18, 4),
+        (0x20, 2),
+        (0x22, 2),
+        (0x24, 2),
+        (0x26, 2),
+        (0x2C, 2),
+        (0x2E, 2),
+        (0x30, 2),
+    ] {
+        let mut bytes = header;
+        bytes[index..index + len].iter_mut().for_each(|u| *u = 0xFF);
+        assert!(FseBlock::default().load_v1(&bytes).is_err());
+        assert!(super::v1_probe(bytes.as_ref()).is_err());
+    }
+    Ok(())
+}
+
+// Max valued v2 header fields. In all cases the decoder should reject the header via `Err(error)`
+// with explicit range checks rather than wrapping arithmetic. The bits and literal state fields are
+// excluded as their max values are valid.
+#[test]
+fn header_max_v2() -> crate::Result<()> {
+    let bytes = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let mut buddy = Buddy::default();
+    let mut lmds = Vec::default();
+    lmd::split_lmd(&mut lmds, bytes.len() as u32, 0x1000, 1);
+    buddy.encode_lmds(&bytes, &lmds)?;
+    let mut header = [0u8; V2_HEADER_SIZE as usize];
+    header.copy_from_slice(&buddy.enc[..V2_HEADER_SIZE as usize]);
+    FseBlock::default().load_v2(&header)?;
+    // (byte index, bit offset, bit width)
+    for &(index, offset, width) in &[
+        (0x04, 0, 32),
+        (0x08, 0, 20),
+        (0x08, 20, 20),
+        (0x08, 40, 20),
+        (0x10, 40, 20),
+        (0x18, 0, 32),
+        (0x18, 32, 10),
+        (0x18, 42, 10),
+        (0x18, 52, 10),
+    ] {
+        let mut bytes = header;
+        let mut p = [0u8; 8];
+        p.copy_from_slice(&bytes[index..index + 8]);
+        let u = u64::from_le_bytes(p) | ((1u64 << width) - 1) << offset;
+        bytes[index..index + 8].copy_from_slice(&u.to_le_bytes());
+        assert!(FseBlock::default().load_v2(&bytes).is_err());
+        assert!(super::v2_probe(bytes.as_ref()).is_err());
+    }
+    Ok(())
+}
+
+// Trimmed LMD pad, minimally padded streams are accepted in lenient mode only.
+#[test]
+fn lenient_pad() -> crate::Result<()> {
+    let bytes = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let mut buddy = Buddy::default();
+    let mut lmds = Vec::default();
+    for &(match_len, match_distance) in &[(0, 1), (0x1000, 1), (0x0100, 0x0100)] {
+        for n in 0..=8 {
+            lmds.clear();
+            lmd::split_lmd(&mut lmds, bytes.len() as u32, match_len, match_distance);
+            buddy.encode_lmds(&bytes, &lmds)?;
+            buddy.trim_lmd_pad(n)?;
+            buddy.core.set_pad_mode(PadMode::Lenient);
+            buddy.decode()?;
+            assert!(buddy.check_lmds(&bytes, &lmds));
+            buddy.decode_n(0x0100)?;
+            assert!(buddy.check_lmds(&bytes, &lmds));
+            buddy.core.set_pad_mode(PadMode::Strict);
+            if n != 0 {
+                assert!(buddy.decode().is_err());
+                assert!(buddy.decode_n(0x0100).is_err());
+            }
+        }
+        // Beyond the pad.
+        buddy.encode_lmds(&bytes, &lmds)?;
+        buddy.trim_lmd_pad(9)?;
+        buddy.core.set_pad_mode(PadMode::Lenient);
+        match buddy.decode() {
+            Err(Error::PayloadUnderflow) => {}
+            Err(err) => panic!("{:?}", err),
+            Ok(_) => panic!(),
+        }
+    }
+    Ok(())
+}
+
+// LMD payloads below the 8 byte bit reader minimum are rejected irrespective of the pad mode.
+#[test]
+fn lmd_payload_min() {
+    for n_payload_bytes in 0..8 {
+        assert!(LmdParam::new(0, n_payload_bytes, 0, [0; 3]).is_err());
+    }
+    assert!(LmdParam::new(0, 8, 0, [0; 3]).is_ok());
+}
@@ -3,6 +3,7 @@ use crate::types::ShortWriter;
 
 use super::constants::{self, *};
 use super::error_kind::FseErrorKind;
+use super::normalization_strategy::NormalizationStrategy;
 use super::weight_encoder::{self};
 use super::Fse;
 use std::mem;
@@ -22,17 +23,22 @@ pub struct Weights([u16; N_WEIGHTS]);
 
 impl Weights {
     #[inline(never)]
-    pub fn load(&mut self, lmds: &[LmdPack<Fse>], literals: &[u8]) -> u8 {
+    pub fn load(
+        &mut self,
+        lmds: &[LmdPack<Fse>],
+        literals: &[u8],
+        strategy: NormalizationStrategy,
+    ) -> u8 {
         self.reset_weights();
-        self.add_lmds(lmds);
-        self.add_literals(literals)
+        self.add_lmds(lmds, strategy);
+        self.add_literals(literals, strategy)
     }
 
     pub fn reset_weights(&mut self) {
         reset_weights(&mut self.0);
     }
 
-    fn add_lmds(&mut self, lmds: &[LmdPack<Fse>]) {
+    fn add_lmds(&mut self, lmds: &[LmdPack<Fse>], strategy: NormalizationStrategy) {
         if lmds.is_empty() {
             return;
         }
@@ -48,19 +54,19 @@ impl Weights {
                 *self.0[D_RANGE].get_unchecked_mut(d_base) += 1
             }
         }
-        normalize_m1(&mut self.0[L_RANGE], lmds.len() as u32, L_STATES);
-        normalize_m1(&mut self.0[M_RANGE], lmds.len() as u32, M_STATES);
-        normalize_m1(&mut self.0[D_RANGE], lmds.len() as u32, D_STATES);
+        normalize(&mut self.0[L_RANGE], lmds.len() as u32, L_STATES, strategy);
+        normalize(&mut self.0[M_RANGE], lmds.len() as u32, M_STATES, strategy);
+        normalize(&mut self.0[D_RANGE], lmds.len() as u32, D_STATES, strategy);
     }
 
-    fn add_literals(&mut self, literals: &[u8]) -> u8 {
+    fn add_literals(&mut self, literals: &[u8], strategy: NormalizationStrategy) -> u8 {
         if literals.is_empty() {
             return 0;
         }
         for &u in literals {
             (&mut self.0[U_RANGE])[u as usize] += 1;
         }
-        normalize_m1(&mut self.0[U_RANGE], literals.len() as u32, U_STATES) as u8
+        normalize(&mut self.0[U_RANGE], literals.len() as u32, U_STATES, strategy) as u8
     }
 
     pub fn load_v1(&mut self, src: &[u8]) -> crate::Result<()> {
@@ -215,6 +221,18 @@ fn reset_weights(weights: &mut [u16]) {
     weights.iter_mut().for_each(|u| *u = 0);
 }
 
+fn normalize(
+    weights: &mut [u16],
+    in_total: u32,
+    out_total: u32,
+    strategy: NormalizationStrategy,
+) -> usize {
+    match strategy {
+        NormalizationStrategy::Fast => normalize_m1(weights, in_total, out_total),
+        NormalizationStrategy::Accurate => normalize_cost(weights, in_total, out_total),
+    }
+}
+
 pub fn normalize_m1(weights: &mut [u16], in_total: u32, out_total: u32) -> usize {
     assert!(out_total.is_power_of_two());
     assert!(out_total <= 0x4000_0000);
@@ -260,6 +278,92 @@ fn normalize_m1_coarse(weights: &mut [u16], in_total: u32, out_total: u32) -> (i
     (remaining, max_weight_index)
 }
 
+// Greedy cost-minimizing normalization. The encoded cost of a symbol with count `c` and weight `w`
+// approximates `c * log2(out_total / w)` bits. Starting from the proportional floor, with a
+// minimum weight of 1, we add or remove single states until the total is reached and then exchange
+// states whilst doing so reduces the cost. The cost is convex per symbol, so the result is optimal.
+// Weight header costs are not considered.
+pub fn normalize_cost(weights: &mut [u16], in_total: u32, out_total: u32) -> usize {
+    assert!(out_total.is_power_of_two());
+    assert!(out_total <= 0x4000_0000);
+    assert!(weights.len() <= out_total as usize);
+    assert!(weights.len() <= 0x0400);
+    debug_assert_eq!(total_weights(weights), in_total);
+    if in_total == 0 {
+        return 0;
+    }
+    let mut counts = [0u32; 0x0400];
+    let counts = &mut counts[..weights.len()];
+    let mut remaining = out_total as i64;
+    for (c, w) in counts.iter_mut().zip(weights.iter_mut()) {
+        *c = *w as u32;
+        if *c != 0 {
+            let f = ((*c as u64 * out_total as u64) / in_total as u64).max(1);
+            *w = f as u16;
+            remaining -= f as i64;
+        }
+    }
+    loop {
+        let (add, gain) = best_add(counts, weights);
+        let (sub, loss) = best_sub(counts, weights);
+        if remaining > 0 {
+            weights[add] += 1;
+            remaining -= 1;
+        } else if remaining < 0 {
+            weights[sub] -= 1;
+            remaining += 1;
+        } else if add != sub && gain > loss * (1.0 + 1e-9) {
+            weights[add] += 1;
+            weights[sub] -= 1;
+        } else {
+            break;
+        }
+    }
+    let mut max_weight = 0;
+    let mut max_weight_index = 0;
+    for (i, &w) in weights.iter().enumerate() {
+        if w > max_weight {
+            max_weight = w;
+            max_weight_index = i;
+        }
+    }
+    max_weight_index
+}
+
+// Returns the index with the greatest cost reduction on adding a state and the reduction.
+fn best_add(counts: &[u32], weights: &[u16]) -> (usize, f64) {
+    let mut best = 0.0;
+    let mut best_index = 0;
+    for (i, (&c, &w)) in counts.iter().zip(weights.iter()).enumerate() {
+        if c == 0 {
+            continue;
+        }
+        let gain = c as f64 * (1.0 + 1.0 / w as f64).log2();
+        if gain > best {
+            best = gain;
+            best_index = i;
+        }
+    }
+    (best_index, best)
+}
+
+// Returns the index with the least cost increase on removing a state and the increase.
+fn best_sub(counts: &[u32], weights: &[u16]) -> (usize, f64) {
+    let mut best = f64::MAX;
+    let mut best_index = 0;
+    for (i, (&c, &w)) in counts.iter().zip(weights.iter()).enumerate() {
+        if w <= 1 {
+            continue;
+        }
+        let loss = c as f64 * (w as f64 / (w - 1) as f64).log2();
+        if loss < best {
+            best = loss;
+            best_index = i;
+        }
+    }
+    (best_index, best)
+}
+
 fn normalize_m1_trim(weights: &mut [u16], mut overflow: u32) {
     for shift in (0..=3).rev() {
         for w in weights.iter_mut() {
@@ -363,13 +467,19 @@ mod tests {
         assert!(weights.load_v2(&bs[..n as usize]).is_err());
     }
 
-    fn normalize_check(mut weights: [u16; 12]) {
+    fn normalize_check(weights: [u16; 12]) {
+        normalize_check_with(weights, normalize_m1);
+        normalize_check_with(weights, normalize_cost);
+    }
+
+    fn normalize_check_with(mut weights: [u16; 12], normalize: fn(&mut [u16], u32, u32) -> usize) {
         for &out_total in &[64, 256, 1024] {
             while weights[0] != 0 {
                 let mut copy = weights;
                 let in_total = total_weights(&copy);
-                normalize_m1(&mut copy, in_total, out_total);
+                let index = normalize(&mut copy, in_total, out_total);
                 assert_eq!(total_weights(&copy), out_total);
+                assert!(copy[index] != 0);
                 for (&w, &c) in weights.iter().zip(copy.iter()) {
                     if w != 0 {
                         assert!(c != 0);
@@ -465,6 +575,38 @@ mod tests {
         }
     }
 
+    fn cost(counts: &[u16], weights: &[u16], out_total: u32) -> f64 {
+        counts
+            .iter()
+            .zip(weights.iter())
+            .filter(|(&c, _)| c != 0)
+            .map(|(&c, &w)| c as f64 * (out_total as f64 / w as f64).log2())
+            .sum()
+    }
+
+    #[test]
+    fn normalize_cost_le_m1() {
+        let mut rng = Rng::default();
+        for _ in 0..1024 {
+            let mut weights = [0u16; 12];
+            for w in weights.iter_mut() {
+                let v = rng.gen() % 0x1_0000;
+                let v = (v * v) / 0x10_0000;
+                *w = v as u16;
+            }
+            let in_total = total_weights(&weights);
+            for &out_total in &[64, 256, 1024] {
+                let mut m1 = weights;
+                let mut accurate = weights;
+                normalize_m1(&mut m1, in_total, out_total);
+                normalize_cost(&mut accurate, in_total, out_total);
+                let m1 = cost(&weights, &m1, out_total);
+                let accurate = cost(&weights, &accurate, out_total);
+                assert!(accurate <= m1 + 1e-6);
+            }
+        }
+    }
+
     #[test]
     fn trim_0() {
         trim_check([2048, 1024, 512, 256, 128, 64, 32, 16, 8, 4, 2, 1]);
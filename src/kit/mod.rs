@@ -1,9 +1,11 @@
 mod copy;
 mod pack_bits;
 mod read_ext_fully;
+mod warm;
 mod wide;
 
 pub use copy::*;
 pub use pack_bits::PackBits;
 pub use read_ext_fully::ReadExtFully;
+pub use warm::warm_sample;
 pub use wide::*;
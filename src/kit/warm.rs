@@ -0,0 +1,25 @@
+/// Deterministic warm up sample: low entropy literals interspersed with matches over a range of
+/// distances, sufficient to exercise the FSE encode and decode paths.
+pub fn warm_sample() -> Vec<u8> {
+    const LEN: usize = 0x0001_0000;
+    const CHUNK: usize = 0x40;
+    let mut vec = Vec::with_capacity(LEN);
+    let mut x: u32 = 0x1234_5678;
+    while vec.len() < LEN {
+        x = x.wrapping_mul(0x0019_660D).wrapping_add(0x3C6E_F35F);
+        if x & 1 == 1 && CHUNK <= vec.len() {
+            let distance = (x >> 8) as usize % (vec.len() - CHUNK + 1) + CHUNK;
+            let index = vec.len() - distance;
+            for i in index..index + CHUNK {
+                vec.push(vec[i]);
+            }
+        } else {
+            for _ in 0..CHUNK {
+                x = x.wrapping_mul(0x0019_660D).wrapping_add(0x3C6E_F35F);
+                vec.push((x >> 24) as u8 & 0x1F);
+            }
+        }
+    }
+    vec.truncate(LEN);
+    vec
+}
@@ -116,29 +116,73 @@ fn main() -> io::Result<()> {
 ```
 */
 
+mod archive;
 mod base;
 mod bits;
+mod cpu;
 mod decode;
 mod encode;
 mod error;
+mod footprint;
+mod frame;
 mod fse;
 mod kit;
 mod lmd;
 mod lz;
 mod match_kit;
+mod metadata;
+mod mux;
 mod ops;
 mod raw;
 mod ring;
+mod spsc;
+mod trailer;
 mod types;
 mod vn;
 
+pub mod checksum;
 #[cfg(test)]
 pub mod test_utils;
-
-pub use decode::{decode_bytes, LzfseDecoder, LzfseReader, LzfseReaderBytes, LzfseRingDecoder};
-pub use encode::{encode_bytes, LzfseEncoder, LzfseRingEncoder, LzfseWriter, LzfseWriterBytes};
-pub use error::{Error, Result};
-pub use fse::FseErrorKind;
+#[cfg(feature = "testdata")]
+pub mod testdata;
+
+pub use archive::{ArchiveMember, ArchiveReader, ArchiveWriter};
+pub use base::{peek_frame_kind, FrameKind, TrailerKind};
+pub use cpu::{cpu_features, CpuFeatures};
+#[cfg(feature = "digest")]
+pub use decode::decode_verify_digest;
+#[cfg(feature = "rayon")]
+pub use decode::verify_many;
+#[cfg(feature = "tokio")]
+pub use decode::AsyncLzfseReader;
+pub use decode::{
+    decode_block, decode_blocks_vec, decode_bounded, decode_bytes, decode_bytes_expect,
+    decode_bytes_into, decode_chunked, decode_string, decoded_len, explain, quick_verify,
+    BlockCost, CircularFileSink, DecodeEvent, DecodeLeniency, DecodeReport, DecodedCursor,
+    FrameSource, LzfseDecoder, LzfseReader, LzfseReaderBytes, LzfseRingDecoder, OutputSink,
+    RingProfile,
+};
+#[cfg(feature = "rayon")]
+pub use encode::encode_bytes_parallel;
+#[cfg(feature = "digest")]
+pub use encode::encode_with_digest;
+#[cfg(feature = "tokio")]
+pub use encode::AsyncLzfseWriter;
+pub use encode::{
+    block_ratios, compress_reader_to_writer, encode_bytes, encode_segmented, encode_str,
+    encode_within, entropy_floor, lz_entropy_floor, match_distance_histogram, merge_reencode,
+    repack, BlockStats, BlockStrategy, EncodeStats, FrameSink, HistoryTable, LzfseEncoder,
+    LzfseEncoderBuilder, LzfseRingEncoder, LzfseSink, LzfseWriter, LzfseWriterBytes, MatchStrategy,
+    ReplayEncoder, ReplayPoint, SegmentEntry, N_BUCKETS,
+};
+pub use error::{DecodeError, Error, Result};
+pub use footprint::{memory_footprint, Role};
+pub use frame::{add_frame, strip_frame, FrameInfo};
+pub use fse::{FseErrorKind, NormalizationStrategy, PadMode};
+pub use metadata::Metadata;
+pub use mux::{MuxDecoder, MuxEncoder, MuxSegment};
+pub use raw::{decode_raw_block, encode_raw_block, RAW_HEADER_SIZE};
+pub use spsc::{spsc_ring, SpscReader, SpscWriter};
 pub use vn::VnErrorKind;
 
 #[cfg(test)]
@@ -8,6 +8,39 @@ use std::ptr;
 const OFFSET: [u32; 8] = [0, 0, 2, 1, 0, 4, 4, 4];
 const DELTA: [u32; 8] = [0, 0, 2, 2, 4, 3, 2, 1];
 
+/// Bounds checked match copy of `len` bytes from `buf[src..]` to `buf[dst..]`.
+///
+/// Overlapping matches, `src < dst < src + len`, replicate the `dst - src` byte pattern as per LZ
+/// semantics. Used in place of the unchecked copies by the `safe-copy` feature, all match copies
+/// are routed through this single routine.
+///
+/// # Errors
+///
+/// * [Error::BadDValue](crate::Error::BadDValue) if `src == dst` or either range exceeds `buf`.
+pub fn write_match_checked(
+    buf: &mut [u8],
+    src: usize,
+    dst: usize,
+    len: usize,
+) -> crate::Result<()> {
+    let is_in_bounds = |index: usize| matches!(index.checked_add(len), Some(u) if u <= buf.len());
+    if src == dst || !is_in_bounds(src) || !is_in_bounds(dst) {
+        return Err(crate::Error::BadDValue);
+    }
+    if dst < src || src + len <= dst {
+        buf.copy_within(src..src + len, dst);
+    } else {
+        let distance = dst - src;
+        let mut off = 0;
+        while off < len {
+            let n = distance.min(len - off);
+            buf.copy_within(src + off..src + off + n, dst + off);
+            off += n;
+        }
+    }
+    Ok(())
+}
+
 // Implementation notes:
 //
 // Public function method signatures are low level benchmark driven as opposed to a more
@@ -154,6 +154,11 @@ impl LzWriter for Vec<u8> {
         let len = len.get();
         let distance = distance.get() as usize;
         let dst_index = self.len();
+        if cfg!(feature = "safe-copy") {
+            let src_index = dst_index.checked_sub(distance).ok_or(Error::BadDValue)?;
+            self.resize(dst_index + len as usize, 0);
+            return object::write_match_checked(self, src_index, dst_index, len as usize);
+        }
         if distance <= dst_index {
             // Likely
             let src_index = dst_index - distance;
@@ -416,4 +421,39 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn write_match_checked() -> crate::Result<()> {
+        let src = Seq::default().take(0x0100).collect::<Vec<_>>();
+        for distance in 1..0x0040 {
+            for len in 0..0x0080 {
+                let mut buf = src.clone();
+                let mut expected = src.clone();
+                let dst = 0x0040;
+                for i in 0..len {
+                    expected[dst + i] = expected[dst - distance + i];
+                }
+                object::write_match_checked(&mut buf, dst - distance, dst, len)?;
+                assert!(buf == expected);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn write_match_checked_bounds() {
+        let mut buf = [0u8; 0x0100];
+        for &(src, dst, len) in &[
+            (0x0010, 0x0010, 0x0001),
+            (0x0000, 0x00FF, 0x0002),
+            (0x00FF, 0x0000, 0x0002),
+            (0x0000, 0x0101, 0x0000),
+            (0x0000, 0x0001, usize::MAX),
+        ] {
+            match object::write_match_checked(&mut buf, src, dst, len) {
+                Err(Error::BadDValue) => {}
+                _ => panic!(),
+            }
+        }
+    }
 }
@@ -18,7 +18,8 @@ pub fn fast_match_inc(
 }
 
 #[allow(clippy::missing_safety_doc)]
-#[inline(always)]
+#[cfg_attr(not(feature = "size-opt"), inline(always))]
+#[cfg_attr(feature = "size-opt", inline)]
 pub unsafe fn fast_match_inc_unchecked(
     bytes: &[u8],
     index: usize,
@@ -57,7 +58,8 @@ pub fn fast_match_dec(bytes: &[u8], index: usize, match_index: usize, max: usize
     unsafe { fast_match_dec_unchecked(bytes, index, match_index, max) }
 }
 
-#[inline(always)]
+#[cfg_attr(not(feature = "size-opt"), inline(always))]
+#[cfg_attr(feature = "size-opt", inline)]
 pub unsafe fn fast_match_dec_unchecked(
     bytes: &[u8],
     index: usize,
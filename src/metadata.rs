@@ -0,0 +1,134 @@
+use crate::error::Error;
+
+use std::convert::TryInto;
+use std::io::{self, Read, Write};
+
+/// Original file metadata.
+///
+/// Stored in an optional frame preceding an LZFSE stream, in the manner of `gzip`. The frame is
+/// specific to this crate and is NOT part of the LZFSE format, other LZFSE implementations will
+/// reject it. Frame layout: magic bytes `lzfm`, modification time in seconds since the Unix epoch
+/// (u64 LE), name length (u16 LE), UTF-8 name.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::Metadata;
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let metadata = Metadata { name: "a.txt".to_owned(), mtime: 1_600_000_000 };
+///     let mut enc = Vec::default();
+///     metadata.write_to(&mut enc)?;
+///     lzfse_rust::encode_bytes(b"test", &mut enc)?;
+///     assert!(Metadata::is_metadata(&enc));
+///     let mut src = enc.as_slice();
+///     assert_eq!(Metadata::read_from(&mut src)?, metadata);
+///     let mut dec = Vec::default();
+///     lzfse_rust::decode_bytes(src, &mut dec)?;
+///     assert_eq!(dec, b"test");
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Metadata {
+    /// Original file name.
+    pub name: String,
+    /// Original file modification time in seconds since the Unix epoch.
+    pub mtime: u64,
+}
+
+impl Metadata {
+    /// Metadata frame magic bytes.
+    pub const MAGIC: [u8; 4] = *b"lzfm";
+
+    /// Returns `true` if `bytes` begins with the metadata frame [MAGIC](Self::MAGIC) bytes.
+    pub fn is_metadata(bytes: &[u8]) -> bool {
+        bytes.starts_with(&Self::MAGIC)
+    }
+
+    /// Write the metadata frame into `dst` returning the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::InvalidInput](std::io::ErrorKind) if the name exceeds `u16::MAX` bytes.
+    /// * [Error](std::io::Error) in case of `dst` IO errors.
+    pub fn write_to<W: Write>(&self, dst: &mut W) -> io::Result<u64> {
+        let len: u16 = self
+            .name
+            .len()
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "name overflow"))?;
+        dst.write_all(&Self::MAGIC)?;
+        dst.write_all(&self.mtime.to_le_bytes())?;
+        dst.write_all(&len.to_le_bytes())?;
+        dst.write_all(self.name.as_bytes())?;
+        Ok(14 + len as u64)
+    }
+
+    /// Read a metadata frame from `src`, leaving `src` positioned at the following LZFSE stream.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::BadMetadata](crate::Error::BadMetadata) if `src` does not begin with the
+    ///   metadata frame [MAGIC](Self::MAGIC) bytes.
+    /// * [Error::Utf8](crate::Error::Utf8) if the name is not valid UTF-8.
+    /// * [Error](crate::Error) in case of `src` IO errors, including a truncated frame.
+    pub fn read_from<R: Read>(src: &mut R) -> crate::Result<Self> {
+        let mut header = [0u8; 14];
+        src.read_exact(&mut header)?;
+        if !Self::is_metadata(&header) {
+            return Err(Error::BadMetadata);
+        }
+        let mtime = u64::from_le_bytes(header[4..12].try_into().unwrap());
+        let len = u16::from_le_bytes(header[12..14].try_into().unwrap());
+        let mut name = vec![0u8; len as usize];
+        src.read_exact(&mut name)?;
+        let name = String::from_utf8(name).map_err(|err| Error::Utf8(err.utf8_error()))?;
+        Ok(Self { name, mtime })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() -> crate::Result<()> {
+        for name in &["", "a.txt", "ταχίστη αλώπηξ"] {
+            let metadata = Metadata { name: (*name).to_owned(), mtime: u64::MAX };
+            let mut dst = Vec::default();
+            let n = metadata.write_to(&mut dst)?;
+            assert_eq!(n, dst.len() as u64);
+            let mut src = dst.as_slice();
+            assert_eq!(Metadata::read_from(&mut src)?, metadata);
+            assert!(src.is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bad_magic() {
+        let mut src = [0u8; 14].as_ref();
+        assert!(matches!(Metadata::read_from(&mut src), Err(Error::BadMetadata)));
+    }
+
+    #[test]
+    fn truncated() -> io::Result<()> {
+        let metadata = Metadata { name: "a.txt".to_owned(), mtime: 0 };
+        let mut dst = Vec::default();
+        metadata.write_to(&mut dst)?;
+        for n in 0..dst.len() {
+            let mut src = &dst[..n];
+            assert!(matches!(Metadata::read_from(&mut src), Err(Error::Io(_))));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn name_overflow() {
+        let metadata = Metadata { name: "a".repeat(0x0001_0000), mtime: 0 };
+        let err = metadata.write_to(&mut Vec::default()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,336 @@
+use crate::decode::LzfseRingDecoder;
+use crate::encode::LzfseEncoder;
+use crate::error::Error;
+
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+// Segment header layout: channel id (u32 LE), payload length (u64 LE).
+const SEGMENT_HEADER_LEN: u64 = 12;
+
+// Index entry layout: channel id (u32 LE), offset, raw length, payload length (u64 LE).
+const ENTRY_LEN: usize = 28;
+
+// Footer layout: index offset (u64 LE), segment count (u32 LE), magic bytes.
+const FOOTER_LEN: u64 = 16;
+
+const MAGIC: [u8; 4] = *b"lzfm";
+
+// Default segment length.
+const SEGMENT_LEN: usize = 0x0004_0000;
+
+/// Mux segment index entry.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct MuxSegment {
+    /// Channel id.
+    pub channel: u32,
+    /// Offset of the segment LZFSE stream from the start of the mux.
+    pub offset: u64,
+    /// Decoded segment size.
+    pub n_raw_bytes: u64,
+    /// Encoded segment size.
+    pub n_payload_bytes: u64,
+}
+
+impl MuxSegment {
+    fn write_to<W: Write>(&self, dst: &mut W) -> io::Result<()> {
+        dst.write_all(&self.channel.to_le_bytes())?;
+        dst.write_all(&self.offset.to_le_bytes())?;
+        dst.write_all(&self.n_raw_bytes.to_le_bytes())?;
+        dst.write_all(&self.n_payload_bytes.to_le_bytes())?;
+        Ok(())
+    }
+
+    fn read_from(src: &[u8; ENTRY_LEN]) -> Self {
+        Self {
+            channel: u32::from_le_bytes(src[..4].try_into().unwrap()),
+            offset: u64::from_le_bytes(src[4..12].try_into().unwrap()),
+            n_raw_bytes: u64::from_le_bytes(src[12..20].try_into().unwrap()),
+            n_payload_bytes: u64::from_le_bytes(src[20..].try_into().unwrap()),
+        }
+    }
+}
+
+/// Multiplexed channel encoder.
+///
+/// Interleaves multiple logical channels, identified by `u32` ids, into a single output. Channel
+/// writes are buffered per channel and encoded in segments, each an independent LZFSE stream,
+/// followed by an index and footer enabling single channel extraction, see [MuxDecoder]. The
+/// container is specific to this crate and is NOT part of the LZFSE format. Layout: segments in
+/// encoding order, index entries in segment order, footer. Segment: channel id (u32 LE), LZFSE
+/// stream length (u64 LE), LZFSE stream. Index entry: channel id (u32 LE), stream offset (u64 LE),
+/// decoded size (u64 LE), encoded size (u64 LE). Footer: index offset (u64 LE), segment count
+/// (u32 LE), magic bytes `lzfm`.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::{MuxDecoder, MuxEncoder};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut encoder = MuxEncoder::new(Vec::default());
+///     encoder.write(0, b"channel 0, ")?;
+///     encoder.write(1, b"channel 1, ")?;
+///     encoder.write(0, b"more channel 0")?;
+///     let mux = encoder.finalize()?;
+///     let mut decoder = MuxDecoder::new(Cursor::new(mux))?;
+///     assert_eq!(decoder.channels(), vec![0, 1]);
+///     let mut dec = Vec::default();
+///     decoder.extract(0, &mut dec)?;
+///     assert_eq!(dec, b"channel 0, more channel 0");
+///     Ok(())
+/// }
+/// ```
+pub struct MuxEncoder<W: Write> {
+    encoder: LzfseEncoder,
+    inner: W,
+    buffers: BTreeMap<u32, Vec<u8>>,
+    segments: Vec<MuxSegment>,
+    segment_len: usize,
+    offset: u64,
+    enc: Vec<u8>,
+}
+
+impl<W: Write> MuxEncoder<W> {
+    /// Create a new instance writing into `inner` with the default segment length.
+    pub fn new(inner: W) -> Self {
+        Self::with_segment_len(inner, SEGMENT_LEN)
+    }
+
+    /// Create a new instance writing into `inner`, encoding a channel segment once
+    /// `segment_len` bytes are buffered for that channel.
+    ///
+    /// Larger segments improve the compression ratio at the expense of buffered memory, up to
+    /// `segment_len` bytes per channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `segment_len` is zero.
+    pub fn with_segment_len(inner: W, segment_len: usize) -> Self {
+        assert_ne!(segment_len, 0);
+        Self {
+            encoder: LzfseEncoder::default(),
+            inner,
+            buffers: BTreeMap::default(),
+            segments: Vec::default(),
+            segment_len,
+            offset: 0,
+            enc: Vec::default(),
+        }
+    }
+
+    /// Write `bytes` to `channel`.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) in case of `inner` IO errors, after which the mux is incomplete.
+    pub fn write(&mut self, channel: u32, mut bytes: &[u8]) -> io::Result<()> {
+        while !bytes.is_empty() {
+            let buffer = self.buffers.entry(channel).or_default();
+            let n = (self.segment_len - buffer.len()).min(bytes.len());
+            buffer.extend_from_slice(&bytes[..n]);
+            bytes = &bytes[n..];
+            if buffer.len() == self.segment_len {
+                self.flush_channel(channel)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode any bytes buffered for `channel` as a segment.
+    ///
+    /// # Errors
+    ///
+    /// * [Error](std::io::Error) in case of `inner` IO errors, after which the mux is incomplete.
+    pub fn flush_channel(&mut self, channel: u32) -> io::Result<()> {
+        let buffer = match self.buffers.get_mut(&channel) {
+            Some(buffer) if !buffer.is_empty() => buffer,
+            _ => return Ok(()),
+        };
+        self.enc.clear();
+        let n_payload_bytes = self.encoder.encode_bytes(buffer, &mut self.enc)?;
+        self.inner.write_all(&channel.to_le_bytes())?;
+        self.inner.write_all(&n_payload_bytes.to_le_bytes())?;
+        self.inner.write_all(&self.enc)?;
+        self.segments.push(MuxSegment {
+            channel,
+            offset: self.offset + SEGMENT_HEADER_LEN,
+            n_raw_bytes: buffer.len() as u64,
+            n_payload_bytes,
+        });
+        self.offset += SEGMENT_HEADER_LEN + n_payload_bytes;
+        buffer.clear();
+        Ok(())
+    }
+
+    /// Segments encoded so far.
+    #[inline(always)]
+    pub fn segments(&self) -> &[MuxSegment] {
+        &self.segments
+    }
+
+    /// Encode all buffered bytes, in channel order, write the index and footer, returning the
+    /// underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// * [ErrorKind::InvalidInput](std::io::ErrorKind) if the segment count exceeds `u32::MAX`.
+    /// * [Error](std::io::Error) in case of `inner` IO errors.
+    pub fn finalize(mut self) -> io::Result<W> {
+        let channels = self.buffers.keys().copied().collect::<Vec<_>>();
+        for channel in channels {
+            self.flush_channel(channel)?;
+        }
+        let n_segments: u32 = self
+            .segments
+            .len()
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "segment overflow"))?;
+        for segment in self.segments.iter() {
+            segment.write_to(&mut self.inner)?;
+        }
+        self.inner.write_all(&self.offset.to_le_bytes())?;
+        self.inner.write_all(&n_segments.to_le_bytes())?;
+        self.inner.write_all(&MAGIC)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> fmt::Debug for MuxEncoder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MuxEncoder")
+            .field("segment_len", &self.segment_len)
+            .field("segments", &self.segments)
+            .finish()
+    }
+}
+
+/// Multiplexed channel decoder.
+///
+/// Reads the index of a mux written by [MuxEncoder] on construction. Channels are then extracted
+/// by id, seeking directly to each of the channel's segments without reading other channels.
+pub struct MuxDecoder<R: Read + Seek> {
+    decoder: LzfseRingDecoder,
+    inner: R,
+    segments: Vec<MuxSegment>,
+}
+
+impl<R: Read + Seek> MuxDecoder<R> {
+    /// Create a new instance reading the mux index from `inner`.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::BadArchive](crate::Error::BadArchive) if the footer or index is missing or
+    ///   malformed.
+    /// * [Error](crate::Error) in case of `inner` IO errors.
+    pub fn new(mut inner: R) -> crate::Result<Self> {
+        let end = inner.seek(SeekFrom::End(0))?;
+        let footer_pos = end.checked_sub(FOOTER_LEN).ok_or(Error::BadArchive)?;
+        let mut footer = [0u8; FOOTER_LEN as usize];
+        inner.seek(SeekFrom::Start(footer_pos))?;
+        inner.read_exact(&mut footer)?;
+        if footer[12..] != MAGIC {
+            return Err(Error::BadArchive);
+        }
+        let index_pos = u64::from_le_bytes(footer[..8].try_into().unwrap());
+        let n_segments = u32::from_le_bytes(footer[8..12].try_into().unwrap()) as u64;
+        let index_len = footer_pos.checked_sub(index_pos).ok_or(Error::BadArchive)?;
+        if index_len != n_segments * ENTRY_LEN as u64 {
+            return Err(Error::BadArchive);
+        }
+        let mut index = vec![0u8; index_len as usize];
+        inner.seek(SeekFrom::Start(index_pos))?;
+        inner.read_exact(&mut index)?;
+        let mut segments = Vec::with_capacity(n_segments as usize);
+        for entry in index.chunks_exact(ENTRY_LEN) {
+            let segment = MuxSegment::read_from(entry.try_into().unwrap());
+            match segment.offset.checked_add(segment.n_payload_bytes) {
+                Some(u) if segment.offset >= SEGMENT_HEADER_LEN && u <= index_pos => {}
+                _ => return Err(Error::BadArchive),
+            }
+            segments.push(segment);
+        }
+        Ok(Self { decoder: LzfseRingDecoder::default(), inner, segments })
+    }
+
+    /// Segments in order.
+    #[inline(always)]
+    pub fn segments(&self) -> &[MuxSegment] {
+        &self.segments
+    }
+
+    /// Channel ids in ascending order.
+    pub fn channels(&self) -> Vec<u32> {
+        let mut channels = self.segments.iter().map(|segment| segment.channel).collect::<Vec<_>>();
+        channels.sort_unstable();
+        channels.dedup();
+        channels
+    }
+
+    /// Extract `channel` into `writer` returning the number of bytes written into `writer`.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::MemberNotFound](crate::Error::MemberNotFound) if `channel` is not present.
+    /// * [Error::BadArchive](crate::Error::BadArchive) if a segment header does not match the
+    ///   index entry.
+    /// * [Error::OutputSizeMismatch](crate::Error::OutputSizeMismatch) if a decoded segment size
+    ///   does not match the index entry.
+    /// * [Error](crate::Error) detailing the nature of any other errors.
+    pub fn extract<W: Write>(&mut self, channel: u32, writer: &mut W) -> crate::Result<u64> {
+        let mut n_bytes = 0;
+        let mut is_found = false;
+        for segment in self.segments.iter().filter(|segment| segment.channel == channel) {
+            is_found = true;
+            let mut header = [0u8; SEGMENT_HEADER_LEN as usize];
+            self.inner.seek(SeekFrom::Start(segment.offset - SEGMENT_HEADER_LEN))?;
+            self.inner.read_exact(&mut header)?;
+            if u32::from_le_bytes(header[..4].try_into().unwrap()) != channel
+                || u64::from_le_bytes(header[4..].try_into().unwrap()) != segment.n_payload_bytes
+            {
+                return Err(Error::BadArchive);
+            }
+            let mut reader = (&mut self.inner).take(segment.n_payload_bytes);
+            let (_, n) = self.decoder.decode(&mut reader, writer)?;
+            if n != segment.n_raw_bytes {
+                return Err(Error::OutputSizeMismatch);
+            }
+            n_bytes += n;
+        }
+        if !is_found {
+            return Err(Error::MemberNotFound);
+        }
+        Ok(n_bytes)
+    }
+
+    /// Unwraps this `MuxDecoder<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read + Seek> fmt::Debug for MuxDecoder<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MuxDecoder").field("segments", &self.segments).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_round_trip() -> io::Result<()> {
+        let segment =
+            MuxSegment { channel: u32::MAX, offset: 1, n_raw_bytes: 2, n_payload_bytes: u64::MAX };
+        let mut dst = Vec::default();
+        segment.write_to(&mut dst)?;
+        assert_eq!(dst.len(), ENTRY_LEN);
+        assert_eq!(MuxSegment::read_from(dst.as_slice().try_into().unwrap()), segment);
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use std::io;
 
 pub trait Allocate {
-    /// Allocate `len` bytes returning `io::ErrorKind::Other` in case of failure.
+    /// Allocate `len` bytes returning `io::ErrorKind::OutOfMemory` in case of failure.
     fn allocate(&mut self, len: usize) -> io::Result<()>;
 
     fn is_allocated(&mut self, len: usize) -> bool;
@@ -20,26 +20,14 @@ impl<T: Allocate + ?Sized> Allocate for &mut T {
 }
 
 impl Allocate for Vec<u8> {
-    // TODO: Issue 48043, use `vec::try_reserve`
-    #[cfg(target_pointer_width = "32")]
     fn allocate(&mut self, len: usize) -> io::Result<()> {
-        let index = self.len();
         if !self.is_allocated(len) {
-            if index + len > isize::MAX as usize {
-                // Unlikely.
-                return Err(io::ErrorKind::Other.into());
-            }
-            self.reserve(len);
+            // Unlikely.
+            self.try_reserve(len).map_err(|_| io::Error::from(io::ErrorKind::OutOfMemory))?;
         }
         Ok(())
     }
 
-    #[cfg(target_pointer_width = "64")]
-    fn allocate(&mut self, additional: usize) -> io::Result<()> {
-        self.reserve(additional);
-        Ok(())
-    }
-
     #[inline(always)]
     fn is_allocated(&mut self, len: usize) -> bool {
         len <= self.capacity().wrapping_sub(self.len())
@@ -5,6 +5,7 @@ use crate::lz::LzWriter;
 use crate::ops::{Len, Limit, ReadData, WriteData};
 use crate::types::{ByteReader, ShortBuffer};
 
+/// RAW block header size in bytes: magic bytes `bvx-` followed by the `u32` payload length.
 pub const RAW_HEADER_SIZE: u32 = 0x08;
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -5,4 +5,4 @@ mod ops;
 mod tests;
 
 pub use block::{RawBlock, RAW_HEADER_SIZE};
-pub use ops::{raw_compress, raw_decompress, raw_probe};
+pub use ops::{decode_raw_block, encode_raw_block, raw_compress, raw_decompress, raw_probe};
@@ -1,8 +1,11 @@
+use crate::base::MagicBytes;
+use crate::error::Error;
 use crate::lz::LzWriter;
 use crate::types::{ByteReader, ShortBuffer, ShortWriter};
 
 use super::block::{RawBlock, RAW_HEADER_SIZE};
 
+use std::convert::TryInto;
 use std::io;
 
 pub fn raw_probe<I>(src: I) -> crate::Result<(u32, u32)>
@@ -11,7 +14,8 @@ where
 {
     let mut block = RawBlock::default();
     block.load_short(src)?;
-    let n_payload_bytes = RAW_HEADER_SIZE + block.n_raw_bytes();
+    let n_payload_bytes =
+        RAW_HEADER_SIZE.checked_add(block.n_raw_bytes()).ok_or(crate::Error::PayloadOverflow)?;
     let n_raw_bytes = block.n_raw_bytes();
     Ok((n_payload_bytes, n_raw_bytes))
 }
@@ -41,3 +45,83 @@ where
     block.decode(dst, src)?;
     Ok(())
 }
+
+/// Encode `src` into `dst` as a single bare RAW (store-only) block, returning the number of bytes
+/// written into `dst`.
+///
+/// The block comprises a [RAW_HEADER_SIZE] byte header followed by `src` verbatim, no compression
+/// analysis is performed. Intended for container formats with data that is known to be
+/// incompressible. Blocks are not streams, a valid LZFSE stream is one or more blocks followed by
+/// the end of stream magic bytes `bvx$`.
+///
+/// # Errors
+///
+/// * [ErrorKind::Other](std::io::ErrorKind) in case of `src` buffer overflow, that is `src`
+///   exceeds `i32::MAX` bytes.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     let n = lzfse_rust::encode_raw_block(b"test", &mut enc)?;
+///     assert_eq!(n, lzfse_rust::RAW_HEADER_SIZE as u64 + 4);
+///     enc.extend_from_slice(b"bvx$");
+///     let mut dec = Vec::default();
+///     lzfse_rust::decode_bytes(&enc, &mut dec)?;
+///     assert_eq!(dec, b"test");
+///     Ok(())
+/// }
+/// ```
+pub fn encode_raw_block(src: &[u8], dst: &mut Vec<u8>) -> io::Result<u64> {
+    if src.len() > i32::MAX as usize {
+        return Err(io::ErrorKind::Other.into());
+    }
+    let mark = dst.len();
+    raw_compress(dst, src)?;
+    Ok((dst.len() - mark) as u64)
+}
+
+/// Decode the bare RAW block at the start of `src` into `dst`, returning a tuple (u, v) where u is
+/// the number of bytes read from `src` and v is the number of bytes written into `dst`.
+///
+/// Bytes following the block are ignored, they may be further blocks or the end of stream magic
+/// bytes. See [encode_raw_block].
+///
+/// # Errors
+///
+/// * [Error::BadBlock](crate::Error::BadBlock) if `src` does not begin with a RAW block.
+/// * [Error::PayloadUnderflow](crate::Error::PayloadUnderflow) if `src` is truncated.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut enc = Vec::default();
+///     lzfse_rust::encode_raw_block(b"test", &mut enc)?;
+///     let mut dec = Vec::default();
+///     let (u, v) = lzfse_rust::decode_raw_block(&enc, &mut dec)?;
+///     assert_eq!(u, enc.len() as u64);
+///     assert_eq!(v, 4);
+///     assert_eq!(dec, b"test");
+///     Ok(())
+/// }
+/// ```
+pub fn decode_raw_block(src: &[u8], dst: &mut Vec<u8>) -> crate::Result<(u64, u64)> {
+    let header = src.get(..RAW_HEADER_SIZE as usize).ok_or(Error::PayloadUnderflow)?;
+    let magic_bytes = u32::from_le_bytes(header[..4].try_into().unwrap());
+    if magic_bytes != MagicBytes::Raw.into() {
+        return Err(Error::BadBlock(magic_bytes));
+    }
+    let mut block = RawBlock::default();
+    let n_header_bytes = block.load(header)? as usize;
+    let n_raw_bytes = block.n_raw_bytes() as usize;
+    let payload =
+        src.get(n_header_bytes..n_header_bytes + n_raw_bytes).ok_or(Error::PayloadUnderflow)?;
+    dst.extend_from_slice(payload);
+    Ok(((n_header_bytes + n_raw_bytes) as u64, n_raw_bytes as u64))
+}
@@ -12,7 +12,7 @@ pub use object::{Ring, OVERMATCH_LEN};
 pub use ring_block::RingBlock;
 pub use ring_box::RingBox;
 pub use ring_lz_writer::RingLzWriter;
-pub use ring_reader::RingReader;
+pub use ring_reader::{RingFnReader, RingReader};
 pub use ring_short_writer::RingShortWriter;
 pub use ring_size::RingSize;
 pub use ring_type::RingType;
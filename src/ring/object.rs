@@ -35,7 +35,8 @@ pub struct Ring<'a, T>(*mut u8, PhantomData<T>, PhantomData<&'a mut ()>);
 
 impl<'a, T: RingType> Ring<'a, T> {
     /// May overmatch `max` by  `LEN + OVERMATCH_LEN` bytes
-    #[inline(always)]
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline)]
     pub fn match_inc_coarse<const LEN: usize>(&self, idxs: (Idx, Idx), max: usize) -> usize {
         assert!(LEN + OVERMATCH_LEN <= T::RING_LIMIT as usize);
         debug_assert!(self.head_shadowed_len(LEN + OVERMATCH_LEN));
@@ -84,7 +85,8 @@ impl<'a, T: RingType> Ring<'a, T> {
     }
 
     /// May overmatch `max` by  `LEN + OVERMATCH_LEN` bytes
-    #[inline(always)]
+    #[cfg_attr(not(feature = "size-opt"), inline(always))]
+    #[cfg_attr(feature = "size-opt", inline)]
     pub fn match_dec_coarse<const LEN: usize>(&self, idxs: (Idx, Idx), max: usize) -> usize {
         assert!(LEN + OVERMATCH_LEN <= T::RING_LIMIT as usize);
         debug_assert!(self.head_shadowed_len(LEN + OVERMATCH_LEN));
@@ -202,6 +204,14 @@ impl<'a, T: RingType> Ring<'a, T> {
         unsafe { zone_copy_1::<T>(self.0.sub(T::RING_LIMIT as usize), len) };
     }
 
+    /// Full `RING_CAPACITY` buffer, the ring proper starting at index `RING_LIMIT`.
+    #[inline(always)]
+    pub fn as_capacity_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            slice::from_raw_parts_mut(self.0.sub(T::RING_LIMIT as usize), T::RING_CAPACITY as usize)
+        }
+    }
+
     #[inline(always)]
     pub fn view(&self, head: Idx, tail: Idx) -> RingView<T> {
         RingView::new(self, head, tail)
@@ -209,10 +219,11 @@ impl<'a, T: RingType> Ring<'a, T> {
 }
 
 impl<'a, T: RingSize> Ring<'a, T> {
+    /// Reads data as little endian `u32` from the specified index.
     #[inline(always)]
     pub fn get_u32(&self, idx: Idx) -> u32 {
         let index = idx % T::RING_SIZE;
-        unsafe { self.0.add(index as usize).cast::<u32>().read_unaligned() }
+        unsafe { self.0.add(index as usize).cast::<u32>().read_unaligned().to_le() }
     }
 
     #[inline(always)]
@@ -404,4 +415,19 @@ mod tests {
             }
         }
     }
+
+    // Little endian reads, including across the ring boundary.
+    #[test]
+    fn get_u32() {
+        let mut ring_box = RingBox::<T>::default();
+        let mut ring = Ring::from(&mut ring_box);
+        for i in 0..0x0100 {
+            ring[i] = i as u8;
+        }
+        ring.head_copy_out();
+        for index in 0..0x0100 {
+            let bytes = [0, 1, 2, 3].map(|n| ((index + n) % 0x0100) as u8);
+            assert_eq!(ring.get_u32(Idx::new(index as u32)), u32::from_le_bytes(bytes));
+        }
+    }
 }
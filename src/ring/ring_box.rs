@@ -2,16 +2,85 @@ use crate::kit::WIDE;
 
 use super::ring_type::RingType;
 
+use std::io;
 use std::marker::PhantomData;
 
 pub struct RingBox<T>(pub(super) Box<[u8]>, PhantomData<T>);
 
-impl<T: RingType> Default for RingBox<T> {
-    fn default() -> Self {
+impl<T: RingType> RingBox<T> {
+    /// Takes ownership of `vec`, reusing its allocation. The length must equal `RING_CAPACITY`.
+    /// Contents are zeroed.
+    pub fn from_vec(mut vec: Vec<u8>) -> io::Result<Self> {
+        Self::check();
+        if vec.len() != T::RING_CAPACITY as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "bad buffer length"));
+        }
+        vec.iter_mut().for_each(|u| *u = 0);
+        Ok(Self(vec.into_boxed_slice(), PhantomData))
+    }
+
+    /// Touch every byte, committing lazily allocated pages. Contents are zeroed.
+    pub fn warm(&mut self) {
+        self.0.iter_mut().for_each(|u| *u = 0);
+    }
+
+    #[inline(always)]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0.into_vec()
+    }
+
+    #[inline(always)]
+    fn check() {
         assert!(WIDE <= T::RING_SIZE as usize);
         assert!(T::RING_SIZE <= 0x4000_0000);
         assert!(T::RING_SIZE.is_power_of_two());
         assert!(T::RING_LIMIT <= T::RING_SIZE);
+    }
+}
+
+impl<T: RingType> Default for RingBox<T> {
+    fn default() -> Self {
+        Self::check();
         Self(vec![0u8; T::RING_CAPACITY as usize].into_boxed_slice(), PhantomData::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::ring::RingSize;
+
+    use super::*;
+
+    #[derive(Copy, Clone, Debug)]
+    pub struct T;
+
+    unsafe impl RingSize for T {
+        const RING_SIZE: u32 = 0x0002_0000;
+    }
+
+    unsafe impl RingType for T {
+        const RING_LIMIT: u32 = 0x0100;
+    }
+
+    #[test]
+    fn from_vec() -> io::Result<()> {
+        let vec = vec![0xFF; T::RING_CAPACITY as usize];
+        let ptr = vec.as_ptr();
+        let ring_box = RingBox::<T>::from_vec(vec)?;
+        assert!(ring_box.0.iter().all(|&u| u == 0));
+        let vec = ring_box.into_vec();
+        assert_eq!(vec.as_ptr(), ptr);
+        assert_eq!(vec.len(), T::RING_CAPACITY as usize);
+        Ok(())
+    }
+
+    #[test]
+    fn from_vec_bad_len() {
+        for &n in &[0, T::RING_CAPACITY as usize - 1, T::RING_CAPACITY as usize + 1] {
+            match RingBox::<T>::from_vec(vec![0; n]) {
+                Err(err) if err.kind() == io::ErrorKind::InvalidInput => {}
+                _ => panic!(),
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::decode::OutputSink;
 use crate::error::Error;
 use crate::kit::CopyTypeIndex;
 use crate::kit::{Width, WIDE};
@@ -9,7 +10,6 @@ use crate::types::{Idx, ShortBytes};
 use super::object::Ring;
 use super::ring_type::RingType;
 
-use std::io::Write;
 use std::ptr;
 
 /// Ring LZ output.
@@ -47,10 +47,10 @@ impl<'a, O, T: RingType> RingLzWriter<'a, O, T> {
     }
 }
 
-impl<'a, O: Write, T: RingType> RingLzWriter<'a, O, T> {
+impl<'a, O: OutputSink, T: RingType> RingLzWriter<'a, O, T> {
     #[inline(never)]
     fn flush(&mut self, len: usize) -> crate::Result<()> {
-        self.inner.write_all(&self.ring)?;
+        self.inner.write_decoded(&self.ring)?;
         self.ring.head_copy_in_len(len);
         self.ring.tail_copy_out();
         Ok(())
@@ -63,11 +63,34 @@ impl<'a, O: Write, T: RingType> RingLzWriter<'a, O, T> {
         }
         let index = self.index as u32 % T::RING_SIZE;
         let bytes = unsafe { &self.ring.get_unchecked(..index as usize) };
-        self.inner.write_all(bytes)?;
+        self.inner.write_decoded(bytes)?;
         Ok(self.inner)
     }
 }
 
+impl<'a, O: OutputSink, T: RingType> RingLzWriter<'a, O, T> {
+    // Ring capacity relative indices: [tail shadow | ring | head shadow | slack].
+    fn write_match_checked(&mut self, len: u32, distance: u32) -> crate::Result<()> {
+        if distance as u64 > self.index {
+            return Err(Error::BadDValue);
+        }
+        let dst_index = self.index as usize % T::RING_SIZE as usize;
+        let dst = T::RING_LIMIT as usize + dst_index;
+        let src = if distance as usize > dst_index + T::RING_LIMIT as usize {
+            dst + T::RING_SIZE as usize - distance as usize
+        } else {
+            dst - distance as usize
+        };
+        lz::write_match_checked(self.ring.as_capacity_mut(), src, dst, len as usize)?;
+        self.index += len as u64;
+        if (dst_index + len as usize) < T::RING_SIZE as usize {
+            Ok(())
+        } else {
+            self.flush(dst_index + len as usize - T::RING_SIZE as usize)
+        }
+    }
+}
+
 impl<'a, O, T: RingType> Pos for RingLzWriter<'a, O, T> {
     #[inline(always)]
     fn pos(&self) -> Idx {
@@ -79,8 +102,8 @@ unsafe impl<'a, O, T: RingType> ShortLimit for RingLzWriter<'a, O, T> {
     const SHORT_LIMIT: u32 = T::RING_LIMIT;
 }
 
-impl<'a, O: Write, T: RingType> LzWriter for RingLzWriter<'a, O, T> {
-    const MAX_MATCH_DISTANCE: u32 = T::RING_SIZE / 2;
+impl<'a, O: OutputSink, T: RingType> LzWriter for RingLzWriter<'a, O, T> {
+    const MAX_MATCH_DISTANCE: u32 = T::RING_SIZE / 2 - 1;
 
     const MAX_MATCH_LEN: u32 = T::RING_LIMIT;
 
@@ -154,9 +177,15 @@ impl<'a, O: Write, T: RingType> LzWriter for RingLzWriter<'a, O, T> {
         // println!("M:{}", len.get());
         // println!("D:{}", distance.get());
         assert!(U::MAX_MATCH_LEN as u32 <= Self::MAX_MATCH_LEN);
-        assert!(U::MAX_MATCH_DISTANCE <= Self::MAX_MATCH_DISTANCE);
         let len = len.get();
         let distance = distance.get();
+        if U::MAX_MATCH_DISTANCE > Self::MAX_MATCH_DISTANCE && distance > Self::MAX_MATCH_DISTANCE {
+            // Unlikely. Reduced rings reject distances beyond their reach.
+            return Err(Error::BadDValue);
+        }
+        if cfg!(feature = "safe-copy") {
+            return self.write_match_checked(len, distance);
+        }
         if distance as u64 <= self.index {
             // Likely
             let dst_idx = Idx::from(self.index);
@@ -15,6 +15,7 @@ pub struct RingReader<'a, I, T> {
     inner: I,
     head: Idx,
     tail: Idx,
+    n_fill: u64,
     is_eof: bool,
 }
 
@@ -24,13 +25,44 @@ impl<'a, I, T: RingBlock> RingReader<'a, I, T> {
         assert!(0x0100 <= T::RING_BLK_SIZE);
         assert!(T::RING_BLK_SIZE <= T::RING_SIZE / 4);
         assert!(WIDE <= T::RING_LIMIT as usize);
-        Self { ring, inner, head: Idx::default(), tail: Idx::default(), is_eof: false }
+        Self { ring, inner, head: Idx::default(), tail: Idx::default(), n_fill: 0, is_eof: false }
     }
 
     pub fn into_inner(self) -> I {
         self.inner
     }
 
+    /// Total number of bytes consumed, that is filled and subsequently skipped.
+    #[inline(always)]
+    pub fn n_consumed(&self) -> u64 {
+        self.n_fill - self.len() as u64
+    }
+
+    /// Fill directly, bypassing `inner`, returning the number of bytes written.
+    ///
+    /// `f` is handed the contiguous writable ring region, which may be empty, and returns the
+    /// number of bytes it wrote. A zero return, with a non-empty region, marks EOF.
+    ///
+    /// The tail is left unaligned, as such subsequent fills must also be made via `fill_from`, see
+    /// [RingFnReader].
+    pub fn fill_from<F: FnOnce(&mut [u8]) -> usize>(&mut self, f: F) -> usize {
+        if self.is_eof {
+            return 0;
+        }
+        let index = self.tail % T::RING_SIZE as usize;
+        let limit = (T::RING_SIZE as usize - index).min(self.fill_len());
+        let buf = &mut self.ring[index..index + limit];
+        let n = f(buf);
+        assert!(n <= limit);
+        if index < T::RING_LIMIT as usize {
+            self.ring.head_copy_out();
+        }
+        self.tail += n as u32;
+        self.n_fill += n as u64;
+        self.is_eof = n == 0 && limit != 0;
+        n
+    }
+
     #[inline(always)]
     fn fill_blk_len(&self) -> usize {
         (self.fill_len() / T::RING_BLK_SIZE as usize) * T::RING_BLK_SIZE as usize
@@ -76,6 +108,7 @@ impl<'a, 'b, I: Read, T: 'a + Copy + RingBlock> ByteReader<'a> for RingReader<'b
             }
             len -= n;
             self.tail += n as u32;
+            self.n_fill += n as u64;
             self.is_eof = n != m;
         }
         debug_assert!(self.ring.head_shadowed());
@@ -122,6 +155,74 @@ impl<'a, I, T> Len for RingReader<'a, I, T> {
     }
 }
 
+/// [RingReader] filled exclusively via [fill_from](RingReader::fill_from) using the closure `f`.
+pub struct RingFnReader<'a, F, T> {
+    rdr: RingReader<'a, (), T>,
+    f: F,
+}
+
+impl<'a, F, T: RingBlock> RingFnReader<'a, F, T> {
+    #[inline(always)]
+    pub fn new(ring: Ring<'a, T>, f: F) -> Self {
+        Self { rdr: RingReader::new(ring, ()), f }
+    }
+}
+
+impl<'a, F, T: RingSize> PeekData for RingFnReader<'a, F, T> {
+    #[inline(always)]
+    unsafe fn peek_data(&self, dst: &mut [u8]) {
+        self.rdr.peek_data(dst)
+    }
+}
+
+impl<'a, 'b, F, T> ByteReader<'a> for RingFnReader<'b, F, T>
+where
+    F: FnMut(&mut [u8]) -> usize,
+    T: 'a + Copy + RingBlock,
+{
+    const VIEW_LIMIT: usize = T::RING_SIZE as usize - T::RING_BLK_SIZE as usize;
+
+    type View = RingView<'a, T>;
+
+    fn fill(&mut self) -> io::Result<()> {
+        while self.rdr.fill_blk_len() != 0 && !self.rdr.is_eof {
+            self.rdr.fill_from(&mut self.f);
+        }
+        debug_assert!(self.rdr.ring.head_shadowed());
+        Ok(())
+    }
+
+    /// Returns a head shadowed view.
+    #[inline(always)]
+    fn view(&'a self) -> Self::View {
+        RingView::new(&self.rdr.ring, self.rdr.head, self.rdr.tail)
+    }
+
+    #[inline(always)]
+    fn is_eof(&self) -> bool {
+        self.rdr.is_eof
+    }
+
+    #[inline(always)]
+    fn is_full(&self) -> bool {
+        self.rdr.is_eof || self.rdr.fill_blk_len() == 0
+    }
+}
+
+impl<'a, F, T> Skip for RingFnReader<'a, F, T> {
+    #[inline(always)]
+    unsafe fn skip_unchecked(&mut self, len: usize) {
+        self.rdr.skip_unchecked(len)
+    }
+}
+
+impl<'a, F, T> Len for RingFnReader<'a, F, T> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.rdr.len()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ring::{RingBlock, RingBox, RingSize, RingType};
@@ -263,4 +364,50 @@ mod tests {
         }
         Ok(())
     }
+
+    // Loop: fill from closure in odd sized chunks and drain.
+    #[test]
+    fn fill_from() -> io::Result<()> {
+        let mut core = RingBox::<T>::default();
+        let src = Iterator::take(Seq::default(), T::RING_SIZE as usize * 4).collect::<Vec<_>>();
+        for &k in &[1, 0x0101, 0x0123, T::RING_BLK_SIZE as usize, T::RING_SIZE as usize - 1] {
+            let mut rem = src.as_slice();
+            let mut rdr = RingFnReader::new((&mut core).into(), |buf: &mut [u8]| {
+                let n = buf.len().min(rem.len()).min(k);
+                buf[..n].copy_from_slice(&rem[..n]);
+                rem = &rem[n..];
+                n
+            });
+            let mut seq = Seq::default();
+            while !rdr.is_eof() || rdr.len() != 0 {
+                rdr.fill()?;
+                assert!(rdr.is_eof() || RingReader::<&[u8], T>::VIEW_LIMIT <= rdr.len());
+                let mut view = rdr.view();
+                let view_len = view.len();
+                while view.len() != 0 {
+                    let bytes = view.short_bytes();
+                    let bytes_len = bytes.len();
+                    for &b in bytes.iter() {
+                        assert_eq!(seq.next().unwrap(), b);
+                    }
+                    view.skip(bytes_len);
+                }
+                rdr.skip(view_len);
+            }
+            assert_eq!(rdr.rdr.n_consumed(), src.len() as u64);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fill_from_eof() {
+        let mut core = RingBox::<T>::default();
+        let mut rdr = RingReader::new((&mut core).into(), ());
+        assert_eq!(rdr.fill_from(|buf| buf.len()), T::RING_SIZE as usize);
+        assert_eq!(rdr.fill_from(|buf| buf.len()), 0);
+        assert!(!rdr.is_eof);
+        rdr.head += T::RING_BLK_SIZE;
+        assert_eq!(rdr.fill_from(|_| 0), 0);
+        assert!(rdr.is_eof);
+    }
 }
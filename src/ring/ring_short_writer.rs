@@ -44,6 +44,14 @@ impl<'a, O, T: RingBlock> RingShortWriter<'a, O, T> {
     }
 }
 
+impl<'a, O, T> RingShortWriter<'a, O, T> {
+    /// Bytes written to the inner writer.
+    #[inline(always)]
+    pub fn n_payload_bytes(&self) -> u64 {
+        self.n_payload_bytes
+    }
+}
+
 impl<'a, O: Write, T: RingBlock> RingShortWriter<'a, O, T> {
     pub fn into_inner(mut self) -> crate::Result<(O, u64)> {
         self.flush(true)?;
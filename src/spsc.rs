@@ -0,0 +1,272 @@
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Create a bounded single producer, single consumer byte ring with a capacity of `capacity`
+/// bytes, returning the producer [SpscWriter] and consumer [SpscReader] halves.
+///
+/// Intended for in-process streaming pipelines: an encoder writes into the [SpscWriter] on one
+/// thread whilst another thread drains the [SpscReader], without an intermediary `Vec`. Bytes are
+/// copied directly into and out of the ring, each side owning its region of the ring. Each read
+/// and write also takes a shared mutex, briefly, to hand off to the other side via a condition
+/// variable. Should the ring be full, writes block until the consumer catches up. Should the ring
+/// be empty, reads block until the producer catches up.
+///
+/// Dropping the [SpscWriter] signals the end of the stream, the [SpscReader] returns `Ok(0)` once
+/// drained. Dropping the [SpscReader] fails subsequent writes with
+/// [ErrorKind::BrokenPipe](std::io::ErrorKind), see
+/// [compress_reader_to_writer](crate::compress_reader_to_writer).
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero or exceeds `usize::MAX / 4`.
+///
+/// # Examples
+///
+/// ```
+/// use lzfse_rust::LzfseRingEncoder;
+/// use std::io::{self, Read};
+/// use std::thread;
+///
+/// fn main() -> io::Result<()> {
+///     let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+///     let (mut writer, mut reader) = lzfse_rust::spsc_ring(0x1000);
+///     let producer = thread::spawn(move || {
+///         LzfseRingEncoder::default().encode(&mut src.as_slice(), &mut writer)
+///     });
+///     let mut enc = Vec::default();
+///     reader.read_to_end(&mut enc)?;
+///     producer.join().unwrap()?;
+///     let mut dec = Vec::default();
+///     lzfse_rust::decode_bytes(&enc, &mut dec)?;
+///     assert_eq!(dec, b"a quick brown fox jumps over the lazy dog ".repeat(0x0100));
+///     Ok(())
+/// }
+/// ```
+pub fn spsc_ring(capacity: usize) -> (SpscWriter, SpscReader) {
+    assert!(capacity != 0 && capacity <= usize::MAX / 4);
+    let shared = Arc::new(Shared {
+        buf: (0..capacity).map(|_| UnsafeCell::new(0)).collect(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        is_writer_closed: AtomicBool::new(false),
+        is_reader_closed: AtomicBool::new(false),
+        lock: Mutex::new(()),
+        cond: Condvar::new(),
+    });
+    (SpscWriter { shared: Arc::clone(&shared) }, SpscReader { shared })
+}
+
+// `head` and `tail` are the counts of bytes written and read, modulo twice the capacity. The
+// producer exclusively owns the free region [head, tail + capacity), the consumer exclusively owns
+// the filled region [tail, head). Each read and write waits on and notifies the other side via the
+// mutex/ condvar pair, the buffer copies themselves take place outside of the mutex.
+struct Shared {
+    buf: Box<[UnsafeCell<u8>]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    is_writer_closed: AtomicBool,
+    is_reader_closed: AtomicBool,
+    lock: Mutex<()>,
+    cond: Condvar,
+}
+
+// Safety: the producer and consumer access disjoint buffer regions, synchronized by `head` and
+// `tail` release/ acquire pairs.
+unsafe impl Send for Shared {}
+unsafe impl Sync for Shared {}
+
+impl Shared {
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    // Filled region length.
+    #[inline(always)]
+    fn len(&self, head: usize, tail: usize) -> usize {
+        (head + 2 * self.capacity() - tail) % (2 * self.capacity())
+    }
+
+    #[inline(always)]
+    fn advance(&self, u: usize, n: usize) -> usize {
+        (u + n) % (2 * self.capacity())
+    }
+
+    #[inline(always)]
+    fn ptr(&self) -> *mut u8 {
+        self.buf.as_ptr() as *mut u8
+    }
+
+    fn notify(&self) {
+        // Acquiring the lock orders the notification after any waiter's condition check.
+        drop(self.lock.lock().unwrap_or_else(|err| err.into_inner()));
+        self.cond.notify_one();
+    }
+
+    fn wait_while(&self, f: impl Fn() -> bool) {
+        let mut guard = self.lock.lock().unwrap_or_else(|err| err.into_inner());
+        while f() {
+            guard = self.cond.wait(guard).unwrap_or_else(|err| err.into_inner());
+        }
+    }
+}
+
+/// [spsc_ring] producer half.
+pub struct SpscWriter {
+    shared: Arc<Shared>,
+}
+
+impl Write for SpscWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let shared = &*self.shared;
+        let capacity = shared.capacity();
+        let head = shared.head.load(Ordering::Relaxed);
+        shared.wait_while(|| {
+            !shared.is_reader_closed.load(Ordering::Acquire)
+                && shared.len(head, shared.tail.load(Ordering::Acquire)) == capacity
+        });
+        if shared.is_reader_closed.load(Ordering::Acquire) {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+        let tail = shared.tail.load(Ordering::Acquire);
+        let n = (capacity - shared.len(head, tail)).min(buf.len());
+        let index = head % capacity;
+        let n_lo = n.min(capacity - index);
+        // Safety: [head, head + n) lies within the free region, exclusive to the producer.
+        unsafe {
+            ptr::copy_nonoverlapping(buf.as_ptr(), shared.ptr().add(index), n_lo);
+            ptr::copy_nonoverlapping(buf.as_ptr().add(n_lo), shared.ptr(), n - n_lo);
+        }
+        shared.head.store(shared.advance(head, n), Ordering::Release);
+        shared.notify();
+        Ok(n)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for SpscWriter {
+    fn drop(&mut self) {
+        self.shared.is_writer_closed.store(true, Ordering::Release);
+        self.shared.notify();
+    }
+}
+
+impl fmt::Debug for SpscWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpscWriter").field("capacity", &self.shared.capacity()).finish()
+    }
+}
+
+/// [spsc_ring] consumer half.
+pub struct SpscReader {
+    shared: Arc<Shared>,
+}
+
+impl Read for SpscReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let shared = &*self.shared;
+        let capacity = shared.capacity();
+        let tail = shared.tail.load(Ordering::Relaxed);
+        shared.wait_while(|| {
+            !shared.is_writer_closed.load(Ordering::Acquire)
+                && shared.head.load(Ordering::Acquire) == tail
+        });
+        let head = shared.head.load(Ordering::Acquire);
+        let n = shared.len(head, tail).min(buf.len());
+        let index = tail % capacity;
+        let n_lo = n.min(capacity - index);
+        // Safety: [tail, tail + n) lies within the filled region, exclusive to the consumer.
+        unsafe {
+            ptr::copy_nonoverlapping(shared.ptr().add(index), buf.as_mut_ptr(), n_lo);
+            ptr::copy_nonoverlapping(shared.ptr(), buf.as_mut_ptr().add(n_lo), n - n_lo);
+        }
+        shared.tail.store(shared.advance(tail, n), Ordering::Release);
+        shared.notify();
+        Ok(n)
+    }
+}
+
+impl Drop for SpscReader {
+    fn drop(&mut self) {
+        self.shared.is_reader_closed.store(true, Ordering::Release);
+        self.shared.notify();
+    }
+}
+
+impl fmt::Debug for SpscReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpscReader").field("capacity", &self.shared.capacity()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    #[test]
+    fn wrap() -> io::Result<()> {
+        let (mut writer, mut reader) = spsc_ring(5);
+        let mut buf = [0u8; 8];
+        assert_eq!(writer.write(b"abc")?, 3);
+        assert_eq!(reader.read(&mut buf[..2])?, 2);
+        assert_eq!(&buf[..2], b"ab");
+        assert_eq!(writer.write(b"defgh")?, 4);
+        assert_eq!(reader.read(&mut buf)?, 5);
+        assert_eq!(&buf[..5], b"cdefg");
+        drop(writer);
+        assert_eq!(reader.read(&mut buf)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn broken_pipe() {
+        let (mut writer, reader) = spsc_ring(4);
+        drop(reader);
+        assert_eq!(writer.write(b"a").unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn broken_pipe_blocked() {
+        let (mut writer, reader) = spsc_ring(4);
+        let consumer = thread::spawn(move || drop(reader));
+        let err = writer.write_all(&[0; 0x0100]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        consumer.join().unwrap();
+    }
+
+    #[test]
+    fn threaded() -> io::Result<()> {
+        let src: Vec<u8> = (0..0x0010_0000u32).map(|u| (u % 251) as u8).collect();
+        let (mut writer, mut reader) = spsc_ring(0x0101);
+        let producer = thread::spawn({
+            let src = src.clone();
+            move || -> io::Result<()> {
+                for chunk in src.chunks(0x0123) {
+                    writer.write_all(chunk)?;
+                }
+                Ok(())
+            }
+        });
+        let mut dst = Vec::default();
+        reader.read_to_end(&mut dst)?;
+        producer.join().unwrap()?;
+        assert_eq!(dst, src);
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+//! Deterministic test data generators.
+//!
+//! These are the generators used to produce this crate's own test data, for example the
+//! `pattern_*` and `random_*` test suites. Downstream users may use them to build reproducible
+//! benchmarks and bug reports using the same data as the maintainers.
+//!
+//! Requires the `testdata` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use lzfse_rust::testdata::{Rng, Seq};
+//! use std::io;
+//!
+//! fn main() -> io::Result<()> {
+//!     // Random low entropy data, as used by the `random_0` test suite.
+//!     let src = Seq::masked(Rng::new(0), 0x0101_0101).take(0x0001_0000).collect::<Vec<_>>();
+//!     let mut enc = Vec::default();
+//!     lzfse_rust::encode_bytes(&src, &mut enc)?;
+//!     assert!(enc.len() < src.len());
+//!     Ok(())
+//! }
+//! ```
+mod rng;
+mod seq;
+mod useq;
+
+pub use rng::Rng;
+pub use seq::Seq;
+pub use useq::Useq;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Output must match `test_kit`, the test suite's data source.
+
+    #[test]
+    fn rng_test_kit() {
+        for &seed in &[0, 1, 0xFFFF_FFFF] {
+            assert!(Rng::new(seed).take(0x1000).eq(test_kit::Rng::new(seed).take(0x1000)));
+            for len in 0..16 {
+                assert_eq!(
+                    Rng::new(seed).gen_vec(len),
+                    test_kit::Rng::new(seed).gen_vec(len).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn seq_test_kit() {
+        assert!(Seq::default().take(0x1000).eq(test_kit::Seq::default().take(0x1000)));
+        let seq = Seq::masked(Rng::new(7), 0x0000_0F0F);
+        let test_kit_seq = test_kit::Seq::masked(test_kit::Rng::new(7), 0x0000_0F0F);
+        assert!(seq.take(0x1000).eq(test_kit_seq.take(0x1000)));
+    }
+
+    #[test]
+    fn useq_test_kit() {
+        assert!(Useq::default().eq(test_kit::Useq::default()));
+    }
+}
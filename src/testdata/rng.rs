@@ -0,0 +1,54 @@
+use std::mem;
+
+/// Deterministic random number generator.
+///
+/// A linear congruential generator, fast and reproducible across platforms but NOT suitable for
+/// cryptographic purposes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Rng(u32);
+
+impl Rng {
+    /// Create a new instance with the specified `seed`.
+    pub fn new(seed: u32) -> Self {
+        Self(seed)
+    }
+
+    /// Generate the next value.
+    #[inline(always)]
+    pub fn gen(&mut self) -> u32 {
+        self.0 = self.0.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        self.0
+    }
+
+    /// Generate `len` bytes.
+    ///
+    /// Values are stored in native byte order, as such output is platform endian dependent.
+    pub fn gen_vec(&mut self, len: usize) -> Vec<u8> {
+        let mut vec = Vec::with_capacity(len);
+        for _ in 0..(len / mem::size_of::<u32>()) {
+            vec.extend_from_slice(&self.0.to_ne_bytes());
+            self.gen();
+        }
+        let mut u = self.0;
+        for _ in 0..(len % mem::size_of::<u32>()) {
+            vec.push(u as u8);
+            u >>= 8;
+        }
+        vec
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Iterator for Rng {
+    type Item = u32;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.gen())
+    }
+}
@@ -0,0 +1,85 @@
+use super::rng::Rng;
+
+use std::io::{self, ErrorKind, Read, Write};
+
+/// Byte sequence generator with [Rng] core.
+///
+/// Generated values are masked, lower entropy masks produce more compressible sequences. The
+/// sequence is infinite.
+///
+/// As a [Read] instance generates bytes. As a [Write] instance verifies written bytes against the
+/// sequence, failing with [ErrorKind::InvalidData](std::io::ErrorKind) on mismatch, allowing
+/// decoded output to be verified without buffering.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Seq {
+    rng: Rng,
+    mask: u32,
+    u: u32,
+    n: u32,
+}
+
+impl Seq {
+    /// Create a new unmasked instance.
+    pub fn new(rng: Rng) -> Self {
+        Self { rng, mask: 0xFFFF_FFFF, u: 0, n: 0 }
+    }
+
+    /// Create a new instance with generated values masked by `mask`.
+    pub fn masked(rng: Rng, mask: u32) -> Self {
+        Self { rng, mask, u: 0, n: 0 }
+    }
+
+    /// Generate the next byte.
+    #[inline(always)]
+    pub fn gen(&mut self) -> u8 {
+        if self.n == 0 {
+            self.u = self.rng.gen() & self.mask;
+            self.n = 4;
+        }
+        self.n -= 1;
+        let v = self.u as u8;
+        self.u >>= 8;
+        v
+    }
+}
+
+impl Default for Seq {
+    fn default() -> Self {
+        Self::new(Rng::default())
+    }
+}
+
+impl Iterator for Seq {
+    type Item = u8;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.gen())
+    }
+}
+
+impl Read for Seq {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        for b in buf.iter_mut() {
+            *b = self.gen();
+        }
+        Ok(buf.len())
+    }
+}
+
+impl Write for Seq {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.iter().all(|&u| u == self.gen()) {
+            Ok(buf.len())
+        } else {
+            Err(ErrorKind::InvalidData.into())
+        }
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
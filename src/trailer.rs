@@ -0,0 +1,190 @@
+use crate::base::TrailerKind;
+use crate::decode::stream_len;
+use crate::error::Error;
+use crate::ops::{Len, PeekData, Skip};
+use crate::types::ShortWriter;
+
+use std::convert::{TryFrom, TryInto};
+use std::io;
+use std::mem;
+
+// Trailers follow the end of stream block. Each trailer is a `TrailerKind` magic u32, a payload
+// length u32 and the payload itself. Any number of trailers may be chained, for example a checksum
+// trailer followed by a padding trailer.
+
+const U32: usize = mem::size_of::<u32>();
+
+const HEADER_LEN: usize = U32 * 2;
+
+const CHUNK_LEN: usize = 0x0100;
+
+/// Write a `kind` trailer into `dst`, the payload being the concatenation of `chunks`.
+///
+/// # Errors
+///
+/// * [ErrorKind::InvalidInput](std::io::ErrorKind) if the payload exceeds `u32::MAX` bytes.
+/// * [ErrorKind::Other](std::io::ErrorKind) in case of `dst` buffer overflow.
+pub fn write<'a, O, I>(dst: &mut O, kind: TrailerKind, chunks: I) -> io::Result<()>
+where
+    O: ShortWriter,
+    I: IntoIterator<Item = &'a [u8]>,
+    I::IntoIter: Clone,
+{
+    let chunks = chunks.into_iter();
+    let len: u32 = chunks
+        .clone()
+        .map(|chunk| chunk.len() as u64)
+        .sum::<u64>()
+        .try_into()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "trailer overflow"))?;
+    dst.write_short_bytes(&u32::from(kind).to_le_bytes())?;
+    dst.write_short_bytes(&len.to_le_bytes())?;
+    for chunk in chunks {
+        for bytes in chunk.chunks(CHUNK_LEN) {
+            dst.write_short_bytes(bytes)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write a `kind` trailer holding `len` zero bytes into `dst`.
+///
+/// # Errors
+///
+/// * [ErrorKind::Other](std::io::ErrorKind) in case of `dst` buffer overflow.
+pub fn write_zeros<O: ShortWriter>(dst: &mut O, kind: TrailerKind, len: u32) -> io::Result<()> {
+    const ZEROS: [u8; CHUNK_LEN] = [0; CHUNK_LEN];
+    let n = len as usize / CHUNK_LEN;
+    let rem = len as usize % CHUNK_LEN;
+    write(dst, kind, (0..n).map(|_| &ZEROS[..]).chain(Some(&ZEROS[..rem])))
+}
+
+/// Split the trailers following the LZFSE stream in `src` into their kinds and payloads.
+///
+/// # Errors
+///
+/// * [Error::BadTrailer](crate::Error::BadTrailer) if the bytes following the end of stream block
+///   are not a sequence of well formed trailers of known [TrailerKind].
+/// * [Error](crate::Error) detailing the nature of any block header errors.
+pub fn split(src: &[u8]) -> crate::Result<Vec<(TrailerKind, &[u8])>> {
+    let mut rem = &src[stream_len(src)?..];
+    let mut trailers = Vec::default();
+    while !rem.is_empty() {
+        if rem.len() < HEADER_LEN {
+            return Err(Error::BadTrailer);
+        }
+        let kind = TrailerKind::try_from(u32::from_le_bytes(rem[..U32].try_into().unwrap()))?;
+        let len = u32::from_le_bytes(rem[U32..HEADER_LEN].try_into().unwrap()) as usize;
+        if rem.len() - HEADER_LEN < len {
+            return Err(Error::BadTrailer);
+        }
+        let (payload, tail) = rem[HEADER_LEN..].split_at(len);
+        trailers.push((kind, payload));
+        rem = tail;
+    }
+    Ok(trailers)
+}
+
+/// Returns the payload of the first `kind` trailer following the LZFSE stream in `src`.
+///
+/// # Errors
+///
+/// * [Error::BadTrailer](crate::Error::BadTrailer) if the trailers are malformed or there is no
+///   `kind` trailer.
+/// * [Error](crate::Error) detailing the nature of any block header errors.
+pub fn find(src: &[u8], kind: TrailerKind) -> crate::Result<&[u8]> {
+    split(src)?
+        .into_iter()
+        .find(|&(k, _)| k == kind)
+        .map(|(_, payload)| payload)
+        .ok_or(Error::BadTrailer)
+}
+
+/// Skip the end of stream block and optional trailers, which must be the final bytes in `src`.
+/// Returns the number of bytes skipped.
+pub fn skip_eos<I: Len + PeekData + Skip>(src: &mut I) -> crate::Result<u64> {
+    let len = src.len();
+    debug_assert!(len >= U32);
+    src.skip(U32);
+    while src.len() != 0 {
+        if src.len() < HEADER_LEN || TrailerKind::try_from(src.peek_u32()).is_err() {
+            return Err(Error::PayloadOverflow);
+        }
+        src.skip(U32);
+        let n = src.peek_u32() as usize;
+        src.skip(U32);
+        if src.len() < n {
+            return Err(Error::PayloadOverflow);
+        }
+        src.skip(n);
+    }
+    Ok(len as u64)
+}
+
+/// Skip the end of stream block and optional trailers, which may be followed by further bytes in
+/// `src`. Returns the number of bytes skipped.
+pub fn skip_eos_frame<I: Len + PeekData + Skip>(src: &mut I) -> crate::Result<u64> {
+    let len = src.len();
+    debug_assert!(len >= U32);
+    src.skip(U32);
+    while src.len() >= HEADER_LEN && TrailerKind::try_from(src.peek_u32()).is_ok() {
+        src.skip(U32);
+        let n = src.peek_u32() as usize;
+        src.skip(U32);
+        if src.len() < n {
+            return Err(Error::PayloadUnderflow);
+        }
+        src.skip(n);
+    }
+    Ok((len - src.len()) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bare() -> crate::Result<Vec<u8>> {
+        let mut bare = Vec::default();
+        crate::encode_bytes(b"test test test test", &mut bare)?;
+        Ok(bare)
+    }
+
+    #[test]
+    fn chained() -> crate::Result<()> {
+        let bare = bare()?;
+        let mut enc = bare.clone();
+        write(&mut enc, TrailerKind::Crc32, Some(&b"crc"[..]))?;
+        write_zeros(&mut enc, TrailerKind::Opaque, 0x0203)?;
+        write(&mut enc, TrailerKind::Digest, vec![&b"dig"[..], &b"est"[..]])?;
+        let trailers = split(&enc)?;
+        assert_eq!(trailers.len(), 3);
+        assert_eq!(trailers[0], (TrailerKind::Crc32, &b"crc"[..]));
+        assert_eq!(trailers[1].0, TrailerKind::Opaque);
+        assert!(trailers[1].1.len() == 0x0203 && trailers[1].1.iter().all(|&u| u == 0));
+        assert_eq!(trailers[2], (TrailerKind::Digest, &b"digest"[..]));
+        assert_eq!(find(&enc, TrailerKind::Digest)?, b"digest");
+        let index = bare.len() - U32;
+        assert_eq!(skip_eos(&mut &enc[index..])?, (enc.len() - index) as u64);
+        let mut src = &enc[index..];
+        assert_eq!(skip_eos_frame(&mut src)?, (enc.len() - index) as u64);
+        assert!(src.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn chained_truncated() -> crate::Result<()> {
+        let bare = bare()?;
+        let mut enc = bare.clone();
+        write(&mut enc, TrailerKind::Crc32, Some(&b"crc"[..]))?;
+        write(&mut enc, TrailerKind::Opaque, Some(&b"pad"[..]))?;
+        let index = bare.len() - U32;
+        for n in 1..HEADER_LEN + 3 {
+            let enc = &enc[..enc.len() - n];
+            assert!(matches!(split(enc), Err(Error::BadTrailer)));
+            assert!(matches!(find(enc, TrailerKind::Crc32), Err(Error::BadTrailer)));
+            assert!(matches!(skip_eos(&mut &enc[index..]), Err(Error::PayloadOverflow)));
+        }
+        assert!(matches!(find(&bare, TrailerKind::Crc32), Err(Error::BadTrailer)));
+        Ok(())
+    }
+}
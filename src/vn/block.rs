@@ -14,11 +14,9 @@ pub struct VnBlock {
 
 impl VnBlock {
     pub fn new(n_raw_bytes: u32, n_payload_bytes: u32) -> crate::Result<Self> {
-        if n_payload_bytes < 8 {
-            Err(VnErrorKind::BadPayloadCount(n_payload_bytes).into())
-        } else {
-            Ok(Self { n_raw_bytes, n_payload_bytes })
-        }
+        let s = Self { n_raw_bytes, n_payload_bytes };
+        s.validate()?;
+        Ok(s)
     }
 
     pub fn load_short<I: Copy + ShortBuffer>(&mut self, mut src: I) -> crate::Result<u32> {
@@ -31,8 +29,9 @@ impl VnBlock {
         let mut src = &src[..VN_HEADER_SIZE as usize];
         let magic_bytes      = src.read_u32();
         assert_eq!(magic_bytes, MagicBytes::Vxn.into());
-        self.n_raw_bytes     = src.read_u32();
-        self.n_payload_bytes = src.read_u32();
+        let n_raw_bytes      = src.read_u32();
+        let n_payload_bytes  = src.read_u32();
+        *self = Self::new(n_raw_bytes, n_payload_bytes)?;
         Ok(VN_HEADER_SIZE)
     }
 
@@ -52,4 +51,16 @@ impl VnBlock {
     pub fn n_raw_bytes(&self) -> u32 {
         self.n_raw_bytes
     }
+
+    fn validate(&self) -> crate::Result<()> {
+        if self.n_payload_bytes < 8 || self.n_payload_bytes > u32::MAX - VN_HEADER_SIZE {
+            Err(VnErrorKind::BadPayloadCount(self.n_payload_bytes).into())
+        } else if self.n_raw_bytes as u64
+            > self.n_payload_bytes as u64 * MAX_RAW_PAYLOAD_RATIO as u64
+        {
+            Err(VnErrorKind::BadRawByteCount(self.n_raw_bytes).into())
+        } else {
+            Ok(())
+        }
+    }
 }
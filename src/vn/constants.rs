@@ -4,6 +4,10 @@ pub const MAX_D_VALUE: u32 = 65_535;
 
 pub const VN_HEADER_SIZE: u32 = 0x0C;
 
+// Maximum `n_raw_bytes` to `n_payload_bytes` ratio. Opcodes encode at most 0x010F raw bytes using
+// 0x02 payload bytes, `LrgM`, so a block cannot represent more than 0x88 raw bytes per payload byte.
+pub const MAX_RAW_PAYLOAD_RATIO: u32 = 0x88;
+
 pub const EOS: u8 = 0x06;
 
 // `VN_PAYLOAD_LIMIT`. As a concession to pragmatism we'll use a relatively small limit of
@@ -11,6 +11,8 @@ pub enum VnErrorKind {
     BadPayloadCount(u32),
     /// Bad payload.
     BadPayload,
+    /// Bad raw byte count.
+    BadRawByteCount(u32),
     /// Bad opcode.
     BadOpcode,
 }
@@ -20,6 +22,7 @@ impl fmt::Display for VnErrorKind {
         match self {
             Self::BadPayloadCount(u) => write!(f, "bad payload count: 0x{u:08X}"),
             Self::BadPayload => write!(f, "bad payload"),
+            Self::BadRawByteCount(u) => write!(f, "bad raw byte count: 0x{u:08X}"),
             Self::BadOpcode => write!(f, "bad opcode"),
         }
     }
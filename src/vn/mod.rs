@@ -12,6 +12,7 @@ mod tests;
 
 pub use backend::VnBackend;
 pub use block::VnBlock;
+pub use constants::VN_HEADER_SIZE;
 pub use error_kind::VnErrorKind;
 pub use object::Vn;
 pub use ops::{vn_decompress, vn_probe};
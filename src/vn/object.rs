@@ -23,26 +23,13 @@ impl LmdMax for Vn {}
 unsafe impl MatchUnit for Vn {
     const MATCH_UNIT: u32 = 3;
 
-    #[cfg(target_endian = "little")]
     const MATCH_MASK: u32 = 0x00FF_FFFF;
 
-    #[cfg(target_endian = "big")]
-    const MATCH_MASK: u32 = 0xFFFF_FF00;
-
     #[inline(always)]
     fn hash_u(mut u: u32) -> u32 {
-        // Donald Knuth's multiplicative hash for big/ little endian simplicity, as opposed to the
-        // undefined hash function as per LZFSE reference.
-        // As we don't swap bytes, big/ little endian implementations will produce different,
-        // although presumably statistically equivalent outputs.
-        #[cfg(target_endian = "little")]
-        {
-            u &= Self::MATCH_MASK;
-        }
-        #[cfg(target_endian = "big")]
-        {
-            u >>= 8;
-        }
+        // Donald Knuth's multiplicative hash, as opposed to the undefined hash function as per
+        // LZFSE reference. Values are little endian, hence outputs are platform independent.
+        u &= Self::MATCH_MASK;
         u.wrapping_mul(0x9E37_79B1)
     }
 
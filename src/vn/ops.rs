@@ -10,7 +10,7 @@ where
 {
     let mut block = VnBlock::default();
     block.load_short(src)?;
-    let n_payload_bytes = VN_HEADER_SIZE + block.n_raw_bytes();
+    let n_payload_bytes = VN_HEADER_SIZE + block.n_payload_bytes();
     let n_raw_bytes = block.n_raw_bytes();
     Ok((n_payload_bytes, n_raw_bytes))
 }
@@ -455,3 +455,46 @@ fn fuzz_noise() -> crate::Result<()> {
     }
     Ok(())
 }
+
+// Max valued header fields. In all cases the decoder should reject the header via `Err(error)`
+// with explicit range checks rather than wrapping arithmetic.
+#[test]
+fn header_max() {
+    for &(n_raw_bytes, n_payload_bytes) in &[
+        (u32::MAX, u32::MAX),
+        (0, u32::MAX),
+        (0, u32::MAX - VN_HEADER_SIZE + 1),
+        (u32::MAX, 8),
+        (u32::MAX, u32::MAX / MAX_RAW_PAYLOAD_RATIO),
+        (8 * MAX_RAW_PAYLOAD_RATIO + 1, 8),
+        (0, 0),
+        (0, 7),
+    ] {
+        let mut bytes = Vec::default();
+        bytes.extend_from_slice(&u32::from(MagicBytes::Vxn).to_le_bytes());
+        bytes.extend_from_slice(&n_raw_bytes.to_le_bytes());
+        bytes.extend_from_slice(&n_payload_bytes.to_le_bytes());
+        assert!(VnBlock::default().load(&bytes).is_err());
+        assert!(super::vn_probe(bytes.as_slice()).is_err());
+    }
+}
+
+// Max valued header fields within range.
+#[test]
+fn header_max_valid() -> crate::Result<()> {
+    for &(n_raw_bytes, n_payload_bytes) in &[
+        (0, u32::MAX - VN_HEADER_SIZE),
+        (u32::MAX, u32::MAX - VN_HEADER_SIZE),
+        (8 * MAX_RAW_PAYLOAD_RATIO, 8),
+    ] {
+        let mut bytes = Vec::default();
+        bytes.extend_from_slice(&u32::from(MagicBytes::Vxn).to_le_bytes());
+        bytes.extend_from_slice(&n_raw_bytes.to_le_bytes());
+        bytes.extend_from_slice(&n_payload_bytes.to_le_bytes());
+        VnBlock::default().load(&bytes)?;
+        let (u, v) = super::vn_probe(bytes.as_slice())?;
+        assert_eq!(u, VN_HEADER_SIZE + n_payload_bytes);
+        assert_eq!(v, n_raw_bytes);
+    }
+    Ok(())
+}
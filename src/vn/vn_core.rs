@@ -47,6 +47,17 @@ impl VnCore {
         Ok(n_payload_bytes_len)
     }
 
+    #[inline(always)]
+    pub fn n_payload_bytes(&self) -> u32 {
+        self.n_payload_bytes
+    }
+
+    /// Loaded block declared raw byte count.
+    #[inline(always)]
+    pub fn n_raw_bytes(&self) -> u32 {
+        self.n_raw_bytes
+    }
+
     /// Decode all remaining bytes. Returning `n_payload_bytes`.
     pub fn decode<I, O>(&mut self, dst: &mut O, src: &mut I) -> crate::Result<u32>
     where
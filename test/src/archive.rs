@@ -0,0 +1,136 @@
+use lzfse_rust::{ArchiveReader, ArchiveWriter, Error};
+use test_kit::{Rng, Seq};
+
+use std::cell::RefCell;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::rc::Rc;
+
+fn members() -> Vec<(String, Vec<u8>)> {
+    vec![
+        ("empty".to_owned(), Vec::default()),
+        ("test.txt".to_owned(), b"test".to_vec()),
+        ("seq.bin".to_owned(), Iterator::take(Seq::default(), 0x0010_0000).collect()),
+        ("rng.bin".to_owned(), Rng::default().gen_vec(0x0004_0000).unwrap()),
+        (
+            "dir/ταχίστη αλώπηξ".to_owned(),
+            b"a quick brown fox jumps over the lazy dog ".repeat(0x1000),
+        ),
+    ]
+}
+
+fn archive() -> io::Result<Vec<u8>> {
+    let mut writer = ArchiveWriter::new(Vec::default());
+    for (name, data) in members() {
+        let member = writer.add(&name, &mut data.as_slice())?;
+        assert_eq!(member.n_raw_bytes, data.len() as u64);
+    }
+    writer.finalize()
+}
+
+// Records the byte ranges read.
+struct RecordReader {
+    inner: Cursor<Vec<u8>>,
+    reads: Rc<RefCell<Vec<Range<u64>>>>,
+}
+
+impl Read for RecordReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let pos = self.inner.position();
+        let n = self.inner.read(buf)?;
+        self.reads.borrow_mut().push(pos..pos + n as u64);
+        Ok(n)
+    }
+}
+
+impl Seek for RecordReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn round_trip() -> io::Result<()> {
+    let mut reader = ArchiveReader::new(Cursor::new(archive()?))?;
+    let members = members();
+    assert_eq!(reader.members().len(), members.len());
+    for (name, data) in members.iter() {
+        let member = reader.member(name).unwrap();
+        assert_eq!(member.n_raw_bytes, data.len() as u64);
+        let mut dec = Vec::default();
+        assert_eq!(reader.extract(name, &mut dec)?, data.len() as u64);
+        assert_eq!(dec, *data);
+    }
+    // Reverse order.
+    for (name, data) in members.iter().rev() {
+        let mut dec = Vec::default();
+        reader.extract(name, &mut dec)?;
+        assert_eq!(dec, *data);
+    }
+    Ok(())
+}
+
+#[test]
+fn extract_reads_member_only() -> io::Result<()> {
+    let reads = Rc::new(RefCell::new(Vec::default()));
+    let inner = RecordReader { inner: Cursor::new(archive()?), reads: Rc::clone(&reads) };
+    let mut reader = ArchiveReader::new(inner)?;
+    let index_pos = reader.members().iter().map(|m| m.offset + m.n_payload_bytes).max().unwrap();
+    // Index and footer reads only.
+    assert!(reads.borrow().iter().all(|range| range.start >= index_pos));
+    reads.borrow_mut().clear();
+    let member = reader.member("rng.bin").unwrap().clone();
+    let mut dec = Vec::default();
+    reader.extract(&member.name, &mut dec)?;
+    let end = member.offset + member.n_payload_bytes;
+    let reads = reads.borrow();
+    assert!(!reads.is_empty());
+    assert!(reads.iter().all(|range| range.start >= member.offset && range.end <= end));
+    Ok(())
+}
+
+#[test]
+fn member_not_found() -> io::Result<()> {
+    let mut reader = ArchiveReader::new(Cursor::new(archive()?))?;
+    assert!(reader.member("none").is_none());
+    assert!(matches!(reader.extract("none", &mut Vec::default()), Err(Error::MemberNotFound)));
+    Ok(())
+}
+
+#[test]
+fn duplicate_name() -> io::Result<()> {
+    let mut writer = ArchiveWriter::new(Vec::default());
+    writer.add("a", &mut b"a".as_ref())?;
+    let err = writer.add("a", &mut b"b".as_ref()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(writer.members().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn empty_archive() -> io::Result<()> {
+    let archive = ArchiveWriter::new(Vec::default()).finalize()?;
+    assert_eq!(archive.len(), 16);
+    let reader = ArchiveReader::new(Cursor::new(archive))?;
+    assert!(reader.members().is_empty());
+    Ok(())
+}
+
+#[test]
+fn bad_archive() -> io::Result<()> {
+    let archive = archive()?;
+    // Truncated.
+    for n in 0..32 {
+        assert!(ArchiveReader::new(Cursor::new(&archive[..archive.len() - 1 - n])).is_err());
+    }
+    // Plain LZFSE stream.
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(b"test", &mut enc)?;
+    assert!(matches!(ArchiveReader::new(Cursor::new(enc)), Err(Error::BadArchive)));
+    // Corrupt member count.
+    let mut archive = archive;
+    let n = archive.len();
+    archive[n - 8] ^= 0x01;
+    assert!(matches!(ArchiveReader::new(Cursor::new(archive)), Err(Error::BadArchive)));
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io::{self, Write};
+
+// Returns the block end offsets, all blocks are FSE blocks.
+fn block_ends(enc: &[u8]) -> lzfse_rust::Result<Vec<u64>> {
+    let mut end = 0;
+    let mut ends = Vec::default();
+    for (i, cost) in lzfse_rust::explain(enc)?.iter().enumerate() {
+        assert_eq!(cost.index, i as u32);
+        end += cost.n_raw_bytes as u64;
+        ends.push(end);
+    }
+    Ok(ends)
+}
+
+fn check_enc(enc: &[u8], dec: &[u8], boundaries: &[u64]) -> lzfse_rust::Result<()> {
+    let mut buf = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut buf)?;
+    assert!(buf == dec);
+    let ends = block_ends(enc)?;
+    assert_eq!(ends.last().copied().unwrap_or_default(), dec.len() as u64);
+    for &boundary in boundaries {
+        assert!(ends.contains(&boundary));
+    }
+    // Blocks decode to the expected ranges.
+    let mut decoder = LzfseDecoder::default();
+    let mut start = 0;
+    for (i, &end) in ends.iter().enumerate() {
+        buf.clear();
+        decoder.decode_block(enc, i, &mut buf)?;
+        assert!(buf == dec[start as usize..end as usize]);
+        start = end;
+    }
+    Ok(())
+}
+
+fn check(dec: &[u8], boundaries: &[u64]) -> lzfse_rust::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_block_boundaries(boundaries)?;
+    assert_eq!(encoder.block_boundaries(), boundaries);
+    let mut enc = Vec::default();
+    encoder.encode_bytes(dec, &mut enc)?;
+    check_enc(&enc, dec, boundaries)?;
+    let mut encoder = LzfseRingEncoder::default();
+    encoder.set_block_boundaries(boundaries)?;
+    let mut enc = Vec::default();
+    encoder.encode(&mut &*dec, &mut enc)?;
+    check_enc(&enc, dec, boundaries)?;
+    let mut wtr = encoder.writer_bytes(Vec::default());
+    wtr.write_all(dec)?;
+    let enc = wtr.finalize()?;
+    check_enc(&enc, dec, boundaries)?;
+    Ok(())
+}
+
+fn records(len: usize, record_len: usize) -> Vec<u64> {
+    (1..=len / record_len).map(|u| (u * record_len) as u64).collect()
+}
+
+#[test]
+fn text() -> lzfse_rust::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    for &record_len in &[0x0100, 0x1000, 0x3000, 0x0001_0000] {
+        check(&dec, &records(dec.len(), record_len))?;
+    }
+    check(&dec, &[1, 2, 3, 0x1001, 0x7FFF, dec.len() as u64 - 1])?;
+    Ok(())
+}
+
+#[test]
+fn seq() -> lzfse_rust::Result<()> {
+    let dec = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    check(&dec, &records(dec.len(), 0x0001_2345))
+}
+
+#[test]
+fn rng() -> lzfse_rust::Result<()> {
+    let dec = Rng::default().gen_vec(0x0004_0000).unwrap();
+    check(&dec, &records(dec.len(), 0x0800))
+}
+
+// Small inputs otherwise encoded as raw or VN blocks.
+#[test]
+fn small() -> lzfse_rust::Result<()> {
+    let dec = [0x41; 0x20];
+    check(&dec, &[0x10])?;
+    check(&dec, &[0x01, 0x1F, 0x20])?;
+    check(&dec[..2], &[1])?;
+    Ok(())
+}
+
+#[test]
+fn bad_boundaries() {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_block_boundaries(&[0x10]).unwrap();
+    for boundaries in &[&[0][..], &[2, 1], &[1, 1]] {
+        let err = encoder.set_block_boundaries(boundaries).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+    assert_eq!(encoder.block_boundaries(), &[0x10]);
+    encoder.set_block_boundaries(&[]).unwrap();
+    assert!(encoder.block_boundaries().is_empty());
+}
+
+#[test]
+fn out_of_range() {
+    let dec = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_block_boundaries(&[0x0800, 0x1001]).unwrap();
+    let err = encoder.encode_bytes(&dec, &mut Vec::default()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let mut encoder = LzfseRingEncoder::default();
+    encoder.set_block_boundaries(&[0x1001]).unwrap();
+    let err = encoder.encode(&mut dec.as_slice(), &mut Vec::default()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,71 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+use test_kit::Rng;
+
+use std::io;
+
+const ALICE: &[u8] = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+
+fn alice() -> io::Result<Vec<u8>> {
+    let mut data = Vec::default();
+    LzfseDecoder::default().decode_bytes(ALICE, &mut data)?;
+    Ok(data)
+}
+
+fn check(data: &[u8], block_size: usize) -> io::Result<()> {
+    let ratios = lzfse_rust::block_ratios(data, block_size)?;
+    assert_eq!(ratios.len(), data.chunks(block_size).count());
+    let mut encoder = LzfseEncoder::default();
+    let mut t_enc = 0;
+    let mut t_weighted = 0.0;
+    for (chunk, &ratio) in data.chunks(block_size).zip(ratios.iter()) {
+        let mut enc = Vec::default();
+        encoder.encode_bytes(chunk, &mut enc)?;
+        assert_eq!(ratio, chunk.len() as f64 / enc.len() as f64);
+        t_enc += enc.len();
+        t_weighted += ratio * enc.len() as f64;
+    }
+    let ratio = data.len() as f64 / t_enc as f64;
+    assert!((t_weighted / t_enc as f64 - ratio).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    assert!(lzfse_rust::block_ratios(&[], 0x1000)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn text() -> io::Result<()> {
+    let data = alice()?;
+    for &block_size in &[0x0100, 0x1000, 0x0001_0000, data.len(), data.len() + 1] {
+        check(&data, block_size)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn text_block_size() -> io::Result<()> {
+    // Larger blocks, better ratios.
+    let data = alice()?;
+    let ratio = |block_size| -> io::Result<f64> {
+        let ratios = lzfse_rust::block_ratios(&data, block_size)?;
+        Ok(ratios.iter().sum::<f64>() / ratios.len() as f64)
+    };
+    assert!(ratio(0x0400)? < ratio(0x4000)?);
+    Ok(())
+}
+
+#[test]
+fn random() -> io::Result<()> {
+    let data = Rng::default().gen_vec(0x0001_0000).unwrap();
+    check(&data, 0x1000)?;
+    assert!(lzfse_rust::block_ratios(&data, 0x1000)?.iter().all(|&u| u < 1.0));
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn zero_block_size() {
+    let _ = lzfse_rust::block_ratios(b"test", 0);
+}
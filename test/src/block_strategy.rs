@@ -0,0 +1,128 @@
+use lzfse_rust::{BlockStrategy, LzfseDecoder, LzfseEncoder, LzfseRingEncoder};
+use test_kit::Rng;
+
+use std::io::{self, Write};
+
+// Assumes the defaults (RAW_CUTOFF: 0x0014, VN_CUTOFF: 0x1000)
+const RAW_CUTOFF: usize = 0x0014;
+const VN_CUTOFF: usize = 0x1000;
+
+fn check(enc: &[u8], dec: &[u8], magic: &[u8; 4]) -> io::Result<()> {
+    assert_eq!(&enc[..4], magic);
+    let mut buf = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut buf)?;
+    assert!(buf == dec);
+    Ok(())
+}
+
+#[test]
+fn auto_raw() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    assert_eq!(encoder.block_strategy(), BlockStrategy::Auto);
+    for n in 0..=RAW_CUTOFF {
+        let dec = Rng::default().gen_vec(n).unwrap();
+        let mut enc = Vec::default();
+        encoder.encode_bytes(&dec, &mut enc)?;
+        check(&enc, &dec, b"bvx-")?;
+    }
+    Ok(())
+}
+
+#[test]
+fn never_raw() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_block_strategy(BlockStrategy::NeverRaw);
+    for n in 0..=VN_CUTOFF {
+        let dec = Rng::default().gen_vec(n).unwrap();
+        let mut enc = Vec::default();
+        encoder.encode_bytes(&dec, &mut enc)?;
+        check(&enc, &dec, b"bvxn")?;
+    }
+    Ok(())
+}
+
+#[test]
+fn never_raw_ring() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::default();
+    encoder.set_block_strategy(BlockStrategy::NeverRaw);
+    for n in 0..=VN_CUTOFF {
+        let dec = Rng::default().gen_vec(n).unwrap();
+        let mut enc = Vec::default();
+        encoder.encode(&mut dec.as_slice(), &mut enc)?;
+        check(&enc, &dec, b"bvxn")?;
+        let mut wtr = encoder.writer_bytes(Vec::default());
+        wtr.write_all(&dec)?;
+        let enc = wtr.finalize()?;
+        check(&enc, &dec, b"bvxn")?;
+    }
+    Ok(())
+}
+
+#[test]
+fn compact() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_block_strategy(BlockStrategy::Compact);
+    let dec = [0x41; 100];
+    let mut enc = Vec::default();
+    encoder.encode_bytes(&dec, &mut enc)?;
+    check(&enc, &dec, b"bvxn")?;
+    // Raw block: 8 byte header + 100 literal bytes + 4 byte end of stream marker.
+    assert!(enc.len() * 3 < 112);
+    // Above the VN cutoff.
+    let dec = [0x41; VN_CUTOFF * 2];
+    let mut enc = Vec::default();
+    encoder.encode_bytes(&dec, &mut enc)?;
+    check(&enc, &dec, b"bvxn")?;
+    let mut auto = Vec::default();
+    LzfseEncoder::default().encode_bytes(&dec, &mut auto)?;
+    check(&auto, &dec, b"bvx2")?;
+    assert!(enc.len() < auto.len());
+    Ok(())
+}
+
+#[test]
+fn compact_ring() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::default();
+    encoder.set_block_strategy(BlockStrategy::Compact);
+    for &n in &[100, VN_CUTOFF * 2] {
+        let dec = vec![0x41; n];
+        let mut enc = Vec::default();
+        encoder.encode(&mut dec.as_slice(), &mut enc)?;
+        check(&enc, &dec, b"bvxn")?;
+        let mut wtr = encoder.writer_bytes(Vec::default());
+        wtr.write_all(&dec)?;
+        let enc = wtr.finalize()?;
+        check(&enc, &dec, b"bvxn")?;
+    }
+    Ok(())
+}
+
+// Compact output is never larger than Auto output.
+#[test]
+fn compact_le_auto() -> io::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut text = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut text)?;
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_block_strategy(BlockStrategy::Compact);
+    let mut ring_encoder = LzfseRingEncoder::default();
+    ring_encoder.set_block_strategy(BlockStrategy::Compact);
+    for &n in &[VN_CUTOFF + 1, 0x2000, 0x8000, 0x0001_0000, 0x0002_0000] {
+        let dec = &text[..n];
+        let mut auto = Vec::default();
+        LzfseEncoder::default().encode_bytes(dec, &mut auto)?;
+        let mut enc = Vec::default();
+        encoder.encode_bytes(dec, &mut enc)?;
+        assert!(enc.len() <= auto.len());
+        let mut buf = Vec::default();
+        LzfseDecoder::default().decode_bytes(&enc, &mut buf)?;
+        assert!(buf == dec);
+        let mut enc = Vec::default();
+        ring_encoder.encode(&mut &*dec, &mut enc)?;
+        assert!(enc.len() <= auto.len());
+        let mut buf = Vec::default();
+        LzfseDecoder::default().decode_bytes(&enc, &mut buf)?;
+        assert!(buf == dec);
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use lzfse_rust::{Error, LzfseDecoder, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// "test" string encoded as a single raw block.
+const TEST: [u8; 16] = [
+    0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76, 0x78, 0x24,
+];
+
+fn raw_lie(n_raw_bytes: u32) -> Vec<u8> {
+    let mut enc = TEST.to_vec();
+    enc[4..8].copy_from_slice(&n_raw_bytes.to_le_bytes());
+    enc
+}
+
+#[test]
+fn raw_correct() -> io::Result<()> {
+    let mut dec = Vec::default();
+    let n = LzfseDecoder::default().decode_bounded(&TEST, &mut dec, 4)?;
+    assert_eq!(n, 4);
+    assert_eq!(dec, b"test");
+    Ok(())
+}
+
+#[test]
+fn raw_lie_big() {
+    let mut decoder = LzfseDecoder::default();
+    for &n in &[5, 6, 7, 8, 0x0000_1000, 0x7FFF_FFFF, 0xFFFF_FFF7] {
+        let enc = raw_lie(n);
+        let mut dec = Vec::default();
+        let result = decoder.decode_bounded(&enc, &mut dec, u64::MAX);
+        assert!(matches!(result, Err(Error::PayloadUnderflow)), "{:?}", result);
+        assert_eq!(dec.capacity(), 0);
+    }
+}
+
+#[test]
+fn raw_lie_overflow() {
+    let mut decoder = LzfseDecoder::default();
+    for &n in &[0xFFFF_FFF8, 0xFFFF_FFFF] {
+        let enc = raw_lie(n);
+        let mut dec = Vec::default();
+        let result = decoder.decode_bounded(&enc, &mut dec, u64::MAX);
+        assert!(matches!(result, Err(Error::PayloadOverflow)), "{:?}", result);
+        assert_eq!(dec.capacity(), 0);
+    }
+}
+
+#[test]
+fn raw_lie_small() {
+    let mut decoder = LzfseDecoder::default();
+    for n in 0..4 {
+        let enc = raw_lie(n);
+        let mut dec = Vec::default();
+        let result = decoder.decode_bounded(&enc, &mut dec, u64::MAX);
+        assert!(matches!(result, Err(Error::BadBlock(_))), "{:?}", result);
+        assert_eq!(dec.capacity(), 0);
+    }
+}
+
+#[test]
+fn limit() -> io::Result<()> {
+    let data = Seq::masked(Rng::new(0), 0x0000_0F0F).take(0x8000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(&data, &mut enc)?;
+    let mut decoder = LzfseDecoder::default();
+    let mut dec = Vec::default();
+    match decoder.decode_bounded(&enc, &mut dec, data.len() as u64 - 1) {
+        Err(Error::OutputLimitExceeded) => {}
+        Err(err) => panic!("{}", err),
+        Ok(_) => panic!(),
+    }
+    assert_eq!(dec.capacity(), 0);
+    let n = decoder.decode_bounded(&enc, &mut dec, data.len() as u64)?;
+    assert_eq!(n, data.len() as u64);
+    assert!(dec == data);
+    Ok(())
+}
+
+#[test]
+fn vn() -> io::Result<()> {
+    let data = Seq::masked(Rng::new(0), 0x0000_0F0F).take(0x0400).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(&data, &mut enc)?;
+    assert_eq!(&enc[..4], b"bvxn");
+    let mut dec = Vec::default();
+    let n = LzfseDecoder::default().decode_bounded(&enc, &mut dec, data.len() as u64)?;
+    assert_eq!(n, data.len() as u64);
+    assert!(dec == data);
+    Ok(())
+}
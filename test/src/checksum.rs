@@ -0,0 +1,151 @@
+use lzfse_rust::{checksum, Error, LzfseDecoder, LzfseRingDecoder, TrailerKind};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Trailer: magic u32, len u32, CRC32 u32, decoded length u64.
+const TRAILER_LEN: usize = 20;
+
+fn encode(src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    let n = checksum::encode_bytes(src, &mut enc)?;
+    assert_eq!(n, enc.len() as u64);
+    Ok(enc)
+}
+
+fn check_mismatch(enc: &[u8]) {
+    let mut dec = Vec::default();
+    match checksum::decode_bytes(enc, &mut dec) {
+        Err(Error::ChecksumMismatch) => {}
+        res => panic!("{:?}", res),
+    }
+}
+
+#[test]
+fn round_trip() -> lzfse_rust::Result<()> {
+    for n in [0, 1, 4, 0x1000, 0x0010_0000] {
+        let src = Seq::default().take(n).collect::<Vec<_>>();
+        let enc = encode(&src)?;
+        let mut dec = Vec::default();
+        assert_eq!(checksum::decode_bytes(&enc, &mut dec)?, n as u64);
+        assert!(dec == src);
+    }
+    Ok(())
+}
+
+#[test]
+fn stream_unaltered() -> lzfse_rust::Result<()> {
+    let src = Rng::new(1).gen_vec(0x8000).unwrap();
+    let enc = encode(&src)?;
+    let mut plain = Vec::default();
+    lzfse_rust::encode_bytes(&src, &mut plain)?;
+    assert!(enc[..enc.len() - TRAILER_LEN] == plain);
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+    assert!(dec == src);
+    let mut dec = Vec::default();
+    LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut dec)?;
+    assert!(dec == src);
+    Ok(())
+}
+
+#[test]
+fn dst_prefix() -> lzfse_rust::Result<()> {
+    let src = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let enc = encode(&src)?;
+    let mut dec = b"prefix".to_vec();
+    checksum::decode_bytes(&enc, &mut dec)?;
+    assert_eq!(&dec[..6], b"prefix");
+    assert!(dec[6..] == src);
+    Ok(())
+}
+
+#[test]
+fn corrupt_payload() -> io::Result<()> {
+    // Random data encodes as a raw block, payload corruption is invisible to LZFSE alone.
+    let src = Rng::new(2).gen_vec(0x1000).unwrap();
+    let enc = encode(&src)?;
+    for index in [8, 0x0800, 8 + 0x0FFF] {
+        let mut enc = enc.clone();
+        enc[index] ^= 1;
+        let mut dec = Vec::default();
+        LzfseDecoder::default().decode_bytes(&enc, &mut dec).unwrap();
+        check_mismatch(&enc);
+    }
+    Ok(())
+}
+
+#[test]
+fn corrupt_trailer() -> io::Result<()> {
+    let src = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let enc = encode(&src)?;
+    for index in enc.len() - 12..enc.len() {
+        let mut enc = enc.clone();
+        enc[index] ^= 1;
+        check_mismatch(&enc);
+    }
+    Ok(())
+}
+
+#[test]
+fn missing_trailer() -> io::Result<()> {
+    let src = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let enc = encode(&src)?;
+    for n in [1, TRAILER_LEN - 1, TRAILER_LEN] {
+        let mut dec = Vec::default();
+        match checksum::decode_bytes(&enc[..enc.len() - n], &mut dec) {
+            Err(Error::BadTrailer) => {}
+            res => panic!("{:?}", res),
+        }
+    }
+    for len in [0, TRAILER_LEN - 1] {
+        let mut dec = Vec::default();
+        match checksum::decode_bytes(&enc[..len], &mut dec) {
+            Err(Error::BadTrailer) => {}
+            res => panic!("{:?}", res),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn other_trailer_kind() -> io::Result<()> {
+    let src = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let enc = encode(&src)?;
+    let index = enc.len() - TRAILER_LEN;
+    for kind in [TrailerKind::Opaque, TrailerKind::Digest] {
+        let mut enc = enc.clone();
+        enc[index..index + 4].copy_from_slice(&kind.magic_bytes());
+        let mut dec = Vec::default();
+        match checksum::decode_bytes(&enc, &mut dec) {
+            Err(Error::BadTrailer) => {}
+            res => panic!("{:?}", res),
+        }
+        // Plain decoders skip any known trailer kind.
+        dec.clear();
+        lzfse_rust::decode_bytes(&enc, &mut dec)?;
+        assert!(dec == src);
+    }
+    Ok(())
+}
+
+#[test]
+fn chained_trailers() -> lzfse_rust::Result<()> {
+    let src = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let enc = encode(&src)?;
+    // Checksum trailer followed by a padding trailer.
+    let (mut info, bare) = lzfse_rust::strip_frame(&enc)?;
+    info.trailers.push((TrailerKind::Opaque, vec![0; 0x33]));
+    let mut padded = Vec::default();
+    lzfse_rust::add_frame(bare, &info, &mut padded)?;
+    let mut dec = Vec::default();
+    assert_eq!(checksum::decode_bytes(&padded, &mut dec)?, src.len() as u64);
+    assert!(dec == src);
+    dec.clear();
+    LzfseDecoder::default().decode_bytes(&padded, &mut dec)?;
+    assert!(dec == src);
+    dec.clear();
+    LzfseRingDecoder::default().decode(&mut padded.as_slice(), &mut dec)?;
+    assert!(dec == src);
+    Ok(())
+}
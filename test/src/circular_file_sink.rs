@@ -0,0 +1,84 @@
+use lzfse_rust::{CircularFileSink, LzfseEncoder, LzfseRingDecoder};
+use test_kit::{Rng, Seq};
+
+use std::env;
+use std::fs;
+use std::io::{self, Cursor};
+use std::process;
+
+fn encode(src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(src, &mut enc)?;
+    Ok(enc)
+}
+
+fn check(src: &[u8], capacity: u64) -> io::Result<()> {
+    let enc = encode(src)?;
+    let mut sink = CircularFileSink::new(Cursor::new(Vec::default()), capacity)?;
+    let (_, n) = LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut sink)?;
+    assert_eq!(n, src.len() as u64);
+    assert_eq!(sink.n_bytes(), src.len() as u64);
+    let len = (src.len() as u64).min(capacity) as usize;
+    let tail = &src[src.len() - len..];
+    // Physical layout, rotated by the logical head.
+    let head = sink.head() as usize;
+    let file = sink.get_ref().get_ref();
+    assert_eq!(file.len(), len);
+    assert!(file[head..] == tail[..len - head]);
+    assert!(file[..head] == tail[len - head..]);
+    // Logical order.
+    let mut dst = Vec::default();
+    assert_eq!(sink.copy_to(&mut dst)?, len as u64);
+    assert!(dst == tail);
+    Ok(())
+}
+
+#[test]
+fn last_n() -> io::Result<()> {
+    let src =
+        Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), 0x0010_0000).collect::<Vec<_>>();
+    for &capacity in &[1, 2, 3, 0x0100, 0x1000, 0x0001_2345, 0x000F_FFFF, 0x0010_0000, 0x0020_0000]
+    {
+        check(&src, capacity)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn short() -> io::Result<()> {
+    for n in 0..0x40 {
+        let src = Iterator::take(Seq::default(), n).collect::<Vec<_>>();
+        for capacity in 1..0x24 {
+            check(&src, capacity)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn rng() -> io::Result<()> {
+    let src = Rng::default().gen_vec(0x0008_0000).unwrap();
+    check(&src, 0x0001_0001)
+}
+
+#[test]
+fn file() -> io::Result<()> {
+    let src = Iterator::take(Seq::default(), 0x0040_0000).collect::<Vec<_>>();
+    let enc = encode(&src)?;
+    let path = env::temp_dir().join(format!("lzfse_rust_circular_{}.bin", process::id()));
+    let mut sink = CircularFileSink::create(&path, 0x0001_0000)?;
+    let result = LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut sink);
+    let mut dst = Vec::default();
+    let copy = sink.copy_to(&mut dst);
+    let head = sink.head() as usize;
+    drop(sink);
+    let file = fs::read(&path);
+    fs::remove_file(&path)?;
+    result?;
+    assert_eq!(copy?, 0x0001_0000);
+    assert!(dst == src[src.len() - 0x0001_0000..]);
+    let file = file?;
+    assert_eq!(file.len(), 0x0001_0000);
+    assert!(file[head..] == dst[..0x0001_0000 - head]);
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use lzfse_rust::{Error, LzfseDecoder, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Returns the number of blocks.
+fn check(dec: &[u8]) -> io::Result<usize> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(dec, &mut enc)?;
+    let mut decoder = LzfseDecoder::default();
+    let mut buf = Vec::default();
+    let mut index = 0;
+    loop {
+        let mark = buf.len();
+        match decoder.decode_block(&enc, index, &mut buf) {
+            Ok(n) => assert_eq!(n, (buf.len() - mark) as u64),
+            Err(Error::BlockIndexOutOfRange) => break,
+            Err(err) => return Err(err.into()),
+        }
+        index += 1;
+    }
+    assert!(buf == dec);
+    Ok(index)
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    check(&[])?;
+    Ok(())
+}
+
+#[test]
+fn text() -> io::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut dec = Vec::default();
+    lzfse_rust::decode_bytes(enc, &mut dec)?;
+    assert!(check(&dec)? > 1);
+    Ok(())
+}
+
+#[test]
+fn mixed() -> io::Result<()> {
+    let mut dec = Vec::default();
+    for _ in 0..4 {
+        dec.extend(Iterator::take(Seq::default(), 0x0001_0000));
+        dec.extend(Rng::default().gen_vec(0x0001_0000).unwrap());
+    }
+    assert!(check(&dec)? > 1);
+    Ok(())
+}
+
+#[test]
+fn truncated() -> io::Result<()> {
+    let dec = Rng::default().gen_vec(0x1000).unwrap();
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(&dec, &mut enc)?;
+    let mut buf = Vec::default();
+    assert!(lzfse_rust::decode_block(&enc[..enc.len() - 5], 0, &mut buf).is_err());
+    Ok(())
+}
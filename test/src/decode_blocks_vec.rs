@@ -0,0 +1,87 @@
+use lzfse_rust::{DecodeReport, LzfseDecoder, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Returns the number of blocks.
+fn check(enc: &[u8], dec: &[u8]) -> io::Result<usize> {
+    let blocks = LzfseDecoder::default().decode_blocks_vec(enc)?;
+    assert_eq!(blocks.concat(), dec);
+    let mut buf = Vec::default();
+    let report: DecodeReport = LzfseDecoder::default().decode_bytes_report(enc, &mut buf)?;
+    assert_eq!(blocks.len() as u64, report.n_blocks);
+    for (index, block) in blocks.iter().enumerate() {
+        buf.clear();
+        lzfse_rust::decode_block(enc, index, &mut buf)?;
+        assert_eq!(*block, buf);
+    }
+    Ok(blocks.len())
+}
+
+fn encode(dec: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(dec, &mut enc)?;
+    Ok(enc)
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    let enc = encode(&[])?;
+    check(&enc, &[])?;
+    Ok(())
+}
+
+#[test]
+fn text() -> io::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut dec = Vec::default();
+    lzfse_rust::decode_bytes(enc, &mut dec)?;
+    assert!(check(enc, &dec)? > 1);
+    Ok(())
+}
+
+#[test]
+fn mixed() -> io::Result<()> {
+    let mut dec = Vec::default();
+    for _ in 0..4 {
+        dec.extend(Iterator::take(Seq::default(), 0x0001_0000));
+        dec.extend(Rng::default().gen_vec(0x0001_0000).unwrap());
+    }
+    assert!(check(&encode(&dec)?, &dec)? > 1);
+    Ok(())
+}
+
+#[test]
+fn block_boundaries() -> io::Result<()> {
+    let dec: Vec<u8> = Iterator::take(Seq::default(), 0x0001_0000).collect();
+    let offsets = [0x1000, 0x2000, 0x8000];
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_block_boundaries(&offsets)?;
+    let mut enc = Vec::default();
+    encoder.encode_bytes(&dec, &mut enc)?;
+    check(&enc, &dec)?;
+    // Block boundaries align with block ends.
+    let blocks = lzfse_rust::decode_blocks_vec(&enc)?;
+    let ends: Vec<u64> = blocks
+        .iter()
+        .scan(0, |u, block| {
+            *u += block.len() as u64;
+            Some(*u)
+        })
+        .collect();
+    for offset in offsets.iter() {
+        assert!(ends.contains(offset));
+    }
+    Ok(())
+}
+
+#[test]
+fn truncated() -> io::Result<()> {
+    let dec = Rng::default().gen_vec(0x1000).unwrap();
+    let enc = encode(&dec)?;
+    assert!(lzfse_rust::decode_blocks_vec(&enc[..enc.len() - 5]).is_err());
+    let mut enc = enc;
+    enc.push(0);
+    assert!(lzfse_rust::decode_blocks_vec(&enc).is_err());
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use lzfse_rust::{Error, LzfseDecoder, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn data() -> Vec<Vec<u8>> {
+    vec![
+        Vec::default(),
+        b"test".to_vec(),
+        Seq::masked(Rng::new(0), 0x0000_0F0F).take(0x0400).collect(),
+        Seq::masked(Rng::new(0), 0x0000_0F0F).take(0x0010_0000).collect(),
+        Rng::default().gen_vec(0x8000).unwrap(),
+    ]
+}
+
+#[test]
+fn correct() -> io::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    for src in data() {
+        let mut enc = Vec::default();
+        LzfseEncoder::default().encode_bytes(&src, &mut enc)?;
+        let mut dec = Vec::default();
+        let n = decoder.decode_bytes_expect(&enc, &mut dec, src.len() as u64)?;
+        assert_eq!(n, src.len() as u64);
+        assert!(dec == src);
+    }
+    Ok(())
+}
+
+#[test]
+fn incorrect() -> io::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    for src in data() {
+        let mut enc = Vec::default();
+        LzfseEncoder::default().encode_bytes(&src, &mut enc)?;
+        let len = src.len() as u64;
+        for &expected_len in &[len.wrapping_sub(1), len + 1, len * 2 + 1, u64::MAX] {
+            let mut dec = Vec::default();
+            match decoder.decode_bytes_expect(&enc, &mut dec, expected_len) {
+                Err(Error::OutputSizeMismatch) => {}
+                Err(err) => panic!("{}", err),
+                Ok(_) => panic!(),
+            }
+            // Rejected ahead of decoding.
+            assert_eq!(dec.capacity(), 0);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn append() -> io::Result<()> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(b"test", &mut enc)?;
+    let mut dec = b"prefix ".to_vec();
+    let n = lzfse_rust::decode_bytes_expect(&enc, &mut dec, 4)?;
+    assert_eq!(n, 4);
+    assert_eq!(dec, b"prefix test");
+    Ok(())
+}
+
+#[test]
+fn corrupt_header() {
+    // "test" string encoded as a single raw block, claiming 5 bytes.
+    let enc = [
+        0x62, 0x76, 0x78, 0x2d, 0x05, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76, 0x78,
+        0x24,
+    ];
+    let mut decoder = LzfseDecoder::default();
+    for &expected_len in &[4, 5] {
+        let mut dec = Vec::default();
+        assert!(decoder.decode_bytes_expect(&enc, &mut dec, expected_len).is_err());
+        assert_eq!(dec.capacity(), 0);
+    }
+}
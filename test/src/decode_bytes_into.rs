@@ -0,0 +1,77 @@
+use lzfse_rust::{Error, LzfseDecoder};
+use test_kit::Seq;
+
+use std::io;
+
+const RAW: &[u8] = include_bytes!("../../data/mutate/raw.lzfse");
+const VX1: &[u8] = include_bytes!("../../data/mutate/vx1.lzfse");
+const VX2: &[u8] = include_bytes!("../../data/mutate/vx2.lzfse");
+const VXN: &[u8] = include_bytes!("../../data/mutate/vxn.lzfse");
+
+const GUARD: u8 = 0xA5;
+
+fn encoded() -> io::Result<Vec<Vec<u8>>> {
+    let mut vec = vec![b"bvx$".to_vec(), RAW.to_vec(), VX1.to_vec(), VX2.to_vec(), VXN.to_vec()];
+    for len in [1, 4, 0x0100, 0x1000, 0x0040_0000] {
+        let src = Seq::default().take(len).collect::<Vec<_>>();
+        let mut enc = Vec::default();
+        lzfse_rust::encode_bytes(&src, &mut enc)?;
+        vec.push(enc);
+    }
+    Ok(vec)
+}
+
+#[test]
+fn exact() -> io::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    for enc in encoded()? {
+        let mut expected = Vec::default();
+        decoder.decode_bytes(&enc, &mut expected)?;
+        let mut buf = vec![GUARD; expected.len() + 0x0100];
+        let n = decoder.decode_bytes_into(&enc, &mut buf)?;
+        assert_eq!(n, expected.len());
+        assert!(buf[..n] == expected);
+        // The remainder is untouched.
+        assert!(buf[n..].iter().all(|&u| u == GUARD));
+        let n = lzfse_rust::decode_bytes_into(&enc, &mut buf[..expected.len()])?;
+        assert_eq!(n, expected.len());
+    }
+    Ok(())
+}
+
+#[test]
+fn overflow() -> io::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    for enc in encoded()? {
+        let mut expected = Vec::default();
+        decoder.decode_bytes(&enc, &mut expected)?;
+        let len = expected.len();
+        for n in [0, 1, len / 2, len.saturating_sub(1)] {
+            if n >= len {
+                continue;
+            }
+            let mut buf = vec![GUARD; len + 0x0100];
+            match decoder.decode_bytes_into(&enc, &mut buf[..n]) {
+                Err(Error::BufferOverflow) => {}
+                res => panic!("{}: {:?}", n, res),
+            }
+            // Nothing is written beyond `dst`.
+            assert!(buf[n..].iter().all(|&u| u == GUARD), "{}", n);
+        }
+    }
+    Ok(())
+}
+
+// Other errors are reported as per `decode_bytes`.
+#[test]
+fn truncated() {
+    let mut decoder = LzfseDecoder::default();
+    let mut buf = vec![0u8; 0x0010_0000];
+    for enc in [RAW, VX1, VX2, VXN] {
+        for n in 0..enc.len() {
+            let err = decoder.decode_bytes_into(&enc[..n], &mut buf).unwrap_err();
+            let expected = decoder.decode_bytes(&enc[..n], &mut Vec::default()).unwrap_err();
+            assert_eq!(format!("{err:?}"), format!("{expected:?}"));
+        }
+    }
+}
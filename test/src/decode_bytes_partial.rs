@@ -0,0 +1,139 @@
+use lzfse_rust::{Error, LzfseDecoder};
+use test_kit::Seq;
+
+use std::io;
+
+const RAW: &[u8] = include_bytes!("../../data/mutate/raw.lzfse");
+const VX1: &[u8] = include_bytes!("../../data/mutate/vx1.lzfse");
+const VX2: &[u8] = include_bytes!("../../data/mutate/vx2.lzfse");
+const VXN: &[u8] = include_bytes!("../../data/mutate/vxn.lzfse");
+
+fn decode(enc: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    Ok(dec)
+}
+
+fn partial(decoder: &mut LzfseDecoder, enc: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dec = Vec::default();
+    let n = decoder.decode_bytes_partial(enc, &mut dec)?;
+    assert_eq!(n, dec.len());
+    Ok(dec)
+}
+
+// Truncate at every offset, recovered output must be a growing prefix of the decoded output.
+fn check_truncated(enc: &[u8]) -> io::Result<Vec<usize>> {
+    let expected = decode(enc)?;
+    let mut decoder = LzfseDecoder::default();
+    let mut lens = Vec::default();
+    for n in 0..enc.len() {
+        let dec = partial(&mut decoder, &enc[..n])?;
+        assert!(expected.starts_with(&dec));
+        if let Some(&u) = lens.last() {
+            assert!(u <= dec.len());
+        }
+        lens.push(dec.len());
+    }
+    assert_eq!(partial(&mut decoder, enc)?, expected);
+    Ok(lens)
+}
+
+#[test]
+fn complete() -> io::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    for &enc in [b"bvx$".as_ref(), RAW, VX1, VX2, VXN].iter() {
+        assert_eq!(partial(&mut decoder, enc)?, decode(enc)?);
+    }
+    Ok(())
+}
+
+#[test]
+fn raw() -> io::Result<()> {
+    let lens = check_truncated(RAW)?;
+    let n = decode(RAW)?.len();
+    // Raw blocks recover byte by byte.
+    for (i, &len) in lens.iter().enumerate().skip(8).take(n) {
+        assert_eq!(len, i - 8);
+    }
+    assert_eq!(lens[RAW.len() - 4], n);
+    Ok(())
+}
+
+#[test]
+fn vxn() -> io::Result<()> {
+    let lens = check_truncated(VXN)?;
+    let n = decode(VXN)?.len();
+    // LZVN blocks recover complete operations.
+    assert!(lens.iter().any(|&u| u != 0 && u < n));
+    assert_eq!(lens[VXN.len() - 4], n);
+    Ok(())
+}
+
+#[test]
+fn vx1() -> io::Result<()> {
+    let lens = check_truncated(VX1)?;
+    let n = decode(VX1)?.len();
+    // LZFSE blocks recover only if complete.
+    assert!(lens.iter().all(|&u| u == 0 || u == n));
+    assert_eq!(lens[VX1.len() - 4], n);
+    Ok(())
+}
+
+#[test]
+fn vx2() -> io::Result<()> {
+    let lens = check_truncated(VX2)?;
+    let n = decode(VX2)?.len();
+    assert!(lens.iter().all(|&u| u == 0 || u == n));
+    assert_eq!(lens[VX2.len() - 4], n);
+    Ok(())
+}
+
+#[test]
+fn multi_block() -> io::Result<()> {
+    let src = Seq::default().take(0x0040_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&src, &mut enc)?;
+    let mut decoder = LzfseDecoder::default();
+    let mut prev = 0;
+    for i in 1..8 {
+        let dec = partial(&mut decoder, &enc[..enc.len() * i / 8])?;
+        assert!(src.starts_with(&dec));
+        assert!(prev <= dec.len());
+        prev = dec.len();
+    }
+    assert_ne!(prev, 0);
+    assert!(prev < src.len());
+    assert!(partial(&mut decoder, &enc[..enc.len() - 4])? == src);
+    assert!(partial(&mut decoder, &enc)? == src);
+    Ok(())
+}
+
+#[test]
+fn append() -> io::Result<()> {
+    let mut dec = b"prefix".to_vec();
+    let n = LzfseDecoder::default().decode_bytes_partial(&VXN[..VXN.len() / 2], &mut dec)?;
+    assert!(dec.starts_with(b"prefix"));
+    assert_eq!(dec.len(), 6 + n);
+    assert!(decode(VXN)?.starts_with(&dec[6..]));
+    Ok(())
+}
+
+#[test]
+fn bad_block() {
+    let mut dec = Vec::default();
+    match LzfseDecoder::default().decode_bytes_partial(b"bvxXbvx$", &mut dec) {
+        Err(Error::BadBlock(_)) => {}
+        res => panic!("{:?}", res),
+    }
+}
+
+#[test]
+fn payload_overflow() {
+    let mut enc = RAW.to_vec();
+    enc.push(0);
+    let mut dec = Vec::default();
+    match LzfseDecoder::default().decode_bytes_partial(&enc, &mut dec) {
+        Err(Error::PayloadOverflow) => {}
+        res => panic!("{:?}", res),
+    }
+}
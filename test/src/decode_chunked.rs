@@ -0,0 +1,90 @@
+use lzfse_rust::{LzfseEncoder, LzfseRingDecoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn check(src: &[u8], chunk_size: usize) -> io::Result<()> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(src, &mut enc)?;
+    let mut dec = Vec::default();
+    let mut lens = Vec::default();
+    let n = LzfseRingDecoder::default().decode_chunked(&enc, chunk_size, |chunk| {
+        dec.extend_from_slice(chunk);
+        lens.push(chunk.len());
+    })?;
+    assert_eq!(n, src.len() as u64);
+    assert!(dec == src);
+    // Exact sizes.
+    assert_eq!(lens.len(), src.chunks(chunk_size).count());
+    if let Some((last, lens)) = lens.split_last() {
+        assert!(lens.iter().all(|&len| len == chunk_size));
+        assert_eq!(*last, src.len() - lens.len() * chunk_size);
+    }
+    Ok(())
+}
+
+#[test]
+fn chunk_sizes() -> io::Result<()> {
+    let src =
+        Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), 0x0020_0000).collect::<Vec<_>>();
+    for &chunk_size in &[1, 2, 3, 1400, 1500, 0x1000, 0x0001_0001, 0x0020_0000, 0x0040_0000] {
+        check(&src, chunk_size)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn data() -> io::Result<()> {
+    for src in [
+        Vec::default(),
+        b"test".to_vec(),
+        vec![0; 0x0010_0000],
+        Rng::default().gen_vec(0x0010_0000).unwrap(),
+        Iterator::take(Seq::default(), 0x0010_0000).collect(),
+    ]
+    .iter()
+    {
+        for &chunk_size in &[1, 1500, 0x0001_0000] {
+            check(src, chunk_size)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn short() -> io::Result<()> {
+    for n in 0..0x40 {
+        let src = Iterator::take(Seq::default(), n).collect::<Vec<_>>();
+        for chunk_size in 1..0x24 {
+            check(&src, chunk_size)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn free_function() -> io::Result<()> {
+    let src = Iterator::take(Seq::default(), 0x8000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&src, &mut enc)?;
+    let mut dec = Vec::default();
+    lzfse_rust::decode_chunked(&enc, 1000, |chunk| dec.extend_from_slice(chunk))?;
+    assert!(dec == src);
+    Ok(())
+}
+
+#[test]
+fn error() {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&[0u8; 0x1000], &mut enc).unwrap();
+    enc.truncate(enc.len() - 1);
+    let mut n = 0;
+    assert!(lzfse_rust::decode_chunked(&enc, 0x0100, |chunk| n += chunk.len()).is_err());
+    assert!(n <= 0x1000);
+}
+
+#[test]
+#[should_panic]
+fn zero_chunk_size() {
+    let _ = lzfse_rust::decode_chunked(&[], 0, |_| {});
+}
@@ -0,0 +1,102 @@
+use lzfse_rust::{LzfseDecoder, LzfseRingDecoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn encode(src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(src, &mut enc)?;
+    Ok(enc)
+}
+
+fn check(srcs: &[&[u8]], enc: &[u8]) -> io::Result<()> {
+    let expected = srcs.concat();
+    let mut dec = Vec::default();
+    let (u, v) = LzfseRingDecoder::default().decode_concatenated(&mut &*enc, &mut dec)?;
+    assert_eq!(u, enc.len() as u64);
+    assert_eq!(v, expected.len() as u64);
+    assert!(dec == expected);
+    let mut dec = Vec::default();
+    let n = LzfseDecoder::default().decode_bytes_concatenated(enc, &mut dec)?;
+    assert_eq!(n, expected.len() as u64);
+    assert!(dec == expected);
+    Ok(())
+}
+
+fn check_err(enc: &[u8]) {
+    let mut dec = Vec::default();
+    assert!(LzfseRingDecoder::default().decode_concatenated(&mut &*enc, &mut dec).is_err());
+    let mut dec = Vec::default();
+    assert!(LzfseDecoder::default().decode_bytes_concatenated(enc, &mut dec).is_err());
+}
+
+#[test]
+fn single() -> io::Result<()> {
+    let u = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    check(&[&u], &encode(&u)?)
+}
+
+#[test]
+fn multiple() -> io::Result<()> {
+    let u = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    let v = Rng::new(1).gen_vec(0x0008_0000).unwrap();
+    let w = b"test".to_vec();
+    let mut enc = encode(&u)?;
+    enc.extend_from_slice(&encode(&v)?);
+    enc.extend_from_slice(&encode(&w)?);
+    check(&[&u, &v, &w], &enc)
+}
+
+#[test]
+fn empty_frames() -> io::Result<()> {
+    let u = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let mut enc = encode(&[])?;
+    enc.extend_from_slice(&encode(&u)?);
+    enc.extend_from_slice(&encode(&[])?);
+    check(&[&u], &enc)
+}
+
+#[test]
+fn trailer() -> io::Result<()> {
+    let u = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let mut enc = encode(&u)?;
+    enc.extend_from_slice(b"bvxt");
+    enc.extend_from_slice(&3u32.to_le_bytes());
+    enc.extend_from_slice(b"abc");
+    enc.extend_from_slice(&encode(&u)?);
+    check(&[&u, &u], &enc)
+}
+
+// Standard decoding rejects concatenated frames.
+#[test]
+fn decode_rejects() -> io::Result<()> {
+    let mut enc = encode(b"abc")?;
+    enc.extend_from_slice(&encode(b"def")?);
+    let mut dec = Vec::default();
+    assert!(LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut dec).is_err());
+    Ok(())
+}
+
+#[test]
+fn trailing_garbage() -> io::Result<()> {
+    let u = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let enc = encode(&u)?;
+    for garbage in [&b"x"[..], b"xyz", b"xyzw", b"bvx", b"\0\0\0\0\0\0\0\0"] {
+        let mut bad = enc.clone();
+        bad.extend_from_slice(garbage);
+        check_err(&bad);
+    }
+    Ok(())
+}
+
+#[test]
+fn truncated_frame() -> io::Result<()> {
+    let u = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let enc = encode(&u)?;
+    for n in (4..enc.len()).step_by(61).chain(enc.len() - 4..enc.len()) {
+        let mut bad = enc.clone();
+        bad.extend_from_slice(&enc[..n]);
+        check_err(&bad);
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use lzfse_rust::{LzfseEncoder, LzfseRingDecoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn check(src: &[u8], max_fill: usize) -> io::Result<()> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(src, &mut enc)?;
+    let mut rem = enc.as_slice();
+    let mut dec = Vec::default();
+    let (u, v) = LzfseRingDecoder::default().decode_fill(
+        |buf| {
+            let n = buf.len().min(rem.len()).min(max_fill);
+            buf[..n].copy_from_slice(&rem[..n]);
+            rem = &rem[n..];
+            n
+        },
+        &mut dec,
+    )?;
+    assert_eq!(u, enc.len() as u64);
+    assert_eq!(v, src.len() as u64);
+    assert!(dec == src);
+    Ok(())
+}
+
+#[test]
+fn data() -> io::Result<()> {
+    for src in [
+        Vec::default(),
+        b"test".to_vec(),
+        vec![0; 0x0010_0000],
+        Rng::default().gen_vec(0x0010_0000).unwrap(),
+        Iterator::take(Seq::default(), 0x0010_0000).collect(),
+    ]
+    .iter()
+    {
+        for &max_fill in &[1, 0x0123, 0x1000, usize::MAX] {
+            check(src, max_fill)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn short() -> io::Result<()> {
+    for n in 0..0x40 {
+        let src = Iterator::take(Seq::default(), n).collect::<Vec<_>>();
+        for max_fill in 1..0x11 {
+            check(&src, max_fill)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn truncated() -> io::Result<()> {
+    let src = Iterator::take(Seq::default(), 0x0001_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(&src, &mut enc)?;
+    let mut rem = &enc[..enc.len() - 1];
+    let mut dec = Vec::default();
+    let result = LzfseRingDecoder::default().decode_fill(
+        |buf| {
+            let n = buf.len().min(rem.len());
+            buf[..n].copy_from_slice(&rem[..n]);
+            rem = &rem[n..];
+            n
+        },
+        &mut dec,
+    );
+    assert!(matches!(result, Err(lzfse_rust::Error::PayloadUnderflow)));
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use lzfse_rust::{DecodeLeniency, Error, LzfseDecoder};
+use test_kit::Seq;
+
+use std::io;
+
+// Hand crafted LZVN stream, "abcd" "abcd" "????" "wxyz" "wxyz", with a single invalid match,
+// distance 0x0100, in place of "????".
+#[rustfmt::skip]
+const BAD_MATCH: [u8; 40] = [
+    // bvxn, n_raw_bytes 20, n_payload_bytes 24.
+    0x62, 0x76, 0x78, 0x6E, 0x14, 0x00, 0x00, 0x00, 0x18, 0x00, 0x00, 0x00,
+    // SmlL "abcd".
+    0xE4, 0x61, 0x62, 0x63, 0x64,
+    // SmlD L 0, M 4, D 0x0004.
+    0x08, 0x04,
+    // SmlD L 0, M 4, D 0x0100, invalid.
+    0x09, 0x00,
+    // SmlL "wxyz".
+    0xE4, 0x77, 0x78, 0x79, 0x7A,
+    // SmlD L 0, M 4, D 0x0004.
+    0x08, 0x04,
+    // Eos.
+    0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    // bvx$
+    0x62, 0x76, 0x78, 0x24,
+];
+
+#[test]
+fn strict() {
+    let mut decoder = LzfseDecoder::default();
+    assert_eq!(decoder.leniency(), DecodeLeniency::Strict);
+    let mut dec = Vec::default();
+    match decoder.decode_bytes(&BAD_MATCH, &mut dec) {
+        Err(Error::BadDValue) => {}
+        res => panic!("{:?}", res),
+    }
+}
+
+#[test]
+fn fill_sentinel() -> io::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    decoder.set_leniency(DecodeLeniency::FillSentinel(b'?'));
+    let mut dec = Vec::default();
+    let n = decoder.decode_bytes(&BAD_MATCH, &mut dec)?;
+    assert_eq!(n, 20);
+    assert_eq!(dec, b"abcdabcd????wxyzwxyz");
+    Ok(())
+}
+
+#[test]
+fn fill_sentinel_expect() -> io::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    decoder.set_leniency(DecodeLeniency::FillSentinel(0));
+    let mut dec = b"prefix".to_vec();
+    decoder.decode_bytes_expect(&BAD_MATCH, &mut dec, 20)?;
+    assert_eq!(dec, b"prefixabcdabcd\0\0\0\0wxyzwxyz");
+    Ok(())
+}
+
+// Well formed streams are unaffected.
+#[test]
+fn fill_sentinel_valid() -> io::Result<()> {
+    let src = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&src, &mut enc)?;
+    let mut decoder = LzfseDecoder::default();
+    decoder.set_leniency(DecodeLeniency::FillSentinel(0xFF));
+    let mut dec = Vec::default();
+    decoder.decode_bytes(&enc, &mut dec)?;
+    assert!(src == dec);
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use lzfse_rust::{Error, LzfseDecoder, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Random length reads, with interruptions.
+struct ShortReader<'a> {
+    inner: &'a [u8],
+    rng: Rng,
+}
+
+impl<'a> ShortReader<'a> {
+    fn new(inner: &'a [u8], seed: u32) -> Self {
+        Self { inner, rng: Rng::new(seed) }
+    }
+}
+
+impl<'a> io::Read for ShortReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let u = self.rng.gen() as usize;
+        if u & 0x0F == 0 {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        let n = (u % 0x0400).min(buf.len());
+        io::Read::read(&mut self.inner, &mut buf[..n])
+    }
+}
+
+fn encode(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(data, &mut enc)?;
+    Ok(enc)
+}
+
+fn check(data: &[u8]) -> io::Result<()> {
+    let enc = encode(data)?;
+    let mut decoder = LzfseDecoder::default();
+    for seed in 0..4 {
+        let mut dec = Vec::default();
+        let n = decoder.decode_reader(ShortReader::new(&enc, seed), &mut dec)?;
+        assert_eq!(n, data.len() as u64);
+        assert!(dec == data);
+    }
+    let mut dec = Vec::default();
+    let n = decoder.decode_reader(enc.as_slice(), &mut dec)?;
+    assert_eq!(n, data.len() as u64);
+    assert!(dec == data);
+    Ok(())
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    check(&[])
+}
+
+#[test]
+fn small() -> io::Result<()> {
+    for n in 1..0x0100 {
+        check(&Seq::default().take(n).collect::<Vec<_>>())?;
+    }
+    Ok(())
+}
+
+// Raw, VN and FSE blocks spanning many reads.
+#[test]
+fn blocks() -> io::Result<()> {
+    let mut data = Vec::default();
+    data.extend(Seq::new(Rng::new(0)).take(0x0008_0000));
+    data.extend(Seq::masked(Rng::new(1), 0x0000_0F0F).take(0x0008_0000));
+    data.extend(Seq::default().take(0x0008_0000));
+    data.extend(Seq::new(Rng::new(2)).take(0x0008_0000));
+    check(&data)
+}
+
+#[test]
+fn dst_prefix() -> io::Result<()> {
+    let data = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let enc = encode(&data)?;
+    let mut dec = b"prefix".to_vec();
+    let n = LzfseDecoder::default().decode_reader(enc.as_slice(), &mut dec)?;
+    assert_eq!(n, data.len() as u64);
+    assert_eq!(&dec[..6], b"prefix");
+    assert!(dec[6..] == data);
+    Ok(())
+}
+
+#[test]
+fn trailer() -> io::Result<()> {
+    let data = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    lzfse_rust::checksum::encode_bytes(&data, &mut enc)?;
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_reader(ShortReader::new(&enc, 0), &mut dec)?;
+    assert!(dec == data);
+    Ok(())
+}
+
+#[test]
+fn trailing_bytes() -> io::Result<()> {
+    let mut enc = encode(b"test")?;
+    enc.extend_from_slice(b"xxxx");
+    let mut dec = Vec::default();
+    match LzfseDecoder::default().decode_reader(enc.as_slice(), &mut dec) {
+        Err(Error::PayloadOverflow) => {}
+        Err(err) => panic!("{}", err),
+        Ok(_) => panic!(),
+    }
+    Ok(())
+}
+
+#[test]
+fn truncated() -> io::Result<()> {
+    let data = Seq::masked(Rng::new(0), 0x0000_0F0F).take(0x0001_0000).collect::<Vec<_>>();
+    let enc = encode(&data)?;
+    let mut decoder = LzfseDecoder::default();
+    let mut dec = Vec::default();
+    for n in (0..enc.len()).step_by(0x0101).chain(enc.len() - 8..enc.len()) {
+        dec.clear();
+        assert!(decoder.decode_reader(&enc[..n], &mut dec).is_err());
+        assert!(decoder.decode_bytes(&enc[..n], &mut Vec::default()).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn io_error() {
+    struct Broken;
+
+    impl io::Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+    }
+
+    match LzfseDecoder::default().decode_reader(Broken, &mut Vec::default()) {
+        Err(Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::BrokenPipe),
+        Err(err) => panic!("{}", err),
+        Ok(_) => panic!(),
+    }
+}
+
+// Staged input is bounded by the maximum block payload. An endless raw block is rejected on its
+// header, rather than staged in memory.
+#[test]
+fn staged_bound() {
+    let mut header = Vec::default();
+    header.extend_from_slice(b"bvx-");
+    header.extend_from_slice(&0xFFFF_FFF0u32.to_le_bytes());
+    let mut decoder = LzfseDecoder::default();
+    decoder.set_max_block_payload(Some(0x0001_0000));
+    let mut dec = Vec::default();
+    match decoder.decode_reader(io::Read::chain(header.as_slice(), io::repeat(0)), &mut dec) {
+        Err(Error::PayloadOverflow) => {}
+        Err(err) => panic!("{}", err),
+        Ok(_) => panic!(),
+    }
+    assert!(dec.is_empty());
+}
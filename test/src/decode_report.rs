@@ -0,0 +1,89 @@
+use lzfse_rust::{BlockStrategy, DecodeEvent, DecodeReport, Error, LzfseDecoder, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn n_blocks(enc: &[u8]) -> u64 {
+    let mut decoder = LzfseDecoder::default();
+    let mut dec = Vec::default();
+    let mut index = 0;
+    loop {
+        match decoder.decode_block(enc, index, &mut dec) {
+            Ok(_) => index += 1,
+            Err(Error::BlockIndexOutOfRange) => break index as u64,
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+}
+
+// Cross check against trace events.
+fn check(enc: &[u8], src: &[u8]) -> DecodeReport {
+    let mut n_literals = 0;
+    let mut n_matches = 0;
+    LzfseDecoder::default()
+        .trace(enc, |event| match event {
+            DecodeEvent::Literal(bytes) => n_literals += bytes.len() as u64,
+            DecodeEvent::Match { .. } => n_matches += 1,
+            _ => {}
+        })
+        .unwrap();
+    let mut dec = b"prefix".to_vec();
+    let report = LzfseDecoder::default().decode_bytes_report(enc, &mut dec).unwrap();
+    assert_eq!(&dec[6..], src);
+    assert_eq!(report.n_raw_bytes, src.len() as u64);
+    assert_eq!(report.n_literals, n_literals);
+    assert_eq!(report.n_matches, n_matches);
+    assert_eq!(report.n_blocks, n_blocks(enc));
+    report
+}
+
+#[test]
+fn raw() -> io::Result<()> {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(b"test", &mut enc)?;
+    let report = check(&enc, b"test");
+    assert_eq!(report, DecodeReport { n_literals: 4, n_matches: 0, n_blocks: 1, n_raw_bytes: 4 });
+    Ok(())
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(b"", &mut enc)?;
+    // Empty raw block.
+    assert_eq!(check(&enc, b""), DecodeReport { n_blocks: 1, ..DecodeReport::default() });
+    Ok(())
+}
+
+#[test]
+fn vn() -> io::Result<()> {
+    let src = b"test test test test test test test test";
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_block_strategy(BlockStrategy::NeverRaw);
+    let mut enc = Vec::default();
+    encoder.encode_bytes(src, &mut enc)?;
+    assert_eq!(&enc[..4], b"bvxn");
+    let report = check(&enc, src);
+    assert!(report.n_literals < src.len() as u64);
+    assert_ne!(report.n_matches, 0);
+    assert_eq!(report.n_blocks, 1);
+    Ok(())
+}
+
+#[test]
+fn fse() -> io::Result<()> {
+    let mut data = [
+        b"a quick brown fox jumps over the lazy dog ".repeat(0x1000),
+        Rng::default().gen_vec(0x0001_0000).unwrap(),
+        Iterator::take(Seq::default(), 0x0010_0000).collect(),
+    ];
+    let text = data[0].clone();
+    data[1].extend_from_slice(&text);
+    for src in data.iter() {
+        let mut enc = Vec::default();
+        lzfse_rust::encode_bytes(src, &mut enc)?;
+        let report = check(&enc, src);
+        assert_ne!(report.n_matches, 0);
+    }
+    Ok(())
+}
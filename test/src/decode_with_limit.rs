@@ -0,0 +1,91 @@
+use lzfse_rust::{Error, LzfseEncoder, LzfseRingDecoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// VN block raw to payload byte ratio limit.
+const VN_RATIO: u32 = 0x88;
+
+// A VN block header declaring `n_raw_bytes`, with the minimum permitted payload count. The payload
+// itself is a lone end of stream op, truncated.
+fn vn_bomb(n_raw_bytes: u32) -> Vec<u8> {
+    let n_payload_bytes = (n_raw_bytes / VN_RATIO + 1).max(8);
+    let mut enc = Vec::default();
+    enc.extend_from_slice(b"bvxn");
+    enc.extend_from_slice(&n_raw_bytes.to_le_bytes());
+    enc.extend_from_slice(&n_payload_bytes.to_le_bytes());
+    enc.push(0x06);
+    enc.extend_from_slice(b"bvx$");
+    enc
+}
+
+fn encode(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(data, &mut enc)?;
+    Ok(enc)
+}
+
+fn check_exceeded(decoder: &mut LzfseRingDecoder, enc: &[u8], max_output: u64) -> Vec<u8> {
+    let mut dec = Vec::default();
+    match decoder.decode_with_limit(&mut &*enc, &mut dec, max_output) {
+        Err(Error::OutputLimitExceeded) => {}
+        Err(err) => panic!("{}", err),
+        Ok(_) => panic!(),
+    }
+    assert!(dec.len() as u64 <= max_output);
+    dec
+}
+
+#[test]
+fn vn_declared() {
+    let mut decoder = LzfseRingDecoder::default();
+    for &n in &[0x0010_0001, 0x4000_0000, 0xFFFF_FFFF] {
+        let enc = vn_bomb(n);
+        let dec = check_exceeded(&mut decoder, &enc, 0x0010_0000);
+        assert!(dec.is_empty());
+    }
+}
+
+#[test]
+fn vn_declared_within() {
+    // Within the limit the lie is caught by the block decoder instead.
+    let enc = vn_bomb(0x0100);
+    let mut dec = Vec::default();
+    match LzfseRingDecoder::default().decode_with_limit(&mut enc.as_slice(), &mut dec, 0x0100) {
+        Err(Error::OutputLimitExceeded) => panic!(),
+        Err(_) => {}
+        Ok(_) => panic!(),
+    }
+}
+
+#[test]
+fn limit() -> io::Result<()> {
+    let data = Seq::masked(Rng::new(0), 0x0000_0F0F).take(0x0010_0000).collect::<Vec<_>>();
+    let enc = encode(&data)?;
+    let mut decoder = LzfseRingDecoder::default();
+    for &max_output in &[0, 1, 0x0001_0000, data.len() as u64 - 1] {
+        let dec = check_exceeded(&mut decoder, &enc, max_output);
+        assert_eq!(dec, data[..dec.len()]);
+    }
+    for &max_output in &[data.len() as u64, data.len() as u64 + 1, u64::MAX] {
+        let mut dec = Vec::default();
+        let (u, v) = decoder.decode_with_limit(&mut enc.as_slice(), &mut dec, max_output)?;
+        assert_eq!(u, enc.len() as u64);
+        assert_eq!(v, data.len() as u64);
+        assert!(dec == data);
+    }
+    Ok(())
+}
+
+#[test]
+fn reuse() -> io::Result<()> {
+    let data = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let enc = encode(&data)?;
+    let mut decoder = LzfseRingDecoder::default();
+    check_exceeded(&mut decoder, &enc, 0x1000);
+    // The limit applies to a single call only.
+    let mut dec = Vec::default();
+    decoder.decode(&mut enc.as_slice(), &mut dec)?;
+    assert!(dec == data);
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+use test_kit::Seq;
+
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+
+fn encode(dec: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(dec, &mut enc)?;
+    Ok(enc)
+}
+
+#[test]
+fn read_to_end() -> io::Result<()> {
+    let dec = Iterator::take(Seq::default(), 0x0001_0000).collect::<Vec<_>>();
+    let enc = encode(&dec)?;
+    let mut decoder = LzfseDecoder::default();
+    let mut buf = Vec::default();
+    decoder.cursor(&enc).read_to_end(&mut buf)?;
+    assert!(buf == dec);
+    Ok(())
+}
+
+#[test]
+fn seek_read() -> io::Result<()> {
+    let dec = Iterator::take(Seq::default(), 0x8000).collect::<Vec<_>>();
+    let enc = encode(&dec)?;
+    let mut decoder = LzfseDecoder::default();
+    let mut cursor = decoder.cursor(&enc);
+    let mut buf = [0u8; 0x0123];
+    for &(pos, expected) in &[
+        (SeekFrom::Start(0x1000), 0x1000),
+        (SeekFrom::Current(0x0100), 0x1000 + 0x0123 + 0x0100),
+        (SeekFrom::Current(-0x0200), 0x1000 + 0x0123 * 2 + 0x0100 - 0x0200),
+        (SeekFrom::End(-0x0123), 0x8000 - 0x0123),
+        (SeekFrom::Start(0), 0),
+    ] {
+        assert_eq!(cursor.seek(pos)?, expected);
+        cursor.read_exact(&mut buf)?;
+        assert!(buf[..] == dec[expected as usize..expected as usize + buf.len()]);
+    }
+    assert_eq!(cursor.stream_position()?, buf.len() as u64);
+    Ok(())
+}
+
+#[test]
+fn seek_past_end() -> io::Result<()> {
+    let enc = encode(b"test")?;
+    let mut decoder = LzfseDecoder::default();
+    let mut cursor = decoder.cursor(&enc);
+    assert_eq!(cursor.seek(SeekFrom::End(4))?, 8);
+    let mut buf = [0u8; 4];
+    assert_eq!(cursor.read(&mut buf)?, 0);
+    assert!(cursor.fill_buf()?.is_empty());
+    assert_eq!(cursor.seek(SeekFrom::Start(2))?, 2);
+    assert_eq!(cursor.read(&mut buf)?, 2);
+    assert_eq!(&buf[..2], b"st");
+    Ok(())
+}
+
+#[test]
+fn seek_negative() -> io::Result<()> {
+    let enc = encode(b"test")?;
+    let mut decoder = LzfseDecoder::default();
+    let mut cursor = decoder.cursor(&enc);
+    let err = cursor.seek(SeekFrom::End(-5)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let err = cursor.seek(SeekFrom::Current(-1)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(cursor.stream_position()?, 0);
+    Ok(())
+}
+
+#[test]
+fn corrupt() -> io::Result<()> {
+    let mut enc = encode(&Iterator::take(Seq::default(), 0x8000).collect::<Vec<_>>())?;
+    enc.truncate(enc.len() - 1);
+    let mut decoder = LzfseDecoder::default();
+    let mut cursor = decoder.cursor(&enc);
+    // Lazy, seeking from the start does not decode.
+    assert_eq!(cursor.seek(SeekFrom::Start(4))?, 4);
+    let mut buf = [0u8; 4];
+    assert!(cursor.read(&mut buf).is_err());
+    assert!(cursor.read(&mut buf).is_err());
+    assert!(cursor.seek(SeekFrom::End(0)).is_err());
+    assert!(cursor.into_decoded().is_err());
+    Ok(())
+}
+
+#[test]
+fn into_decoded() -> lzfse_rust::Result<()> {
+    let dec = Iterator::take(Seq::default(), 0x1000).collect::<Vec<_>>();
+    let enc = encode(&dec)?;
+    let mut decoder = LzfseDecoder::default();
+    let mut cursor = decoder.cursor(&enc);
+    assert!(cursor.decoded()? == dec.as_slice());
+    assert!(cursor.into_decoded()? == dec);
+    Ok(())
+}
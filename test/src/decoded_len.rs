@@ -0,0 +1,86 @@
+use lzfse_rust::{Error, LzfseDecoder};
+use test_kit::Seq;
+
+use std::io;
+
+const RAW: &[u8] = include_bytes!("../../data/mutate/raw.lzfse");
+const VX1: &[u8] = include_bytes!("../../data/mutate/vx1.lzfse");
+const VX2: &[u8] = include_bytes!("../../data/mutate/vx2.lzfse");
+const VXN: &[u8] = include_bytes!("../../data/mutate/vxn.lzfse");
+
+fn check(enc: &[u8]) -> io::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    let n = decoder.decoded_len(enc)?;
+    let mut dec = Vec::default();
+    assert_eq!(decoder.decode_bytes(enc, &mut dec)?, n);
+    assert_eq!(dec.len() as u64, n);
+    assert_eq!(lzfse_rust::decoded_len(enc)?, n);
+    Ok(())
+}
+
+#[test]
+fn block_types() -> io::Result<()> {
+    for enc in [RAW, VX1, VX2, VXN] {
+        check(enc)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn multi_block() -> io::Result<()> {
+    let src = Seq::default().take(0x0040_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&src, &mut enc)?;
+    assert!(lzfse_rust::explain(&enc)?.len() > 1);
+    assert_eq!(lzfse_rust::decoded_len(&enc)?, src.len() as u64);
+    check(&enc)
+}
+
+#[test]
+fn raw() -> io::Result<()> {
+    // "test" string encoded as a single raw block.
+    let enc = [
+        0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76, 0x78,
+        0x24,
+    ];
+    assert_eq!(lzfse_rust::decoded_len(&enc)?, 4);
+    check(&enc)
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    let enc = b"bvx$";
+    assert_eq!(lzfse_rust::decoded_len(enc)?, 0);
+    check(enc)
+}
+
+fn check_err(decoder: &mut LzfseDecoder, enc: &[u8]) {
+    let err = decoder.decoded_len(enc).unwrap_err();
+    let expected = decoder.decode_bytes(enc, &mut Vec::default()).unwrap_err();
+    assert_eq!(format!("{err:?}"), format!("{expected:?}"));
+}
+
+// Errors as per `decode_bytes`.
+#[test]
+fn truncated() {
+    let mut decoder = LzfseDecoder::default();
+    for enc in [RAW, VX1, VX2, VXN] {
+        for n in 0..enc.len() {
+            check_err(&mut decoder, &enc[..n]);
+        }
+    }
+}
+
+#[test]
+fn bad_magic() {
+    let mut decoder = LzfseDecoder::default();
+    for enc in [RAW, VX1, VX2, VXN] {
+        let mut enc = enc.to_vec();
+        enc[3] = b'?';
+        check_err(&mut decoder, &enc);
+        match decoder.decoded_len(&enc) {
+            Err(Error::BadBlock(_)) => {}
+            res => panic!("{:?}", res),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use lzfse_rust::{BlockStrategy, LzfseDecoder, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn check(encoder: &mut LzfseEncoder, dict: &[u8], src: &[u8]) -> io::Result<usize> {
+    let mut enc = Vec::default();
+    let n = encoder.encode_bytes_with_dict(dict, src, &mut enc)?;
+    assert_eq!(n, enc.len() as u64);
+    let mut dec = Vec::default();
+    let n = LzfseDecoder::default().decode_bytes_with_dict(dict, &enc, &mut dec)?;
+    assert_eq!(n, src.len() as u64);
+    assert!(dec == src);
+    Ok(enc.len())
+}
+
+fn record(id: u32) -> Vec<u8> {
+    format!(
+        r#"{{"id":{},"name":"user{}","email":"user{}@example.com","active":true,"roles":["read"]}}"#,
+        id, id, id
+    )
+    .into_bytes()
+}
+
+#[test]
+fn records() -> io::Result<()> {
+    let dict = record(0);
+    let mut encoder = LzfseEncoder::default();
+    let mut n_plain = 0;
+    let mut n_dict = 0;
+    for id in 1..0x0100 {
+        let src = record(id);
+        let mut enc = Vec::default();
+        encoder.encode_bytes(&src, &mut enc)?;
+        n_plain += enc.len();
+        n_dict += check(&mut encoder, &dict, &src)?;
+    }
+    assert!(n_dict * 2 < n_plain);
+    Ok(())
+}
+
+#[test]
+fn empty_dict() -> io::Result<()> {
+    let src = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let mut encoder = LzfseEncoder::default();
+    let mut enc = Vec::default();
+    encoder.encode_bytes(&src, &mut enc)?;
+    let mut enc_dict = Vec::default();
+    encoder.encode_bytes_with_dict(&[], &src, &mut enc_dict)?;
+    assert!(enc == enc_dict);
+    Ok(())
+}
+
+#[test]
+fn sizes() -> io::Result<()> {
+    let dict = Seq::default().take(0x4000).collect::<Vec<_>>();
+    let mut encoder = LzfseEncoder::default();
+    for strategy in [BlockStrategy::Auto, BlockStrategy::NeverRaw, BlockStrategy::Compact] {
+        encoder.set_block_strategy(strategy);
+        for n in (0..0x0100).chain([0x0FFF, 0x1000, 0x1001, 0x4000, 0x0001_0000]) {
+            let src = Seq::new(Rng::new(7)).take(n).collect::<Vec<_>>();
+            check(&mut encoder, &dict, &src)?;
+            check(&mut encoder, &dict, &dict[dict.len() - n.min(dict.len())..])?;
+            check(&mut encoder, &dict[..n.min(dict.len())], &dict)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn long_dict() -> io::Result<()> {
+    let dict = Rng::new(1).gen_vec(0x0008_0000).unwrap();
+    let mut encoder = LzfseEncoder::default();
+    // Head, beyond the maximum match distance.
+    let n_head = check(&mut encoder, &dict, &dict[..0x8000])?;
+    assert!(n_head > 0x8000);
+    // Tail, within the maximum match distance.
+    let n_tail = check(&mut encoder, &dict, &dict[dict.len() - 0x8000..])?;
+    assert!(n_tail < 0x0100);
+    Ok(())
+}
+
+#[test]
+fn dst_prefix() -> io::Result<()> {
+    let dict = record(0);
+    let src = record(1);
+    let mut enc = b"prefix".to_vec();
+    LzfseEncoder::default().encode_bytes_with_dict(&dict, &src, &mut enc)?;
+    let mut dec = b"prefix".to_vec();
+    LzfseDecoder::default().decode_bytes_with_dict(&dict, &enc[6..], &mut dec)?;
+    assert_eq!(&dec[..6], b"prefix");
+    assert!(dec[6..] == src);
+    Ok(())
+}
+
+#[test]
+fn missing_dict() -> io::Result<()> {
+    let dict = Rng::new(2).gen_vec(0x1000).unwrap();
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes_with_dict(&dict, &dict, &mut enc)?;
+    let mut dec = Vec::default();
+    assert!(LzfseDecoder::default().decode_bytes(&enc, &mut dec).is_err());
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes_with_dict(&[0; 0x1000], &enc, &mut dec)?;
+    assert!(dec != dict);
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use lzfse_rust::LzfseEncoder;
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn check(encoder: &mut LzfseEncoder, src: &[u8]) -> io::Result<()> {
+    let mut enc = Vec::default();
+    let n = encoder.encode_bytes(src, &mut enc)?;
+    // Output is appended to, and identical to, `encode_bytes`.
+    let mut verified = b"prefix".to_vec();
+    assert_eq!(encoder.encode_bytes_verified(src, &mut verified)?, n);
+    assert_eq!(&verified[..6], b"prefix");
+    assert!(verified[6..] == enc);
+    Ok(())
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    check(&mut LzfseEncoder::default(), b"")
+}
+
+#[test]
+fn seq() -> io::Result<()> {
+    let src = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    check(&mut LzfseEncoder::default(), &src)
+}
+
+#[test]
+fn random() -> io::Result<()> {
+    let src = Rng::default().gen_vec(0x0001_0000).unwrap();
+    check(&mut LzfseEncoder::default(), &src)
+}
+
+#[test]
+fn zeros() -> io::Result<()> {
+    check(&mut LzfseEncoder::default(), &[0u8; 0x0010_0000])
+}
+
+#[test]
+fn small() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    let src = Seq::default().take(0x1000).collect::<Vec<_>>();
+    for n in 0..0x100 {
+        check(&mut encoder, &src[..n * 0x10])?;
+    }
+    Ok(())
+}
+
+#[test]
+fn pad_output() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_pad_output_to(Some(0x1000));
+    let src = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    check(&mut encoder, &src)
+}
+
+#[test]
+fn skip_if_compressed() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    let src = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    encoder.encode_bytes(&src, &mut enc)?;
+    encoder.set_skip_if_compressed(true);
+    check(&mut encoder, &enc)
+}
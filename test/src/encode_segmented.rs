@@ -0,0 +1,104 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, SegmentEntry};
+use test_kit::Rng;
+
+use std::io;
+
+// Sections, 4KiB to 16KiB long, each a distinct random 256 byte block repeated. Returns the data
+// and section offsets.
+fn sections(len: usize) -> (Vec<u8>, Vec<usize>) {
+    let mut rng = Rng::default();
+    let mut vec = Vec::with_capacity(len + 0x4000);
+    let mut offsets = Vec::default();
+    while vec.len() < len {
+        offsets.push(vec.len());
+        let block = (0..0x0100).map(|_| (rng.gen() >> 24) as u8).collect::<Vec<_>>();
+        let n = 0x1000 + (rng.gen() >> 16) as usize % 0x3000;
+        vec.extend(block.iter().cycle().take(n));
+    }
+    (vec, offsets)
+}
+
+fn check(src: &[u8], enc: &[u8], mark: usize, segments: &[SegmentEntry]) -> io::Result<()> {
+    let mut src_offset = 0;
+    let mut offset = mark as u64;
+    let mut decoder = LzfseDecoder::default();
+    for segment in segments {
+        assert_eq!(segment.src_offset, src_offset);
+        assert_eq!(segment.offset, offset);
+        let enc = &enc[segment.offset as usize..][..segment.n_payload_bytes as usize];
+        let mut dec = Vec::default();
+        decoder.decode_bytes(enc, &mut dec)?;
+        assert!(dec == src[segment.src_offset as usize..][..segment.n_raw_bytes as usize]);
+        src_offset += segment.n_raw_bytes;
+        offset += segment.n_payload_bytes;
+    }
+    assert_eq!(src_offset, src.len() as u64);
+    assert_eq!(offset, enc.len() as u64);
+    Ok(())
+}
+
+#[test]
+fn independent() -> io::Result<()> {
+    let (src, _) = sections(0x0020_0000);
+    let mut enc = b"prefix".to_vec();
+    let segments = lzfse_rust::encode_segmented(&src, 8, &mut enc)?;
+    assert_eq!(segments.len(), 8);
+    check(&src, &enc, 6, &segments)
+}
+
+#[test]
+fn balanced() -> io::Result<()> {
+    let (src, _) = sections(0x0020_0000);
+    let mut enc = Vec::default();
+    let segments = lzfse_rust::encode_segmented(&src, 8, &mut enc)?;
+    let nominal = src.len() as u64 / 8;
+    for segment in segments.iter().take(7) {
+        assert!(segment.n_raw_bytes >= nominal * 3 / 4, "{:?}", segment);
+        assert!(segment.n_raw_bytes <= nominal * 5 / 4, "{:?}", segment);
+    }
+    Ok(())
+}
+
+// Boundaries land on section starts, as opposed to arbitrary offsets.
+#[test]
+fn boundaries() -> io::Result<()> {
+    let (src, offsets) = sections(0x0020_0000);
+    let mut enc = Vec::default();
+    let segments = lzfse_rust::encode_segmented(&src, 8, &mut enc)?;
+    for segment in segments.iter().skip(1) {
+        assert!(offsets.contains(&(segment.src_offset as usize)), "{:?}", segment);
+    }
+    Ok(())
+}
+
+#[test]
+fn ratio() -> io::Result<()> {
+    let (src, _) = sections(0x0020_0000);
+    let mut encoder = LzfseEncoder::default();
+    let mut enc = Vec::default();
+    encoder.encode_segmented(&src, 8, &mut enc)?;
+    let mut fixed = Vec::default();
+    for chunk in src.chunks(src.len() / 8) {
+        encoder.encode_bytes(chunk, &mut fixed)?;
+    }
+    assert!(enc.len() < fixed.len(), "{} {}", enc.len(), fixed.len());
+    Ok(())
+}
+
+#[test]
+fn small() -> io::Result<()> {
+    for len in [0, 1, 7, 8, 0x0100] {
+        let src = vec![0x55; len];
+        let mut enc = Vec::default();
+        let segments = lzfse_rust::encode_segmented(&src, 8, &mut enc)?;
+        assert_eq!(segments.len(), if len < 8 { 1 } else { 8 });
+        check(&src, &enc, 0, &segments)?;
+    }
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn zero_segments() {
+    let _ = lzfse_rust::encode_segmented(&[0; 0x0100], 0, &mut Vec::default());
+}
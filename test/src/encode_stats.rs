@@ -0,0 +1,100 @@
+use lzfse_rust::{EncodeStats, LzfseDecoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io::{self, Write};
+
+// Accepts at most `self.1` bytes per call.
+struct Trickle(Vec<u8>, usize);
+
+impl Write for Trickle {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.1);
+        self.0.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn check(src: &[u8]) -> io::Result<EncodeStats> {
+    let mut encoder = LzfseRingEncoder::default();
+    let mut enc = Vec::default();
+    let (n_raw_bytes, n_payload_bytes, stats) = encoder.encode_with_stats(&mut &*src, &mut enc)?;
+    assert_eq!(n_raw_bytes, src.len() as u64);
+    assert_eq!(n_payload_bytes, enc.len() as u64);
+    // Identical output.
+    let mut expected = Vec::default();
+    LzfseRingEncoder::default().encode(&mut &*src, &mut expected)?;
+    assert!(enc == expected);
+    // Totals.
+    assert_eq!(stats.raw.n_raw_bytes + stats.vn.n_raw_bytes + stats.fse.n_raw_bytes, n_raw_bytes);
+    assert_eq!(
+        stats.raw.n_payload_bytes + stats.vn.n_payload_bytes + stats.fse.n_payload_bytes + 4,
+        n_payload_bytes
+    );
+    let mut dec = Vec::default();
+    let report = LzfseDecoder::default().decode_bytes_report(&enc, &mut dec)?;
+    assert_eq!(stats.raw.n_blocks + stats.vn.n_blocks + stats.fse.n_blocks, report.n_blocks);
+    if stats.raw.n_blocks == 0 && stats.vn.n_blocks == 0 {
+        assert!(report.n_literals <= stats.n_literals);
+        assert!(stats.n_literals <= report.n_literals + stats.fse.n_blocks * 3);
+        assert!(report.n_matches <= stats.n_lmds);
+    }
+    // Insensitive to writer chunking.
+    for &n in &[1, 3, 7, 0x0101] {
+        let mut wtr = Trickle(Vec::default(), n);
+        let (_, _, u) = encoder.encode_with_stats(&mut &*src, &mut wtr)?;
+        assert_eq!(u, stats);
+        assert!(wtr.0 == enc);
+    }
+    Ok(stats)
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    let stats = check(&[])?;
+    assert_eq!(stats.raw.n_blocks, 1);
+    assert_eq!(stats.raw.n_raw_bytes, 0);
+    assert_eq!(stats.vn.n_blocks + stats.fse.n_blocks, 0);
+    Ok(())
+}
+
+#[test]
+fn raw() -> io::Result<()> {
+    let stats = check(b"test")?;
+    assert_eq!(stats.raw.n_blocks, 1);
+    assert_eq!(stats.raw.n_raw_bytes, 4);
+    assert_eq!(stats.raw.n_payload_bytes, 12);
+    assert_eq!(stats.vn.n_blocks + stats.fse.n_blocks, 0);
+    Ok(())
+}
+
+#[test]
+fn vn() -> io::Result<()> {
+    let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x10);
+    let stats = check(&src)?;
+    assert_eq!(stats.vn.n_blocks, 1);
+    assert_eq!(stats.raw.n_blocks + stats.fse.n_blocks, 0);
+    assert_eq!(stats.n_literals + stats.n_lmds, 0);
+    Ok(())
+}
+
+#[test]
+fn fse() -> io::Result<()> {
+    let src = Seq::default().take(0x0040_0000).collect::<Vec<_>>();
+    let stats = check(&src)?;
+    assert!(stats.fse.n_blocks > 1);
+    assert_eq!(stats.raw.n_blocks + stats.vn.n_blocks, 0);
+    assert!(stats.n_lmds != 0);
+    Ok(())
+}
+
+#[test]
+fn random() -> io::Result<()> {
+    for &n in &[0x0100, 0x1000, 0x0010_0000] {
+        check(&Rng::new(n).gen_vec(n as usize).unwrap())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use lzfse_rust::{LzfseEncoder, LzfseSink};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Growable sink.
+#[derive(Default)]
+struct VecSink {
+    buf: Vec<u8>,
+    n: usize,
+}
+
+unsafe impl LzfseSink for VecSink {
+    fn buffer(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+
+    fn n_bytes(&self) -> usize {
+        self.n
+    }
+
+    fn set_n_bytes(&mut self, n_bytes: usize) {
+        self.n = n_bytes;
+    }
+
+    fn reserve(&mut self, additional: usize) -> io::Result<()> {
+        let len = self.n + additional;
+        if self.buf.len() < len {
+            self.buf.resize(len, 0);
+        }
+        Ok(())
+    }
+}
+
+// Fixed size sink.
+struct SliceSink<'a> {
+    buf: &'a mut [u8],
+    n: usize,
+}
+
+unsafe impl<'a> LzfseSink for SliceSink<'a> {
+    fn buffer(&mut self) -> &mut [u8] {
+        self.buf
+    }
+
+    fn n_bytes(&self) -> usize {
+        self.n
+    }
+
+    fn set_n_bytes(&mut self, n_bytes: usize) {
+        self.n = n_bytes;
+    }
+
+    fn reserve(&mut self, additional: usize) -> io::Result<()> {
+        if additional <= self.buf.len() - self.n {
+            Ok(())
+        } else {
+            Err(io::ErrorKind::WriteZero.into())
+        }
+    }
+}
+
+fn data() -> Vec<Vec<u8>> {
+    vec![
+        Vec::default(),
+        b"test".to_vec(),
+        Iterator::take(Seq::default(), 0x0100).collect(),
+        Iterator::take(Seq::masked(Rng::default(), 0x0F0F_0F0F), 0x0800).collect(),
+        Iterator::take(Seq::default(), 0x0001_0000).collect(),
+        Rng::default().gen_vec(0x0001_0000).unwrap(),
+        b"a quick brown fox jumps over the lazy dog ".repeat(0x1000),
+        Iterator::take(Seq::default(), 0x0010_0000).collect(),
+    ]
+}
+
+fn encode_bytes(encoder: &mut LzfseEncoder, src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    encoder.encode_bytes(src, &mut enc)?;
+    Ok(enc)
+}
+
+// Fixed size sinks require headroom over the encoded length.
+const HEADROOM: usize = 0x2000;
+
+fn check(encoder: &mut LzfseEncoder, src: &[u8]) -> io::Result<()> {
+    let expected = encode_bytes(encoder, src)?;
+    let mut sink = VecSink::default();
+    let n = encoder.encode_to_sink(src, &mut sink)?;
+    assert_eq!(n, expected.len() as u64);
+    assert!(sink.buf[..sink.n] == expected[..]);
+    let mut buf = vec![0u8; expected.len() + HEADROOM];
+    let mut sink = SliceSink { buf: &mut buf, n: 0 };
+    let n = encoder.encode_to_sink(src, &mut sink)?;
+    assert_eq!(n, expected.len() as u64);
+    assert!(buf[..n as usize] == expected[..]);
+    Ok(())
+}
+
+#[test]
+fn identical() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    for src in data() {
+        check(&mut encoder, &src)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn pad_output_to() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_pad_output_to(Some(0x1000));
+    for src in data() {
+        check(&mut encoder, &src)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn skip_if_compressed() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_skip_if_compressed(true);
+    for src in data() {
+        let enc = encode_bytes(&mut encoder, &src)?;
+        check(&mut encoder, &enc)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn append() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+    let expected = encode_bytes(&mut encoder, &src)?;
+    let mut sink = VecSink { buf: b"prefix".to_vec(), n: 6 };
+    let n = encoder.encode_to_sink(&src, &mut sink)?;
+    assert_eq!(n, expected.len() as u64);
+    assert_eq!(sink.n, 6 + expected.len());
+    assert_eq!(&sink.buf[..6], b"prefix");
+    assert!(sink.buf[6..sink.n] == expected[..]);
+    Ok(())
+}
+
+#[test]
+fn overflow() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    for src in data().iter().take(4) {
+        let expected = encode_bytes(&mut encoder, src)?;
+        for len in 0..expected.len() + HEADROOM {
+            let mut buf = vec![0u8; 4 + len];
+            buf[..4].copy_from_slice(b"head");
+            let mut sink = SliceSink { buf: &mut buf, n: 4 };
+            match encoder.encode_to_sink(src, &mut sink) {
+                Ok(n) => {
+                    assert!(expected.len() <= len);
+                    assert_eq!(n, expected.len() as u64);
+                    assert!(buf[4..4 + n as usize] == expected[..]);
+                }
+                Err(err) => {
+                    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+                    assert_eq!(sink.n, 4);
+                    assert_eq!(&buf[..4], b"head");
+                }
+            }
+        }
+    }
+    Ok(())
+}
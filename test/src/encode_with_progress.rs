@@ -0,0 +1,67 @@
+use lzfse_rust::{LzfseRingEncoder, RingProfile};
+use test_kit::Seq;
+
+use std::io;
+
+// Returns the progress values, checking the output and totals against `encode`.
+fn check(encoder: &mut LzfseRingEncoder, src: &[u8]) -> io::Result<Vec<(u64, u64)>> {
+    let mut enc = Vec::default();
+    let totals = encoder.encode(&mut &src[..], &mut enc)?;
+    let mut progress = Vec::default();
+    let mut enc_progress = Vec::default();
+    let totals_progress =
+        encoder
+            .encode_with_progress(&mut &src[..], &mut enc_progress, |u, v| progress.push((u, v)))?;
+    assert_eq!(totals_progress, totals);
+    assert!(enc_progress == enc);
+    assert_eq!(*progress.last().unwrap(), totals);
+    for window in progress.windows(2) {
+        assert!(window[0].0 <= window[1].0);
+        assert!(window[0].1 <= window[1].1);
+    }
+    Ok(progress)
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    let progress = check(&mut LzfseRingEncoder::default(), b"")?;
+    assert_eq!(progress.len(), 1);
+    assert_eq!(progress[0].0, 0);
+    Ok(())
+}
+
+#[test]
+fn small() -> io::Result<()> {
+    let progress = check(&mut LzfseRingEncoder::default(), b"test")?;
+    assert_eq!(progress, [(4, 16)]);
+    Ok(())
+}
+
+#[test]
+fn seq() -> io::Result<()> {
+    let src = Seq::default().take(0x0100_0000).collect::<Vec<_>>();
+    let progress = check(&mut LzfseRingEncoder::default(), &src)?;
+    // Intermediate progress is reported, including encoded output prior to completion.
+    assert!(progress.len() > 2);
+    let (u, v) = progress[progress.len() / 2];
+    assert!(0 < u && u < src.len() as u64);
+    assert!(0 < v);
+    Ok(())
+}
+
+#[test]
+fn zeros() -> io::Result<()> {
+    let src = vec![0u8; 0x0100_0000];
+    let progress = check(&mut LzfseRingEncoder::default(), &src)?;
+    assert!(progress.len() > 2);
+    Ok(())
+}
+
+#[test]
+fn profile() -> io::Result<()> {
+    let src = Seq::default().take(0x0040_0000).collect::<Vec<_>>();
+    let profile = RingProfile::for_max_distance(0);
+    let progress = check(&mut LzfseRingEncoder::with_profile(profile), &src)?;
+    assert!(progress.len() > 2);
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, LzfseEncoderBuilder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn data() -> Vec<Vec<u8>> {
+    vec![
+        Vec::default(),
+        b"test".to_vec(),
+        Seq::default().take(0x0010_0000).collect(),
+        Rng::new(1).gen_vec(0x0004_0000).unwrap(),
+        b"a quick brown fox jumps over the lazy dog ".repeat(0x1000),
+    ]
+}
+
+fn check(builder: LzfseEncoderBuilder, src: &[u8]) -> io::Result<()> {
+    let mut enc = Vec::default();
+    builder.build().encode_bytes(src, &mut enc)?;
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+    assert!(dec == src);
+    // Likewise the ring encoder.
+    let mut ring_enc = Vec::default();
+    builder.build_ring().encode(&mut &*src, &mut ring_enc)?;
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(&ring_enc, &mut dec)?;
+    assert!(dec == src);
+    Ok(())
+}
+
+#[test]
+fn defaults() -> io::Result<()> {
+    let encoder = LzfseEncoderBuilder::default().build();
+    let default = LzfseEncoder::default();
+    assert_eq!(encoder.good_match_len(), default.good_match_len());
+    assert_eq!(encoder.search_depth(), default.search_depth());
+    assert_eq!(encoder.search_depth(), 4);
+    let ring = LzfseEncoderBuilder::new().build_ring();
+    assert_eq!(ring.good_match_len(), LzfseRingEncoder::default().good_match_len());
+    for src in data() {
+        let mut u = Vec::default();
+        LzfseEncoderBuilder::new().build().encode_bytes(&src, &mut u)?;
+        let mut v = Vec::default();
+        LzfseEncoder::default().encode_bytes(&src, &mut v)?;
+        assert!(u == v);
+    }
+    Ok(())
+}
+
+#[test]
+fn settings() {
+    let encoder = LzfseEncoderBuilder::new().good_match_len(8).search_depth(2).build_ring();
+    assert_eq!(encoder.good_match_len(), 8);
+    assert_eq!(encoder.search_depth(), 2);
+}
+
+#[test]
+fn round_trip() -> io::Result<()> {
+    for src in data() {
+        for &good_match_len in &[1, 4, 16, 40, 0x0100, u32::MAX] {
+            for search_depth in 1..=LzfseEncoderBuilder::MAX_SEARCH_DEPTH {
+                let builder = LzfseEncoderBuilder::new()
+                    .good_match_len(good_match_len)
+                    .search_depth(search_depth);
+                check(builder, &src)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Lines sharing a common prefix, each followed by one of a number of variants, such that the
+// best match is often beyond the nearest few candidates.
+fn variants() -> Vec<u8> {
+    let suffixes = (0..12)
+        .map(|i| format!("{:03} {}\n", i * 37, "abcdefghijklmnopqrstuvwxyz".repeat(1 + i % 3)))
+        .collect::<Vec<_>>();
+    let mut rng = Rng::new(3);
+    let mut src = Vec::default();
+    for _ in 0..0x2000 {
+        src.extend_from_slice(b"key=");
+        src.extend_from_slice(suffixes[rng.gen() as usize % suffixes.len()].as_bytes());
+    }
+    src
+}
+
+fn encode_len(search_depth: usize, src: &[u8]) -> io::Result<usize> {
+    let mut enc = Vec::default();
+    LzfseEncoderBuilder::new().search_depth(search_depth).build().encode_bytes(src, &mut enc)?;
+    let mut ring_enc = Vec::default();
+    LzfseEncoderBuilder::new()
+        .search_depth(search_depth)
+        .build_ring()
+        .encode(&mut &*src, &mut ring_enc)?;
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+    assert!(dec == src);
+    Ok(enc.len())
+}
+
+#[test]
+fn deep_ratio() -> io::Result<()> {
+    let src = variants();
+    let n4 = encode_len(4, &src)?;
+    let n8 = encode_len(8, &src)?;
+    let n16 = encode_len(16, &src)?;
+    assert!(n8 < n4);
+    assert!(n16 <= n8);
+    Ok(())
+}
+
+// Extension levels do not leak from one configuration into the next.
+#[test]
+fn deep_reuse() -> io::Result<()> {
+    let src = variants();
+    let mut deep = LzfseEncoderBuilder::new().search_depth(16).build_ring();
+    deep.encode(&mut src.as_slice(), &mut Vec::default())?;
+    let table = deep.into_history_table();
+    let mut ring_fresh = Vec::default();
+    LzfseRingEncoder::default().encode(&mut src.as_slice(), &mut ring_fresh)?;
+    let mut ring_reused = Vec::default();
+    LzfseRingEncoder::with_history_table(table).encode(&mut src.as_slice(), &mut ring_reused)?;
+    assert!(ring_fresh == ring_reused);
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn zero_good_match_len() {
+    LzfseEncoderBuilder::new().good_match_len(0);
+}
+
+#[test]
+#[should_panic]
+fn zero_search_depth() {
+    LzfseEncoderBuilder::new().search_depth(0);
+}
+
+#[test]
+#[should_panic]
+fn excess_search_depth() {
+    LzfseEncoderBuilder::new().search_depth(LzfseEncoderBuilder::MAX_SEARCH_DEPTH + 1);
+}
@@ -0,0 +1,129 @@
+// `LzfseRingEncoder` input ring profiles.
+
+use lzfse_rust::{LzfseRingDecoder, LzfseRingEncoder, RingProfile, Role};
+use test_kit::{Rng, Seq};
+
+use std::io::{self, Write};
+
+// Profiles, smallest to largest.
+fn profiles() -> Vec<RingProfile> {
+    let mut profile = RingProfile::for_max_distance(0);
+    let mut vec = vec![profile];
+    while profile != RingProfile::default() {
+        profile = RingProfile::for_max_distance(profile.max_match_distance() + 1);
+        vec.push(profile);
+    }
+    vec
+}
+
+// Random bytes followed by a copy of the leading bytes at exactly `distance`.
+fn distance_data(distance: u32) -> Vec<u8> {
+    let mut data = Rng::default().gen_vec(distance as usize).unwrap();
+    data.extend_from_within(..0x1000);
+    data
+}
+
+fn encode(profile: RingProfile, data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = LzfseRingEncoder::with_profile(profile);
+    let mut enc = Vec::default();
+    encoder.encode(&mut &data[..], &mut enc)?;
+    Ok(enc)
+}
+
+// Decode with both the default and the matching profile decoder.
+fn check_decode(profile: RingProfile, enc: &[u8], data: &[u8]) -> io::Result<()> {
+    for &profile in &[RingProfile::default(), profile] {
+        let mut decoder = LzfseRingDecoder::with_profile(profile);
+        let mut dec = Vec::default();
+        decoder.decode(&mut &enc[..], &mut dec)?;
+        assert!(dec == data, "{}", profile);
+    }
+    Ok(())
+}
+
+#[test]
+fn default_profile() -> io::Result<()> {
+    let encoder = LzfseRingEncoder::default();
+    assert_eq!(encoder.profile(), RingProfile::default());
+    let data = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    LzfseRingEncoder::default().encode(&mut data.as_slice(), &mut enc)?;
+    assert!(encode(RingProfile::default(), &data)? == enc);
+    Ok(())
+}
+
+#[test]
+fn profile() {
+    for profile in profiles() {
+        assert_eq!(LzfseRingEncoder::with_profile(profile).profile(), profile);
+    }
+}
+
+#[test]
+fn seq() -> io::Result<()> {
+    let data = Seq::default().take(0x0040_0000).collect::<Vec<_>>();
+    for profile in profiles() {
+        let enc = encode(profile, &data)?;
+        check_decode(profile, &enc, &data)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn zeros() -> io::Result<()> {
+    let data = vec![0u8; 0x0040_0000];
+    for profile in profiles() {
+        let enc = encode(profile, &data)?;
+        check_decode(profile, &enc, &data)?;
+    }
+    Ok(())
+}
+
+// Matches beyond the profile's reach are not emitted, the matching profile decoder accepts the
+// stream.
+#[test]
+fn max_distance() -> io::Result<()> {
+    let profiles = profiles();
+    for window in profiles.windows(2) {
+        let (u, v) = (window[0], window[1]);
+        let data = distance_data(u.max_match_distance() + 1);
+        let enc_u = encode(u, &data)?;
+        check_decode(u, &enc_u, &data)?;
+        let enc_v = encode(v, &data)?;
+        check_decode(v, &enc_v, &data)?;
+        // The larger profile finds the distant match.
+        assert!(enc_v.len() < enc_u.len(), "{}", u);
+    }
+    Ok(())
+}
+
+#[test]
+fn writer() -> io::Result<()> {
+    let data = Seq::default().take(0x0020_0000).collect::<Vec<_>>();
+    for profile in profiles() {
+        let enc = encode(profile, &data)?;
+        let mut encoder = LzfseRingEncoder::with_profile(profile);
+        let mut writer = encoder.writer(Vec::default());
+        for chunk in data.chunks(0x1234) {
+            writer.write_all(chunk)?;
+        }
+        assert!(writer.finalize()? == enc, "{}", profile);
+        let mut writer = encoder.writer_bytes(Vec::default());
+        for chunk in data.chunks(0x4321) {
+            writer.write_all(chunk)?;
+        }
+        assert!(writer.finalize()? == enc, "{}", profile);
+    }
+    Ok(())
+}
+
+#[test]
+fn memory_footprint() {
+    let profiles = profiles();
+    for window in profiles.windows(2) {
+        assert!(
+            lzfse_rust::memory_footprint(window[0], Role::Encoder)
+                < lzfse_rust::memory_footprint(window[1], Role::Encoder)
+        );
+    }
+}
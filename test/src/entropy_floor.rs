@@ -0,0 +1,57 @@
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Actual encoded size in bits per byte.
+fn actual(src: &[u8]) -> io::Result<f64> {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(src, &mut enc)?;
+    Ok(enc.len() as f64 * 8.0 / src.len() as f64)
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    assert_eq!(lzfse_rust::entropy_floor(&[]), 0.0);
+    assert_eq!(lzfse_rust::lz_entropy_floor(&[])?, 0.0);
+    Ok(())
+}
+
+#[test]
+fn zeros() -> io::Result<()> {
+    let src = vec![0; 0x0010_0000];
+    assert_eq!(lzfse_rust::entropy_floor(&src), 0.0);
+    assert!(lzfse_rust::lz_entropy_floor(&src)? < 0.01);
+    Ok(())
+}
+
+#[test]
+fn rng() -> io::Result<()> {
+    let src = Rng::default().gen_vec(0x0010_0000).unwrap();
+    let order0 = lzfse_rust::entropy_floor(&src);
+    assert!(order0 > 7.99 && order0 <= 8.0);
+    let lz = lzfse_rust::lz_entropy_floor(&src)?;
+    assert!(lz > 7.9 && lz <= 8.0);
+    assert!(lz <= actual(&src)?);
+    Ok(())
+}
+
+#[test]
+fn masked() -> io::Result<()> {
+    let src: Vec<u8> =
+        Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), 0x0010_0000).collect();
+    let order0 = lzfse_rust::entropy_floor(&src);
+    assert!(order0 > 0.0 && order0 <= 8.0);
+    let lz = lzfse_rust::lz_entropy_floor(&src)?;
+    assert!(lz <= actual(&src)?);
+    Ok(())
+}
+
+#[test]
+fn text() -> io::Result<()> {
+    let src = b"a quick brown fox jumps over the lazy dog ".repeat(0x1000);
+    let order0 = lzfse_rust::entropy_floor(&src);
+    let lz = lzfse_rust::lz_entropy_floor(&src)?;
+    assert!(lz < order0);
+    assert!(lz <= actual(&src)?);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use lzfse_rust::{BlockCost, LzfseDecoder, LzfseEncoder, NormalizationStrategy};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Costs account for the payload bits in full. Padding is limited to the 8 byte LMD bitstream pad
+// and the partial final bytes of the literal and LMD bitstreams.
+fn check(cost: &BlockCost) {
+    assert_eq!(cost.n_bits() + cost.pad_bits, cost.n_payload_bytes as u64 * 8);
+    assert!(64 <= cost.pad_bits);
+    assert!(cost.pad_bits < 64 + 16);
+    assert!(cost.n_raw_bytes != 0);
+}
+
+fn explain(enc: &[u8]) -> lzfse_rust::Result<Vec<BlockCost>> {
+    let costs = lzfse_rust::explain(enc)?;
+    for cost in &costs {
+        check(cost);
+    }
+    Ok(costs)
+}
+
+#[test]
+fn text() -> lzfse_rust::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let costs = explain(enc)?;
+    assert!(!costs.is_empty());
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    let n_raw_bytes: u64 = costs.iter().map(|u| u.n_raw_bytes as u64).sum();
+    assert_eq!(n_raw_bytes, dec.len() as u64);
+    for cost in &costs {
+        assert!(cost.literal_bits != 0);
+        assert!(cost.match_distance_bits != 0);
+    }
+    Ok(())
+}
+
+#[test]
+fn normalization() -> lzfse_rust::Result<()> {
+    let data = Seq::default().take(0x0008_0000).collect::<Vec<_>>();
+    let mut encoder = LzfseEncoder::default();
+    for &strategy in &[NormalizationStrategy::Fast, NormalizationStrategy::Accurate] {
+        encoder.set_normalization_strategy(strategy);
+        let mut enc = Vec::default();
+        encoder.encode_bytes(&data, &mut enc)?;
+        let costs = explain(&enc)?;
+        assert!(!costs.is_empty());
+        for (i, cost) in costs.iter().enumerate() {
+            assert_eq!(cost.index, i as u32);
+        }
+    }
+    Ok(())
+}
+
+// Raw and VN blocks are not reported.
+#[test]
+fn non_fse() -> io::Result<()> {
+    for data in &[Vec::default(), b"test".to_vec(), Rng::default().gen_vec(0x0100).unwrap()] {
+        let mut enc = Vec::default();
+        LzfseEncoder::default().encode_bytes(data, &mut enc)?;
+        assert!(lzfse_rust::explain(&enc)?.is_empty());
+    }
+    Ok(())
+}
+
+#[test]
+fn bad_stream() -> io::Result<()> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default()
+        .encode_bytes(&Seq::default().take(0x1000).collect::<Vec<_>>(), &mut enc)?;
+    enc.truncate(enc.len() - 1);
+    assert!(lzfse_rust::explain(&enc).is_err());
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use lzfse_rust::{LzfseRingDecoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+
+// Shared FIFO byte pipe.
+#[derive(Clone, Default)]
+struct Pipe(Rc<RefCell<VecDeque<u8>>>);
+
+impl Read for Pipe {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut queue = self.0.borrow_mut();
+        let n = buf.len().min(queue.len());
+        for (u, v) in buf.iter_mut().zip(queue.drain(..n)) {
+            *u = v;
+        }
+        Ok(n)
+    }
+}
+
+impl Write for Pipe {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn messages() -> Vec<Vec<u8>> {
+    (0..64u32)
+        .map(|n| {
+            let len = (n * n * 17) as usize % 0x2000;
+            Iterator::take(Seq::masked(Rng::new(n), 0x0000_0F0F), len).collect()
+        })
+        .collect()
+}
+
+#[test]
+fn interleaved() -> io::Result<()> {
+    let pipe = Pipe::default();
+    let mut encoder = LzfseRingEncoder::default();
+    let mut decoder = LzfseRingDecoder::default();
+    let mut sink = encoder.frame_sink(pipe.clone());
+    let mut source = decoder.frame_source(pipe);
+    let mut dec = Vec::default();
+    for msg in messages() {
+        assert_eq!(source.read_frame(&mut dec)?, None);
+        sink.write_frame(&msg)?;
+        assert_eq!(source.read_frame(&mut dec)?, Some(msg.len() as u64));
+        assert!(dec == msg);
+        dec.clear();
+    }
+    assert_eq!(source.read_frame(&mut dec)?, None);
+    Ok(())
+}
+
+#[test]
+fn index() -> io::Result<()> {
+    let messages = messages();
+    let mut encoder = LzfseRingEncoder::default();
+    let mut sink = encoder.frame_sink(Vec::default());
+    let mut index = Vec::default();
+    let mut offset = 0;
+    for msg in messages.iter() {
+        index.push(offset);
+        offset += sink.write_frame(msg)? as usize;
+    }
+    let enc = sink.into_inner();
+    assert_eq!(enc.len(), offset);
+    let mut decoder = LzfseRingDecoder::default();
+    let mut dec = Vec::default();
+    for (i, &offset) in index.iter().enumerate().rev().step_by(3) {
+        let mut source = decoder.frame_source(&enc[offset..]);
+        assert_eq!(source.read_frame(&mut dec)?, Some(messages[i].len() as u64));
+        assert!(dec == messages[i]);
+        dec.clear();
+    }
+    Ok(())
+}
+
+#[test]
+fn truncated() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::default();
+    let mut sink = encoder.frame_sink(Vec::default());
+    sink.write_frame(b"test")?;
+    let enc = sink.into_inner();
+    let mut decoder = LzfseRingDecoder::default();
+    let mut dec = Vec::default();
+    for n in 1..enc.len() {
+        let mut source = decoder.frame_source(&enc[..n]);
+        assert!(source.read_frame(&mut dec).is_err());
+    }
+    Ok(())
+}
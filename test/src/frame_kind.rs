@@ -0,0 +1,62 @@
+use lzfse_rust::{Error, FrameKind, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+const KINDS: [FrameKind; 5] =
+    [FrameKind::Raw, FrameKind::Vxn, FrameKind::Vx1, FrameKind::Vx2, FrameKind::Eos];
+
+fn first_kind(data: &[u8]) -> io::Result<FrameKind> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(data, &mut enc)?;
+    assert_eq!(lzfse_rust::peek_frame_kind(&enc[enc.len() - 4..])?, FrameKind::Eos);
+    Ok(lzfse_rust::peek_frame_kind(&enc)?)
+}
+
+#[test]
+fn encoded() -> io::Result<()> {
+    assert_eq!(first_kind(b"")?, FrameKind::Raw);
+    assert_eq!(first_kind(b"test")?, FrameKind::Raw);
+    let data = Seq::masked(Rng::new(0), 0x0000_0F0F).take(0x0400).collect::<Vec<_>>();
+    assert_eq!(first_kind(&data)?, FrameKind::Vxn);
+    let data = Seq::masked(Rng::new(0), 0x0000_0F0F).take(0x0001_0000).collect::<Vec<_>>();
+    assert_eq!(first_kind(&data)?, FrameKind::Vx2);
+    Ok(())
+}
+
+#[test]
+fn magic_bytes() -> lzfse_rust::Result<()> {
+    for &kind in KINDS.iter() {
+        let magic_bytes = kind.magic_bytes();
+        assert_eq!(&magic_bytes[..3], b"bvx");
+        assert_eq!(kind.to_string().as_bytes(), magic_bytes);
+        assert_eq!(lzfse_rust::peek_frame_kind(&magic_bytes)?, kind);
+        // Trailing bytes are ignored.
+        let mut src = magic_bytes.to_vec();
+        src.extend_from_slice(b"xxxx");
+        assert_eq!(lzfse_rust::peek_frame_kind(&src)?, kind);
+    }
+    Ok(())
+}
+
+#[test]
+fn underflow() {
+    for n in 0..4 {
+        match lzfse_rust::peek_frame_kind(&b"bvx2"[..n]) {
+            Err(Error::PayloadUnderflow) => {}
+            Err(err) => panic!("{}", err),
+            Ok(_) => panic!(),
+        }
+    }
+}
+
+#[test]
+fn bad_block() {
+    for src in [b"bvx0", b"bvx3", b"Bvx2", b"xbvx"].iter() {
+        match lzfse_rust::peek_frame_kind(*src) {
+            Err(Error::BadBlock(u)) => assert_eq!(u, u32::from_le_bytes(**src)),
+            Err(err) => panic!("{}", err),
+            Ok(_) => panic!(),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Golden outputs are endian neutral: any change here breaks cross platform determinism.
+
+const FOX: &[u8] = b"a quick brown fox jumps over the lazy dog ";
+
+const TEST_ENC: [u8; 16] = *b"bvx-\x04\x00\x00\x00testbvx$";
+
+// (length, FNV-1a 64 digest)
+const FOX_SMALL: (usize, u64) = (0x0050, 0x1EE0_AA2A_748B_6257);
+const FOX_LARGE: (usize, u64) = (0x0135, 0xCEC1_E6D5_E2AB_6D4E);
+const MASKED_SMALL: (usize, u64) = (0x0762, 0x7B41_9008_BC53_2C53);
+const MASKED_LARGE: (usize, u64) = (0x8C66, 0x32AB_4685_05EA_BAE0);
+const MASKED_RING: (usize, u64) = (0x0008_FE09, 0x2DE0_C162_AD8D_B434);
+const MASKED_SEED: (usize, u64) = (0x8C3A, 0xCC62_632E_CBE9_4AB8);
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |h, &b| (h ^ b as u64).wrapping_mul(0x0100_0000_01B3))
+}
+
+// Byte sequences are generated one byte at a time and are thus identical across platforms.
+fn masked(len: usize) -> Vec<u8> {
+    Iterator::take(Seq::masked(Rng::default(), 0x0F0F_0F0F), len).collect()
+}
+
+fn encode_bytes(src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(src, &mut enc)?;
+    Ok(enc)
+}
+
+fn check(enc: &[u8], src: &[u8], expected: (usize, u64)) -> io::Result<()> {
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    assert!(dec == src);
+    assert_eq!((enc.len(), fnv1a(enc)), expected);
+    Ok(())
+}
+
+#[test]
+fn test() -> io::Result<()> {
+    let enc = encode_bytes(b"test")?;
+    assert_eq!(enc, TEST_ENC);
+    Ok(())
+}
+
+#[test]
+fn fox_small() -> io::Result<()> {
+    let src = FOX.repeat(0x20);
+    check(&encode_bytes(&src)?, &src, FOX_SMALL)
+}
+
+#[test]
+fn fox_large() -> io::Result<()> {
+    let src = FOX.repeat(0x1000);
+    check(&encode_bytes(&src)?, &src, FOX_LARGE)
+}
+
+#[test]
+fn masked_small() -> io::Result<()> {
+    let src = masked(0x0800);
+    check(&encode_bytes(&src)?, &src, MASKED_SMALL)
+}
+
+#[test]
+fn masked_large() -> io::Result<()> {
+    let src = masked(0x0001_0000);
+    check(&encode_bytes(&src)?, &src, MASKED_LARGE)
+}
+
+#[test]
+fn masked_ring() -> io::Result<()> {
+    let src = masked(0x0010_0000);
+    let mut enc = Vec::default();
+    LzfseRingEncoder::default().encode(&mut src.as_slice(), &mut enc)?;
+    check(&enc, &src, MASKED_RING)
+}
+
+#[test]
+fn masked_seed() -> io::Result<()> {
+    let src = masked(0x0001_0000);
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_hash_seed(0x9E37_79B1);
+    let mut enc = Vec::default();
+    encoder.encode_bytes(&src, &mut enc)?;
+    check(&enc, &src, MASKED_SEED)
+}
@@ -0,0 +1,93 @@
+use lzfse_rust::{HistoryTable, LzfseDecoder, LzfseEncoder, LzfseRingDecoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+const SEEDS: [u32; 5] = [0, 1, 0x0000_FFFF, 0x9E37_79B1, 0xFFFF_FFFF];
+
+fn data() -> Vec<Vec<u8>> {
+    vec![
+        b"test".to_vec(),
+        Iterator::take(Seq::default(), 0x0001_0000).collect(),
+        Rng::default().gen_vec(0x0001_0000).unwrap(),
+        b"a quick brown fox jumps over the lazy dog ".repeat(0x1000),
+        Iterator::take(Seq::default(), 0x0010_0000).collect(),
+    ]
+}
+
+fn check(enc: &[u8], src: &[u8]) -> io::Result<()> {
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    assert_eq!(dec, src);
+    dec.clear();
+    LzfseRingDecoder::default().decode(&mut &*enc, &mut dec)?;
+    assert_eq!(dec, src);
+    Ok(())
+}
+
+#[test]
+fn default_seed() -> io::Result<()> {
+    assert_eq!(LzfseEncoder::default().hash_seed(), 0);
+    assert_eq!(LzfseRingEncoder::default().hash_seed(), 0);
+    for src in data() {
+        let mut expected = Vec::default();
+        LzfseEncoder::default().encode_bytes(&src, &mut expected)?;
+        let mut encoder = LzfseEncoder::default();
+        encoder.set_hash_seed(0x1234_5678);
+        encoder.set_hash_seed(0);
+        let mut enc = Vec::default();
+        encoder.encode_bytes(&src, &mut enc)?;
+        assert_eq!(enc, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn seeds() -> io::Result<()> {
+    for src in data() {
+        for &seed in SEEDS.iter() {
+            let mut encoder = LzfseEncoder::default();
+            encoder.set_hash_seed(seed);
+            assert_eq!(encoder.hash_seed(), seed);
+            let mut enc = Vec::default();
+            encoder.encode_bytes(&src, &mut enc)?;
+            check(&enc, &src)?;
+            let mut encoder = LzfseRingEncoder::default();
+            encoder.set_hash_seed(seed);
+            let mut ring_enc = Vec::default();
+            encoder.encode(&mut src.as_slice(), &mut ring_enc)?;
+            check(&ring_enc, &src)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn seed_perturbs_output() -> io::Result<()> {
+    let src: Vec<u8> = Iterator::take(Seq::default(), 0x0010_0000).collect();
+    let mut expected = Vec::default();
+    LzfseEncoder::default().encode_bytes(&src, &mut expected)?;
+    let n = SEEDS[1..]
+        .iter()
+        .map(|&seed| {
+            let mut encoder = LzfseEncoder::default();
+            encoder.set_hash_seed(seed);
+            let mut enc = Vec::default();
+            encoder.encode_bytes(&src, &mut enc).map(|_| enc)
+        })
+        .collect::<io::Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|enc| *enc != expected)
+        .count();
+    assert_ne!(n, 0);
+    Ok(())
+}
+
+#[test]
+fn history_table_resets_seed() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::with_history_table(HistoryTable::default());
+    encoder.set_hash_seed(0x1234_5678);
+    let encoder = LzfseRingEncoder::with_history_table(encoder.into_history_table());
+    assert_eq!(encoder.hash_seed(), 0);
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use lzfse_rust::{HistoryTable, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io::{self, Write};
+
+fn check(table: HistoryTable, dec: &[u8]) -> io::Result<HistoryTable> {
+    let mut encoder = LzfseRingEncoder::with_history_table(table);
+    let mut enc = Vec::default();
+    encoder.encode(&mut &*dec, &mut enc)?;
+    let mut expected = Vec::default();
+    LzfseRingEncoder::default().encode(&mut &*dec, &mut expected)?;
+    assert!(enc == expected);
+    let mut wtr = encoder.writer_bytes(Vec::default());
+    wtr.write_all(dec)?;
+    assert!(wtr.finalize()? == expected);
+    let mut out = Vec::default();
+    lzfse_rust::decode_bytes(&enc, &mut out)?;
+    assert!(out == dec);
+    Ok(encoder.into_history_table())
+}
+
+#[test]
+fn reuse() -> io::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut text = Vec::default();
+    lzfse_rust::decode_bytes(enc, &mut text)?;
+    let seq = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    let rng = Rng::default().gen_vec(0x0001_0000).unwrap();
+    let mut table = HistoryTable::default();
+    for dec in [&text, &seq, &rng, &text, &Vec::default()].iter() {
+        table = check(table, dec)?;
+    }
+    Ok(())
+}
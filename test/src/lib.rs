@@ -1,18 +1,102 @@
+#[cfg(test)]
+mod archive;
 #[cfg(all(test, feature = "big_mem"))]
 mod big_mem;
 #[cfg(test)]
+mod block_boundaries;
+#[cfg(test)]
+mod block_ratios;
+#[cfg(test)]
+mod block_strategy;
+#[cfg(test)]
+mod bounded;
+#[cfg(test)]
 mod buddy;
 #[cfg(test)]
+mod checksum;
+#[cfg(test)]
+mod circular_file_sink;
+#[cfg(test)]
 mod data;
 #[cfg(test)]
+mod decode_block;
+#[cfg(test)]
+mod decode_blocks_vec;
+#[cfg(test)]
+mod decode_bytes_expect;
+#[cfg(test)]
+mod decode_bytes_into;
+#[cfg(test)]
+mod decode_bytes_partial;
+#[cfg(test)]
+mod decode_chunked;
+#[cfg(test)]
+mod decode_concatenated;
+#[cfg(test)]
+mod decode_fill;
+#[cfg(test)]
+mod decode_leniency;
+#[cfg(test)]
+mod decode_reader;
+#[cfg(test)]
+mod decode_report;
+#[cfg(test)]
+mod decode_with_limit;
+#[cfg(test)]
+mod decoded_cursor;
+#[cfg(test)]
+mod decoded_len;
+#[cfg(test)]
+mod dict;
+#[cfg(test)]
+mod encode_bytes_verified;
+#[cfg(test)]
+mod encode_segmented;
+#[cfg(test)]
+mod encode_stats;
+#[cfg(test)]
+mod encode_to_sink;
+#[cfg(test)]
+mod encode_with_progress;
+#[cfg(test)]
+mod encoder_builder;
+#[cfg(test)]
+mod encoder_profile;
+#[cfg(test)]
+mod entropy_floor;
+#[cfg(test)]
+mod explain;
+#[cfg(test)]
+mod frame;
+#[cfg(test)]
+mod frame_kind;
+#[cfg(test)]
 mod fuzz_read;
 #[cfg(test)]
 mod fuzz_write;
+#[cfg(test)]
+mod golden;
+#[cfg(test)]
+mod hash_seed;
+#[cfg(test)]
+mod history_table;
 #[cfg(all(test, feature = "huge_data"))]
 mod huge;
 #[cfg(test)]
 mod len;
 #[cfg(test)]
+mod match_distance_histogram;
+#[cfg(test)]
+mod match_strategy;
+#[cfg(test)]
+mod max_block_payload;
+#[cfg(test)]
+mod merge;
+#[cfg(test)]
+mod metadata;
+#[cfg(test)]
+mod mixed_blocks;
+#[cfg(test)]
 mod mutate_0;
 #[cfg(test)]
 mod mutate_1;
@@ -29,8 +113,16 @@ mod mutate_6;
 #[cfg(test)]
 mod mutate_7;
 #[cfg(test)]
+mod mux;
+#[cfg(test)]
+mod normalization;
+#[cfg(test)]
 mod ops;
 #[cfg(test)]
+mod output_sink;
+#[cfg(test)]
+mod pad_output;
+#[cfg(test)]
 mod patchwork_0;
 #[cfg(test)]
 mod patchwork_1;
@@ -47,8 +139,54 @@ mod pattern_5;
 #[cfg(test)]
 mod pattern_6;
 #[cfg(test)]
+mod pipe;
+#[cfg(test)]
+mod quick_verify;
+#[cfg(test)]
 mod random_0;
 #[cfg(test)]
 mod random_1;
 #[cfg(test)]
 mod random_2;
+#[cfg(test)]
+mod raw_block;
+#[cfg(test)]
+mod raw_byte_count;
+#[cfg(test)]
+mod reader_totals;
+#[cfg(test)]
+mod recycle;
+#[cfg(test)]
+mod repack;
+#[cfg(test)]
+mod replay;
+#[cfg(test)]
+mod reset;
+#[cfg(test)]
+mod ring_profile;
+#[cfg(test)]
+mod rle_bias;
+#[cfg(test)]
+mod short_write;
+#[cfg(test)]
+mod skip_if_compressed;
+#[cfg(test)]
+mod sparse_scan;
+#[cfg(test)]
+mod spsc;
+#[cfg(test)]
+mod strict_fse;
+#[cfg(test)]
+mod strip_frame;
+#[cfg(test)]
+mod trace;
+#[cfg(test)]
+mod trailing_bytes;
+#[cfg(test)]
+mod utf8;
+#[cfg(test)]
+mod warm;
+#[cfg(test)]
+mod within;
+#[cfg(test)]
+mod writer_flush;
@@ -0,0 +1,98 @@
+use lzfse_rust::{DecodeEvent, LzfseDecoder, LzfseEncoder, MatchStrategy, N_BUCKETS};
+use test_kit::Rng;
+
+use std::io;
+
+// Byte runs, 4 to 64 bytes long, interleaved with short random literal runs.
+fn runs(len: usize) -> Vec<u8> {
+    let mut rng = Rng::default();
+    let mut vec = Vec::with_capacity(len + 0x80);
+    while vec.len() < len {
+        let u = (rng.gen() >> 24) as u8;
+        let n = 4 + (rng.gen() >> 16) as usize % 0x3D;
+        vec.resize(vec.len() + n, u);
+        let n = (rng.gen() >> 16) as usize % 8;
+        vec.extend((0..n).map(|_| (rng.gen() >> 24) as u8));
+    }
+    vec
+}
+
+fn bucket(distance: u32) -> usize {
+    (31 - distance.leading_zeros()) as usize
+}
+
+#[test]
+fn run_heavy() -> io::Result<()> {
+    let src = runs(0x0010_0000);
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_rle_bias(true);
+    let histogram = encoder.match_distance_histogram(&src, usize::MAX)?;
+    let n_matches = histogram.iter().sum::<u64>();
+    assert!(n_matches != 0);
+    assert!(histogram[0] * 10 >= n_matches * 9, "{:?}", histogram);
+    Ok(())
+}
+
+#[test]
+fn random() -> io::Result<()> {
+    let src = Rng::default().gen_vec(0x0010_0000).unwrap();
+    let histogram = lzfse_rust::match_distance_histogram(&src, usize::MAX)?;
+    // Chance matches only.
+    assert!(histogram.iter().sum::<u64>() < 0x20, "{:?}", histogram);
+    Ok(())
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    assert_eq!(lzfse_rust::match_distance_histogram(&[], usize::MAX)?, [0; N_BUCKETS]);
+    assert_eq!(lzfse_rust::match_distance_histogram(&[0; 0x1000], 0)?, [0; N_BUCKETS]);
+    Ok(())
+}
+
+// A full scan agrees with the matches emitted by an equivalent encode.
+#[test]
+fn trace() -> io::Result<()> {
+    let src = runs(0x0008_0000);
+    let mut encoder = LzfseEncoder::default();
+    let histogram = encoder.match_distance_histogram(&src, usize::MAX)?;
+    let mut enc = Vec::default();
+    encoder.encode_bytes(&src, &mut enc)?;
+    let mut expected = [0; N_BUCKETS];
+    LzfseDecoder::default().trace(&enc, |event| {
+        if let DecodeEvent::Match { distance, .. } = event {
+            expected[bucket(distance)] += 1;
+        }
+    })?;
+    assert_eq!(histogram, expected);
+    Ok(())
+}
+
+#[test]
+fn sample() -> io::Result<()> {
+    let src = runs(0x0080_0000);
+    let mut encoder = LzfseEncoder::default();
+    let full = encoder.match_distance_histogram(&src, usize::MAX)?;
+    let sampled = encoder.match_distance_histogram(&src, 0x0020_0000)?;
+    let n_full = full.iter().sum::<u64>();
+    let n_sampled = sampled.iter().sum::<u64>();
+    assert!(n_sampled != 0);
+    assert!(n_sampled * 3 < n_full);
+    let small = encoder.match_distance_histogram(&src, 0x1000)?;
+    assert!(small.iter().sum::<u64>() < n_sampled);
+    Ok(())
+}
+
+// Long distance repeats are visible to the default strategy and excluded by the decode optimized
+// strategy.
+#[test]
+fn match_strategy() -> io::Result<()> {
+    let block = Rng::default().gen_vec(0x0002_0000).unwrap();
+    let src = block.repeat(4);
+    let mut encoder = LzfseEncoder::default();
+    let histogram = encoder.match_distance_histogram(&src, usize::MAX)?;
+    assert!(histogram[bucket(0x0002_0000)] != 0);
+    encoder.set_match_strategy(MatchStrategy::DecodeOptimized);
+    let histogram = encoder.match_distance_histogram(&src, usize::MAX)?;
+    assert!(histogram[bucket(0x0001_0000) + 1..].iter().all(|&u| u == 0), "{:?}", histogram);
+    Ok(())
+}
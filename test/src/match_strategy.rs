@@ -0,0 +1,105 @@
+use lzfse_rust::{
+    DecodeEvent, LzfseDecoder, LzfseEncoder, LzfseRingDecoder, LzfseRingEncoder, MatchStrategy,
+};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+const NEAR_MATCH_DISTANCE: u32 = 0x0001_0000;
+const NEAR_MATCH_LEN: u32 = 0x0100;
+
+fn data() -> Vec<Vec<u8>> {
+    let seq: Vec<u8> = Iterator::take(Seq::default(), 0x0004_0000).collect();
+    let mut far = Rng::default().gen_vec(0x0002_0000).unwrap();
+    let head = far[..0x8000].to_vec();
+    far.extend_from_slice(&head);
+    vec![
+        Vec::default(),
+        b"test".to_vec(),
+        vec![0; 0x0010_0000],
+        b"a quick brown fox jumps over the lazy dog ".repeat(0x1000),
+        Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), 0x0010_0000).collect(),
+        seq.repeat(4),
+        far,
+    ]
+}
+
+fn check(enc: &[u8], src: &[u8]) -> io::Result<()> {
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    assert_eq!(dec, src);
+    dec.clear();
+    LzfseRingDecoder::default().decode(&mut &*enc, &mut dec)?;
+    assert_eq!(dec, src);
+    let mut n_matches = 0;
+    LzfseDecoder::default().trace(enc, |event| {
+        if let DecodeEvent::Match { distance, len } = event {
+            assert!(distance <= NEAR_MATCH_DISTANCE);
+            assert!(len <= NEAR_MATCH_LEN);
+            n_matches += 1;
+        }
+    })?;
+    assert!(src.len() < 0x1000 || n_matches != 0);
+    Ok(())
+}
+
+#[test]
+fn default_strategy() -> io::Result<()> {
+    assert_eq!(MatchStrategy::default(), MatchStrategy::Auto);
+    assert_eq!(LzfseEncoder::default().match_strategy(), MatchStrategy::Auto);
+    assert_eq!(LzfseRingEncoder::default().match_strategy(), MatchStrategy::Auto);
+    for src in data() {
+        let mut expected = Vec::default();
+        LzfseEncoder::default().encode_bytes(&src, &mut expected)?;
+        let mut encoder = LzfseEncoder::default();
+        encoder.set_match_strategy(MatchStrategy::DecodeOptimized);
+        encoder.set_match_strategy(MatchStrategy::Auto);
+        let mut enc = Vec::default();
+        encoder.encode_bytes(&src, &mut enc)?;
+        assert_eq!(enc, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn decode_optimized() -> io::Result<()> {
+    for src in data() {
+        let mut encoder = LzfseEncoder::default();
+        encoder.set_match_strategy(MatchStrategy::DecodeOptimized);
+        assert_eq!(encoder.match_strategy(), MatchStrategy::DecodeOptimized);
+        let mut enc = Vec::default();
+        encoder.encode_bytes(&src, &mut enc)?;
+        check(&enc, &src)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn decode_optimized_ring() -> io::Result<()> {
+    for src in data() {
+        let mut encoder = LzfseRingEncoder::default();
+        encoder.set_match_strategy(MatchStrategy::DecodeOptimized);
+        assert_eq!(encoder.match_strategy(), MatchStrategy::DecodeOptimized);
+        let mut enc = Vec::default();
+        encoder.encode(&mut src.as_slice(), &mut enc)?;
+        check(&enc, &src)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn decode_optimized_far_matches() -> io::Result<()> {
+    let mut src = Rng::default().gen_vec(0x0002_0000).unwrap();
+    let head = src[..0x8000].to_vec();
+    src.extend_from_slice(&head);
+    let mut max_distance = 0;
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(&src, &mut enc)?;
+    LzfseDecoder::default().trace(&enc, |event| {
+        if let DecodeEvent::Match { distance, .. } = event {
+            max_distance = max_distance.max(distance);
+        }
+    })?;
+    assert!(max_distance > NEAR_MATCH_DISTANCE);
+    Ok(())
+}
@@ -0,0 +1,119 @@
+// Oversized block payload fields.
+
+use lzfse_rust::{Error, LzfseDecoder, LzfseEncoder, LzfseRingDecoder};
+use test_kit::{Rng, Seq};
+
+use std::convert::TryInto;
+use std::io;
+
+const LIMIT: u32 = 0x0001_0000;
+
+// "test" string encoded as a single raw block.
+const TEST: [u8; 16] = [
+    0x62, 0x76, 0x78, 0x2d, 0x04, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x62, 0x76, 0x78, 0x24,
+];
+
+fn encode(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(data, &mut enc)?;
+    Ok(enc)
+}
+
+fn check_overflow(enc: &[u8]) {
+    assert!(matches!(decode(enc), Err(Error::PayloadOverflow)));
+}
+
+// Decode with `LIMIT`, both decoders must reject `enc` identically without output.
+fn decode(enc: &[u8]) -> lzfse_rust::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    decoder.set_max_block_payload(Some(LIMIT));
+    let mut dec = Vec::default();
+    let err = decoder.decode_bytes(enc, &mut dec).unwrap_err();
+    assert!(dec.is_empty());
+    let mut decoder = LzfseRingDecoder::default();
+    decoder.set_max_block_payload(Some(LIMIT));
+    let ring_err = decoder.decode(&mut &*enc, &mut dec).unwrap_err();
+    assert!(dec.is_empty());
+    assert_eq!(ring_err.to_string(), err.to_string());
+    Err(err)
+}
+
+// Random payload field values exceeding `LIMIT`, in `LIMIT + 1..=max`.
+fn payloads(seed: u32, max: u32) -> impl Iterator<Item = u32> {
+    let mut rng = Rng::new(seed);
+    (0..0x0400).map(move |_| LIMIT + 1 + rng.gen() % (max - LIMIT))
+}
+
+#[test]
+fn default() -> io::Result<()> {
+    assert_eq!(LzfseDecoder::default().max_block_payload(), None);
+    assert_eq!(LzfseRingDecoder::default().max_block_payload(), None);
+    let data: Vec<u8> = Iterator::take(Seq::default(), 0x0010_0000).collect();
+    let enc = encode(&data)?;
+    for &limit in [None, Some(u32::MAX)].iter() {
+        let mut decoder = LzfseDecoder::default();
+        decoder.set_max_block_payload(limit);
+        let mut dec = Vec::default();
+        decoder.decode_bytes(&enc, &mut dec)?;
+        assert!(dec == data);
+    }
+    Ok(())
+}
+
+#[test]
+fn limit() -> io::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    let mut dec = Vec::default();
+    decoder.set_max_block_payload(Some(4));
+    decoder.decode_bytes(&TEST, &mut dec)?;
+    assert_eq!(dec, b"test");
+    decoder.set_max_block_payload(Some(3));
+    assert!(matches!(decoder.decode_bytes(&TEST, &mut dec), Err(Error::PayloadOverflow)));
+    Ok(())
+}
+
+#[test]
+fn fuzz_raw() {
+    for n_raw_bytes in payloads(0, u32::MAX) {
+        let mut enc = TEST.to_vec();
+        enc[4..8].copy_from_slice(&n_raw_bytes.to_le_bytes());
+        check_overflow(&enc);
+    }
+}
+
+#[test]
+fn fuzz_vxn() -> io::Result<()> {
+    let enc = encode(&b"a quick brown fox jumps over the lazy dog ".repeat(0x10))?;
+    assert_eq!(&enc[..4], b"bvxn");
+    for n_payload_bytes in payloads(1, u32::MAX - 12) {
+        let mut enc = enc.clone();
+        enc[8..12].copy_from_slice(&n_payload_bytes.to_le_bytes());
+        check_overflow(&enc);
+    }
+    Ok(())
+}
+
+#[test]
+fn fuzz_vx2() -> io::Result<()> {
+    let data: Vec<u8> =
+        Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), 0x0010_0000).collect();
+    let enc = encode(&data)?;
+    assert_eq!(&enc[..4], b"bvx2");
+    // Packed field 1, bits 40..60: n_lmd_payload_bytes. Values inconsistent with the LMD count
+    // are rejected by header validation.
+    let packed = u64::from_le_bytes(enc[16..24].try_into().unwrap());
+    let mut n_overflow = 0;
+    for n_lmd_payload_bytes in payloads(2, 0x000F_FFFF) {
+        let mut enc = enc.clone();
+        let packed = packed & !(0x000F_FFFF << 40) | (n_lmd_payload_bytes as u64) << 40;
+        enc[16..24].copy_from_slice(&packed.to_le_bytes());
+        match decode(&enc) {
+            Err(Error::PayloadOverflow) => n_overflow += 1,
+            Err(Error::Fse(_)) => {}
+            Err(err) => panic!("{}", err),
+            Ok(()) => unreachable!(),
+        }
+    }
+    assert_ne!(n_overflow, 0);
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn encode(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(data, &mut enc)?;
+    Ok(enc)
+}
+
+// Returns (merged, a + b) encoded lengths.
+fn check(data: &[u8], mid: usize) -> io::Result<(usize, usize)> {
+    let a = encode(&data[..mid])?;
+    let b = encode(&data[mid..])?;
+    let mut enc = vec![0xFF; 4];
+    let (u, v) = lzfse_rust::merge_reencode(&a, &b, &mut enc)?;
+    assert_eq!(u, data.len() as u64);
+    assert_eq!(v + 4, enc.len() as u64);
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(&enc[4..], &mut dec)?;
+    assert!(dec == data);
+    Ok((v as usize, a.len() + b.len()))
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    check(&[], 0)?;
+    Ok(())
+}
+
+#[test]
+fn text() -> io::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut data = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut data)?;
+    for &mid in &[0, 1, data.len() / 3, data.len() / 2, data.len()] {
+        let (u, v) = check(&data, mid)?;
+        assert!(u <= v);
+    }
+    Ok(())
+}
+
+#[test]
+fn repeat() -> io::Result<()> {
+    // Cross-boundary matches only.
+    let mut data = Rng::default().gen_vec(0x8000).unwrap();
+    data.extend_from_slice(&data.clone());
+    let a = encode(&data[..0x8000])?;
+    let mut enc = Vec::default();
+    lzfse_rust::merge_reencode(&a, &a, &mut enc)?;
+    assert!(enc.len() < a.len() + a.len() / 2);
+    check(&data, 0x8000)?;
+    Ok(())
+}
+
+#[test]
+fn seq() -> io::Result<()> {
+    let data = Iterator::take(Seq::default(), 0x0010_0000).collect::<Vec<_>>();
+    check(&data, 0x0004_1234)?;
+    Ok(())
+}
+
+#[test]
+fn bad_stream() {
+    let a = encode(b"test").unwrap();
+    let mut enc = Vec::default();
+    assert!(lzfse_rust::merge_reencode(&a, &a[..a.len() - 1], &mut enc).is_err());
+    assert!(enc.is_empty());
+}
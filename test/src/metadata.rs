@@ -0,0 +1,71 @@
+use lzfse_rust::{LzfseDecoder, LzfseRingDecoder, LzfseRingEncoder, Metadata};
+use test_kit::Seq;
+
+use std::io;
+
+fn encode(metadata: &Metadata, dec: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    metadata.write_to(&mut enc)?;
+    LzfseRingEncoder::default().encode(&mut &*dec, &mut enc)?;
+    Ok(enc)
+}
+
+#[test]
+fn round_trip() -> lzfse_rust::Result<()> {
+    let dec = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let metadata = Metadata { name: "alice29.txt".to_owned(), mtime: 1_600_000_000 };
+    let enc = encode(&metadata, &dec)?;
+    assert!(Metadata::is_metadata(&enc));
+    let mut src = enc.as_slice();
+    assert_eq!(Metadata::read_from(&mut src)?, metadata);
+    let mut out = Vec::default();
+    LzfseRingDecoder::default().decode(&mut src, &mut out)?;
+    assert!(out == dec);
+    Ok(())
+}
+
+#[test]
+fn round_trip_bytes() -> lzfse_rust::Result<()> {
+    let dec = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let metadata = Metadata { name: "a.txt".to_owned(), mtime: 0 };
+    let enc = encode(&metadata, &dec)?;
+    let mut src = enc.as_slice();
+    assert_eq!(Metadata::read_from(&mut src)?, metadata);
+    let mut out = Vec::default();
+    LzfseDecoder::default().decode_bytes(src, &mut out)?;
+    assert!(out == dec);
+    Ok(())
+}
+
+#[test]
+fn no_metadata() -> lzfse_rust::Result<()> {
+    let mut enc = Vec::default();
+    LzfseRingEncoder::default().encode(&mut &b"test"[..], &mut enc)?;
+    assert!(!Metadata::is_metadata(&enc));
+    assert!(matches!(
+        Metadata::read_from(&mut enc.as_slice()),
+        Err(lzfse_rust::Error::BadMetadata)
+    ));
+    Ok(())
+}
+
+#[test]
+fn bad_name() -> io::Result<()> {
+    let metadata = Metadata { name: "ab".to_owned(), mtime: 0 };
+    let mut enc = encode(&metadata, b"test")?;
+    enc[14] = 0xFF;
+    assert!(matches!(Metadata::read_from(&mut enc.as_slice()), Err(lzfse_rust::Error::Utf8(_))));
+    Ok(())
+}
+
+#[test]
+fn decoder_rejects_metadata() -> io::Result<()> {
+    // Metadata frames are not part of the LZFSE format.
+    let metadata = Metadata { name: "a.txt".to_owned(), mtime: 0 };
+    let enc = encode(&metadata, b"test")?;
+    let mut out = Vec::default();
+    assert!(LzfseDecoder::default().decode_bytes(&enc, &mut out).is_err());
+    let mut out = Vec::default();
+    assert!(LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut out).is_err());
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, LzfseRingDecoder};
+use test_kit::Seq;
+
+use std::io::{self, Read};
+
+const RAW: &[u8] = include_bytes!("../../data/mutate/raw.lzfse");
+const VX1: &[u8] = include_bytes!("../../data/mutate/vx1.lzfse");
+const VX2: &[u8] = include_bytes!("../../data/mutate/vx2.lzfse");
+const VXN: &[u8] = include_bytes!("../../data/mutate/vxn.lzfse");
+
+const EOS: &[u8] = b"bvx$";
+
+struct Member {
+    enc: Vec<u8>,
+    dec: Vec<u8>,
+}
+
+impl Member {
+    fn decode(enc: &[u8], magic: &[u8]) -> lzfse_rust::Result<Self> {
+        assert_eq!(&enc[..4], magic);
+        let mut dec = Vec::default();
+        LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+        Ok(Self { enc: strip_eos(enc).to_vec(), dec })
+    }
+
+    fn encode(dec: Vec<u8>, magic: &[u8]) -> lzfse_rust::Result<Self> {
+        let mut enc = Vec::default();
+        LzfseEncoder::default().encode_bytes(&dec, &mut enc)?;
+        assert_eq!(&enc[..4], magic);
+        Ok(Self { enc: strip_eos(&enc).to_vec(), dec })
+    }
+}
+
+fn strip_eos(enc: &[u8]) -> &[u8] {
+    assert!(enc.ends_with(EOS));
+    &enc[..enc.len() - EOS.len()]
+}
+
+// Mixed block type members, each encoded independently with EOS blocks stripped.
+fn members() -> lzfse_rust::Result<Vec<Member>> {
+    Ok(vec![
+        Member::decode(RAW, b"bvx-")?,
+        Member::decode(VX1, b"bvx1")?,
+        Member::decode(VX2, b"bvx2")?,
+        Member::decode(VXN, b"bvxn")?,
+        Member::encode(b"test".to_vec(), b"bvx-")?,
+        Member::encode(b"a quick brown fox jumps over the lazy dog ".repeat(0x10), b"bvxn")?,
+        Member::encode(Iterator::take(Seq::default(), 0x0002_0000).collect(), b"bvx2")?,
+    ])
+}
+
+fn check(members: &[&Member]) -> lzfse_rust::Result<()> {
+    let mut enc = Vec::default();
+    let mut dec = Vec::default();
+    for member in members {
+        enc.extend_from_slice(&member.enc);
+        dec.extend_from_slice(&member.dec);
+    }
+    enc.extend_from_slice(EOS);
+    let mut out = Vec::default();
+    LzfseDecoder::default().decode_bytes(&enc, &mut out)?;
+    assert!(out == dec);
+    out.clear();
+    lzfse_rust::decode_bounded(&enc, &mut out, dec.len() as u64)?;
+    assert!(out == dec);
+    out.clear();
+    let mut decoder = LzfseRingDecoder::default();
+    decoder.decode(&mut enc.as_slice(), &mut out)?;
+    assert!(out == dec);
+    out.clear();
+    read_chunked(decoder.reader(enc.as_slice()), &mut out, 0x0123)?;
+    assert!(out == dec);
+    Ok(())
+}
+
+fn read_chunked<R: Read>(mut rdr: R, dst: &mut Vec<u8>, n: usize) -> io::Result<()> {
+    let mut buf = vec![0u8; n];
+    loop {
+        let n = rdr.read(&mut buf)?;
+        if n == 0 {
+            break Ok(());
+        }
+        dst.extend_from_slice(&buf[..n]);
+    }
+}
+
+#[test]
+fn pairs() -> lzfse_rust::Result<()> {
+    let members = members()?;
+    for u in members.iter() {
+        for v in members.iter() {
+            check(&[u, v])?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn rotations() -> lzfse_rust::Result<()> {
+    let members = members()?;
+    let mut order = members.iter().collect::<Vec<_>>();
+    for _ in 0..order.len() {
+        check(&order)?;
+        order.reverse();
+        check(&order)?;
+        order.reverse();
+        order.rotate_left(1);
+    }
+    Ok(())
+}
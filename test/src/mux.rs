@@ -0,0 +1,139 @@
+use lzfse_rust::{Error, MuxDecoder, MuxEncoder};
+use test_kit::{Rng, Seq};
+
+use std::cell::RefCell;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::rc::Rc;
+
+// Three channels, ids 0, 7 and 14, written in interleaved chunks. Returns the mux and the
+// expected channel contents.
+fn mux(segment_len: usize) -> io::Result<(Vec<u8>, [Vec<u8>; 3])> {
+    let mut rng = Rng::new(1);
+    let mut seq = Seq::masked(Rng::default(), 0x0000_0F0F);
+    let mut channels = [Vec::default(), Vec::default(), Vec::default()];
+    let mut encoder = MuxEncoder::with_segment_len(Vec::default(), segment_len);
+    for _ in 0..0x0400 {
+        let i = (rng.gen() >> 16) as usize % 3;
+        let n = (rng.gen() >> 16) as usize % 0x0800;
+        let bytes = Iterator::take(&mut seq, n).collect::<Vec<_>>();
+        encoder.write(i as u32 * 7, &bytes)?;
+        channels[i].extend_from_slice(&bytes);
+    }
+    Ok((encoder.finalize()?, channels))
+}
+
+// Records the byte ranges read.
+struct RecordReader {
+    inner: Cursor<Vec<u8>>,
+    reads: Rc<RefCell<Vec<Range<u64>>>>,
+}
+
+impl Read for RecordReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let pos = self.inner.position();
+        let n = self.inner.read(buf)?;
+        self.reads.borrow_mut().push(pos..pos + n as u64);
+        Ok(n)
+    }
+}
+
+impl Seek for RecordReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn interleaved() -> io::Result<()> {
+    for &segment_len in &[1, 0x0100, 0x1000, 0x0004_0000] {
+        let (mux, channels) = mux(segment_len)?;
+        let mut decoder = MuxDecoder::new(Cursor::new(mux))?;
+        assert_eq!(decoder.channels(), vec![0, 7, 14]);
+        // Arbitrary order, repeated.
+        for &i in &[2, 0, 1, 0] {
+            let mut dec = Vec::default();
+            let n = decoder.extract(i as u32 * 7, &mut dec)?;
+            assert_eq!(n, channels[i].len() as u64);
+            assert!(dec == channels[i]);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn segments() -> io::Result<()> {
+    let (mux, channels) = mux(0x1000)?;
+    let decoder = MuxDecoder::new(Cursor::new(mux.as_slice()))?;
+    for (i, channel) in channels.iter().enumerate() {
+        let mut dec = Vec::default();
+        for segment in decoder.segments().iter().filter(|s| s.channel == i as u32 * 7) {
+            assert!(segment.n_raw_bytes <= 0x1000);
+            // Independent LZFSE streams.
+            let start = segment.offset as usize;
+            let end = start + segment.n_payload_bytes as usize;
+            lzfse_rust::decode_bytes(&mux[start..end], &mut dec)?;
+        }
+        assert!(dec == *channel);
+    }
+    Ok(())
+}
+
+#[test]
+fn extract_reads_channel_only() -> io::Result<()> {
+    let reads = Rc::new(RefCell::new(Vec::default()));
+    let inner = RecordReader { inner: Cursor::new(mux(0x1000)?.0), reads: Rc::clone(&reads) };
+    let mut decoder = MuxDecoder::new(inner)?;
+    let segments =
+        decoder.segments().iter().filter(|s| s.channel == 7).copied().collect::<Vec<_>>();
+    reads.borrow_mut().clear();
+    decoder.extract(7, &mut io::sink())?;
+    let reads = reads.borrow();
+    assert!(!reads.is_empty());
+    assert!(reads.iter().all(|range| segments
+        .iter()
+        .any(|s| range.start >= s.offset - 12 && range.end <= s.offset + s.n_payload_bytes)));
+    Ok(())
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    let mux = MuxEncoder::new(Vec::default()).finalize()?;
+    assert_eq!(mux.len(), 16);
+    let mut decoder = MuxDecoder::new(Cursor::new(mux))?;
+    assert!(decoder.segments().is_empty());
+    assert!(decoder.channels().is_empty());
+    assert!(matches!(decoder.extract(0, &mut Vec::default()), Err(Error::MemberNotFound)));
+    Ok(())
+}
+
+#[test]
+fn channel_not_found() -> io::Result<()> {
+    let mut decoder = MuxDecoder::new(Cursor::new(mux(0x1000)?.0))?;
+    assert!(matches!(decoder.extract(1, &mut Vec::default()), Err(Error::MemberNotFound)));
+    Ok(())
+}
+
+#[test]
+fn bad_footer() -> io::Result<()> {
+    let (mux, _) = mux(0x1000)?;
+    for n in 1..0x40 {
+        assert!(MuxDecoder::new(Cursor::new(&mux[..mux.len() - n])).is_err());
+    }
+    let mut bad = mux;
+    let n = bad.len();
+    bad[n - 1] ^= 1;
+    assert!(matches!(MuxDecoder::new(Cursor::new(bad)), Err(Error::BadArchive)));
+    Ok(())
+}
+
+#[test]
+fn bad_segment_tag() -> io::Result<()> {
+    let (mut mux, _) = mux(0x1000)?;
+    let segment = MuxDecoder::new(Cursor::new(mux.as_slice()))?.segments()[0];
+    mux[segment.offset as usize - 12] ^= 1;
+    let mut decoder = MuxDecoder::new(Cursor::new(mux))?;
+    let mut dec = Vec::default();
+    assert!(matches!(decoder.extract(segment.channel, &mut dec), Err(Error::BadArchive)));
+    Ok(())
+}
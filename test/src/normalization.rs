@@ -0,0 +1,46 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, LzfseRingEncoder, NormalizationStrategy};
+
+use std::io::{self, Write};
+
+const CORPORA: [&[u8]; 4] = [
+    include_bytes!("../../data/snappy/alice29.txt.lzfse"),
+    include_bytes!("../../data/snappy/asyoulik.txt.lzfse"),
+    include_bytes!("../../data/snappy/html.lzfse"),
+    include_bytes!("../../data/snappy/urls.10K.lzfse"),
+];
+
+fn decode(enc: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    Ok(dec)
+}
+
+#[test]
+fn accurate() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    assert_eq!(encoder.normalization_strategy(), NormalizationStrategy::Fast);
+    encoder.set_normalization_strategy(NormalizationStrategy::Accurate);
+    assert_eq!(encoder.normalization_strategy(), NormalizationStrategy::Accurate);
+    for &data in CORPORA.iter() {
+        let dec = decode(data)?;
+        let mut enc = Vec::default();
+        encoder.encode_bytes(&dec, &mut enc)?;
+        assert!(decode(&enc)? == dec);
+    }
+    Ok(())
+}
+
+#[test]
+fn accurate_ring() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::default();
+    encoder.set_normalization_strategy(NormalizationStrategy::Accurate);
+    for &data in CORPORA.iter() {
+        let dec = decode(data)?;
+        let mut enc = Vec::default();
+        let mut writer = encoder.writer(&mut enc);
+        writer.write_all(&dec)?;
+        writer.finalize()?;
+        assert!(decode(&enc)? == dec);
+    }
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, LzfseRingDecoder, OutputSink};
+use test_kit::Seq;
+
+use std::io;
+
+// Transposes fixed width row-major records into column-major order.
+struct Columns {
+    columns: Vec<Vec<u8>>,
+    index: usize,
+}
+
+impl Columns {
+    fn new(width: usize) -> Self {
+        Self { columns: vec![Vec::default(); width], index: 0 }
+    }
+}
+
+impl OutputSink for Columns {
+    fn write_decoded(&mut self, bytes: &[u8]) -> io::Result<()> {
+        for &b in bytes {
+            self.columns[self.index].push(b);
+            self.index = (self.index + 1) % self.columns.len();
+        }
+        Ok(())
+    }
+}
+
+// Fails after `limit` bytes.
+struct Limit(usize);
+
+impl OutputSink for Limit {
+    fn write_decoded(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if bytes.len() > self.0 {
+            return Err(io::ErrorKind::Other.into());
+        }
+        self.0 -= bytes.len();
+        Ok(())
+    }
+}
+
+fn encode(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(data, &mut enc)?;
+    Ok(enc)
+}
+
+#[test]
+fn columns() -> lzfse_rust::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    let mut decoder = LzfseRingDecoder::default();
+    for &width in &[1, 3, 16] {
+        let mut sink = Columns::new(width);
+        let (u, v) = decoder.decode(&mut enc.as_ref(), &mut sink)?;
+        assert_eq!(u, enc.len() as u64);
+        assert_eq!(v, dec.len() as u64);
+        for (i, column) in sink.columns.iter().enumerate() {
+            assert!(column.iter().copied().eq(dec.iter().skip(i).step_by(width).copied()));
+        }
+    }
+    Ok(())
+}
+
+// Vec and Write implementations remain sinks.
+#[test]
+fn write() -> lzfse_rust::Result<()> {
+    let data = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    let enc = encode(&data)?;
+    let mut decoder = LzfseRingDecoder::default();
+    let mut vec = Vec::default();
+    decoder.decode(&mut enc.as_slice(), &mut vec)?;
+    assert!(vec == data);
+    let mut cursor = io::Cursor::new(Vec::default());
+    decoder.decode(&mut enc.as_slice(), &mut cursor)?;
+    assert!(cursor.into_inner() == data);
+    Ok(())
+}
+
+#[test]
+fn sink_error() -> io::Result<()> {
+    let data = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    let enc = encode(&data)?;
+    let mut decoder = LzfseRingDecoder::default();
+    match decoder.decode(&mut enc.as_slice(), &mut Limit(0x1000)) {
+        Err(lzfse_rust::Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::Other),
+        _ => panic!(),
+    }
+    decoder.decode(&mut enc.as_slice(), &mut Limit(data.len()))?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, LzfseRingDecoder, TrailerKind};
+use test_kit::Seq;
+
+use std::io;
+
+fn check(src: &[u8], multiple: u32) -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_pad_output_to(Some(multiple));
+    let mut enc = b"prefix".to_vec();
+    let n = encoder.encode_bytes(src, &mut enc)?;
+    assert_eq!(n % multiple as u64, 0);
+    assert_eq!(n, enc.len() as u64 - 6);
+    let enc = &enc[6..];
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    assert!(dec == src);
+    dec.clear();
+    LzfseRingDecoder::default().decode(&mut &*enc, &mut dec)?;
+    assert!(dec == src);
+    // Padding is a zero filled trailer, if any.
+    let (info, bare) = lzfse_rust::strip_frame(enc)?;
+    let mut unpadded = Vec::default();
+    lzfse_rust::encode_bytes(src, &mut unpadded)?;
+    assert!(bare == unpadded);
+    match info.trailers.as_slice() {
+        [(kind, trailer)] => {
+            assert_eq!(*kind, TrailerKind::Opaque);
+            assert!(trailer.iter().all(|&u| u == 0));
+        }
+        [] => assert_eq!(unpadded.len() % multiple as usize, 0),
+        _ => panic!(),
+    }
+    Ok(())
+}
+
+#[test]
+fn multiples() -> io::Result<()> {
+    for len in [0, 1, 4, 5, 0x0100, 0x1000, 0x0001_0000] {
+        let src = Seq::default().take(len).collect::<Vec<_>>();
+        for multiple in [1, 2, 3, 8, 16, 17, 512, 4096] {
+            check(&src, multiple)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn default() {
+    assert_eq!(LzfseEncoder::default().pad_output_to(), None);
+}
+
+#[test]
+#[should_panic]
+fn zero() {
+    LzfseEncoder::default().set_pad_output_to(Some(0));
+}
@@ -0,0 +1,89 @@
+#![cfg(unix)]
+
+use lzfse_rust::{LzfseRingDecoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+
+// Large file, streamed from a pipe into `cat` and decoded from `cat` via a second pipe.
+#[test]
+fn cat() -> io::Result<()> {
+    let src: Vec<u8> = Iterator::take(Seq::default(), 0x0100_0000).collect();
+    let mut child = Command::new("cat").stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+    let mut stdout = child.stdout.take().unwrap();
+    let handle = thread::spawn(move || {
+        let mut dec = Vec::default();
+        LzfseRingDecoder::default().decode(&mut stdout, &mut dec).map(|(u, _)| (u, dec))
+    });
+    let mut stdin = child.stdin.take().unwrap();
+    let (u, v) = lzfse_rust::compress_reader_to_writer(&mut src.as_slice(), &mut stdin)?;
+    drop(stdin);
+    let (n, dec) = handle.join().unwrap()?;
+    child.wait()?;
+    assert_eq!(u, src.len() as u64);
+    assert_eq!(n, v);
+    assert!(dec == src);
+    Ok(())
+}
+
+#[test]
+fn broken_pipe() -> io::Result<()> {
+    let src = Rng::default().gen_vec(0x0100_0000).unwrap();
+    let mut child = Command::new("head")
+        .arg("-c")
+        .arg("16")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()?;
+    let mut stdin = child.stdin.take().unwrap();
+    let (u, v) = lzfse_rust::compress_reader_to_writer(&mut src.as_slice(), &mut stdin)?;
+    drop(stdin);
+    child.wait()?;
+    assert!(u < src.len() as u64);
+    assert!(v < src.len() as u64);
+    Ok(())
+}
+
+// Large file, streamed from a pipe via `cat` into `head` via a second pipe, with `head` closing
+// its read end mid-stream. The output is truncated, but otherwise identical.
+#[test]
+fn broken_pipe_mid_stream() -> io::Result<()> {
+    const LIMIT: usize = 0x0001_0000;
+    let src = Rng::default().gen_vec(0x0100_0000).unwrap();
+    let mut enc = Vec::default();
+    LzfseRingEncoder::default().encode(&mut src.as_slice(), &mut enc)?;
+    let mut cat = Command::new("cat").stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+    let mut cat_stdin = cat.stdin.take().unwrap();
+    let data = src.clone();
+    let feed = thread::spawn(move || {
+        // Fails once `cat` exits, which is expected.
+        let _ = cat_stdin.write_all(&data);
+    });
+    let mut head = Command::new("head")
+        .arg("-c")
+        .arg(LIMIT.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let mut head_stdout = head.stdout.take().unwrap();
+    let drain = thread::spawn(move || {
+        let mut vec = Vec::default();
+        head_stdout.read_to_end(&mut vec).map(|_| vec)
+    });
+    let mut cat_stdout = cat.stdout.take().unwrap();
+    let mut head_stdin = head.stdin.take().unwrap();
+    let (u, v) = lzfse_rust::compress_reader_to_writer(&mut cat_stdout, &mut head_stdin)?;
+    drop(head_stdin);
+    drop(cat_stdout);
+    let out = drain.join().unwrap()?;
+    feed.join().unwrap();
+    head.wait()?;
+    cat.wait()?;
+    assert!(u < src.len() as u64);
+    assert!(v >= LIMIT as u64);
+    assert!(v < enc.len() as u64);
+    assert!(out == enc[..LIMIT]);
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use lzfse_rust::LzfseDecoder;
+use test_kit::Seq;
+
+use std::io;
+
+// Multiple FSE block stream, returning the stream and block offsets.
+fn blocks() -> io::Result<(Vec<u8>, Vec<usize>)> {
+    let src = Seq::default().take(0x0040_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&src, &mut enc)?;
+    let costs = lzfse_rust::explain(&enc)?;
+    let mut offsets = Vec::default();
+    let mut offset = 0;
+    for cost in costs {
+        offsets.push(offset);
+        offset += cost.n_payload_bytes as usize;
+    }
+    assert_eq!(offset + 4, enc.len());
+    assert!(offsets.len() >= 3);
+    Ok((enc, offsets))
+}
+
+#[test]
+fn valid() -> io::Result<()> {
+    let mut decoder = LzfseDecoder::default();
+    for len in [0, 1, 4, 0x0100, 0x1000, 0x0010_0000] {
+        let src = Seq::default().take(len).collect::<Vec<_>>();
+        let mut enc = Vec::default();
+        lzfse_rust::encode_bytes(&src, &mut enc)?;
+        assert_eq!(decoder.quick_verify(&enc)?, len as u64);
+    }
+    let (enc, _) = blocks()?;
+    assert_eq!(lzfse_rust::quick_verify(&enc)?, 0x0040_0000);
+    Ok(())
+}
+
+#[test]
+fn truncated() -> io::Result<()> {
+    let (enc, _) = blocks()?;
+    let mut decoder = LzfseDecoder::default();
+    for n in [0, 1, 4, 0x0100, enc.len() / 2, enc.len() - 5, enc.len() - 4, enc.len() - 1] {
+        assert!(decoder.quick_verify(&enc[..n]).is_err(), "{}", n);
+    }
+    Ok(())
+}
+
+#[test]
+fn corrupt_first() -> io::Result<()> {
+    let (mut enc, offsets) = blocks()?;
+    let index = offsets[1] - 0x10;
+    enc[index] = !enc[index];
+    assert!(lzfse_rust::quick_verify(&enc).is_err());
+    Ok(())
+}
+
+#[test]
+fn corrupt_last() -> io::Result<()> {
+    let (mut enc, _) = blocks()?;
+    let index = enc.len() - 4 - 0x10;
+    enc[index] = !enc[index];
+    assert!(lzfse_rust::quick_verify(&enc).is_err());
+    Ok(())
+}
+
+// Intermediate block payloads are not verified.
+#[test]
+fn corrupt_intermediate() -> io::Result<()> {
+    let (mut enc, offsets) = blocks()?;
+    let index = offsets[2] - 0x10;
+    enc[index] = !enc[index];
+    assert!(lzfse_rust::quick_verify(&enc).is_ok());
+    assert!(lzfse_rust::decode_bytes(&enc, &mut Vec::default()).is_err());
+    Ok(())
+}
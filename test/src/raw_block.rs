@@ -0,0 +1,80 @@
+use lzfse_rust::{Error, LzfseRingDecoder, RAW_HEADER_SIZE};
+use test_kit::Rng;
+
+use std::io;
+
+#[test]
+fn round_trip() -> lzfse_rust::Result<()> {
+    for n in [0, 1, 4, 0x1000, 0x0010_0000] {
+        let src = Rng::new(n as u32).gen_vec(n).unwrap();
+        let mut enc = Vec::default();
+        let u = lzfse_rust::encode_raw_block(&src, &mut enc)?;
+        assert_eq!(u, enc.len() as u64);
+        assert_eq!(u, RAW_HEADER_SIZE as u64 + n as u64);
+        assert!(enc[RAW_HEADER_SIZE as usize..] == src);
+        // Trailing bytes are ignored.
+        enc.extend_from_slice(b"bvx$");
+        let mut dec = Vec::default();
+        assert_eq!(lzfse_rust::decode_raw_block(&enc, &mut dec)?, (u, n as u64));
+        assert!(dec == src);
+    }
+    Ok(())
+}
+
+#[test]
+fn stream() -> lzfse_rust::Result<()> {
+    let blocks = [0, 0x0100, 0x1000, 0x8000]
+        .iter()
+        .map(|&n| Rng::new(1).gen_vec(n).unwrap())
+        .collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    for block in &blocks {
+        lzfse_rust::encode_raw_block(block, &mut enc)?;
+    }
+    enc.extend_from_slice(b"bvx$");
+    let src = blocks.concat();
+    let mut dec = Vec::default();
+    lzfse_rust::decode_bytes(&enc, &mut dec)?;
+    assert!(dec == src);
+    let mut dec = Vec::default();
+    LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut dec)?;
+    assert!(dec == src);
+    // Block by block.
+    let mut rem = enc.as_slice();
+    let mut dec = Vec::default();
+    for block in &blocks {
+        let (u, v) = lzfse_rust::decode_raw_block(rem, &mut dec)?;
+        assert_eq!(v, block.len() as u64);
+        rem = &rem[u as usize..];
+    }
+    assert_eq!(rem, b"bvx$");
+    assert!(dec == src);
+    Ok(())
+}
+
+#[test]
+fn not_raw() -> io::Result<()> {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&[0u8; 0x1000], &mut enc)?;
+    let mut dec = Vec::default();
+    match lzfse_rust::decode_raw_block(&enc, &mut dec) {
+        Err(Error::BadBlock(_)) => {}
+        res => panic!("{:?}", res),
+    }
+    Ok(())
+}
+
+#[test]
+fn truncated() -> io::Result<()> {
+    let src = Rng::new(2).gen_vec(0x0100).unwrap();
+    let mut enc = Vec::default();
+    lzfse_rust::encode_raw_block(&src, &mut enc)?;
+    for n in [0, 4, RAW_HEADER_SIZE as usize, enc.len() - 1] {
+        let mut dec = Vec::default();
+        match lzfse_rust::decode_raw_block(&enc[..n], &mut dec) {
+            Err(Error::PayloadUnderflow) => {}
+            res => panic!("{:?}", res),
+        }
+    }
+    Ok(())
+}
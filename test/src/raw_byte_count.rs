@@ -0,0 +1,48 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, LzfseRingDecoder};
+use test_kit::Seq;
+
+use std::convert::TryInto;
+
+// Block header `n_raw_bytes` field offset, common to all block types.
+const N_RAW_BYTES_OFFSET: usize = 4;
+
+// Patch the first block header `n_raw_bytes` by `delta`.
+fn patch(enc: &[u8], delta: i64) -> Vec<u8> {
+    let mut enc = enc.to_vec();
+    let bytes = &mut enc[N_RAW_BYTES_OFFSET..N_RAW_BYTES_OFFSET + 4];
+    let n_raw_bytes = u32::from_le_bytes(bytes.as_ref().try_into().unwrap());
+    let n_raw_bytes = (n_raw_bytes as i64 + delta) as u32;
+    bytes.copy_from_slice(&n_raw_bytes.to_le_bytes());
+    enc
+}
+
+fn check(dec: &[u8], magic: &[u8; 4]) -> lzfse_rust::Result<()> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(dec, &mut enc)?;
+    assert_eq!(&enc[..4], magic);
+    for &delta in &[-0x0100, -1, 1, 0x0100] {
+        let enc = patch(&enc, delta);
+        let mut out = Vec::default();
+        assert!(LzfseDecoder::default().decode_bytes(&enc, &mut out).is_err());
+        out.clear();
+        assert!(LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut out).is_err());
+        out.clear();
+        assert!(lzfse_rust::decode_bounded(&enc, &mut out, u64::MAX).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn raw() -> lzfse_rust::Result<()> {
+    check(&Seq::default().take(0x0010).collect::<Vec<_>>(), b"bvx-")
+}
+
+#[test]
+fn vn() -> lzfse_rust::Result<()> {
+    check(&b"a quick brown fox jumps over the lazy dog ".repeat(0x10), b"bvxn")
+}
+
+#[test]
+fn fse() -> lzfse_rust::Result<()> {
+    check(&Seq::default().take(0x8000).collect::<Vec<_>>(), b"bvx2")
+}
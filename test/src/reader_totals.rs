@@ -0,0 +1,124 @@
+use lzfse_rust::{Error, LzfseRingDecoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn encode(src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(src, &mut enc)?;
+    Ok(enc)
+}
+
+fn data() -> Vec<Vec<u8>> {
+    vec![
+        Vec::default(),
+        b"test".to_vec(),
+        Seq::default().take(0x0010_0000).collect(),
+        Rng::new(1).gen_vec(0x0010_0000).unwrap(),
+        vec![0; 0x0040_0000],
+    ]
+}
+
+#[test]
+fn reader_totals() -> lzfse_rust::Result<()> {
+    let mut decoder = LzfseRingDecoder::default();
+    for dec in data() {
+        let enc = encode(&dec)?;
+        let mut reader = decoder.reader(enc.as_slice());
+        assert_eq!(reader.raw_bytes_read(), 0);
+        assert_eq!(reader.payload_bytes_consumed(), 0);
+        let mut out = Vec::default();
+        let mut buf = [0u8; 0x1234];
+        loop {
+            let n = io::Read::read(&mut reader, &mut buf)?;
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+            assert_eq!(reader.raw_bytes_read(), out.len() as u64);
+            assert!(reader.payload_bytes_consumed() <= enc.len() as u64);
+        }
+        assert!(out == dec);
+        assert_eq!(reader.finish()?, (enc.len() as u64, dec.len() as u64));
+    }
+    Ok(())
+}
+
+#[test]
+fn reader_bytes_totals() -> lzfse_rust::Result<()> {
+    let mut decoder = LzfseRingDecoder::default();
+    for dec in data() {
+        let enc = encode(&dec)?;
+        let mut reader = decoder.reader_bytes(&enc);
+        let mut out = Vec::default();
+        io::Read::read_to_end(&mut reader, &mut out)?;
+        assert!(out == dec);
+        assert_eq!(reader.raw_bytes_read(), dec.len() as u64);
+        assert_eq!(reader.payload_bytes_consumed(), enc.len() as u64);
+        assert_eq!(reader.finish()?, (enc.len() as u64, dec.len() as u64));
+    }
+    Ok(())
+}
+
+#[test]
+fn finish_matches_decode() -> lzfse_rust::Result<()> {
+    let mut decoder = LzfseRingDecoder::default();
+    for dec in data() {
+        let enc = encode(&dec)?;
+        let totals = decoder.decode(&mut enc.as_slice(), &mut Vec::default())?;
+        let mut reader = decoder.reader(enc.as_slice());
+        io::Read::read_to_end(&mut reader, &mut Vec::default())?;
+        assert_eq!(reader.finish()?, totals);
+    }
+    Ok(())
+}
+
+#[test]
+fn finish_exact_read() -> lzfse_rust::Result<()> {
+    // All decoded data read, but not yet the end of stream block.
+    let dec = Seq::default().take(0x8000).collect::<Vec<_>>();
+    let enc = encode(&dec)?;
+    let mut decoder = LzfseRingDecoder::default();
+    let mut reader = decoder.reader(enc.as_slice());
+    let mut out = vec![0; dec.len()];
+    io::Read::read_exact(&mut reader, &mut out)?;
+    assert!(out == dec);
+    assert_eq!(reader.finish()?, (enc.len() as u64, dec.len() as u64));
+    Ok(())
+}
+
+#[test]
+fn finish_unread() -> io::Result<()> {
+    let dec = Seq::default().take(0x8000).collect::<Vec<_>>();
+    let enc = encode(&dec)?;
+    let mut decoder = LzfseRingDecoder::default();
+    for n in [0, 1, 0x4000, dec.len() - 1] {
+        let mut reader = decoder.reader(enc.as_slice());
+        let mut out = vec![0; n];
+        io::Read::read_exact(&mut reader, &mut out)?;
+        match reader.finish() {
+            Err(Error::PayloadOverflow) => {}
+            res => panic!("{:?}", res),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn finish_truncated() -> io::Result<()> {
+    // Decoded data complete, end of stream block truncated.
+    let dec = Seq::default().take(0x8000).collect::<Vec<_>>();
+    let enc = encode(&dec)?;
+    let mut decoder = LzfseRingDecoder::default();
+    for n in 1..=4 {
+        let mut reader = decoder.reader(&enc[..enc.len() - n]);
+        let mut out = vec![0; dec.len()];
+        io::Read::read_exact(&mut reader, &mut out)?;
+        assert!(out == dec);
+        match reader.finish() {
+            Err(Error::PayloadUnderflow) => {}
+            res => panic!("{:?}", res),
+        }
+    }
+    Ok(())
+}
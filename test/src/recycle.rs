@@ -0,0 +1,41 @@
+use lzfse_rust::{LzfseDecoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn encode(encoder: &mut LzfseRingEncoder, dec: &[u8]) -> io::Result<()> {
+    let mut enc = Vec::default();
+    encoder.encode_bytes(dec, &mut enc)?;
+    let mut buf = Vec::default();
+    LzfseDecoder::default().decode_bytes(&enc, &mut buf)?;
+    assert!(buf == dec);
+    Ok(())
+}
+
+#[test]
+fn recycle() -> io::Result<()> {
+    let input = vec![0u8; LzfseRingEncoder::INPUT_BUFFER_LEN];
+    let output = vec![0u8; LzfseRingEncoder::OUTPUT_BUFFER_LEN];
+    let ptrs = (input.as_ptr(), output.as_ptr());
+    let mut encoder = LzfseRingEncoder::from_buffers(input, output)?;
+    encode(&mut encoder, &Rng::default().gen_vec(0x0010_0000).unwrap())?;
+    let (input, output) = encoder.into_buffers();
+    assert_eq!((input.as_ptr(), output.as_ptr()), ptrs);
+    let mut encoder = LzfseRingEncoder::from_buffers(input, output)?;
+    encode(&mut encoder, &Iterator::take(Seq::default(), 0x0010_0000).collect::<Vec<_>>())?;
+    let (input, output) = encoder.into_buffers();
+    assert_eq!((input.as_ptr(), output.as_ptr()), ptrs);
+    Ok(())
+}
+
+#[test]
+fn bad_len() {
+    let input_len = LzfseRingEncoder::INPUT_BUFFER_LEN;
+    let output_len = LzfseRingEncoder::OUTPUT_BUFFER_LEN;
+    for &(u, v) in &[(input_len - 1, output_len), (input_len, output_len + 1), (0, 0)] {
+        match LzfseRingEncoder::from_buffers(vec![0; u], vec![0; v]) {
+            Err(err) if err.kind() == io::ErrorKind::InvalidInput => {}
+            _ => panic!(),
+        }
+    }
+}
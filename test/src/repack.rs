@@ -0,0 +1,90 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Repacks `enc`, which must decode to `dec`, and returns the repacked length.
+fn check(enc: &[u8], dec: &[u8]) -> lzfse_rust::Result<usize> {
+    let mut rep = vec![0xFF; 4];
+    let n = lzfse_rust::repack(enc, &mut rep)?;
+    assert_eq!(n + 4, rep.len() as u64);
+    assert!(rep.len() - 4 <= enc.len());
+    let mut buf = Vec::default();
+    LzfseDecoder::default().decode_bytes(&rep[4..], &mut buf)?;
+    assert!(buf == dec);
+    Ok(n as usize)
+}
+
+fn encodings(dec: &[u8]) -> io::Result<Vec<Vec<u8>>> {
+    let mut bytes = Vec::default();
+    LzfseEncoder::default().encode_bytes(dec, &mut bytes)?;
+    let mut ring = Vec::default();
+    LzfseRingEncoder::default().encode(&mut &*dec, &mut ring)?;
+    Ok(vec![bytes, ring])
+}
+
+#[test]
+fn empty() -> lzfse_rust::Result<()> {
+    for enc in encodings(&[])? {
+        check(&enc, &[])?;
+    }
+    Ok(())
+}
+
+#[test]
+fn text() -> lzfse_rust::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut data = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut data)?;
+    check(enc, &data)?;
+    for &n in &[0x10, 0x1000, 0x8000, data.len()] {
+        for enc in encodings(&data[..n])? {
+            check(&enc, &data[..n])?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn seq() -> lzfse_rust::Result<()> {
+    let data = Seq::default().take(0x0002_0000).collect::<Vec<_>>();
+    for enc in encodings(&data)? {
+        check(&enc, &data)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn rng() -> lzfse_rust::Result<()> {
+    for &n in &[1, 0x100, 0x1000, 0x0001_0000] {
+        let data = Rng::default().gen_vec(n).unwrap();
+        for enc in encodings(&data)? {
+            check(&enc, &data)?;
+        }
+    }
+    Ok(())
+}
+
+// Repacking an already repacked stream is stable.
+#[test]
+fn idempotent() -> lzfse_rust::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut data = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut data)?;
+    let mut rep = Vec::default();
+    lzfse_rust::repack(enc, &mut rep)?;
+    let n = check(&rep, &data)?;
+    assert_eq!(n, rep.len());
+    Ok(())
+}
+
+#[test]
+fn bad_stream() -> io::Result<()> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(&[0x41; 0x1000], &mut enc)?;
+    enc.truncate(enc.len() - 1);
+    let mut rep = vec![0xFF; 4];
+    assert!(lzfse_rust::repack(&enc, &mut rep).is_err());
+    assert_eq!(rep, [0xFF; 4]);
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use lzfse_rust::{LzfseDecoder, LzfseRingEncoder, ReplayEncoder, ReplayPoint};
+use test_kit::{Rng, Seq};
+
+use std::io::{self, Cursor, Write};
+
+// Accepts up to `limit` bytes then fails.
+struct FailWriter {
+    vec: Vec<u8>,
+    limit: usize,
+}
+
+impl Write for FailWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = (self.limit - self.vec.len()).min(buf.len());
+        if n == 0 && !buf.is_empty() {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+        self.vec.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn encode(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseRingEncoder::default().encode(&mut &*data, &mut enc)?;
+    Ok(enc)
+}
+
+fn check(data: &[u8]) -> io::Result<()> {
+    let enc = encode(data)?;
+    let mut encoder = ReplayEncoder::new(Cursor::new(data))?;
+    let mut full = Vec::default();
+    assert_eq!(encoder.encode(&mut full)?, enc.len() as u64);
+    assert!(full == enc);
+    assert_eq!(encoder.replay_point(), ReplayPoint::new(0, enc.len() as u64));
+    for &limit in &[0, 1, enc.len() / 3, enc.len() / 2, enc.len().saturating_sub(1)] {
+        if limit >= enc.len() {
+            continue;
+        }
+        // Interrupted.
+        let mut encoder = ReplayEncoder::new(Cursor::new(data))?;
+        let mut wtr = FailWriter { vec: Vec::default(), limit };
+        assert!(encoder.encode(&mut wtr).is_err());
+        let point = encoder.replay_point();
+        assert_eq!(point.n_payload_bytes(), limit as u64);
+        // Resumed, from a recreated point.
+        let point = ReplayPoint::new(point.src_pos(), point.n_payload_bytes());
+        let mut encoder = ReplayEncoder::resume(Cursor::new(data), point);
+        let mut rem = Vec::default();
+        assert_eq!(encoder.encode(&mut rem)?, (enc.len() - limit) as u64);
+        let mut halves = wtr.vec;
+        halves.extend_from_slice(&rem);
+        assert!(halves == enc);
+        assert_eq!(encoder.replay_point().n_payload_bytes(), enc.len() as u64);
+    }
+    Ok(())
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    check(&[])
+}
+
+#[test]
+fn text() -> io::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut data = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut data)?;
+    check(&data)
+}
+
+#[test]
+fn seq() -> io::Result<()> {
+    check(&Seq::default().take(0x0010_0000).collect::<Vec<_>>())
+}
+
+#[test]
+fn rng() -> io::Result<()> {
+    check(&Rng::default().gen_vec(0x0008_0000).unwrap())
+}
+
+// The source need not start at zero.
+#[test]
+fn src_pos() -> io::Result<()> {
+    let data = b"a quick brown fox jumps over the lazy dog ".repeat(0x0100);
+    let enc = encode(&data)?;
+    let mut src = vec![0xFF; 7];
+    src.extend_from_slice(&data);
+    let mut cursor = Cursor::new(src.as_slice());
+    cursor.set_position(7);
+    let mut encoder = ReplayEncoder::new(cursor)?;
+    let mut wtr = FailWriter { vec: Vec::default(), limit: enc.len() / 2 };
+    assert!(encoder.encode(&mut wtr).is_err());
+    let point = encoder.replay_point();
+    assert_eq!(point.src_pos(), 7);
+    let mut encoder = ReplayEncoder::resume(Cursor::new(src.as_slice()), point);
+    encoder.encode(&mut wtr.vec)?;
+    assert!(wtr.vec == enc);
+    Ok(())
+}
+
+#[test]
+fn past_end() -> io::Result<()> {
+    let data = Seq::default().take(0x1000).collect::<Vec<_>>();
+    let point = ReplayPoint::new(0, u64::MAX);
+    let mut encoder = ReplayEncoder::resume(Cursor::new(data.as_slice()), point);
+    let mut rem = Vec::default();
+    assert_eq!(encoder.encode(&mut rem)?, 0);
+    assert!(rem.is_empty());
+    Ok(())
+}
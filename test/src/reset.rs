@@ -0,0 +1,60 @@
+use lzfse_rust::{LzfseRingDecoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io::{self, Write};
+
+fn check(
+    encoder: &mut LzfseRingEncoder,
+    decoder: &mut LzfseRingDecoder,
+    src: &[u8],
+) -> io::Result<()> {
+    let mut enc = Vec::default();
+    let (n_raw_bytes, n_payload_bytes) = encoder.encode(&mut &*src, &mut enc)?;
+    assert_eq!(n_raw_bytes, src.len() as u64);
+    assert_eq!(n_payload_bytes, enc.len() as u64);
+    // Identical to a fresh encoder.
+    let mut expected = Vec::default();
+    LzfseRingEncoder::default().encode(&mut &*src, &mut expected)?;
+    assert!(enc == expected);
+    let mut dec = Vec::default();
+    let (u, v) = decoder.decode(&mut enc.as_slice(), &mut dec)?;
+    assert_eq!(u, enc.len() as u64);
+    assert_eq!(v, src.len() as u64);
+    assert!(dec == src);
+    Ok(())
+}
+
+// Back to back independent streams on a single instance.
+#[test]
+fn back_to_back() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::default();
+    let mut decoder = LzfseRingDecoder::default();
+    let u = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    let v = Rng::new(1).gen_vec(0x0008_0000).unwrap();
+    check(&mut encoder, &mut decoder, &u)?;
+    check(&mut encoder, &mut decoder, &v)?;
+    check(&mut encoder, &mut decoder, &u)?;
+    encoder.reset();
+    decoder.reset();
+    check(&mut encoder, &mut decoder, &v)?;
+    Ok(())
+}
+
+// An abandoned writer does not affect subsequent streams.
+#[test]
+fn abandoned_writer() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::default();
+    let mut decoder = LzfseRingDecoder::default();
+    let u = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    let mut writer = encoder.writer(Vec::default());
+    writer.write_all(&u[..0x0008_0000])?;
+    drop(writer);
+    encoder.reset();
+    check(&mut encoder, &mut decoder, &u)?;
+    // Likewise an abandoned decode.
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&u, &mut enc)?;
+    assert!(decoder.decode(&mut &enc[..enc.len() / 2], &mut Vec::default()).is_err());
+    decoder.reset();
+    check(&mut encoder, &mut decoder, &u)
+}
@@ -0,0 +1,72 @@
+use lzfse_rust::{Error, LzfseEncoder, LzfseRingDecoder, RingProfile};
+use test_kit::Rng;
+
+use std::io::{self, Read};
+
+// Random bytes followed by a copy of the leading bytes at exactly `distance`.
+fn distance_data(distance: u32) -> Vec<u8> {
+    let mut data = Rng::default().gen_vec(distance as usize).unwrap();
+    data.extend_from_within(..0x1000);
+    data
+}
+
+fn encode(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(data, &mut enc)?;
+    Ok(enc)
+}
+
+fn check_decode(decoder: &mut LzfseRingDecoder, enc: &[u8], dec: &[u8]) -> io::Result<()> {
+    let mut reader = enc;
+    let mut writer = Vec::default();
+    decoder.decode(&mut reader, &mut writer)?;
+    assert!(writer == dec);
+    writer.clear();
+    decoder.reader_bytes(enc).read_to_end(&mut writer)?;
+    assert!(writer == dec);
+    writer.clear();
+    decoder.reader(enc).read_to_end(&mut writer)?;
+    assert!(writer == dec);
+    Ok(())
+}
+
+fn check_reject(decoder: &mut LzfseRingDecoder, enc: &[u8]) {
+    let mut reader = enc;
+    match decoder.decode(&mut reader, &mut io::sink()) {
+        Err(Error::BadDValue) => {}
+        Err(err) => panic!("{:?}", err),
+        Ok(_) => panic!(),
+    }
+    let mut writer = Vec::default();
+    assert!(decoder.reader_bytes(enc).read_to_end(&mut writer).is_err());
+    assert!(decoder.reader(enc).read_to_end(&mut writer).is_err());
+}
+
+#[test]
+fn max_distance() -> io::Result<()> {
+    let mut profile = RingProfile::for_max_distance(0);
+    while profile != RingProfile::default() {
+        let d = profile.max_match_distance();
+        assert_eq!(RingProfile::for_max_distance(d), profile);
+        let mut decoder = LzfseRingDecoder::with_profile(profile);
+        let dec = distance_data(d);
+        let enc = encode(&dec)?;
+        check_decode(&mut decoder, &enc, &dec)?;
+        let enc = encode(&distance_data(d + 1))?;
+        check_reject(&mut decoder, &enc);
+        let next = RingProfile::for_max_distance(d + 1);
+        assert!(profile.ring_size() < next.ring_size());
+        profile = next;
+    }
+    Ok(())
+}
+
+#[test]
+fn default_profile() -> io::Result<()> {
+    let mut decoder = LzfseRingDecoder::default();
+    assert_eq!(decoder.profile(), RingProfile::default());
+    let d = RingProfile::for_max_distance(0x4000).max_match_distance() + 1;
+    let dec = distance_data(d);
+    let enc = encode(&dec)?;
+    check_decode(&mut decoder, &enc, &dec)
+}
@@ -0,0 +1,101 @@
+use lzfse_rust::{DecodeEvent, LzfseDecoder, LzfseEncoder, LzfseRingDecoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Byte runs, 4 to 64 bytes long, interleaved with short random literal runs.
+fn runs(len: usize) -> Vec<u8> {
+    let mut rng = Rng::default();
+    let mut vec = Vec::with_capacity(len + 0x80);
+    while vec.len() < len {
+        let u = (rng.gen() >> 24) as u8;
+        let n = 4 + (rng.gen() >> 16) as usize % 0x3D;
+        vec.resize(vec.len() + n, u);
+        let n = (rng.gen() >> 16) as usize % 8;
+        vec.extend((0..n).map(|_| (rng.gen() >> 24) as u8));
+    }
+    vec
+}
+
+fn encode(src: &[u8], rle_bias: bool) -> io::Result<Vec<u8>> {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_rle_bias(rle_bias);
+    assert_eq!(encoder.rle_bias(), rle_bias);
+    let mut enc = Vec::default();
+    encoder.encode_bytes(src, &mut enc)?;
+    Ok(enc)
+}
+
+fn ring_encode(src: &[u8], rle_bias: bool) -> io::Result<Vec<u8>> {
+    let mut encoder = LzfseRingEncoder::default();
+    encoder.set_rle_bias(rle_bias);
+    assert_eq!(encoder.rle_bias(), rle_bias);
+    let mut enc = Vec::default();
+    encoder.encode(&mut &*src, &mut enc)?;
+    Ok(enc)
+}
+
+fn check(enc: &[u8], src: &[u8]) -> io::Result<()> {
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    assert!(dec == src);
+    dec.clear();
+    LzfseRingDecoder::default().decode(&mut &*enc, &mut dec)?;
+    assert!(dec == src);
+    Ok(())
+}
+
+// Returns the number of matches and the number of distance 1 matches.
+fn distance_1(enc: &[u8]) -> io::Result<(u64, u64)> {
+    let mut n = (0, 0);
+    LzfseDecoder::default().trace(enc, |event| {
+        if let DecodeEvent::Match { distance, .. } = event {
+            n.0 += 1;
+            n.1 += (distance == 1) as u64;
+        }
+    })?;
+    Ok(n)
+}
+
+#[test]
+fn default() {
+    assert!(!LzfseEncoder::default().rle_bias());
+    assert!(!LzfseRingEncoder::default().rle_bias());
+}
+
+#[test]
+fn round_trip() -> io::Result<()> {
+    for src in [
+        Vec::default(),
+        vec![0; 0x1000],
+        runs(0x0010_0000),
+        Rng::default().gen_vec(0x8000).unwrap(),
+        Iterator::take(Seq::default(), 0x0010_0000).collect(),
+        Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), 0x0010_0000).collect(),
+    ]
+    .iter()
+    {
+        check(&encode(src, true)?, src)?;
+        check(&ring_encode(src, true)?, src)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn dominate() -> io::Result<()> {
+    let src = runs(0x0010_0000);
+    for &(enc, base) in [
+        (&encode(&src, true)?, &encode(&src, false)?),
+        (&ring_encode(&src, true)?, &ring_encode(&src, false)?),
+    ]
+    .iter()
+    {
+        let (n_matches, n_distance_1) = distance_1(enc)?;
+        let (base_n_matches, base_n_distance_1) = distance_1(base)?;
+        assert!(n_distance_1 * 10 >= n_matches * 9);
+        assert!(n_distance_1 * base_n_matches > base_n_distance_1 * n_matches);
+        // Comparable ratio.
+        assert!(enc.len() <= base.len() * 11 / 10);
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// Short write and failing sinks.
+
+use lzfse_rust::{Error, LzfseRingEncoder};
+use test_kit::Seq;
+
+use std::io::{self, Write};
+
+// Accepts at most one byte per call.
+struct Trickle(Vec<u8>);
+
+impl Write for Trickle {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(&u) = buf.first() {
+            self.0.push(u);
+            Ok(1)
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Accepts `limit` bytes then fails.
+struct Broken(usize);
+
+impl Write for Broken {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.0 == 0 {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken"));
+        }
+        let n = buf.len().min(self.0);
+        self.0 -= n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn trickle() -> io::Result<()> {
+    let data = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    LzfseRingEncoder::default().encode_bytes(&data, &mut enc)?;
+    let mut encoder = LzfseRingEncoder::default();
+    let mut writer = encoder.writer(Trickle(Vec::default()));
+    for chunk in data.chunks(0x1234) {
+        assert_eq!(writer.write(chunk)?, chunk.len());
+    }
+    assert!(writer.finalize()?.0 == enc);
+    Ok(())
+}
+
+#[test]
+fn broken() {
+    let data = Seq::default().take(0x0010_0000).collect::<Vec<_>>();
+    let mut encoder = LzfseRingEncoder::default();
+    let mut writer = encoder.writer(Broken(0x0100));
+    let mut src = data.as_slice();
+    let err = loop {
+        match writer.write(src) {
+            Ok(n) => {
+                assert!(n != 0);
+                src = &src[n..];
+            }
+            Err(err) => break err,
+        }
+    };
+    assert_eq!(err.to_string(), "broken");
+    let err = writer.write(src).unwrap_err();
+    match err.into_inner().map(|u| u.downcast::<Error>()) {
+        Some(Ok(err)) => assert!(matches!(*err, Error::BadWriterState)),
+        _ => panic!(),
+    }
+    assert!(writer.finalize().is_err());
+}
@@ -0,0 +1,84 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+// Raw block header and end of stream overhead.
+const STORED_OVERHEAD: usize = 12;
+
+fn encode(encoder: &mut LzfseEncoder, src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    let n = encoder.encode_bytes(src, &mut enc)?;
+    assert_eq!(n, enc.len() as u64);
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+    assert!(dec == src);
+    Ok(enc)
+}
+
+fn skip_encoder() -> LzfseEncoder {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_skip_if_compressed(true);
+    encoder
+}
+
+#[test]
+fn default() {
+    assert!(!LzfseEncoder::default().skip_if_compressed());
+    assert!(skip_encoder().skip_if_compressed());
+}
+
+// Real LZFSE input, of each block type.
+#[test]
+fn lzfse() -> io::Result<()> {
+    let datas = [
+        b"test".to_vec(),
+        Seq::new(Rng::new(0)).take(0x0400).collect::<Vec<_>>(),
+        Seq::masked(Rng::new(0), 0x0000_0F0F).take(0x0400).collect::<Vec<_>>(),
+        Seq::masked(Rng::new(0), 0x0000_0F0F).take(0x0010_0000).collect::<Vec<_>>(),
+    ];
+    for data in datas.iter() {
+        let enc = encode(&mut LzfseEncoder::default(), data)?;
+        let enc_enc = encode(&mut skip_encoder(), &enc)?;
+        assert_eq!(enc_enc.len(), enc.len() + STORED_OVERHEAD);
+        assert_eq!(&enc_enc[8..enc.len() + 8], enc.as_slice());
+    }
+    Ok(())
+}
+
+// Unencoded input that coincidentally begins with magic bytes is stored, a false positive.
+#[test]
+fn collision() -> io::Result<()> {
+    for magic in [b"bvx1", b"bvx2", b"bvxn", b"bvx-", b"bvx$"].iter() {
+        let mut data = magic.to_vec();
+        data.extend_from_slice(&b"test".repeat(0x0400));
+        let enc = encode(&mut skip_encoder(), &data)?;
+        assert_eq!(enc.len(), data.len() + STORED_OVERHEAD);
+        let enc = encode(&mut LzfseEncoder::default(), &data)?;
+        assert!(enc.len() < data.len() / 4);
+    }
+    Ok(())
+}
+
+// Near misses are encoded as usual.
+#[test]
+fn no_collision() -> io::Result<()> {
+    let magics: [&[u8]; 8] = [b"", b"b", b"bv", b"bvx", b"bvx0", b"bvx3", b"Bvx2", b"xbvx2"];
+    for magic in magics.iter() {
+        let mut data = magic.to_vec();
+        data.extend_from_slice(&b"test".repeat(0x0400));
+        let enc = encode(&mut skip_encoder(), &data)?;
+        assert!(enc == encode(&mut LzfseEncoder::default(), &data)?);
+    }
+    Ok(())
+}
+
+#[test]
+fn pad_output() -> io::Result<()> {
+    let enc = encode(&mut LzfseEncoder::default(), &b"test".repeat(0x0100))?;
+    let mut encoder = skip_encoder();
+    encoder.set_pad_output_to(Some(0x0100));
+    let enc_enc = encode(&mut encoder, &enc)?;
+    assert_eq!(enc_enc.len() % 0x0100, 0);
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use lzfse_rust::{LzfseDecoder, LzfseEncoder, LzfseRingDecoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+use std::time::Instant;
+
+// Zero filled with `n_data_bytes` of random data every `period` bytes.
+fn sparse(len: usize, period: usize, n_data_bytes: usize) -> Vec<u8> {
+    let mut vec = vec![0; len];
+    let mut rng = Rng::default();
+    for chunk in vec.chunks_mut(period) {
+        let n = n_data_bytes.min(chunk.len());
+        chunk[..n].iter_mut().for_each(|u| *u = rng.gen() as u8);
+    }
+    vec
+}
+
+fn data() -> Vec<Vec<u8>> {
+    vec![
+        Vec::default(),
+        vec![0; 0x1000],
+        vec![0; 0x0040_0000],
+        sparse(0x0040_0000, 0x0001_0000, 0x0100),
+        sparse(0x0040_0000, 0x0400, 0x0010),
+        sparse(0x0040_0000, 0x0010_0000, 0x1000),
+        Iterator::take(Seq::default(), 0x0010_0000).collect(),
+    ]
+}
+
+fn encode(src: &[u8], sparse_scan: bool) -> io::Result<Vec<u8>> {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_sparse_scan(sparse_scan);
+    assert_eq!(encoder.sparse_scan(), sparse_scan);
+    let mut enc = Vec::default();
+    encoder.encode_bytes(src, &mut enc)?;
+    Ok(enc)
+}
+
+fn ring_encode(src: &[u8], sparse_scan: bool) -> io::Result<Vec<u8>> {
+    let mut encoder = LzfseRingEncoder::default();
+    encoder.set_sparse_scan(sparse_scan);
+    assert_eq!(encoder.sparse_scan(), sparse_scan);
+    let mut enc = Vec::default();
+    encoder.encode(&mut &*src, &mut enc)?;
+    Ok(enc)
+}
+
+fn check(enc: &[u8], src: &[u8]) -> io::Result<()> {
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    assert!(dec == src);
+    dec.clear();
+    LzfseRingDecoder::default().decode(&mut &*enc, &mut dec)?;
+    assert!(dec == src);
+    Ok(())
+}
+
+#[test]
+fn default() {
+    assert!(!LzfseEncoder::default().sparse_scan());
+    assert!(!LzfseRingEncoder::default().sparse_scan());
+}
+
+#[test]
+fn round_trip() -> io::Result<()> {
+    for src in data() {
+        let enc = encode(&src, true)?;
+        check(&enc, &src)?;
+        // Comparable ratio.
+        assert!(enc.len() <= encode(&src, false)?.len() * 11 / 10 + 0x40);
+        let enc = ring_encode(&src, true)?;
+        check(&enc, &src)?;
+        assert!(enc.len() <= ring_encode(&src, false)?.len() * 11 / 10 + 0x40);
+    }
+    Ok(())
+}
+
+#[test]
+fn dense_unchanged() -> io::Result<()> {
+    // Data without long zero runs encodes identically.
+    for src in [
+        Rng::default().gen_vec(0x0010_0000).unwrap(),
+        Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), 0x0010_0000).collect(),
+        b"a quick brown fox jumps over the lazy dog ".repeat(0x1000),
+    ]
+    .iter()
+    {
+        assert_eq!(encode(src, true)?, encode(src, false)?);
+        assert_eq!(ring_encode(src, true)?, ring_encode(src, false)?);
+    }
+    Ok(())
+}
+
+#[test]
+#[ignore = "expensive"]
+fn throughput() -> io::Result<()> {
+    // 1GiB, 4KiB of data every 1MiB.
+    let src = sparse(0x4000_0000, 0x0010_0000, 0x1000);
+    for &sparse_scan in [false, true].iter() {
+        let start = Instant::now();
+        let enc = encode(&src, sparse_scan)?;
+        let secs = start.elapsed().as_secs_f64();
+        println!(
+            "sparse_scan: {:>5}, encoded: {}, {:.0} MiB/s",
+            sparse_scan,
+            enc.len(),
+            src.len() as f64 / secs / 1048576.0
+        );
+        let start = Instant::now();
+        let enc = ring_encode(&src, sparse_scan)?;
+        let secs = start.elapsed().as_secs_f64();
+        println!(
+            "sparse_scan: {:>5}, encoded: {}, {:.0} MiB/s (ring)",
+            sparse_scan,
+            enc.len(),
+            src.len() as f64 / secs / 1048576.0
+        );
+        check(&enc, &src)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use lzfse_rust::{LzfseDecoder, LzfseRingDecoder, LzfseRingEncoder, LzfseWriter, SpscWriter};
+use test_kit::{Rng, Seq};
+
+use std::io::{self, Read, Write};
+use std::thread;
+use std::time::Instant;
+
+const CAPACITIES: [usize; 4] = [1, 0x0100, 0x1000, 0x0001_0000];
+
+fn data() -> Vec<Vec<u8>> {
+    vec![
+        Vec::default(),
+        b"test".to_vec(),
+        Iterator::take(Seq::default(), 0x0010_0000).collect(),
+        Rng::default().gen_vec(0x0004_0000).unwrap(),
+        Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), 0x0010_0000).collect(),
+    ]
+}
+
+#[test]
+fn encode_decode() -> io::Result<()> {
+    for src in data() {
+        for &capacity in CAPACITIES.iter() {
+            let (mut writer, mut reader) = lzfse_rust::spsc_ring(capacity);
+            let producer = thread::spawn({
+                let src = src.clone();
+                move || LzfseRingEncoder::default().encode(&mut src.as_slice(), &mut writer)
+            });
+            let mut dec = Vec::default();
+            LzfseRingDecoder::default().decode(&mut reader, &mut dec)?;
+            producer.join().unwrap()?;
+            assert_eq!(dec, src);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn lzfse_writer() -> io::Result<()> {
+    for src in data() {
+        let (writer, mut reader) = lzfse_rust::spsc_ring(0x0100);
+        let producer = thread::spawn({
+            let src = src.clone();
+            move || -> io::Result<()> {
+                let mut encoder = LzfseRingEncoder::default();
+                let mut writer: LzfseWriter<SpscWriter> = encoder.writer(writer);
+                for chunk in src.chunks(0x1234) {
+                    writer.write_all(chunk)?;
+                }
+                // Dropping the inner writer signals the end of the stream.
+                writer.finalize().map(drop)
+            }
+        });
+        let mut enc = Vec::default();
+        reader.read_to_end(&mut enc)?;
+        producer.join().unwrap()?;
+        let mut dec = Vec::default();
+        LzfseDecoder::default().decode_bytes(&enc, &mut dec)?;
+        assert_eq!(dec, src);
+    }
+    Ok(())
+}
+
+#[test]
+fn consumer_exits_early() -> io::Result<()> {
+    let src: Vec<u8> = Rng::default().gen_vec(0x0010_0000).unwrap();
+    let (mut writer, mut reader) = lzfse_rust::spsc_ring(0x0100);
+    let producer = thread::spawn(move || {
+        lzfse_rust::compress_reader_to_writer(&mut src.as_slice(), &mut writer)
+    });
+    let mut buf = [0u8; 16];
+    reader.read_exact(&mut buf)?;
+    drop(reader);
+    let (_, v) = producer.join().unwrap()?;
+    assert!(v < 0x0010_0000);
+    Ok(())
+}
+
+#[test]
+#[ignore = "expensive"]
+fn throughput() -> io::Result<()> {
+    let src: Vec<u8> =
+        Iterator::take(Seq::masked(Rng::default(), 0x0000_0F0F), 0x0400_0000).collect();
+    for &capacity in CAPACITIES[1..].iter() {
+        let start = Instant::now();
+        let (mut writer, mut reader) = lzfse_rust::spsc_ring(capacity);
+        let producer = thread::spawn({
+            let src = src.clone();
+            move || LzfseRingEncoder::default().encode(&mut src.as_slice(), &mut writer)
+        });
+        let mut dec = Vec::default();
+        LzfseRingDecoder::default().decode(&mut reader, &mut dec)?;
+        producer.join().unwrap()?;
+        let secs = start.elapsed().as_secs_f64();
+        assert_eq!(dec, src);
+        println!("capacity: {:>6}, {:.1} MiB/s", capacity, src.len() as f64 / secs / 1048576.0);
+    }
+    Ok(())
+}
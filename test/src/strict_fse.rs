@@ -0,0 +1,87 @@
+use lzfse_rust::{Error, FseErrorKind, LzfseDecoder, LzfseRingDecoder};
+use test_kit::Seq;
+
+use std::io;
+
+// V1 block, raw u16 weights: L 20, M 20, D 64, U 256.
+const VX1: &[u8] = include_bytes!("../../data/mutate/vx1.lzfse");
+
+const WEIGHTS_OFFSET: usize = 0x32;
+
+const N_WEIGHTS: usize = 360;
+
+fn decode_bytes(strict: bool, src: &[u8]) -> lzfse_rust::Result<Vec<u8>> {
+    let mut decoder = LzfseDecoder::default();
+    decoder.set_strict_fse(strict);
+    let mut dec = Vec::default();
+    decoder.decode_bytes(src, &mut dec)?;
+    Ok(dec)
+}
+
+fn decode_ring(strict: bool, src: &[u8]) -> lzfse_rust::Result<Vec<u8>> {
+    let mut decoder = LzfseRingDecoder::default();
+    decoder.set_strict_fse(strict);
+    let mut dec = Vec::default();
+    decoder.decode(&mut &*src, &mut dec)?;
+    Ok(dec)
+}
+
+// Decrement each non zero weight in turn, under normalizing the table and exposing latch states.
+fn check_mutate<F>(decode: F) -> io::Result<()>
+where
+    F: Fn(bool, &[u8]) -> lzfse_rust::Result<Vec<u8>>,
+{
+    let mut data = VX1.to_vec();
+    let mut n_latched = 0;
+    for i in 0..N_WEIGHTS {
+        let index = WEIGHTS_OFFSET + i * 2;
+        let w = u16::from_le_bytes([data[index], data[index + 1]]);
+        if w == 0 {
+            continue;
+        }
+        data[index..index + 2].copy_from_slice(&(w - 1).to_le_bytes());
+        let lax = decode(false, &data);
+        let strict = decode(true, &data);
+        match (lax, strict) {
+            (Ok(u), Ok(v)) => assert!(u == v),
+            (Ok(_), Err(Error::Fse(FseErrorKind::LatchState))) => panic!("{}", i),
+            (Err(_), Err(Error::Fse(FseErrorKind::LatchState))) => n_latched += 1,
+            (Err(_), Err(_)) => {}
+            (Err(err), Ok(_)) => panic!("{}: {:?}", i, err),
+            (Ok(_), Err(err)) => panic!("{}: {:?}", i, err),
+        }
+        data[index..index + 2].copy_from_slice(&w.to_le_bytes());
+    }
+    assert_ne!(n_latched, 0);
+    assert!(decode(true, &data)? == decode(false, &data)?);
+    Ok(())
+}
+
+#[test]
+fn mutate_weights() -> io::Result<()> {
+    check_mutate(decode_bytes)
+}
+
+#[test]
+fn mutate_weights_ring() -> io::Result<()> {
+    check_mutate(decode_ring)
+}
+
+// Well formed streams are unaffected.
+#[test]
+fn valid() -> io::Result<()> {
+    for len in [0, 1, 4, 0x0100, 0x1000, 0x0010_0000] {
+        let src = Seq::default().take(len).collect::<Vec<_>>();
+        let mut enc = Vec::default();
+        lzfse_rust::encode_bytes(&src, &mut enc)?;
+        assert!(decode_bytes(true, &enc)? == src);
+        assert!(decode_ring(true, &enc)? == src);
+    }
+    Ok(())
+}
+
+#[test]
+fn default() {
+    assert!(!LzfseDecoder::default().strict_fse());
+    assert!(!LzfseRingDecoder::default().strict_fse());
+}
@@ -0,0 +1,77 @@
+use lzfse_rust::{
+    FrameInfo, LzfseDecoder, LzfseRingDecoder, LzfseRingEncoder, Metadata, TrailerKind,
+};
+use test_kit::{Rng, Seq};
+
+fn infos() -> Vec<FrameInfo> {
+    let metadata = Metadata { name: "alice29.txt".to_owned(), mtime: 1_600_000_000 };
+    vec![
+        FrameInfo::default(),
+        FrameInfo { metadata: Some(metadata.clone()), trailers: Vec::default() },
+        FrameInfo { metadata: None, trailers: vec![(TrailerKind::Opaque, vec![0xAB; 32])] },
+        FrameInfo { metadata: None, trailers: vec![(TrailerKind::Crc32, Vec::default())] },
+        FrameInfo {
+            metadata: Some(metadata),
+            trailers: vec![
+                (TrailerKind::Digest, b"trailer".to_vec()),
+                (TrailerKind::Opaque, vec![0; 0x0123]),
+            ],
+        },
+    ]
+}
+
+fn check(dec: &[u8]) -> lzfse_rust::Result<()> {
+    let mut bare = Vec::default();
+    LzfseRingEncoder::default().encode_bytes(dec, &mut bare)?;
+    for info in infos() {
+        // Bare -> framed.
+        let mut framed = vec![0xFF; 4];
+        let n = lzfse_rust::add_frame(&bare, &info, &mut framed)?;
+        assert_eq!(n + 4, framed.len() as u64);
+        // Framed -> bare.
+        let (stripped_info, stripped) = lzfse_rust::strip_frame(&framed[4..])?;
+        assert_eq!(stripped_info, info);
+        assert!(stripped == bare.as_slice());
+        // Bare -> framed, again.
+        let mut reframed = Vec::default();
+        lzfse_rust::add_frame(stripped, &stripped_info, &mut reframed)?;
+        assert!(reframed == framed[4..]);
+        // The bare stream decodes with the stock decoders.
+        let mut out = Vec::default();
+        LzfseDecoder::default().decode_bytes(stripped, &mut out)?;
+        assert!(out == dec);
+        out.clear();
+        LzfseRingDecoder::default().decode(&mut &*stripped, &mut out)?;
+        assert!(out == dec);
+        #[cfg(feature = "lzfse_ref")]
+        {
+            let mut out = vec![0; dec.len() + 1];
+            let n = lzfse_sys::decode(stripped, &mut out);
+            assert!(out[..n] == *dec);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn empty() -> lzfse_rust::Result<()> {
+    check(&[])
+}
+
+#[test]
+fn seq() -> lzfse_rust::Result<()> {
+    check(&Seq::default().take(0x0010_0000).collect::<Vec<_>>())
+}
+
+#[test]
+fn rng() -> lzfse_rust::Result<()> {
+    check(&Rng::default().gen_vec(0x0001_0000).unwrap())
+}
+
+#[test]
+fn alice() -> lzfse_rust::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut dec)?;
+    check(&dec)
+}
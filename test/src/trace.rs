@@ -0,0 +1,86 @@
+use lzfse_rust::{DecodeEvent, LzfseDecoder, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+const LITERAL_CHUNK_SIZE: usize = 0x1000;
+
+fn replay(enc: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dec = Vec::default();
+    // Previous literal run length, zero if none.
+    let mut literal = 0;
+    let n = LzfseDecoder::default().trace(enc, |event| match event {
+        DecodeEvent::Literal(bytes) => {
+            assert!(!bytes.is_empty());
+            assert!(bytes.len() <= LITERAL_CHUNK_SIZE);
+            // Consecutive literal runs are merged up to the chunk size.
+            assert!(literal == 0 || literal == LITERAL_CHUNK_SIZE);
+            literal = bytes.len();
+            dec.extend_from_slice(bytes);
+        }
+        DecodeEvent::Match { distance, len } => {
+            literal = 0;
+            for _ in 0..len {
+                dec.push(dec[dec.len() - distance as usize]);
+            }
+        }
+        _ => panic!(),
+    })?;
+    assert_eq!(n, dec.len() as u64);
+    Ok(dec)
+}
+
+fn check(data: &[u8]) -> io::Result<()> {
+    let mut enc = Vec::default();
+    LzfseEncoder::default().encode_bytes(data, &mut enc)?;
+    let dec = replay(&enc)?;
+    assert!(dec == data);
+    Ok(())
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    check(&[])
+}
+
+#[test]
+fn seq() -> io::Result<()> {
+    for &n in &[1, 4, 0x0100, 0x1000, 0x0001_0000, 0x0010_0000] {
+        check(&Iterator::take(Seq::default(), n).collect::<Vec<_>>())?;
+    }
+    Ok(())
+}
+
+#[test]
+fn random() -> io::Result<()> {
+    for &n in &[1, 4, 0x0100, 0x1000, 0x0001_0000] {
+        check(&Rng::default().gen_vec(n).unwrap())?;
+    }
+    Ok(())
+}
+
+#[test]
+fn zeros() -> io::Result<()> {
+    for &n in &[1, 4, 0x0100, 0x1000, 0x0001_0000, 0x0010_0000] {
+        check(&vec![0u8; n])?;
+    }
+    Ok(())
+}
+
+#[test]
+fn raw() -> io::Result<()> {
+    let data = Rng::default().gen_vec(0x0010_0000).unwrap();
+    let mut enc = Vec::default();
+    lzfse_rust::encode_raw_block(&data, &mut enc)?;
+    enc.extend_from_slice(b"bvx$");
+    let mut lens = Vec::default();
+    let n = LzfseDecoder::default().trace(&enc, |event| match event {
+        DecodeEvent::Literal(bytes) => lens.push(bytes.len()),
+        _ => panic!(),
+    })?;
+    assert_eq!(n, data.len() as u64);
+    assert_eq!(lens.len(), data.len() / LITERAL_CHUNK_SIZE);
+    assert!(lens.iter().all(|&len| len == LITERAL_CHUNK_SIZE));
+    assert!(replay(&enc)? == data);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use lzfse_rust::{Error, LzfseDecoder, LzfseRingDecoder};
+use test_kit::Seq;
+
+use std::io;
+
+// Single byte reads, trailing bytes arrive after the end of stream block is first seen.
+struct Trickle<'a>(&'a [u8]);
+
+impl<'a> io::Read for Trickle<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.0[0];
+        self.0 = &self.0[1..];
+        Ok(1)
+    }
+}
+
+fn encode(src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(src, &mut enc)?;
+    Ok(enc)
+}
+
+fn check_clean(dec: &[u8], enc: &[u8]) -> lzfse_rust::Result<()> {
+    let mut out = Vec::default();
+    let (u, v) = LzfseRingDecoder::default().decode(&mut &*enc, &mut out)?;
+    assert_eq!(u, enc.len() as u64);
+    assert_eq!(v, dec.len() as u64);
+    assert!(out == dec);
+    let mut out = Vec::default();
+    let (u, v) = LzfseRingDecoder::default().decode(&mut Trickle(enc), &mut out)?;
+    assert_eq!(u, enc.len() as u64);
+    assert_eq!(v, dec.len() as u64);
+    assert!(out == dec);
+    let mut out = Vec::default();
+    LzfseDecoder::default().decode_bytes(enc, &mut out)?;
+    assert!(out == dec);
+    Ok(())
+}
+
+fn check_trailing(enc: &[u8]) {
+    let mut out = Vec::default();
+    match LzfseRingDecoder::default().decode(&mut &*enc, &mut out) {
+        Err(Error::PayloadOverflow) => {}
+        res => panic!("{:?}", res),
+    }
+    let mut out = Vec::default();
+    match LzfseRingDecoder::default().decode(&mut Trickle(enc), &mut out) {
+        Err(Error::PayloadOverflow) => {}
+        res => panic!("{:?}", res),
+    }
+    let mut out = Vec::default();
+    match LzfseDecoder::default().decode_bytes(enc, &mut out) {
+        Err(Error::PayloadOverflow) => {}
+        res => panic!("{:?}", res),
+    }
+    let mut out = Vec::default();
+    let mut decoder = LzfseRingDecoder::default();
+    let err = io::Read::read_to_end(&mut decoder.reader(enc), &mut out).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn clean() -> lzfse_rust::Result<()> {
+    for n in [0, 4, 0x1000, 0x0010_0000] {
+        let dec = Seq::default().take(n).collect::<Vec<_>>();
+        check_clean(&dec, &encode(&dec)?)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn trailing_byte() -> io::Result<()> {
+    for n in [0, 4, 0x1000, 0x0010_0000] {
+        let dec = Seq::default().take(n).collect::<Vec<_>>();
+        let mut enc = encode(&dec)?;
+        enc.push(0);
+        check_trailing(&enc);
+    }
+    Ok(())
+}
+
+#[test]
+fn trailing_whitespace() -> io::Result<()> {
+    let dec = Seq::default().take(0x1000).collect::<Vec<_>>();
+    for whitespace in [&b"\n"[..], b"\r\n", b" ", b"\t\t\t\t", b"\n\n\n\n\n\n\n\n\n\n\n\n"] {
+        let mut enc = encode(&dec)?;
+        enc.extend_from_slice(whitespace);
+        check_trailing(&enc);
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use lzfse_rust::Error;
+
+use std::io;
+
+fn check(s: &str) -> io::Result<()> {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_str(s, &mut enc)?;
+    let dec = lzfse_rust::decode_string(&enc)?;
+    assert_eq!(dec, s);
+    Ok(())
+}
+
+#[test]
+fn empty() -> io::Result<()> {
+    check("")
+}
+
+#[test]
+fn ascii() -> io::Result<()> {
+    check("a quick brown fox jumps over the lazy dog")
+}
+
+#[test]
+fn multi_byte() -> io::Result<()> {
+    check("ταχίστη αλώπηξ βαφής ψημένη γη, δρασκελίζει υπέρ νωθρού κυνός")?;
+    check("いろはにほへと ちりぬるを わかよたれそ つねならむ")?;
+    check(&"🦀🦊🐕 ".repeat(0x1000))?;
+    Ok(())
+}
+
+#[test]
+fn text() -> io::Result<()> {
+    let enc = include_bytes!("../../data/snappy/alice29.txt.lzfse");
+    let s = lzfse_rust::decode_string(enc)?;
+    check(&s)
+}
+
+#[test]
+fn bad_utf8() -> io::Result<()> {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&[0x61, 0x62, 0xE2, 0x82], &mut enc)?;
+    match lzfse_rust::decode_string(&enc) {
+        Err(Error::Utf8(err)) => assert_eq!(err.valid_up_to(), 2),
+        _ => panic!(),
+    }
+    Ok(())
+}
+
+#[test]
+fn bad_stream() -> io::Result<()> {
+    let mut enc = Vec::default();
+    lzfse_rust::encode_str(&"ταχίστη αλώπηξ ".repeat(0x0100), &mut enc)?;
+    enc.truncate(enc.len() - 8);
+    match lzfse_rust::decode_string(&enc) {
+        Err(Error::Utf8(_)) | Ok(_) => panic!(),
+        Err(_) => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use lzfse_rust::{BlockStrategy, LzfseDecoder, LzfseEncoder, LzfseRingDecoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn data() -> Vec<Vec<u8>> {
+    vec![
+        Vec::default(),
+        b"test".to_vec(),
+        Iterator::take(Seq::default(), 0x0001_0000).collect(),
+        Rng::default().gen_vec(0x0001_0000).unwrap(),
+        b"a quick brown fox jumps over the lazy dog ".repeat(0x1000),
+    ]
+}
+
+#[test]
+fn encoder() -> io::Result<()> {
+    for src in data() {
+        let mut expected = Vec::default();
+        LzfseEncoder::default().encode_bytes(&src, &mut expected)?;
+        let mut encoder = LzfseEncoder::default();
+        encoder.warm();
+        let mut enc = Vec::default();
+        encoder.encode_bytes(&src, &mut enc)?;
+        assert_eq!(enc, expected);
+        let mut encoder = LzfseRingEncoder::default();
+        encoder.warm();
+        enc.clear();
+        encoder.encode_bytes(&src, &mut enc)?;
+        assert_eq!(enc, expected);
+        enc.clear();
+        encoder.encode(&mut src.as_slice(), &mut enc)?;
+        assert_eq!(enc, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn encoder_settings() -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_block_strategy(BlockStrategy::NeverRaw);
+    encoder.set_block_boundaries(&[0x10])?;
+    encoder.warm();
+    assert_eq!(encoder.block_strategy(), BlockStrategy::NeverRaw);
+    assert_eq!(encoder.block_boundaries(), &[0x10]);
+    Ok(())
+}
+
+#[test]
+fn decoder() -> io::Result<()> {
+    for src in data() {
+        let mut enc = Vec::default();
+        lzfse_rust::encode_bytes(&src, &mut enc)?;
+        let mut decoder = LzfseDecoder::default();
+        decoder.warm();
+        let mut dec = Vec::default();
+        decoder.decode_bytes(&enc, &mut dec)?;
+        assert_eq!(dec, src);
+        let mut decoder = LzfseRingDecoder::default();
+        decoder.warm();
+        dec.clear();
+        decoder.decode(&mut enc.as_slice(), &mut dec)?;
+        assert_eq!(dec, src);
+    }
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use lzfse_rust::{Error, LzfseEncoder};
+use test_kit::{Rng, Seq};
+
+use std::io;
+
+fn check(src: &[u8]) -> io::Result<()> {
+    let mut encoder = LzfseEncoder::default();
+    let mut enc = Vec::default();
+    let n = encoder.encode_bytes(src, &mut enc)? as usize;
+    let mut dst = b"prefix".to_vec();
+    assert_eq!(encoder.encode_within(src, &mut dst, n)?, n);
+    assert_eq!(&dst[..6], b"prefix");
+    assert!(dst[6..] == enc);
+    let mut dst = b"prefix".to_vec();
+    match encoder.encode_within(src, &mut dst, n - 1) {
+        Err(Error::BufferOverflow) => {}
+        Err(err) => panic!("{:?}", err),
+        Ok(_) => panic!(),
+    }
+    assert_eq!(dst, b"prefix");
+    Ok(())
+}
+
+#[test]
+fn exact() -> io::Result<()> {
+    for &n in &[0, 4, 0x0100, 0x1000, 0x0001_0000, 0x0010_0000] {
+        check(&Iterator::take(Seq::default(), n).collect::<Vec<_>>())?;
+        check(&Rng::default().gen_vec(n).unwrap())?;
+        check(&vec![0u8; n])?;
+    }
+    Ok(())
+}
+
+#[test]
+fn pad_output() -> io::Result<()> {
+    let src = Iterator::take(Seq::default(), 0x1000).collect::<Vec<_>>();
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_pad_output_to(Some(0x0200));
+    let mut enc = Vec::default();
+    let n = encoder.encode_bytes(&src, &mut enc)? as usize;
+    assert_eq!(n % 0x0200, 0);
+    let mut dst = Vec::default();
+    assert_eq!(encoder.encode_within(&src, &mut dst, n)?, n);
+    assert!(dst == enc);
+    // The padding alone exceeds the budget.
+    match encoder.encode_within(&src, &mut Vec::default(), n - 1) {
+        Err(Error::BufferOverflow) => {}
+        Err(err) => panic!("{:?}", err),
+        Ok(_) => panic!(),
+    }
+    Ok(())
+}
+
+#[test]
+fn skip_if_compressed() -> io::Result<()> {
+    let mut src = Vec::default();
+    lzfse_rust::encode_bytes(&vec![0u8; 0x1000], &mut src)?;
+    let mut encoder = LzfseEncoder::default();
+    encoder.set_skip_if_compressed(true);
+    let mut enc = Vec::default();
+    let n = encoder.encode_bytes(&src, &mut enc)? as usize;
+    let mut dst = Vec::default();
+    assert_eq!(encoder.encode_within(&src, &mut dst, n)?, n);
+    assert!(dst == enc);
+    Ok(())
+}
+
+#[test]
+fn tight() {
+    let src = Rng::default().gen_vec(0x0100_0000).unwrap();
+    let mut dst = Vec::default();
+    match lzfse_rust::encode_within(&src, &mut dst, 0x1000) {
+        Err(Error::BufferOverflow) => {}
+        Err(err) => panic!("{:?}", err),
+        Ok(_) => panic!(),
+    }
+    assert!(dst.is_empty());
+    // Abandoned within one block, 40,000 literals, of the budget. The capacity bounds the number
+    // of bytes written.
+    assert!(dst.capacity() <= 0x1000 + 0x0001_0000);
+}
@@ -0,0 +1,147 @@
+// `LzfseWriter` and `LzfseWriterBytes` flush semantics.
+
+use lzfse_rust::{Error, LzfseDecoder, LzfseRingEncoder};
+use test_kit::Seq;
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+// Shared sink, so we can inspect output while the writer is live.
+#[derive(Default, Clone)]
+struct Shared(Rc<RefCell<Vec<u8>>>, Rc<RefCell<usize>>);
+
+impl Write for Shared {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        *self.1.borrow_mut() += 1;
+        Ok(())
+    }
+}
+
+// Fails on flush.
+struct Broken;
+
+impl Write for Broken {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken"))
+    }
+}
+
+// Append an end of stream marker and decode, the result must be a prefix of `data`.
+fn check_prefix(enc: &[u8], data: &[u8]) -> usize {
+    let mut enc = enc.to_vec();
+    enc.extend_from_slice(b"bvx$");
+    let mut dec = Vec::default();
+    LzfseDecoder::default().decode_bytes(&enc, &mut dec).unwrap();
+    assert!(data.starts_with(&dec));
+    dec.len()
+}
+
+// Reference, without flushing.
+fn encode(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = LzfseRingEncoder::default();
+    let mut writer = encoder.writer(Vec::default());
+    writer.write_all(data)?;
+    writer.finalize()
+}
+
+// Flush after each chunk, returning the number of input bytes visible prior to finalization.
+fn flush_n(data: &[u8], chunk_len: usize) -> io::Result<usize> {
+    let enc = encode(data)?;
+    let sink = Shared::default();
+    let mut encoder = LzfseRingEncoder::default();
+    let mut writer = encoder.writer(sink.clone());
+    let mut n_flushed = 0;
+    let mut n_decoded = 0;
+    for (i, chunk) in data.chunks(chunk_len).enumerate() {
+        writer.write_all(chunk)?;
+        writer.flush()?;
+        assert_eq!(*sink.1.borrow(), i + 1);
+        let out = sink.0.borrow();
+        assert!(n_flushed <= out.len());
+        assert!(enc.starts_with(&out));
+        let n = check_prefix(&out, data);
+        assert!(n_decoded <= n);
+        n_flushed = out.len();
+        n_decoded = n;
+    }
+    writer.finalize()?;
+    assert!(*sink.0.borrow() == enc);
+    Ok(n_decoded)
+}
+
+#[test]
+fn flush() -> io::Result<()> {
+    let data = Seq::default().take(0x0040_0000).collect::<Vec<_>>();
+    assert!(flush_n(&data, 0x0001_0000)? != 0);
+    Ok(())
+}
+
+#[test]
+fn flush_odd() -> io::Result<()> {
+    let data = Seq::default().take(0x0020_0000).collect::<Vec<_>>();
+    assert!(flush_n(&data, 0x0003_1234)? != 0);
+    Ok(())
+}
+
+#[test]
+fn flush_zeros() -> io::Result<()> {
+    let data = vec![0u8; 0x0100_0000];
+    flush_n(&data, 0x0010_0000)?;
+    Ok(())
+}
+
+#[test]
+fn flush_empty() -> io::Result<()> {
+    let sink = Shared::default();
+    let mut encoder = LzfseRingEncoder::default();
+    let mut writer = encoder.writer(sink.clone());
+    writer.flush()?;
+    writer.write_all(b"test")?;
+    writer.flush()?;
+    // The in-progress block is retained.
+    assert!(sink.0.borrow().is_empty());
+    writer.finalize()?;
+    assert!(*sink.0.borrow() == encode(b"test")?);
+    Ok(())
+}
+
+#[test]
+fn flush_bytes() -> io::Result<()> {
+    let data = Seq::default().take(0x0020_0000).collect::<Vec<_>>();
+    let enc = encode(&data)?;
+    let mut encoder = LzfseRingEncoder::default();
+    let mut writer = encoder.writer_bytes(Vec::default());
+    for chunk in data.chunks(0x0001_0000) {
+        writer.write_all(chunk)?;
+        writer.flush()?;
+    }
+    assert!(writer.finalize()? == enc);
+    Ok(())
+}
+
+#[test]
+fn flush_broken() {
+    let data = Seq::default().take(0x0020_0000).collect::<Vec<_>>();
+    let mut encoder = LzfseRingEncoder::default();
+    let mut writer = encoder.writer(Broken);
+    writer.write_all(&data).unwrap();
+    let err = writer.flush().unwrap_err();
+    assert_eq!(err.to_string(), "broken");
+    let err = writer.write(&data).unwrap_err();
+    match err.into_inner().map(|u| u.downcast::<Error>()) {
+        Some(Ok(err)) => assert!(matches!(*err, Error::BadWriterState)),
+        _ => panic!(),
+    }
+    assert!(writer.flush().is_err());
+    assert!(writer.finalize().is_err());
+}
@@ -0,0 +1,98 @@
+use lzfse_rust::{Error, LzfseEncoder, LzfseRingEncoder};
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, Write};
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Allocations of `LIMIT` bytes or more fail, `usize::MAX` disables.
+static LIMIT: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+struct FailingAlloc;
+
+unsafe impl GlobalAlloc for FailingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() >= LIMIT.load(Ordering::Relaxed) {
+            return ptr::null_mut();
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if new_size >= LIMIT.load(Ordering::Relaxed) {
+            return ptr::null_mut();
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOC: FailingAlloc = FailingAlloc;
+
+// Incompressible.
+fn data(len: usize) -> Vec<u8> {
+    let mut u: u32 = 1;
+    (0..len)
+        .map(|_| {
+            u ^= u << 13;
+            u ^= u >> 17;
+            u ^= u << 5;
+            (u >> 24) as u8
+        })
+        .collect()
+}
+
+fn with_limit<T>(limit: usize, f: impl FnOnce() -> T) -> T {
+    LIMIT.store(limit, Ordering::Relaxed);
+    let t = f();
+    LIMIT.store(usize::MAX, Ordering::Relaxed);
+    t
+}
+
+// A single test as the allocator limit is global.
+#[test]
+fn out_of_memory() -> io::Result<()> {
+    let src = data(0x0040_0000);
+    let mut encoder = LzfseEncoder::default();
+    let mut ring_encoder = LzfseRingEncoder::default();
+    for _ in 0..2 {
+        // encode_bytes
+        let mut dst = Vec::default();
+        let err = with_limit(0x0010_0000, || encoder.encode_bytes(&src, &mut dst)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::OutOfMemory);
+        assert!(dst.capacity() < 0x0010_0000);
+        // encode_within
+        let mut dst = Vec::default();
+        match with_limit(0x0010_0000, || encoder.encode_within(&src, &mut dst, usize::MAX)) {
+            Err(Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::OutOfMemory),
+            Err(err) => panic!("{}", err),
+            Ok(_) => panic!(),
+        }
+        assert!(dst.is_empty());
+        // writer_bytes
+        let err = with_limit(0x0010_0000, || {
+            let mut writer = ring_encoder.writer_bytes(Vec::default());
+            writer.write_all(&src)?;
+            writer.finalize()
+        })
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::OutOfMemory);
+        // Encoders remain usable.
+        let mut enc = Vec::default();
+        encoder.encode_bytes(&src, &mut enc)?;
+        let mut dec = Vec::default();
+        lzfse_rust::decode_bytes(&enc, &mut dec)?;
+        assert!(dec == src);
+        let mut writer = ring_encoder.writer_bytes(Vec::default());
+        writer.write_all(&src)?;
+        let enc = writer.finalize()?;
+        dec.clear();
+        lzfse_rust::decode_bytes(&enc, &mut dec)?;
+        assert!(dec == src);
+    }
+    Ok(())
+}
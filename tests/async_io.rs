@@ -0,0 +1,131 @@
+#![cfg(feature = "tokio")]
+
+use lzfse_rust::{LzfseDecoder, LzfseRingDecoder, LzfseRingEncoder};
+use test_kit::{Rng, Seq};
+use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+
+use std::io::ErrorKind;
+
+fn data() -> Vec<Vec<u8>> {
+    vec![
+        Vec::default(),
+        b"test".to_vec(),
+        b"a quick brown fox jumps over the lazy dog ".repeat(0x20),
+        Seq::default().take(0x0040_0000).collect(),
+        Rng::new(1).gen_vec(0x0010_0000).unwrap(),
+    ]
+}
+
+// Encode and decode concurrently across a small duplex pipe, forcing both ends to yield.
+#[tokio::test]
+async fn duplex() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::default();
+    let mut decoder = LzfseRingDecoder::default();
+    for src in data() {
+        let (client, server) = io::duplex(0x0100);
+        let encode = async {
+            let mut writer = encoder.async_writer(client);
+            for chunk in src.chunks(0x1234) {
+                writer.write_all(chunk).await?;
+            }
+            writer.shutdown().await?;
+            Ok::<_, std::io::Error>(())
+        };
+        let decode = async {
+            let mut reader = decoder.async_reader(server);
+            let mut dec = Vec::default();
+            reader.read_to_end(&mut dec).await?;
+            Ok::<_, std::io::Error>(dec)
+        };
+        let (u, v) = tokio::join!(encode, decode);
+        u?;
+        assert!(v? == src);
+    }
+    Ok(())
+}
+
+// Async output is identical to synchronous output.
+#[tokio::test]
+async fn writer_output() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::default();
+    for src in data() {
+        let mut writer = encoder.async_writer(Vec::default());
+        writer.write_all(&src).await?;
+        writer.shutdown().await?;
+        let enc = writer.into_inner();
+        let mut expected = Vec::default();
+        LzfseRingEncoder::default().encode(&mut src.as_slice(), &mut expected)?;
+        assert!(enc == expected);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn writer_after_shutdown() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::default();
+    let mut writer = encoder.async_writer(Vec::default());
+    writer.write_all(b"test").await?;
+    writer.shutdown().await?;
+    assert!(writer.write_all(b"test").await.is_err());
+    Ok(())
+}
+
+// Reads of every size, including trickled input.
+#[tokio::test]
+async fn reader_trickle() -> io::Result<()> {
+    let src = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&src, &mut enc)?;
+    let mut decoder = LzfseDecoder::default();
+    let (mut client, server) = io::duplex(1);
+    let encode = async {
+        for &u in &enc {
+            client.write_all(&[u]).await?;
+        }
+        client.shutdown().await
+    };
+    let decode = async {
+        let mut reader = decoder.async_reader(server);
+        let mut dec = Vec::default();
+        let mut buf = [0u8; 7];
+        loop {
+            let n = reader.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            dec.extend_from_slice(&buf[..n]);
+        }
+        Ok::<_, std::io::Error>(dec)
+    };
+    let (u, v) = tokio::join!(encode, decode);
+    u?;
+    assert!(v? == src);
+    Ok(())
+}
+
+#[tokio::test]
+async fn reader_errors() -> io::Result<()> {
+    let src = Seq::default().take(0x0001_0000).collect::<Vec<_>>();
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&src, &mut enc)?;
+    let mut decoder = LzfseDecoder::default();
+    // Truncated.
+    for n in (0..enc.len()).step_by(97) {
+        let mut reader = decoder.async_reader(&enc[..n]);
+        let err = reader.read_to_end(&mut Vec::default()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+    // Trailing garbage.
+    let mut bad = enc.clone();
+    bad.push(0);
+    let mut reader = decoder.async_reader(bad.as_slice());
+    assert!(reader.read_to_end(&mut Vec::default()).await.is_err());
+    // Bad magic.
+    let mut bad = enc.clone();
+    bad[0] = 0;
+    let mut reader = decoder.async_reader(bad.as_slice());
+    assert!(reader.read_to_end(&mut Vec::default()).await.is_err());
+    // Sticky.
+    assert!(reader.read_to_end(&mut Vec::default()).await.is_err());
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use lzfse_rust::{LzfseRingDecoder, LzfseRingEncoder};
+use test_kit::Rng;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const LEN: usize = 100_000_000;
+
+// Output must appear at least once per `PROMPT_LEN` input bytes.
+const PROMPT_LEN: usize = 0x0020_0000;
+
+// Allocation calls.
+static N_ALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        N_ALLOCS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        N_ALLOCS.fetch_add(1, Ordering::SeqCst);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        N_ALLOCS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+// Endless text like data, short range matches over a small alphabet.
+struct Source(Rng);
+
+impl Source {
+    fn fill(&mut self, buf: &mut [u8]) {
+        for u in buf.iter_mut() {
+            *u = b"etaoin shrdlu\n"[(self.0.gen() >> 16) as usize % 14];
+        }
+    }
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Self(Rng::new(1))
+    }
+}
+
+// Pre allocated inner writer, recording the number of bytes written.
+struct Inner<'a> {
+    vec: &'a mut Vec<u8>,
+    len: &'a Cell<usize>,
+}
+
+impl<'a> Write for Inner<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        assert!(self.vec.len() + buf.len() <= self.vec.capacity());
+        self.vec.extend_from_slice(buf);
+        self.len.set(self.vec.len());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Compares written data against a fresh `Source`.
+struct Verifier {
+    source: Source,
+    buf: Vec<u8>,
+    n: usize,
+}
+
+impl Write for Verifier {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for chunk in buf.chunks(self.buf.len()) {
+            let expected = &mut self.buf[..chunk.len()];
+            self.source.fill(expected);
+            assert!(expected == chunk, "{}", self.n);
+            self.n += chunk.len();
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// A single test as the allocation counter is global.
+#[test]
+fn incremental_writer() -> io::Result<()> {
+    let mut encoder = LzfseRingEncoder::default();
+    let mut enc = Vec::with_capacity(LEN + LEN / 8);
+    let len = Cell::new(0);
+    let mut rng = Rng::new(2);
+    let mut source = Source::default();
+    let mut buf = vec![0u8; 0x0001_0000];
+    let mark = N_ALLOCS.load(Ordering::SeqCst);
+    let mut writer = encoder.writer(Inner { vec: &mut enc, len: &len });
+    let mut n = 0;
+    let mut prompt = (0, 0);
+    while n < LEN {
+        // Random sized chunks, empty chunks included.
+        let chunk = &mut buf[..((rng.gen() >> 16) as usize % 0x0001_0001).min(LEN - n)];
+        source.fill(chunk);
+        writer.write_all(chunk)?;
+        n += chunk.len();
+        if len.get() != prompt.1 {
+            prompt = (n, len.get());
+        }
+        assert!(n - prompt.0 <= PROMPT_LEN, "{}", n);
+    }
+    writer.finalize()?;
+    // Bounded internal memory: neither the writer nor the encoder allocates.
+    assert_eq!(N_ALLOCS.load(Ordering::SeqCst), mark);
+    let mut verifier = Verifier { source: Source::default(), buf, n: 0 };
+    let (_, n_raw_bytes) =
+        LzfseRingDecoder::default().decode(&mut enc.as_slice(), &mut verifier)?;
+    assert_eq!(n_raw_bytes, LEN as u64);
+    assert_eq!(verifier.n, LEN);
+    Ok(())
+}
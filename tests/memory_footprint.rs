@@ -0,0 +1,108 @@
+use lzfse_rust::{LzfseRingDecoder, LzfseRingEncoder, RingProfile, Role};
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Live heap bytes.
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        LIVE.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        LIVE.fetch_add(new_size, Ordering::SeqCst);
+        LIVE.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+// Live heap held by the value `f` constructs.
+fn measure<T, F: FnOnce() -> T>(f: F) -> (T, usize) {
+    let mark = LIVE.load(Ordering::SeqCst);
+    let t = f();
+    (t, LIVE.load(Ordering::SeqCst) - mark)
+}
+
+// Incompressible with long matches.
+fn data(len: usize) -> Vec<u8> {
+    let mut u: u32 = 1;
+    let block = (0..0x1000)
+        .map(|_| {
+            u ^= u << 13;
+            u ^= u >> 17;
+            u ^= u << 5;
+            u as u8
+        })
+        .collect::<Vec<_>>();
+    block.iter().copied().cycle().take(len).collect()
+}
+
+fn encoder() -> io::Result<()> {
+    let footprint = lzfse_rust::memory_footprint(RingProfile::default(), Role::Encoder);
+    let src = data(0x0010_0000);
+    let mut enc = Vec::with_capacity(0x0020_0000);
+    let (mut encoder, n) = measure(LzfseRingEncoder::default);
+    assert_eq!(n, footprint);
+    let ((), n) = measure(|| {
+        encoder.encode(&mut src.as_slice(), &mut enc).unwrap();
+    });
+    assert_eq!(n, 0);
+    for d in [0, 0x8000, 0x0001_0000, 0x0002_0000, u32::MAX] {
+        let profile = RingProfile::for_max_distance(d);
+        let footprint = lzfse_rust::memory_footprint(profile, Role::Encoder);
+        let (mut encoder, n) = measure(|| LzfseRingEncoder::with_profile(profile));
+        assert_eq!(n, footprint, "{profile}");
+        enc.clear();
+        let ((), n) = measure(|| {
+            encoder.encode(&mut src.as_slice(), &mut enc).unwrap();
+        });
+        assert_eq!(n, 0, "{profile}");
+    }
+    Ok(())
+}
+
+fn decoder() -> io::Result<()> {
+    let src = data(0x0010_0000);
+    let mut enc = Vec::default();
+    lzfse_rust::encode_bytes(&src, &mut enc)?;
+    let mut dec = Vec::with_capacity(0x0020_0000);
+    for d in [0, 0x8000, 0x0001_0000, 0x0002_0000, u32::MAX] {
+        let profile = RingProfile::for_max_distance(d);
+        let footprint = lzfse_rust::memory_footprint(profile, Role::Decoder);
+        let (mut decoder, n) = measure(|| LzfseRingDecoder::with_profile(profile));
+        assert_eq!(n, footprint, "{profile}");
+        dec.clear();
+        let ((), n) = measure(|| {
+            decoder.decode(&mut enc.as_slice(), &mut dec).unwrap();
+        });
+        assert_eq!(n, 0, "{profile}");
+        assert!(dec == src);
+    }
+    Ok(())
+}
+
+// Single test, the live heap counter is global.
+#[test]
+fn default_profile() -> io::Result<()> {
+    encoder()?;
+    decoder()
+}